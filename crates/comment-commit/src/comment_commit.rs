@@ -1,3 +1,4 @@
+use std::cell::Cell;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
@@ -5,35 +6,33 @@ use git2::{Repository, Signature, Tree};
 use kenjutu_types::CommitChangeIdExt;
 
 use crate::comment_commit_lock::CommentCommitLock;
-use crate::materialize::materialize;
-use crate::model::{ActionEntry, AnchorContext, CommentAction, DiffSide, MaterializedComment};
+use crate::context::{get_context_lines, read_file_from_tree, side_tree};
+use crate::materialize::{DEFAULT_MAX_REPLY_DEPTH, materialize_with_depth};
+use crate::model::{
+    ActionEntry, AnchorContext, CommentAction, CommentFilter, CommentLog, DiffSide,
+    MaterializedComment, NewComment,
+};
+use crate::porting::{find_anchor_position, ported_lines};
 use crate::tree_builder_ext::TreeBuilderExt;
 use crate::{ChangeId, CommitId, Error, Result};
 
 const ANCHOR_CONTEXT_LINES: usize = 3;
 
-/// Read a file's content from a git tree, returning None if the file doesn't exist.
-fn read_file_from_tree(
-    repo: &Repository,
-    tree: &git2::Tree<'_>,
-    file_path: &Path,
-) -> Option<String> {
-    let entry = tree.get_path(file_path).ok()?;
-    let blob = repo.find_blob(entry.id()).ok()?;
-    std::str::from_utf8(blob.content()).ok().map(String::from)
-}
-
 /// Manages inline diff comments for a change_id.
 ///
 /// Comments are stored as an append-only action log in git objects:
 /// - Ref: `refs/kenjutu/{change_id}/comments`
 /// - Tree: each file path maps to a blob containing a JSON array of `ActionEntry`
-/// - Commit parents: all unique target SHAs referenced in Create actions (prevents GC)
+/// - Commit parents: all unique target SHAs referenced in Create/Reanchor actions (prevents GC)
 ///
 /// A file lock is held for the lifetime of this struct to prevent concurrent writes.
 pub struct CommentCommit<'a> {
     change_id: ChangeId,
     actions: HashMap<PathBuf, Vec<ActionEntry>>,
+    /// Set when actions are appended, cleared by [`CommentCommit::write`].
+    dirty: Cell<bool>,
+    /// Deepest reply nesting returned by the getters; see [`CommentCommit::set_max_reply_depth`].
+    max_reply_depth: usize,
     repo: &'a Repository,
     _guard: CommentCommitLock,
 }
@@ -47,6 +46,13 @@ impl<'a> CommentCommit<'a> {
     /// Acquires an exclusive file lock for the duration.
     pub fn get(repo: &'a Repository, commit_id: CommitId) -> Result<Self> {
         let change_id = repo.find_commit(commit_id.oid())?.change_id();
+        Self::for_change(repo, change_id)
+    }
+
+    /// Open or create the comment-commit for `change_id` directly, without resolving a commit.
+    ///
+    /// Behaves like [`CommentCommit::get`], including the lock.
+    pub fn for_change(repo: &'a Repository, change_id: ChangeId) -> Result<Self> {
         let guard = CommentCommitLock::new(repo, change_id)?;
         log::info!("acquired lock for comment-commit: change_id={}", change_id,);
 
@@ -69,46 +75,123 @@ impl<'a> CommentCommit<'a> {
         Ok(Self {
             change_id,
             actions,
+            dirty: Cell::new(false),
+            max_reply_depth: DEFAULT_MAX_REPLY_DEPTH,
             repo,
             _guard: guard,
         })
     }
 
+    /// Open the comment-commit for `commit_id`, run `f`, and write once at the end.
+    ///
+    /// The lock is held throughout, so any number of actions appended by `f` land in a single
+    /// comment-commit. Nothing is written if `f` fails or appends nothing.
+    pub fn with_session<T>(
+        repo: &'a Repository,
+        commit_id: CommitId,
+        f: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        let mut cc = Self::get(repo, commit_id)?;
+        let value = f(&mut cc)?;
+        cc.write_if_dirty()?;
+        Ok(value)
+    }
+
+    /// Cap how deeply the getters nest replies, to keep rendering manageable. Replies below the
+    /// cap are shown as siblings of their parent. Only the view changes; the log keeps every
+    /// reply's real parent.
+    pub fn set_max_reply_depth(&mut self, depth: usize) {
+        self.max_reply_depth = depth;
+    }
+
+    /// Whether actions have been appended since the log was loaded or last written.
+    pub fn dirty(&self) -> bool {
+        self.dirty.get()
+    }
+
+    /// Like [`CommentCommit::write`], but skips creating a commit when nothing changed.
+    pub fn write_if_dirty(&self) -> Result<Option<CommitId>> {
+        if !self.dirty() {
+            return Ok(None);
+        }
+        self.write().map(Some)
+    }
+
     /// Get the raw action log for a specific file.
     pub(crate) fn get_file_actions(&self, file_path: &Path) -> Vec<ActionEntry> {
         self.actions.get(file_path).cloned().unwrap_or_default()
     }
 
     /// Get the materialized comments for a specific file (replays the action log).
+    ///
+    /// Deleted threads are omitted.
     pub fn get_file_comments(&self, file_path: &Path) -> Vec<MaterializedComment> {
+        without_deleted(self.get_file_comments_including_deleted(file_path))
+    }
+
+    /// Like [`CommentCommit::get_file_comments`], keeping only the threads `filter` selects.
+    ///
+    /// Replies are part of their thread, so they are hidden along with its root.
+    pub fn get_file_comments_filtered(
+        &self,
+        file_path: &Path,
+        filter: CommentFilter,
+    ) -> Vec<MaterializedComment> {
+        self.get_file_comments(file_path)
+            .into_iter()
+            .filter(|comment| filter.matches(comment))
+            .collect()
+    }
+
+    /// Like [`CommentCommit::get_file_comments`], but keeps deleted threads.
+    pub fn get_file_comments_including_deleted(
+        &self,
+        file_path: &Path,
+    ) -> Vec<MaterializedComment> {
         let actions = self.get_file_actions(file_path);
-        materialize(&actions)
+        materialize_with_depth(&actions, self.max_reply_depth)
     }
 
-    /// Get all materialized comments across all files.
+    /// Get all materialized comments across all files. Deleted threads are omitted.
     pub fn get_all_comments(&self) -> HashMap<PathBuf, Vec<MaterializedComment>> {
         self.actions
             .iter()
-            .map(|(path, actions)| (path.clone(), materialize(actions)))
+            .map(|(path, actions)| {
+                let comments = materialize_with_depth(actions, self.max_reply_depth);
+                (path.clone(), without_deleted(comments))
+            })
             .collect()
     }
 
-    /// Create a new top-level inline comment on a diff.
+    /// Count unresolved top-level comment threads per file.
     ///
-    /// `sha` is the commit this comment is anchored to (used for anchor context
-    /// and GC protection).
+    /// Files whose threads are all resolved are omitted.
+    pub fn unresolved_counts(&self) -> HashMap<PathBuf, usize> {
+        self.actions
+            .iter()
+            .filter_map(|(path, actions)| {
+                let count = materialize_with_depth(actions, self.max_reply_depth)
+                    .iter()
+                    .filter(|comment| !comment.resolved && !comment.deleted)
+                    .count();
+                (count > 0).then(|| (path.clone(), count))
+            })
+            .collect()
+    }
+
+    /// Create a new top-level inline comment on a diff.
     ///
-    /// Generates the anchor context automatically from the git tree and
-    /// assigns a new UUID v4 as the comment ID.
-    pub fn create_comment(
-        &mut self,
-        sha: CommitId,
-        file_path: &Path,
-        side: DiffSide,
-        line: u32,
-        start_line: Option<u32>,
-        body: String,
-    ) -> Result<()> {
+    /// Generates the anchor context automatically from the git tree at
+    /// `comment.sha` and assigns a new UUID v4 as the comment ID.
+    pub fn create_comment(&mut self, file_path: &Path, comment: NewComment) -> Result<()> {
+        let NewComment {
+            sha,
+            side,
+            line,
+            start_line,
+            body,
+            author,
+        } = comment;
         let anchor = self.build_anchor(sha, file_path, side, line, start_line)?;
         self.append_action(
             file_path,
@@ -120,11 +203,12 @@ impl<'a> CommentCommit<'a> {
                 start_line,
                 body,
                 anchor,
+                author,
             },
         )
     }
 
-    /// Reply to an existing top-level comment (flat threads only).
+    /// Reply to an existing comment or to another reply in its thread.
     ///
     /// Assigns a new UUID v4 as the reply ID.
     pub fn reply_to_comment(
@@ -132,6 +216,7 @@ impl<'a> CommentCommit<'a> {
         file_path: &Path,
         parent_comment_id: String,
         body: String,
+        author: String,
     ) -> Result<()> {
         self.append_action(
             file_path,
@@ -139,6 +224,7 @@ impl<'a> CommentCommit<'a> {
                 comment_id: uuid::Uuid::new_v4().to_string(),
                 parent_comment_id,
                 body,
+                author,
             },
         )
     }
@@ -153,6 +239,14 @@ impl<'a> CommentCommit<'a> {
         self.append_action(file_path, CommentAction::Edit { comment_id, body })
     }
 
+    /// Soft-delete a comment thread (targets the root comment only).
+    ///
+    /// The thread and its replies stay in the action log but are hidden from
+    /// [`CommentCommit::get_file_comments`].
+    pub fn delete_comment(&mut self, file_path: &Path, comment_id: String) -> Result<()> {
+        self.append_action(file_path, CommentAction::Delete { comment_id })
+    }
+
     /// Resolve a comment thread (targets the root comment only).
     pub fn resolve_comment(&mut self, file_path: &Path, comment_id: String) -> Result<()> {
         self.append_action(file_path, CommentAction::Resolve { comment_id })
@@ -163,11 +257,105 @@ impl<'a> CommentCommit<'a> {
         self.append_action(file_path, CommentAction::Unresolve { comment_id })
     }
 
+    /// Snapshot the raw action log, including deleted threads.
+    pub fn export_log(&self) -> CommentLog {
+        CommentLog(self.actions.clone())
+    }
+
+    /// Append the actions of an exported log, keeping their original ids and timestamps.
+    ///
+    /// Actions whose `action_id` is already present are skipped, so importing the same log
+    /// twice is a no-op. Returns the number of actions appended.
+    pub fn import_log(&mut self, log: CommentLog) -> usize {
+        let mut seen: HashSet<String> = self
+            .actions
+            .values()
+            .flatten()
+            .map(|entry| entry.action_id.clone())
+            .collect();
+
+        let mut imported = 0;
+        for (file_path, entries) in log.0 {
+            let actions = self.actions.entry(file_path).or_default();
+            for entry in entries {
+                if seen.insert(entry.action_id.clone()) {
+                    actions.push(entry);
+                    imported += 1;
+                }
+            }
+        }
+        if imported > 0 {
+            self.dirty.set(true);
+        }
+        imported
+    }
+
+    /// Move every thread anchored to `old_sha` onto `new_sha` after the commit was rewritten.
+    ///
+    /// Each thread's anchor text is searched for in `new_sha` and a `Reanchor` action records
+    /// the new position. Threads whose anchor no longer matches are marked orphaned rather than
+    /// guessed at.
+    pub fn reanchor(&mut self, old_sha: CommitId, new_sha: CommitId) -> Result<()> {
+        let stale: Vec<(PathBuf, MaterializedComment)> = self
+            .get_all_comments()
+            .into_iter()
+            .flat_map(|(path, comments)| comments.into_iter().map(move |c| (path.clone(), c)))
+            .filter(|(_, c)| c.target_sha == old_sha && !c.orphaned)
+            .collect();
+
+        for (file_path, comment) in stale {
+            let content = side_tree(self.repo, new_sha, comment.side)?
+                .and_then(|tree| read_file_from_tree(self.repo, &tree, &file_path));
+            let anchor_start = content
+                .as_deref()
+                .and_then(|content| find_anchor_position(content, &comment.anchor));
+            let (line, start_line) = ported_lines(anchor_start, comment.line, comment.start_line);
+
+            self.append_action(
+                &file_path,
+                CommentAction::Reanchor {
+                    comment_id: comment.id,
+                    target_sha: new_sha,
+                    side: comment.side,
+                    line,
+                    start_line,
+                },
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Threads whose anchor text can no longer be found in `current_sha`, as
+    /// `(file path, comment id)` ordered by file and line.
+    ///
+    /// Threads already marked orphaned count as outdated; deleted threads are skipped. A thread
+    /// whose anchor merely moved is not outdated.
+    pub fn outdated_comments(&self, current_sha: CommitId) -> Result<Vec<(PathBuf, String)>> {
+        let mut outdated = Vec::new();
+        for (file_path, comments) in self.get_all_comments() {
+            for comment in comments {
+                let found = !comment.orphaned && {
+                    let content = side_tree(self.repo, current_sha, comment.side)?
+                        .and_then(|tree| read_file_from_tree(self.repo, &tree, &file_path));
+                    content
+                        .as_deref()
+                        .and_then(|content| find_anchor_position(content, &comment.anchor))
+                        .is_some()
+                };
+                if !found {
+                    outdated.push((file_path.clone(), comment.line, comment.id));
+                }
+            }
+        }
+        outdated.sort();
+        Ok(outdated
+            .into_iter()
+            .map(|(file_path, _, id)| (file_path, id))
+            .collect())
+    }
+
     /// Build anchor context by reading file content from the git tree of the
     /// given commit SHA.
-    ///
-    /// For `DiffSide::New`, reads from the commit's tree.
-    /// For `DiffSide::Old`, reads from the commit's parent tree.
     fn build_anchor(
         &self,
         sha: CommitId,
@@ -176,54 +364,15 @@ impl<'a> CommentCommit<'a> {
         line: u32,
         start_line: Option<u32>,
     ) -> Result<AnchorContext> {
-        let commit = self.repo.find_commit(sha.oid())?;
-        let tree = match side {
-            DiffSide::New => commit.tree()?,
-            DiffSide::Old => {
-                let parent = commit.parent(0).map_err(|_| {
-                    Error::Internal("cannot comment on old side of initial commit".into())
-                })?;
-                parent.tree()?
-            }
-        };
-
-        let content = read_file_from_tree(self.repo, &tree, file_path).ok_or_else(|| {
-            Error::Internal(format!("file not found in tree: {}", file_path.display()))
-        })?;
-
-        let lines: Vec<&str> = content.lines().collect();
-        let total = lines.len();
-
-        // Determine the target range (1-based → 0-based).
-        let start_0 = start_line.unwrap_or(line).saturating_sub(1) as usize;
-        let end_0 = line.saturating_sub(1) as usize;
-
-        if start_0 >= total || end_0 >= total || start_0 > end_0 {
-            return Err(Error::Internal(format!(
-                "line range out of bounds: start={}, end={}, total={}",
-                start_0 + 1,
-                end_0 + 1,
-                total
-            )));
-        }
-
-        let before_start = start_0.saturating_sub(ANCHOR_CONTEXT_LINES);
-        let after_end = (end_0 + 1 + ANCHOR_CONTEXT_LINES).min(total);
-
-        Ok(AnchorContext {
-            before: lines[before_start..start_0]
-                .iter()
-                .map(|s| s.to_string())
-                .collect(),
-            target: lines[start_0..=end_0]
-                .iter()
-                .map(|s| s.to_string())
-                .collect(),
-            after: lines[end_0 + 1..after_end]
-                .iter()
-                .map(|s| s.to_string())
-                .collect(),
-        })
+        get_context_lines(
+            self.repo,
+            sha,
+            file_path,
+            side,
+            line,
+            start_line,
+            ANCHOR_CONTEXT_LINES,
+        )
     }
 
     /// Append an action to the log for a specific file.
@@ -232,28 +381,61 @@ impl<'a> CommentCommit<'a> {
     /// for `created_at`.
     ///
     /// Validates:
-    /// - `Reply.parent_comment_id` must reference an existing `Create` action
+    /// - `Reply.parent_comment_id` must reference an existing `Create` or `Reply` action
     /// - `Resolve`/`Unresolve` must target a `Create` action (thread root)
     /// - `Edit` must target an existing `Create` or `Reply` action
+    ///
+    /// Actions on an existing comment are stored with it even when `file_path` differs, since
+    /// a thread ported across a rename is shown (and addressed) under the file's new path.
     fn append_action(&mut self, file_path: &Path, action: CommentAction) -> Result<()> {
+        let file_path = self.log_path(file_path, &action);
+
         // Validate before borrowing mutably.
-        let existing = self.actions.get(file_path).map(|v| v.as_slice());
+        let existing = self.actions.get(&file_path).map(|v| v.as_slice());
         validate_action(existing.unwrap_or(&[]), &action)?;
 
-        let actions = self.actions.entry(file_path.to_path_buf()).or_default();
+        let actions = self.actions.entry(file_path).or_default();
         let entry = ActionEntry {
             action_id: uuid::Uuid::new_v4().to_string(),
             created_at: now_iso8601(),
             action,
         };
         actions.push(entry);
+        self.dirty.set(true);
         Ok(())
     }
 
+    /// File whose log holds the comment `action` targets, falling back to `file_path` for new
+    /// threads and unknown ids (which validation then rejects).
+    fn log_path(&self, file_path: &Path, action: &CommentAction) -> PathBuf {
+        let target = match action {
+            CommentAction::Create { .. } => return file_path.to_path_buf(),
+            CommentAction::Reply {
+                parent_comment_id, ..
+            } => parent_comment_id,
+            CommentAction::Edit { comment_id, .. }
+            | CommentAction::Delete { comment_id }
+            | CommentAction::Resolve { comment_id }
+            | CommentAction::Unresolve { comment_id }
+            | CommentAction::Reanchor { comment_id, .. } => comment_id,
+        };
+        let holds = |actions: &Vec<ActionEntry>| {
+            has_create_action(actions, target) || has_reply_action(actions, target)
+        };
+        if self.actions.get(file_path).is_some_and(holds) {
+            return file_path.to_path_buf();
+        }
+        self.actions
+            .iter()
+            .find(|(_, actions)| holds(actions))
+            .map_or_else(|| file_path.to_path_buf(), |(path, _)| path.clone())
+    }
+
     /// Write the current state to a git commit and update the ref.
     ///
     /// The comment-commit's parents are all unique target SHAs referenced in
-    /// `Create` actions, which prevents those commits from being garbage collected.
+    /// `Create` and `Reanchor` actions, which prevents those commits from being
+    /// garbage collected.
     ///
     /// Returns the `CommitId` of the newly created comment-commit.
     pub fn write(&self) -> Result<CommitId> {
@@ -283,18 +465,20 @@ impl<'a> CommentCommit<'a> {
             self.change_id,
         );
         self.repo.reference(&ref_name, oid, true, &log_message)?;
+        self.dirty.set(false);
 
         Ok(CommitId::from(oid))
     }
 
-    /// Collect all unique target SHAs from Create actions across all files.
+    /// Collect all unique target SHAs from Create and Reanchor actions across all files.
     fn collect_parent_commits(&self) -> Result<Vec<git2::Commit<'a>>> {
         let mut seen = HashSet::new();
         let mut commits = Vec::new();
 
         for actions in self.actions.values() {
             for entry in actions {
-                if let CommentAction::Create { target_sha, .. } = &entry.action
+                if let CommentAction::Create { target_sha, .. }
+                | CommentAction::Reanchor { target_sha, .. } = &entry.action
                     && seen.insert(*target_sha)
                 {
                     let commit = self.repo.find_commit(target_sha.oid())?;
@@ -391,9 +575,16 @@ fn validate_action(existing_actions: &[ActionEntry], action: &CommentAction) ->
         CommentAction::Reply {
             parent_comment_id, ..
         } => {
-            if !has_create_action(existing_actions, parent_comment_id) {
+            // Replies get fresh IDs and must name a parent that already exists, so a chain of
+            // replies can never loop back on itself.
+            if !has_create_action(existing_actions, parent_comment_id)
+                && !has_reply_action(existing_actions, parent_comment_id)
+            {
                 return Err(Error::InvalidAction {
-                    message: format!("Reply targets non-existent comment: {}", parent_comment_id,),
+                    message: format!(
+                        "Reply targets non-existent comment or reply: {}",
+                        parent_comment_id,
+                    ),
                 });
             }
             Ok(())
@@ -424,9 +615,29 @@ fn validate_action(existing_actions: &[ActionEntry], action: &CommentAction) ->
             }
             Ok(())
         }
+        CommentAction::Delete { comment_id } => {
+            if !has_create_action(existing_actions, comment_id) {
+                return Err(Error::InvalidAction {
+                    message: format!("Delete targets non-existent thread root: {}", comment_id,),
+                });
+            }
+            Ok(())
+        }
+        CommentAction::Reanchor { comment_id, .. } => {
+            if !has_create_action(existing_actions, comment_id) {
+                return Err(Error::InvalidAction {
+                    message: format!("Reanchor targets non-existent thread root: {}", comment_id,),
+                });
+            }
+            Ok(())
+        }
     }
 }
 
+fn without_deleted(comments: Vec<MaterializedComment>) -> Vec<MaterializedComment> {
+    comments.into_iter().filter(|c| !c.deleted).collect()
+}
+
 /// Check if an action log contains a Create action with the given comment_id.
 fn has_create_action(actions: &[ActionEntry], comment_id: &str) -> bool {
     actions.iter().any(|entry| {
@@ -481,6 +692,23 @@ fn epoch_days_to_date(days: u64) -> (u64, u64, u64) {
     (y, m, d)
 }
 
+/// Materialized comments of every change in a stack, keyed by change and then by file.
+///
+/// Changes without a comment ref map to an empty file map. Each comment-commit is locked only
+/// while it is read.
+pub fn get_stack_comments(
+    repo: &Repository,
+    change_ids: &[ChangeId],
+) -> Result<HashMap<ChangeId, HashMap<PathBuf, Vec<MaterializedComment>>>> {
+    change_ids
+        .iter()
+        .map(|&change_id| {
+            let cc = CommentCommit::for_change(repo, change_id)?;
+            Ok((change_id, cc.get_all_comments()))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -498,12 +726,15 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
             cc.create_comment(
-                sha,
                 Path::new("src/main.rs"),
-                DiffSide::New,
-                1,
-                None,
-                "looks good".to_string(),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "looks good".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.write().unwrap();
@@ -520,6 +751,104 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_import_log_skips_known_actions() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo.write_file("src/main.rs", "fn main() {}").unwrap();
+        let sha = test_repo
+            .commit("initial commit")
+            .unwrap()
+            .created
+            .commit_id;
+
+        let log = {
+            let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
+            cc.create_comment(
+                Path::new("src/main.rs"),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "looks good".to_string(),
+                    author: "alice".to_string(),
+                },
+            )
+            .unwrap();
+            let comment_id = cc.get_file_comments(Path::new("src/main.rs"))[0].id.clone();
+            cc.reply_to_comment(
+                Path::new("src/main.rs"),
+                comment_id,
+                "thanks".to_string(),
+                "alice".to_string(),
+            )
+            .unwrap();
+            cc.export_log()
+        };
+
+        let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
+        assert_eq!(cc.import_log(log.clone()), 2);
+        assert_eq!(cc.import_log(log), 0);
+
+        let comments = cc.get_file_comments(Path::new("src/main.rs"));
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].replies.len(), 1);
+    }
+
+    #[test]
+    fn test_session_writes_one_commit() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo
+            .write_file("src/main.rs", "fn main() {}\nfn a() {}\nfn b() {}\n")
+            .unwrap();
+        let sha = test_repo
+            .commit("initial commit")
+            .unwrap()
+            .created
+            .commit_id;
+
+        CommentCommit::with_session(&test_repo.repo, sha, |cc| {
+            for line in 1..=3 {
+                cc.create_comment(
+                    Path::new("src/main.rs"),
+                    NewComment {
+                        sha,
+                        side: DiffSide::New,
+                        line,
+                        start_line: None,
+                        body: format!("comment {line}"),
+                        author: "alice".to_string(),
+                    },
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let mut comment_commits = 0;
+        test_repo
+            .repo
+            .odb()
+            .unwrap()
+            .foreach(|oid| {
+                if let Ok(commit) = test_repo.repo.find_commit(*oid)
+                    && commit
+                        .message()
+                        .is_some_and(|m| m.starts_with("update comments"))
+                {
+                    comment_commits += 1;
+                }
+                true
+            })
+            .unwrap();
+        assert_eq!(comment_commits, 1);
+
+        let cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
+        assert!(!cc.dirty());
+        assert_eq!(cc.write_if_dirty().unwrap(), None);
+        assert_eq!(cc.get_file_comments(Path::new("src/main.rs")).len(), 3);
+    }
+
     #[test]
     fn test_append_reply_and_read() {
         let test_repo = TestRepo::new().unwrap();
@@ -530,20 +859,28 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
             cc.create_comment(
-                sha,
                 Path::new("lib.rs"),
-                DiffSide::New,
-                1,
-                None,
-                "why public?".to_string(),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "why public?".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
 
             let comments = cc.get_file_comments(Path::new("lib.rs"));
             let comment_id = comments[0].id.clone();
 
-            cc.reply_to_comment(Path::new("lib.rs"), comment_id, "for testing".to_string())
-                .unwrap();
+            cc.reply_to_comment(
+                Path::new("lib.rs"),
+                comment_id,
+                "for testing".to_string(),
+                "alice".to_string(),
+            )
+            .unwrap();
             cc.write().unwrap();
         }
 
@@ -556,6 +893,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_reply_to_reply_nests() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo.write_file("lib.rs", "pub fn foo() {}").unwrap();
+        let sha = test_repo.commit("add lib").unwrap().created.commit_id;
+        let path = Path::new("lib.rs");
+
+        let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
+        cc.create_comment(
+            path,
+            NewComment {
+                sha,
+                side: DiffSide::New,
+                line: 1,
+                start_line: None,
+                body: "why public?".to_string(),
+                author: "alice".to_string(),
+            },
+        )
+        .unwrap();
+        let root_id = cc.get_file_comments(path)[0].id.clone();
+        cc.reply_to_comment(path, root_id, "for tests".to_string(), "bob".to_string())
+            .unwrap();
+        let reply_id = cc.get_file_comments(path)[0].replies[0].id.clone();
+        cc.reply_to_comment(
+            path,
+            reply_id,
+            "use pub(crate)".to_string(),
+            "alice".to_string(),
+        )
+        .unwrap();
+
+        let comments = cc.get_file_comments(path);
+        let reply = &comments[0].replies[0];
+        assert_eq!(comments[0].replies.len(), 1);
+        assert_eq!(reply.body, "for tests");
+        assert_eq!(reply.replies.len(), 1);
+        assert_eq!(reply.replies[0].body, "use pub(crate)");
+        assert!(reply.replies[0].replies.is_empty());
+
+        cc.set_max_reply_depth(1);
+        let comments = cc.get_file_comments(path);
+        let bodies: Vec<_> = comments[0]
+            .replies
+            .iter()
+            .map(|r| r.body.as_str())
+            .collect();
+        assert_eq!(bodies, ["for tests", "use pub(crate)"]);
+    }
+
     #[test]
     fn test_edit_and_resolve() {
         let test_repo = TestRepo::new().unwrap();
@@ -567,12 +954,15 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
             cc.create_comment(
-                sha,
                 Path::new("app.rs"),
-                DiffSide::New,
-                1,
-                None,
-                "original".to_string(),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "original".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
 
@@ -600,6 +990,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_delete_hides_thread_and_replies() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo.write_file("app.rs", "fn app() {}").unwrap();
+        let result = test_repo.commit("add app").unwrap();
+        let sha = result.created.commit_id;
+
+        {
+            let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
+            cc.create_comment(
+                Path::new("app.rs"),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "mistake".to_string(),
+                    author: "alice".to_string(),
+                },
+            )
+            .unwrap();
+
+            let comment_id = cc.get_file_comments(Path::new("app.rs"))[0].id.clone();
+            cc.reply_to_comment(
+                Path::new("app.rs"),
+                comment_id.clone(),
+                "reply".to_string(),
+                "alice".to_string(),
+            )
+            .unwrap();
+            cc.delete_comment(Path::new("app.rs"), comment_id).unwrap();
+            cc.write().unwrap();
+        }
+
+        let cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
+        assert!(cc.get_file_comments(Path::new("app.rs")).is_empty());
+        assert!(cc.get_all_comments()[Path::new("app.rs")].is_empty());
+
+        let all = cc.get_file_comments_including_deleted(Path::new("app.rs"));
+        assert_eq!(all.len(), 1);
+        assert!(all[0].deleted);
+        assert_eq!(all[0].replies.len(), 1);
+    }
+
+    #[test]
+    fn test_delete_nonexistent_comment_fails() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo.write_file("main.rs", "fn main() {}").unwrap();
+        let result = test_repo.commit("init").unwrap();
+        let sha = result.created.commit_id;
+
+        let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
+        let result = cc.delete_comment(Path::new("main.rs"), "nonexistent".to_string());
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_multiple_files() {
         let test_repo = TestRepo::new().unwrap();
@@ -611,21 +1057,27 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
             cc.create_comment(
-                sha,
                 Path::new("a.rs"),
-                DiffSide::New,
-                1,
-                None,
-                "comment on a".to_string(),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "comment on a".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.create_comment(
-                sha,
                 Path::new("b.rs"),
-                DiffSide::New,
-                1,
-                None,
-                "comment on b".to_string(),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "comment on b".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.write().unwrap();
@@ -654,12 +1106,15 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
             cc.create_comment(
-                sha,
                 Path::new("src/services/auth.rs"),
-                DiffSide::New,
-                1,
-                None,
-                "nested comment".to_string(),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "nested comment".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.write().unwrap();
@@ -686,12 +1141,15 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
             cc.create_comment(
-                sha,
                 Path::new("main.rs"),
-                DiffSide::New,
-                1,
-                None,
-                "first comment".to_string(),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "first comment".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.write().unwrap();
@@ -701,12 +1159,15 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
             cc.create_comment(
-                sha,
                 Path::new("main.rs"),
-                DiffSide::New,
-                5,
-                None,
-                "second comment".to_string(),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 5,
+                    start_line: None,
+                    body: "second comment".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.write().unwrap();
@@ -722,6 +1183,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_file_comments_follow_line_order() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo
+            .write_file("main.rs", "line 1\nline 2\nline 3\nline 4\nline 5\n")
+            .unwrap();
+        let sha = test_repo.commit("init").unwrap().created.commit_id;
+
+        let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
+        for (line, body) in [(5, "on line 5"), (1, "on line 1")] {
+            cc.create_comment(
+                Path::new("main.rs"),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line,
+                    start_line: None,
+                    body: body.to_string(),
+                    author: "alice".to_string(),
+                },
+            )
+            .unwrap();
+        }
+
+        let comments = cc.get_file_comments(Path::new("main.rs"));
+        let bodies: Vec<_> = comments.iter().map(|c| c.body.as_str()).collect();
+        assert_eq!(bodies, ["on line 1", "on line 5"]);
+    }
+
     #[test]
     fn test_reply_to_nonexistent_parent_fails() {
         let test_repo = TestRepo::new().unwrap();
@@ -734,6 +1224,7 @@ mod tests {
             Path::new("main.rs"),
             "nonexistent".to_string(),
             "orphan reply".to_string(),
+            "alice".to_string(),
         );
         assert!(result.is_err());
         assert!(
@@ -783,12 +1274,15 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
             cc.create_comment(
-                sha,
                 Path::new("main.rs"),
-                DiffSide::New,
-                1,
-                None,
-                "test".to_string(),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "test".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             comment_sha = cc.write().unwrap();
@@ -824,21 +1318,27 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, sha_v1).unwrap();
             cc.create_comment(
-                sha_v1,
                 Path::new("main.rs"),
-                DiffSide::New,
-                1,
-                None,
-                "from v1".to_string(),
+                NewComment {
+                    sha: sha_v1,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "from v1".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.create_comment(
-                sha_v2,
                 Path::new("main.rs"),
-                DiffSide::New,
-                4,
-                None,
-                "from v2".to_string(),
+                NewComment {
+                    sha: sha_v2,
+                    side: DiffSide::New,
+                    line: 4,
+                    start_line: None,
+                    body: "from v2".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             comment_sha = cc.write().unwrap();
@@ -865,21 +1365,27 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
             cc.create_comment(
-                sha,
                 Path::new("a.rs"),
-                DiffSide::New,
-                1,
-                None,
-                "on a".to_string(),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "on a".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.create_comment(
-                sha,
                 Path::new("b.rs"),
-                DiffSide::New,
-                1,
-                None,
-                "on b".to_string(),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "on b".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.write().unwrap();
@@ -894,6 +1400,117 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_unresolved_counts_skip_resolved_threads() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo
+            .write_file("main.rs", "fn main() {}\nfn other() {}\n")
+            .unwrap();
+        let result = test_repo.commit("add main").unwrap();
+        let sha = result.created.commit_id;
+
+        {
+            let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
+            cc.create_comment(
+                Path::new("main.rs"),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "first".to_string(),
+                    author: "alice".to_string(),
+                },
+            )
+            .unwrap();
+            cc.create_comment(
+                Path::new("main.rs"),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 2,
+                    start_line: None,
+                    body: "second".to_string(),
+                    author: "alice".to_string(),
+                },
+            )
+            .unwrap();
+
+            let comments = cc.get_file_comments(Path::new("main.rs"));
+            let first = comments.iter().find(|c| c.body == "first").unwrap();
+            cc.resolve_comment(Path::new("main.rs"), first.id.clone())
+                .unwrap();
+            cc.write().unwrap();
+        }
+
+        let cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
+        let counts = cc.unresolved_counts();
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts.get(Path::new("main.rs")), Some(&1));
+    }
+
+    #[test]
+    fn test_get_file_comments_filtered() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo
+            .write_file("main.rs", "fn main() {}\nfn other() {}\n")
+            .unwrap();
+        let sha = test_repo.commit("add main").unwrap().created.commit_id;
+        let path = Path::new("main.rs");
+
+        let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
+        for (line, body) in [(1, "open"), (2, "done")] {
+            cc.create_comment(
+                path,
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line,
+                    start_line: None,
+                    body: body.to_string(),
+                    author: "alice".to_string(),
+                },
+            )
+            .unwrap();
+        }
+        let done_id = cc
+            .get_file_comments(path)
+            .into_iter()
+            .find(|c| c.body == "done")
+            .unwrap()
+            .id;
+        cc.reply_to_comment(
+            path,
+            done_id.clone(),
+            "fixed".to_string(),
+            "bob".to_string(),
+        )
+        .unwrap();
+        cc.resolve_comment(path, done_id).unwrap();
+
+        let bodies = |filter| {
+            let mut bodies: Vec<_> = cc
+                .get_file_comments_filtered(path, filter)
+                .into_iter()
+                .map(|c| (c.body, c.replies.len()))
+                .collect();
+            bodies.sort();
+            bodies
+        };
+        assert_eq!(
+            bodies(CommentFilter::All),
+            vec![("done".to_string(), 1), ("open".to_string(), 0)]
+        );
+        assert_eq!(
+            bodies(CommentFilter::UnresolvedOnly),
+            vec![("open".to_string(), 0)]
+        );
+        assert_eq!(
+            bodies(CommentFilter::ResolvedOnly),
+            vec![("done".to_string(), 1)]
+        );
+    }
+
     #[test]
     fn test_build_anchor_generates_context() {
         let test_repo = TestRepo::new().unwrap();
@@ -908,12 +1525,15 @@ mod tests {
 
         let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
         cc.create_comment(
-            sha,
             Path::new("main.rs"),
-            DiffSide::New,
-            4,
-            None,
-            "middle line".to_string(),
+            NewComment {
+                sha,
+                side: DiffSide::New,
+                line: 4,
+                start_line: None,
+                body: "middle line".to_string(),
+                author: "alice".to_string(),
+            },
         )
         .unwrap();
 
@@ -939,12 +1559,15 @@ mod tests {
         let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
         // Multi-line: start_line=3, line=5 → target is lines 3,4,5
         cc.create_comment(
-            sha,
             Path::new("main.rs"),
-            DiffSide::New,
-            5,
-            Some(3),
-            "block comment".to_string(),
+            NewComment {
+                sha,
+                side: DiffSide::New,
+                line: 5,
+                start_line: Some(3),
+                body: "block comment".to_string(),
+                author: "alice".to_string(),
+            },
         )
         .unwrap();
 
@@ -964,14 +1587,316 @@ mod tests {
 
         let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
         let result = cc.create_comment(
-            sha,
             Path::new("main.rs"),
-            DiffSide::Old,
-            1,
-            None,
-            "old side".to_string(),
+            NewComment {
+                sha,
+                side: DiffSide::Old,
+                line: 1,
+                start_line: None,
+                body: "old side".to_string(),
+                author: "alice".to_string(),
+            },
         );
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("initial commit"));
     }
+
+    #[test]
+    fn test_comment_on_context_line_from_either_side() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo.write_file("main.rs", "a\nb\nc\n").unwrap();
+        test_repo.commit("base").unwrap();
+        test_repo.write_file("main.rs", "new\na\nb\nc\n").unwrap();
+        let sha = test_repo.commit("insert line").unwrap().created.commit_id;
+
+        // `b` is a context line: line 2 in the parent, line 3 in the commit.
+        assert_eq!(
+            DiffSide::for_line(Some(2), Some(3)),
+            Some((DiffSide::New, 3))
+        );
+        assert_eq!(DiffSide::for_line(Some(2), None), Some((DiffSide::Old, 2)));
+
+        let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
+        for (side, line) in [(DiffSide::Old, 2), (DiffSide::New, 3)] {
+            cc.create_comment(
+                Path::new("main.rs"),
+                NewComment {
+                    sha,
+                    side,
+                    line,
+                    start_line: None,
+                    body: format!("{side:?}"),
+                    author: "alice".to_string(),
+                },
+            )
+            .unwrap();
+        }
+
+        let comments = cc.get_file_comments(Path::new("main.rs"));
+        assert_eq!(comments.len(), 2);
+        for comment in &comments {
+            assert_eq!(comment.anchor.target, vec!["b"], "{:?}", comment.side);
+        }
+    }
+
+    #[test]
+    fn test_create_comment_line_out_of_range() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo.write_file("main.rs", "a\nb\n").unwrap();
+        test_repo.commit("base").unwrap();
+        test_repo.write_file("main.rs", "a\nb\nc\n").unwrap();
+        let sha = test_repo.commit("append").unwrap().created.commit_id;
+
+        let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
+        let mut comment = |side, line| {
+            cc.create_comment(
+                Path::new("main.rs"),
+                NewComment {
+                    sha,
+                    side,
+                    line,
+                    start_line: None,
+                    body: "here".to_string(),
+                    author: "alice".to_string(),
+                },
+            )
+        };
+
+        // Line 3 only exists in the commit.
+        assert!(comment(DiffSide::New, 3).is_ok());
+        assert!(matches!(
+            comment(DiffSide::Old, 3),
+            Err(Error::LineOutOfRange {
+                side: DiffSide::Old,
+                start: 3,
+                end: 3,
+                total: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_old_side_of_merge_reads_merged_parents() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo.write_file("shared", "base\n").unwrap();
+        let a = test_repo.commit("A").unwrap().created;
+        test_repo.write_file("left", "left\n").unwrap();
+        let b = test_repo.commit("B").unwrap().created;
+        test_repo.new_revision(a.change_id).unwrap();
+        test_repo.write_file("right", "right\n").unwrap();
+        let c = test_repo.commit("C").unwrap().created;
+
+        test_repo
+            .merge(&[b.change_id, c.change_id], "merge")
+            .unwrap();
+        test_repo.write_file("right", "changed\n").unwrap();
+        let merge = test_repo.work_copy().unwrap();
+
+        // `right` only exists in the second parent.
+        let mut cc = CommentCommit::get(&test_repo.repo, merge.commit_id).unwrap();
+        cc.create_comment(
+            Path::new("right"),
+            NewComment {
+                sha: merge.commit_id,
+                side: DiffSide::Old,
+                line: 1,
+                start_line: None,
+                body: "why change this?".to_string(),
+                author: "alice".to_string(),
+            },
+        )
+        .unwrap();
+
+        let comments = cc.get_file_comments(Path::new("right"));
+        assert_eq!(comments[0].anchor.target, vec!["right"]);
+    }
+
+    #[test]
+    fn test_reanchor_follows_moved_lines() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo
+            .write_file("main.rs", "a\nb\ntarget\nc\nd\n")
+            .unwrap();
+        let created = test_repo.commit("init").unwrap().created;
+        let old_sha = created.commit_id;
+
+        {
+            let mut cc = CommentCommit::get(&test_repo.repo, old_sha).unwrap();
+            cc.create_comment(
+                Path::new("main.rs"),
+                NewComment {
+                    sha: old_sha,
+                    side: DiffSide::New,
+                    line: 3,
+                    start_line: None,
+                    body: "follow me".to_string(),
+                    author: "alice".to_string(),
+                },
+            )
+            .unwrap();
+            cc.write().unwrap();
+        }
+
+        // Rewrite the change: two new lines push the commented line down.
+        test_repo.edit(created.change_id).unwrap();
+        test_repo
+            .write_file("main.rs", "new 1\nnew 2\na\nb\ntarget\nc\nd\n")
+            .unwrap();
+        let new_sha = test_repo.work_copy().unwrap().commit_id;
+
+        {
+            let mut cc = CommentCommit::get(&test_repo.repo, new_sha).unwrap();
+            cc.reanchor(old_sha, new_sha).unwrap();
+            cc.write().unwrap();
+        }
+
+        let cc = CommentCommit::get(&test_repo.repo, new_sha).unwrap();
+        let comments = cc.get_file_comments(Path::new("main.rs"));
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].line, 5);
+        assert_eq!(comments[0].target_sha, new_sha);
+        assert!(!comments[0].orphaned);
+
+        // The original Create is preserved alongside the Reanchor.
+        let actions = cc.get_file_actions(Path::new("main.rs"));
+        assert_eq!(actions.len(), 2);
+        assert!(matches!(
+            &actions[0].action,
+            CommentAction::Create { line: 3, target_sha, .. } if *target_sha == old_sha
+        ));
+    }
+
+    #[test]
+    fn test_reanchor_orphans_when_anchor_changed() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo
+            .write_file("main.rs", "a\nb\ntarget\nc\nd\n")
+            .unwrap();
+        let created = test_repo.commit("init").unwrap().created;
+        let old_sha = created.commit_id;
+
+        test_repo.edit(created.change_id).unwrap();
+        test_repo
+            .write_file("main.rs", "a\nb\nrewritten\nc\nd\n")
+            .unwrap();
+        let new_sha = test_repo.work_copy().unwrap().commit_id;
+
+        let mut cc = CommentCommit::get(&test_repo.repo, new_sha).unwrap();
+        cc.create_comment(
+            Path::new("main.rs"),
+            NewComment {
+                sha: old_sha,
+                side: DiffSide::New,
+                line: 3,
+                start_line: None,
+                body: "stale".to_string(),
+                author: "alice".to_string(),
+            },
+        )
+        .unwrap();
+        cc.reanchor(old_sha, new_sha).unwrap();
+
+        let comments = cc.get_file_comments(Path::new("main.rs"));
+        assert_eq!(comments.len(), 1);
+        assert!(comments[0].orphaned);
+        assert_eq!(
+            comments[0].line, 3,
+            "orphaned comments keep their last position"
+        );
+        assert_eq!(comments[0].target_sha, old_sha);
+    }
+
+    #[test]
+    fn test_outdated_comments_lists_only_lost_anchors() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo
+            .write_file("main.rs", "a\nb\ntarget\nc\nd\nkept\ne\n")
+            .unwrap();
+        let created = test_repo.commit("init").unwrap().created;
+        let old_sha = created.commit_id;
+
+        let mut cc = CommentCommit::get(&test_repo.repo, old_sha).unwrap();
+        for (line, body) in [(3, "will go stale"), (6, "still valid")] {
+            cc.create_comment(
+                Path::new("main.rs"),
+                NewComment {
+                    sha: old_sha,
+                    side: DiffSide::New,
+                    line,
+                    start_line: None,
+                    body: body.to_string(),
+                    author: "alice".to_string(),
+                },
+            )
+            .unwrap();
+        }
+        cc.write().unwrap();
+        drop(cc);
+
+        test_repo.edit(created.change_id).unwrap();
+        test_repo
+            .write_file("main.rs", "a\nb\nrewritten\nc\nd\nkept\ne\n")
+            .unwrap();
+        let new_sha = test_repo.work_copy().unwrap().commit_id;
+
+        let cc = CommentCommit::get(&test_repo.repo, new_sha).unwrap();
+        let stale_id = cc
+            .get_file_comments(Path::new("main.rs"))
+            .into_iter()
+            .find(|c| c.body == "will go stale")
+            .unwrap()
+            .id;
+
+        assert!(cc.outdated_comments(old_sha).unwrap().is_empty());
+        assert_eq!(
+            cc.outdated_comments(new_sha).unwrap(),
+            vec![(PathBuf::from("main.rs"), stale_id)]
+        );
+    }
+
+    #[test]
+    fn test_get_stack_comments_keys_by_change() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo.write_file("a.rs", "a\n").unwrap();
+        let first = test_repo.commit("first").unwrap().created;
+        test_repo.write_file("b.rs", "b\n").unwrap();
+        let second = test_repo.commit("second").unwrap().created;
+        test_repo.write_file("c.rs", "c\n").unwrap();
+        let third = test_repo.commit("third").unwrap().created;
+
+        for (commit_id, file, body) in [
+            (first.commit_id, "a.rs", "on first"),
+            (second.commit_id, "b.rs", "on second"),
+        ] {
+            CommentCommit::with_session(&test_repo.repo, commit_id, |cc| {
+                cc.create_comment(
+                    Path::new(file),
+                    NewComment {
+                        sha: commit_id,
+                        side: DiffSide::New,
+                        line: 1,
+                        start_line: None,
+                        body: body.to_string(),
+                        author: "alice".to_string(),
+                    },
+                )
+            })
+            .unwrap();
+        }
+
+        let change_ids = [first.change_id, second.change_id, third.change_id];
+        let stack = get_stack_comments(&test_repo.repo, &change_ids).unwrap();
+        assert_eq!(stack.len(), 3);
+        assert_eq!(
+            stack[&first.change_id][Path::new("a.rs")][0].body,
+            "on first"
+        );
+        assert_eq!(
+            stack[&second.change_id][Path::new("b.rs")][0].body,
+            "on second"
+        );
+        assert!(!stack[&first.change_id].contains_key(Path::new("b.rs")));
+        assert!(stack[&third.change_id].is_empty());
+    }
 }
@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use git2::{Repository, Tree};
+use marker_commit::calculate_base_tree;
+
+use crate::model::{AnchorContext, DiffSide};
+use crate::{CommitId, Error, Result};
+
+/// Read a file's content from a git tree, returning None if the file doesn't exist.
+pub(crate) fn read_file_from_tree(
+    repo: &Repository,
+    tree: &Tree<'_>,
+    file_path: &Path,
+) -> Option<String> {
+    let entry = tree.get_path(file_path).ok()?;
+    let blob = repo.find_blob(entry.id()).ok()?;
+    std::str::from_utf8(blob.content()).ok().map(String::from)
+}
+
+/// The tree a diff side reads from: the commit itself for `New`, the review base for `Old`.
+/// For merges the base is the octopus merge of all parents, matching the displayed diff.
+/// Returns `None` for the old side of an initial commit.
+pub(crate) fn side_tree<'a>(
+    repo: &'a Repository,
+    sha: CommitId,
+    side: DiffSide,
+) -> Result<Option<Tree<'a>>> {
+    let commit = repo.find_commit(sha.oid())?;
+    match side {
+        DiffSide::New => Ok(Some(commit.tree()?)),
+        DiffSide::Old if commit.parent_count() == 0 => Ok(None),
+        DiffSide::Old => Ok(Some(calculate_base_tree(repo, &commit)?)),
+    }
+}
+
+/// The lines `start_line..=line` of `file_path` on one side of `sha`, with up to
+/// `context_lines` lines before and after.
+///
+/// Line numbers are 1-based, as everywhere else comments are addressed. This is the same
+/// slicing used to record a comment's anchor, so a UI previewing the surrounding source sees
+/// exactly what porting will later search for.
+pub fn get_context_lines(
+    repo: &Repository,
+    sha: CommitId,
+    file_path: &Path,
+    side: DiffSide,
+    line: u32,
+    start_line: Option<u32>,
+    context_lines: usize,
+) -> Result<AnchorContext> {
+    let tree = side_tree(repo, sha, side)?
+        .ok_or_else(|| Error::Internal("cannot comment on old side of initial commit".into()))?;
+
+    let content = read_file_from_tree(repo, &tree, file_path).ok_or_else(|| {
+        Error::Internal(format!("file not found in tree: {}", file_path.display()))
+    })?;
+
+    slice_context(&content, line, start_line, context_lines).ok_or_else(|| Error::LineOutOfRange {
+        path: file_path.to_path_buf(),
+        side,
+        start: start_line.unwrap_or(line),
+        end: line,
+        total: content.lines().count(),
+    })
+}
+
+/// `None` when the 1-based range `start_line..=line` is empty or not inside `content`.
+fn slice_context(
+    content: &str,
+    line: u32,
+    start_line: Option<u32>,
+    context_lines: usize,
+) -> Option<AnchorContext> {
+    let lines: Vec<&str> = content.lines().collect();
+    let total = lines.len();
+
+    // Determine the target range (1-based → 0-based).
+    let start_0 = start_line.unwrap_or(line).checked_sub(1)? as usize;
+    let end_0 = line.checked_sub(1)? as usize;
+    if end_0 >= total || start_0 > end_0 {
+        return None;
+    }
+
+    let before_start = start_0.saturating_sub(context_lines);
+    let after_end = (end_0 + 1 + context_lines).min(total);
+    let owned = |range: &[&str]| -> Vec<String> { range.iter().map(|s| s.to_string()).collect() };
+
+    Some(AnchorContext {
+        before: owned(&lines[before_start..start_0]),
+        target: owned(&lines[start_0..=end_0]),
+        after: owned(&lines[end_0 + 1..after_end]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "l1\nl2\nl3\nl4\nl5\nl6\nl7\nl8\nl9\nl10\n";
+
+    #[test]
+    fn slices_three_lines_around_target() {
+        let ctx = slice_context(CONTENT, 5, None, 3).unwrap();
+        assert_eq!(ctx.before, vec!["l2", "l3", "l4"]);
+        assert_eq!(ctx.target, vec!["l5"]);
+        assert_eq!(ctx.after, vec!["l6", "l7", "l8"]);
+    }
+
+    #[test]
+    fn clamps_context_at_file_edges() {
+        let ctx = slice_context(CONTENT, 10, Some(1), 3).unwrap();
+        assert!(ctx.before.is_empty());
+        assert_eq!(ctx.target.len(), 10);
+        assert!(ctx.after.is_empty());
+    }
+
+    #[test]
+    fn rejects_out_of_range_lines() {
+        assert!(slice_context(CONTENT, 11, None, 3).is_none());
+        assert!(slice_context(CONTENT, 0, None, 3).is_none());
+        assert!(slice_context(CONTENT, 2, Some(3), 3).is_none());
+        assert!(slice_context(CONTENT, 2, Some(0), 3).is_none());
+    }
+}
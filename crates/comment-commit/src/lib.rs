@@ -1,13 +1,21 @@
+use std::path::PathBuf;
+
 mod comment_commit;
 mod comment_commit_lock;
+mod context;
 mod materialize;
 pub(crate) mod model;
 mod porting;
 mod tree_builder_ext;
 
-pub use comment_commit::CommentCommit;
+pub use comment_commit::{CommentCommit, get_stack_comments};
+pub use context::get_context_lines;
 pub use kenjutu_types::{ChangeId, CommitId};
-pub use model::{AnchorContext, DiffSide, MaterializedComment, MaterializedReply, PortedComment};
+pub use materialize::DEFAULT_MAX_REPLY_DEPTH;
+pub use model::{
+    AnchorContext, AnchorStatus, CommentFilter, CommentLog, DiffSide, MaterializedComment,
+    MaterializedReply, NewComment, PortedComment, UNKNOWN_AUTHOR,
+};
 pub use porting::{find_anchor_position, get_all_ported_comments};
 
 #[derive(Debug, thiserror::Error)]
@@ -18,8 +26,21 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error("Marker commit error: {0}")]
+    MarkerCommit(#[from] marker_commit::Error),
     #[error("Internal error: {0}")]
     Internal(String),
+    #[error(
+        "Lines {start}-{end} are outside the {side:?} side of {} ({total} lines)",
+        .path.display()
+    )]
+    LineOutOfRange {
+        path: PathBuf,
+        side: DiffSide,
+        start: u32,
+        end: u32,
+        total: usize,
+    },
     #[error("Comment not found: {comment_id}")]
     CommentNotFound { comment_id: String },
     #[error("Invalid action: {message}")]
@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 
-use crate::model::{ActionEntry, CommentAction, MaterializedComment, MaterializedReply};
+use crate::model::{ActionEntry, CommentAction, DiffSide, MaterializedComment, MaterializedReply};
+
+/// How many levels of replies [`materialize`] nests before flattening deeper ones.
+pub const DEFAULT_MAX_REPLY_DEPTH: usize = 4;
 
 /// Replay an action log to produce the current state of all comment threads.
 ///
@@ -10,7 +13,21 @@ use crate::model::{ActionEntry, CommentAction, MaterializedComment, Materialized
 ///
 /// Actions with unknown `comment_id` references are silently skipped for robustness
 /// (e.g. partial sync scenarios where actions arrive out of order).
+///
+/// Threads are returned in diff order: by side, then line, then creation time, with the
+/// comment id as a final tiebreak. Replies stay in creation order within their parent.
+#[cfg(test)]
 pub(crate) fn materialize(actions: &[ActionEntry]) -> Vec<MaterializedComment> {
+    materialize_with_depth(actions, DEFAULT_MAX_REPLY_DEPTH)
+}
+
+/// Like [`materialize`], nesting replies at most `max_reply_depth` levels below the thread
+/// root. A reply to a reply at the cap is shown as a sibling of its parent instead, so deep
+/// chains stay readable. A cap of 0 is treated as 1, which gives the old flat threads.
+pub(crate) fn materialize_with_depth(
+    actions: &[ActionEntry],
+    max_reply_depth: usize,
+) -> Vec<MaterializedComment> {
     // Sort by timestamp. Stable sort preserves original order for equal timestamps.
     let mut sorted: Vec<&ActionEntry> = actions.iter().collect();
     sorted.sort_by(|a, b| a.created_at.cmp(&b.created_at));
@@ -18,8 +35,11 @@ pub(crate) fn materialize(actions: &[ActionEntry]) -> Vec<MaterializedComment> {
     let mut comments: HashMap<String, MaterializedComment> = HashMap::new();
     // Track insertion order so output is deterministic.
     let mut order: Vec<String> = Vec::new();
-    // Map reply IDs to their parent comment ID for Edit lookups.
-    let mut reply_parent: HashMap<String, String> = HashMap::new();
+    // Replies of each thread in creation order, keyed by root comment ID. The tree is built
+    // once replay is done.
+    let mut thread_replies: HashMap<String, Vec<PendingReply>> = HashMap::new();
+    // Map reply IDs to their thread's root comment ID for Reply and Edit lookups.
+    let mut reply_root: HashMap<String, String> = HashMap::new();
 
     for entry in &sorted {
         let timestamp = &entry.created_at;
@@ -32,6 +52,7 @@ pub(crate) fn materialize(actions: &[ActionEntry]) -> Vec<MaterializedComment> {
                 start_line,
                 body,
                 anchor,
+                author,
             } => {
                 if comments.contains_key(comment_id) {
                     // Duplicate Create — skip.
@@ -48,7 +69,10 @@ pub(crate) fn materialize(actions: &[ActionEntry]) -> Vec<MaterializedComment> {
                         start_line: *start_line,
                         body: body.clone(),
                         anchor: anchor.clone(),
+                        author: author.clone(),
                         resolved: false,
+                        orphaned: false,
+                        deleted: false,
                         created_at: timestamp.clone(),
                         updated_at: timestamp.clone(),
                         edit_count: 0,
@@ -60,19 +84,41 @@ pub(crate) fn materialize(actions: &[ActionEntry]) -> Vec<MaterializedComment> {
                 comment_id,
                 parent_comment_id,
                 body,
+                author,
             } => {
-                if let Some(parent) = comments.get_mut(parent_comment_id) {
-                    reply_parent.insert(comment_id.clone(), parent_comment_id.clone());
-                    parent.replies.push(MaterializedReply {
-                        id: comment_id.clone(),
-                        body: body.clone(),
-                        created_at: timestamp.clone(),
-                        updated_at: timestamp.clone(),
-                        edit_count: 0,
+                if comments.contains_key(comment_id) || reply_root.contains_key(comment_id) {
+                    // Duplicate ID — skip, which also keeps a reply from becoming its own
+                    // ancestor.
+                    continue;
+                }
+                // The parent must already be replayed, so replies can never form a cycle.
+                let (root_id, parent) = if comments.contains_key(parent_comment_id) {
+                    (parent_comment_id.clone(), None)
+                } else if let Some(root_id) = reply_root.get(parent_comment_id) {
+                    (root_id.clone(), Some(parent_comment_id.clone()))
+                } else {
+                    // If parent doesn't exist, silently skip.
+                    continue;
+                };
+                reply_root.insert(comment_id.clone(), root_id.clone());
+                thread_replies
+                    .entry(root_id.clone())
+                    .or_default()
+                    .push(PendingReply {
+                        parent,
+                        reply: MaterializedReply {
+                            id: comment_id.clone(),
+                            body: body.clone(),
+                            author: author.clone(),
+                            created_at: timestamp.clone(),
+                            updated_at: timestamp.clone(),
+                            edit_count: 0,
+                            replies: Vec::new(),
+                        },
                     });
-                    parent.updated_at = timestamp.clone();
+                if let Some(root) = comments.get_mut(&root_id) {
+                    root.updated_at = timestamp.clone();
                 }
-                // If parent doesn't exist, silently skip.
             }
             CommentAction::Edit { comment_id, body } => {
                 // Check if it's a top-level comment.
@@ -80,16 +126,18 @@ pub(crate) fn materialize(actions: &[ActionEntry]) -> Vec<MaterializedComment> {
                     comment.body = body.clone();
                     comment.updated_at = timestamp.clone();
                     comment.edit_count += 1;
-                } else if let Some(parent_id) = reply_parent.get(comment_id) {
-                    // It's a reply — find it in the parent's replies.
-                    if let Some(parent) = comments.get_mut(parent_id) {
-                        if let Some(reply) = parent.replies.iter_mut().find(|r| r.id == *comment_id)
-                        {
-                            reply.body = body.clone();
-                            reply.updated_at = timestamp.clone();
-                            reply.edit_count += 1;
-                        }
-                        parent.updated_at = timestamp.clone();
+                } else if let Some(root_id) = reply_root.get(comment_id) {
+                    // It's a reply — find it among its thread's replies.
+                    if let Some(pending) = thread_replies
+                        .get_mut(root_id)
+                        .and_then(|replies| replies.iter_mut().find(|p| p.reply.id == *comment_id))
+                    {
+                        pending.reply.body = body.clone();
+                        pending.reply.updated_at = timestamp.clone();
+                        pending.reply.edit_count += 1;
+                    }
+                    if let Some(root) = comments.get_mut(root_id) {
+                        root.updated_at = timestamp.clone();
                     }
                 }
                 // Unknown comment_id — silently skip.
@@ -106,20 +154,95 @@ pub(crate) fn materialize(actions: &[ActionEntry]) -> Vec<MaterializedComment> {
                     comment.updated_at = timestamp.clone();
                 }
             }
+            CommentAction::Delete { comment_id } => {
+                if let Some(comment) = comments.get_mut(comment_id) {
+                    comment.deleted = true;
+                    comment.updated_at = timestamp.clone();
+                }
+            }
+            CommentAction::Reanchor {
+                comment_id,
+                target_sha,
+                side,
+                line,
+                start_line,
+            } => {
+                if let Some(comment) = comments.get_mut(comment_id) {
+                    match line {
+                        Some(line) => {
+                            comment.target_sha = *target_sha;
+                            comment.side = *side;
+                            comment.line = *line;
+                            comment.start_line = *start_line;
+                            comment.orphaned = false;
+                        }
+                        None => comment.orphaned = true,
+                    }
+                }
+            }
         }
     }
 
-    // Return in insertion order.
-    order
+    let mut result: Vec<MaterializedComment> = order
         .into_iter()
         .filter_map(|id| comments.remove(&id))
-        .collect()
+        .map(|mut comment| {
+            let replies = thread_replies.remove(&comment.id).unwrap_or_default();
+            comment.replies = build_reply_tree(replies, max_reply_depth.max(1));
+            comment
+        })
+        .collect();
+    result.sort_by(|a, b| {
+        let key = |c: &MaterializedComment| (c.side == DiffSide::New, c.line);
+        key(a)
+            .cmp(&key(b))
+            .then_with(|| a.created_at.cmp(&b.created_at))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    result
+}
+
+/// A replayed reply waiting to be placed in its thread's tree.
+struct PendingReply {
+    /// The reply it answers, or `None` for a direct reply to the thread root.
+    parent: Option<String>,
+    reply: MaterializedReply,
+}
+
+/// Nest `replies` (in creation order) under their parents, at most `max_depth` levels deep.
+fn build_reply_tree(replies: Vec<PendingReply>, max_depth: usize) -> Vec<MaterializedReply> {
+    // Depth below the root and the reply each one ends up attached to after capping.
+    let mut placed: HashMap<String, (usize, Option<String>)> = HashMap::new();
+    let mut children: HashMap<Option<String>, Vec<MaterializedReply>> = HashMap::new();
+    for PendingReply { parent, reply } in replies {
+        let (depth, attach_to) = match parent.and_then(|p| placed.get(&p).map(|d| (p, d))) {
+            Some((parent_id, (parent_depth, _))) if *parent_depth < max_depth => {
+                (parent_depth + 1, Some(parent_id))
+            }
+            Some((_, (parent_depth, parent_attach))) => (*parent_depth, parent_attach.clone()),
+            None => (1, None),
+        };
+        placed.insert(reply.id.clone(), (depth, attach_to.clone()));
+        children.entry(attach_to).or_default().push(reply);
+    }
+    take_children(&mut children, None)
+}
+
+fn take_children(
+    children: &mut HashMap<Option<String>, Vec<MaterializedReply>>,
+    parent: Option<String>,
+) -> Vec<MaterializedReply> {
+    let mut replies = children.remove(&parent).unwrap_or_default();
+    for reply in &mut replies {
+        reply.replies = take_children(children, Some(reply.id.clone()));
+    }
+    replies
 }
 
 #[cfg(test)]
 mod tests {
     use crate::CommitId;
-    use crate::model::{AnchorContext, CommentAction, DiffSide};
+    use crate::model::AnchorContext;
 
     use super::*;
 
@@ -156,6 +279,7 @@ mod tests {
                 start_line: None,
                 body: "looks wrong".to_string(),
                 anchor: make_anchor(),
+                author: "alice".to_string(),
             },
         )];
 
@@ -166,6 +290,7 @@ mod tests {
         assert_eq!(c.side, DiffSide::New);
         assert_eq!(c.line, 42);
         assert_eq!(c.body, "looks wrong");
+        assert_eq!(c.author, "alice");
         assert!(!c.resolved);
         assert_eq!(c.edit_count, 0);
         assert!(c.replies.is_empty());
@@ -185,6 +310,7 @@ mod tests {
                     start_line: None,
                     body: "question".to_string(),
                     anchor: make_anchor(),
+                    author: "alice".to_string(),
                 },
             ),
             action(
@@ -194,6 +320,7 @@ mod tests {
                     comment_id: "r1".to_string(),
                     parent_comment_id: "c1".to_string(),
                     body: "answer".to_string(),
+                    author: "bob".to_string(),
                 },
             ),
         ];
@@ -203,9 +330,129 @@ mod tests {
         assert_eq!(result[0].replies.len(), 1);
         assert_eq!(result[0].replies[0].id, "r1");
         assert_eq!(result[0].replies[0].body, "answer");
+        assert_eq!(result[0].replies[0].author, "bob");
         assert_eq!(result[0].updated_at, "2025-01-01T00:01:00Z");
     }
 
+    fn reply(action_id: &str, created_at: &str, comment_id: &str, parent: &str) -> ActionEntry {
+        action(
+            action_id,
+            created_at,
+            CommentAction::Reply {
+                comment_id: comment_id.to_string(),
+                parent_comment_id: parent.to_string(),
+                body: comment_id.to_string(),
+                author: "bob".to_string(),
+            },
+        )
+    }
+
+    fn root_comment() -> ActionEntry {
+        action(
+            "act-1",
+            "2025-01-01T00:00:00Z",
+            CommentAction::Create {
+                comment_id: "c1".to_string(),
+                target_sha: dummy_sha(),
+                side: DiffSide::New,
+                line: 10,
+                start_line: None,
+                body: "question".to_string(),
+                anchor: make_anchor(),
+                author: "alice".to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_two_level_reply_chain() {
+        let actions = vec![
+            root_comment(),
+            reply("act-2", "2025-01-01T00:01:00Z", "r1", "c1"),
+            reply("act-3", "2025-01-01T00:02:00Z", "r2", "r1"),
+            reply("act-4", "2025-01-01T00:03:00Z", "r3", "c1"),
+        ];
+
+        let result = materialize(&actions);
+        let replies = &result[0].replies;
+        let ids: Vec<_> = replies.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, ["r1", "r3"]);
+        assert_eq!(replies[0].replies.len(), 1);
+        assert_eq!(replies[0].replies[0].id, "r2");
+        assert!(replies[1].replies.is_empty());
+        assert_eq!(result[0].updated_at, "2025-01-01T00:03:00Z");
+    }
+
+    #[test]
+    fn test_replies_below_max_depth_become_siblings() {
+        let actions = vec![
+            root_comment(),
+            reply("act-2", "2025-01-01T00:01:00Z", "r1", "c1"),
+            reply("act-3", "2025-01-01T00:02:00Z", "r2", "r1"),
+            reply("act-4", "2025-01-01T00:03:00Z", "r3", "r2"),
+            reply("act-5", "2025-01-01T00:04:00Z", "r4", "r3"),
+        ];
+
+        let result = materialize_with_depth(&actions, 2);
+        let r1 = &result[0].replies[0];
+        let nested: Vec<_> = r1.replies.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(nested, ["r2", "r3", "r4"]);
+        assert!(r1.replies.iter().all(|r| r.replies.is_empty()));
+    }
+
+    #[test]
+    fn test_reply_reusing_an_id_is_skipped() {
+        let actions = vec![
+            root_comment(),
+            reply("act-2", "2025-01-01T00:01:00Z", "r1", "c1"),
+            reply("act-3", "2025-01-01T00:02:00Z", "r1", "r1"),
+        ];
+
+        let result = materialize(&actions);
+        assert_eq!(result[0].replies.len(), 1);
+        assert!(result[0].replies[0].replies.is_empty());
+    }
+
+    #[test]
+    fn test_edit_nested_reply() {
+        let actions = vec![
+            root_comment(),
+            reply("act-2", "2025-01-01T00:01:00Z", "r1", "c1"),
+            reply("act-3", "2025-01-01T00:02:00Z", "r2", "r1"),
+            action(
+                "act-4",
+                "2025-01-01T00:03:00Z",
+                CommentAction::Edit {
+                    comment_id: "r2".to_string(),
+                    body: "edited".to_string(),
+                },
+            ),
+        ];
+
+        let result = materialize(&actions);
+        let r2 = &result[0].replies[0].replies[0];
+        assert_eq!(r2.body, "edited");
+        assert_eq!(r2.edit_count, 1);
+    }
+
+    #[test]
+    fn test_missing_author_defaults_to_unknown() {
+        let json = r#"[
+            {"action_id": "act-1", "created_at": "2025-01-01T00:00:00Z", "action": {
+                "type": "Create", "comment_id": "c1",
+                "target_sha": "0000000000000000000000000000000000000000",
+                "side": "New", "line": 1, "start_line": null, "body": "old",
+                "anchor": {"before": [], "target": ["x"], "after": []}}},
+            {"action_id": "act-2", "created_at": "2025-01-01T00:01:00Z", "action": {
+                "type": "Reply", "comment_id": "r1", "parent_comment_id": "c1", "body": "re"}}
+        ]"#;
+        let actions: Vec<ActionEntry> = serde_json::from_str(json).unwrap();
+
+        let result = materialize(&actions);
+        assert_eq!(result[0].author, crate::UNKNOWN_AUTHOR);
+        assert_eq!(result[0].replies[0].author, crate::UNKNOWN_AUTHOR);
+    }
+
     #[test]
     fn test_edit_top_level_comment() {
         let actions = vec![
@@ -220,6 +467,7 @@ mod tests {
                     start_line: None,
                     body: "original".to_string(),
                     anchor: make_anchor(),
+                    author: "alice".to_string(),
                 },
             ),
             action(
@@ -252,6 +500,7 @@ mod tests {
                     start_line: None,
                     body: "question".to_string(),
                     anchor: make_anchor(),
+                    author: "alice".to_string(),
                 },
             ),
             action(
@@ -261,6 +510,7 @@ mod tests {
                     comment_id: "r1".to_string(),
                     parent_comment_id: "c1".to_string(),
                     body: "first answer".to_string(),
+                    author: "bob".to_string(),
                 },
             ),
             action(
@@ -293,6 +543,7 @@ mod tests {
                     start_line: None,
                     body: "fix this".to_string(),
                     anchor: make_anchor(),
+                    author: "alice".to_string(),
                 },
             ),
             action(
@@ -347,6 +598,7 @@ mod tests {
                     comment_id: "r1".to_string(),
                     parent_comment_id: "nonexistent".to_string(),
                     body: "orphan reply".to_string(),
+                    author: "bob".to_string(),
                 },
             ),
         ];
@@ -369,6 +621,7 @@ mod tests {
                     start_line: None,
                     body: "first".to_string(),
                     anchor: make_anchor(),
+                    author: "alice".to_string(),
                 },
             ),
             action(
@@ -382,6 +635,7 @@ mod tests {
                     start_line: None,
                     body: "duplicate".to_string(),
                     anchor: make_anchor(),
+                    author: "alice".to_string(),
                 },
             ),
         ];
@@ -393,7 +647,7 @@ mod tests {
     }
 
     #[test]
-    fn test_multiple_comments_preserve_order() {
+    fn test_multiple_comments_sorted_by_position() {
         let actions = vec![
             action(
                 "act-1",
@@ -406,6 +660,7 @@ mod tests {
                     start_line: None,
                     body: "first comment".to_string(),
                     anchor: make_anchor(),
+                    author: "alice".to_string(),
                 },
             ),
             action(
@@ -419,6 +674,7 @@ mod tests {
                     start_line: None,
                     body: "second comment".to_string(),
                     anchor: make_anchor(),
+                    author: "alice".to_string(),
                 },
             ),
             action(
@@ -432,14 +688,16 @@ mod tests {
                     start_line: None,
                     body: "third comment".to_string(),
                     anchor: make_anchor(),
+                    author: "alice".to_string(),
                 },
             ),
         ];
 
         let result = materialize(&actions);
         assert_eq!(result.len(), 3);
-        assert_eq!(result[0].id, "c1");
-        assert_eq!(result[1].id, "c2");
+        // Old-side threads come first, then new-side threads by line.
+        assert_eq!(result[0].id, "c2");
+        assert_eq!(result[1].id, "c1");
         assert_eq!(result[2].id, "c3");
     }
 
@@ -456,6 +714,7 @@ mod tests {
                 start_line: Some(10),
                 body: "this whole block is wrong".to_string(),
                 anchor: make_anchor(),
+                author: "alice".to_string(),
             },
         )];
 
@@ -478,6 +737,7 @@ mod tests {
                     start_line: None,
                     body: "question".to_string(),
                     anchor: make_anchor(),
+                    author: "alice".to_string(),
                 },
             ),
             action(
@@ -487,6 +747,7 @@ mod tests {
                     comment_id: "r1".to_string(),
                     parent_comment_id: "c1".to_string(),
                     body: "reply 1".to_string(),
+                    author: "bob".to_string(),
                 },
             ),
             action(
@@ -496,6 +757,7 @@ mod tests {
                     comment_id: "r2".to_string(),
                     parent_comment_id: "c1".to_string(),
                     body: "reply 2".to_string(),
+                    author: "bob".to_string(),
                 },
             ),
         ];
@@ -520,6 +782,7 @@ mod tests {
                     start_line: None,
                     body: "v1".to_string(),
                     anchor: make_anchor(),
+                    author: "alice".to_string(),
                 },
             ),
             action(
@@ -544,4 +807,61 @@ mod tests {
         assert_eq!(result[0].body, "v3");
         assert_eq!(result[0].edit_count, 2);
     }
+
+    #[test]
+    fn test_reanchor_moves_and_orphans() {
+        let new_sha: CommitId = "1111111111111111111111111111111111111111".parse().unwrap();
+        let mut actions = vec![
+            action(
+                "act-1",
+                "2025-01-01T00:00:00Z",
+                CommentAction::Create {
+                    comment_id: "c1".to_string(),
+                    target_sha: dummy_sha(),
+                    side: DiffSide::New,
+                    line: 3,
+                    start_line: None,
+                    body: "fix this".to_string(),
+                    anchor: make_anchor(),
+                    author: "alice".to_string(),
+                },
+            ),
+            action(
+                "act-2",
+                "2025-01-01T00:10:00Z",
+                CommentAction::Reanchor {
+                    comment_id: "c1".to_string(),
+                    target_sha: new_sha,
+                    side: DiffSide::New,
+                    line: Some(7),
+                    start_line: None,
+                },
+            ),
+        ];
+
+        let result = materialize(&actions);
+        assert_eq!(result[0].line, 7);
+        assert_eq!(result[0].target_sha, new_sha);
+        assert!(!result[0].orphaned);
+
+        actions.push(action(
+            "act-3",
+            "2025-01-01T00:20:00Z",
+            CommentAction::Reanchor {
+                comment_id: "c1".to_string(),
+                target_sha: dummy_sha(),
+                side: DiffSide::New,
+                line: None,
+                start_line: None,
+            },
+        ));
+
+        let result = materialize(&actions);
+        assert!(result[0].orphaned);
+        assert_eq!(
+            result[0].line, 7,
+            "orphaned comments keep their last position"
+        );
+        assert_eq!(result[0].target_sha, new_sha);
+    }
 }
@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 
 use crate::CommitId;
@@ -11,6 +14,35 @@ pub(crate) struct ActionEntry {
     pub(crate) action: CommentAction,
 }
 
+/// The complete action log of a change, keyed by file path.
+///
+/// The entries are opaque outside this crate; the log exists so comments can be moved
+/// between repositories with [`crate::CommentCommit::export_log`] and
+/// [`crate::CommentCommit::import_log`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommentLog(pub(crate) HashMap<PathBuf, Vec<ActionEntry>>);
+
+/// Author recorded for actions written before authors were tracked.
+pub const UNKNOWN_AUTHOR: &str = "unknown";
+
+fn unknown_author() -> String {
+    UNKNOWN_AUTHOR.to_string()
+}
+
+/// A new top-level inline comment, as passed to [`crate::CommentCommit::create_comment`].
+#[derive(Debug, Clone)]
+pub struct NewComment {
+    /// The commit this comment is anchored to (used for anchor context and GC protection).
+    pub sha: CommitId,
+    pub side: DiffSide,
+    /// Last line of the commented range (1-based).
+    pub line: u32,
+    /// First line of a multi-line range; `None` for a single line.
+    pub start_line: Option<u32>,
+    pub body: String,
+    pub author: String,
+}
+
 /// The set of actions that can be appended to the comment log.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -25,12 +57,16 @@ pub(crate) enum CommentAction {
         start_line: Option<u32>,
         body: String,
         anchor: AnchorContext,
+        #[serde(default = "unknown_author")]
+        author: String,
     },
-    /// Reply to an existing top-level comment (flat threads only).
+    /// Reply to a comment or to another reply in the same thread.
     Reply {
         comment_id: String,
         parent_comment_id: String,
         body: String,
+        #[serde(default = "unknown_author")]
+        author: String,
     },
     /// Edit the body of a comment or reply.
     Edit { comment_id: String, body: String },
@@ -38,6 +74,17 @@ pub(crate) enum CommentAction {
     Resolve { comment_id: String },
     /// Unresolve a previously resolved thread (targets the root comment only).
     Unresolve { comment_id: String },
+    /// Soft-delete a thread (targets the root comment only). Replies go with it.
+    Delete { comment_id: String },
+    /// Move a thread onto a rewritten commit. The original `Create` is kept for audit.
+    /// `line` is `None` when the anchor could not be found, which orphans the thread.
+    Reanchor {
+        comment_id: String,
+        target_sha: CommitId,
+        side: DiffSide,
+        line: Option<u32>,
+        start_line: Option<u32>,
+    },
 }
 
 /// Which side of the diff the comment is attached to.
@@ -48,6 +95,42 @@ pub enum DiffSide {
     New,
 }
 
+impl DiffSide {
+    /// The side and line a comment on a diff line attaches to, from the line's old and new
+    /// line numbers.
+    ///
+    /// Context lines have both numbers; they attach to `New`, as the diff views do, so a thread
+    /// on unchanged code keeps one position whichever way it was created. Deletions only exist
+    /// on `Old`.
+    pub fn for_line(old_lineno: Option<u32>, new_lineno: Option<u32>) -> Option<(DiffSide, u32)> {
+        match (old_lineno, new_lineno) {
+            (_, Some(new)) => Some((DiffSide::New, new)),
+            (Some(old), None) => Some((DiffSide::Old, old)),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Which threads to keep, judged by the `resolved` flag on the thread root.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum CommentFilter {
+    #[default]
+    All,
+    UnresolvedOnly,
+    ResolvedOnly,
+}
+
+impl CommentFilter {
+    pub fn matches(self, comment: &MaterializedComment) -> bool {
+        match self {
+            CommentFilter::All => true,
+            CommentFilter::UnresolvedOnly => !comment.resolved,
+            CommentFilter::ResolvedOnly => comment.resolved,
+        }
+    }
+}
+
 /// Context lines around the commented line(s) for anchor-based porting.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "specta", derive(specta::Type))]
@@ -65,14 +148,19 @@ pub struct AnchorContext {
 #[cfg_attr(feature = "specta", derive(specta::Type))]
 pub struct MaterializedComment {
     pub id: String,
-    /// The commit SHA this comment was originally anchored to.
+    /// The commit SHA this comment is anchored to, moved forward by reanchoring.
     pub target_sha: CommitId,
     pub side: DiffSide,
     pub line: u32,
     pub start_line: Option<u32>,
     pub body: String,
     pub anchor: AnchorContext,
+    pub author: String,
     pub resolved: bool,
+    /// The anchor could not be found after the commit was rewritten.
+    pub orphaned: bool,
+    /// The thread was deleted. Hidden from the default views.
+    pub deleted: bool,
     pub created_at: String,
     pub updated_at: String,
     pub edit_count: u32,
@@ -85,9 +173,24 @@ pub struct MaterializedComment {
 pub struct MaterializedReply {
     pub id: String,
     pub body: String,
+    pub author: String,
     pub created_at: String,
     pub updated_at: String,
     pub edit_count: u32,
+    /// Replies to this reply, in creation order.
+    pub replies: Vec<MaterializedReply>,
+}
+
+/// How a comment's anchor lines up with the file it is displayed against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum AnchorStatus {
+    /// The anchored lines are still at the stored line.
+    Exact,
+    /// The anchored lines moved; holds the new line.
+    Shifted(u32),
+    /// The anchored lines could not be found.
+    Orphaned,
 }
 
 /// A materialized comment with ported line numbers for display on a different commit.
@@ -99,4 +202,5 @@ pub struct PortedComment {
     pub ported_start_line: Option<u32>,
     /// Whether this comment was ported from a different commit SHA.
     pub is_ported: bool,
+    pub anchor_status: AnchorStatus,
 }
@@ -2,51 +2,146 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use git2::Repository;
+use marker_commit::calculate_base_tree;
 
 use crate::comment_commit::CommentCommit;
-use crate::model::{AnchorContext, MaterializedComment, PortedComment};
+use crate::context::read_file_from_tree;
+use crate::model::{AnchorContext, AnchorStatus, DiffSide, MaterializedComment, PortedComment};
 use crate::{CommitId, Result};
 
+/// Every comment of `commit_id`'s change, ported to `commit_id` and keyed by the path it now
+/// lives at. `rename_threshold` is the minimum similarity (0-100) for a comment to follow its
+/// file across a rename; pass the diff's own value so comments land on the files shown.
 pub fn get_all_ported_comments(
     repo: &Repository,
     commit_id: CommitId,
+    rename_threshold: u16,
 ) -> Result<HashMap<PathBuf, Vec<PortedComment>>> {
     let cc = CommentCommit::get(repo, commit_id)?;
     let all_comments = cc.get_all_comments();
     let mut result: HashMap<PathBuf, Vec<PortedComment>> = HashMap::new();
 
-    // Load file content from the current commit for anchor matching.
+    // Load both sides of the current commit for anchor matching. The old side of an
+    // initial commit has no tree; a merge's old side is the octopus merge of its parents.
     let current_commit = repo.find_commit(commit_id.oid())?;
-    let current_tree = current_commit.tree()?;
+    let new_tree = current_commit.tree()?;
+    let old_tree = match current_commit.parent_count() {
+        0 => None,
+        _ => Some(calculate_base_tree(repo, &current_commit)?),
+    };
 
+    let mut renames = RenameMaps::new(rename_threshold);
     for (file_path, comments) in all_comments {
-        let ported: Vec<PortedComment> = comments
-            .into_iter()
-            .map(|c| {
-                if c.target_sha == commit_id {
-                    // Comment is on the current SHA — no porting needed.
-                    PortedComment {
-                        ported_line: Some(c.line),
-                        ported_start_line: c.start_line,
-                        is_ported: false,
-                        comment: c,
-                    }
-                } else {
-                    // Comment is on a different SHA — port using anchor text.
-                    let file_content = read_file_from_tree(repo, &current_tree, &file_path);
-                    port_comment(c, file_content.as_deref())
-                }
-            })
-            .collect();
+        for c in comments {
+            if c.target_sha == commit_id {
+                // Comment is on the current SHA — no porting needed.
+                let ported = PortedComment {
+                    ported_line: Some(c.line),
+                    ported_start_line: c.start_line,
+                    is_ported: false,
+                    anchor_status: if c.orphaned {
+                        AnchorStatus::Orphaned
+                    } else {
+                        AnchorStatus::Exact
+                    },
+                    comment: c,
+                };
+                result.entry(file_path.clone()).or_default().push(ported);
+                continue;
+            }
 
-        if !ported.is_empty() {
-            result.entry(file_path).or_default().extend(ported);
+            // Comment is on a different SHA — port using anchor text, following the file if
+            // it has been renamed since.
+            let tree = match c.side {
+                DiffSide::New => Some(&new_tree),
+                DiffSide::Old => old_tree.as_ref(),
+            };
+            let path = match tree {
+                Some(tree) if tree.get_path(&file_path).is_err() => {
+                    renames.renamed_path(repo, &c, &file_path, tree)
+                }
+                _ => None,
+            }
+            .unwrap_or_else(|| file_path.clone());
+            let file_content = tree.and_then(|tree| read_file_from_tree(repo, tree, &path));
+            let ported = port_comment(c, file_content.as_deref());
+            result.entry(path).or_default().push(ported);
         }
     }
 
     Ok(result)
 }
 
+/// Renames between the tree a comment was written against and the current tree, cached per
+/// `(target_sha, side)` since every comment from one revision shares them.
+struct RenameMaps {
+    threshold: u16,
+    maps: HashMap<(CommitId, bool), HashMap<PathBuf, PathBuf>>,
+}
+
+impl RenameMaps {
+    fn new(threshold: u16) -> Self {
+        Self {
+            threshold,
+            maps: HashMap::new(),
+        }
+    }
+
+    /// Where `file_path` of the comment's revision lives in `current`, if it was renamed.
+    fn renamed_path(
+        &mut self,
+        repo: &Repository,
+        comment: &MaterializedComment,
+        file_path: &Path,
+        current: &git2::Tree<'_>,
+    ) -> Option<PathBuf> {
+        let key = (comment.target_sha, comment.side == DiffSide::New);
+        let threshold = self.threshold;
+        let renames = self.maps.entry(key).or_insert_with(|| {
+            rename_map(repo, comment.target_sha, comment.side, current, threshold).unwrap_or_else(
+                |e| {
+                    log::warn!("failed to detect renames since {}: {e}", comment.target_sha);
+                    HashMap::new()
+                },
+            )
+        });
+        renames.get(file_path).cloned()
+    }
+}
+
+/// Old path to new path for every file renamed between `side` of `target_sha` and `current`,
+/// counting pairs at least `threshold` percent similar.
+fn rename_map(
+    repo: &Repository,
+    target_sha: CommitId,
+    side: DiffSide,
+    current: &git2::Tree<'_>,
+    threshold: u16,
+) -> Result<HashMap<PathBuf, PathBuf>> {
+    let commit = repo.find_commit(target_sha.oid())?;
+    let origin = match side {
+        DiffSide::New => commit.tree()?,
+        DiffSide::Old if commit.parent_count() == 0 => return Ok(HashMap::new()),
+        DiffSide::Old => calculate_base_tree(repo, &commit)?,
+    };
+
+    let mut diff = repo.diff_tree_to_tree(Some(&origin), Some(current), None)?;
+    diff.find_similar(Some(
+        git2::DiffFindOptions::new()
+            .renames(true)
+            .rename_threshold(threshold.min(100)),
+    ))?;
+    Ok(diff
+        .deltas()
+        .filter(|delta| delta.status() == git2::Delta::Renamed)
+        .filter_map(|delta| {
+            let old = delta.old_file().path()?.to_path_buf();
+            let new = delta.new_file().path()?.to_path_buf();
+            Some((old, new))
+        })
+        .collect())
+}
+
 /// Port a single comment to a new file content using anchor text matching.
 fn port_comment(comment: MaterializedComment, file_content: Option<&str>) -> PortedComment {
     let Some(content) = file_content else {
@@ -55,34 +150,55 @@ fn port_comment(comment: MaterializedComment, file_content: Option<&str>) -> Por
             ported_line: None,
             ported_start_line: None,
             is_ported: true,
+            anchor_status: AnchorStatus::Orphaned,
             comment,
         };
     };
 
     let anchor_start = find_anchor_position(content, &comment.anchor);
-
-    // find_anchor_position returns where the target block starts (1-based).
-    // For single-line comments, ported_line = anchor_start.
-    // For multi-line comments, we need to compute both start and end:
-    //   ported_start_line = anchor_start
-    //   ported_line = anchor_start + (line - start_line)
-    let (ported_line, ported_start_line) = match (anchor_start, comment.start_line) {
-        (Some(anchor), Some(start)) => {
-            let offset = comment.line.saturating_sub(start);
-            (Some(anchor + offset), Some(anchor))
-        }
-        (Some(anchor), None) => (Some(anchor), None),
-        (None, _) => (None, None),
-    };
+    let (ported_line, ported_start_line) =
+        ported_lines(anchor_start, comment.line, comment.start_line);
 
     PortedComment {
         ported_line,
         ported_start_line,
         is_ported: true,
+        anchor_status: anchor_status(ported_line, comment.line),
         comment,
     }
 }
 
+/// Compare where the anchor was found against the line the comment was stored at.
+fn anchor_status(ported_line: Option<u32>, stored_line: u32) -> AnchorStatus {
+    match ported_line {
+        None => AnchorStatus::Orphaned,
+        Some(line) if line == stored_line => AnchorStatus::Exact,
+        Some(line) => AnchorStatus::Shifted(line),
+    }
+}
+
+/// Translate a comment's `(line, start_line)` to the position its anchor was found at.
+///
+/// `find_anchor_position` returns where the target block starts (1-based).
+/// For single-line comments, ported_line = anchor_start.
+/// For multi-line comments, we need to compute both start and end:
+///   ported_start_line = anchor_start
+///   ported_line = anchor_start + (line - start_line)
+pub(crate) fn ported_lines(
+    anchor_start: Option<u32>,
+    line: u32,
+    start_line: Option<u32>,
+) -> (Option<u32>, Option<u32>) {
+    match (anchor_start, start_line) {
+        (Some(anchor), Some(start)) => {
+            let offset = line.saturating_sub(start);
+            (Some(anchor + offset), Some(anchor))
+        }
+        (Some(anchor), None) => (Some(anchor), None),
+        (None, _) => (None, None),
+    }
+}
+
 pub fn find_anchor_position(file_content: &str, anchor: &AnchorContext) -> Option<u32> {
     if anchor.target.is_empty() {
         return None;
@@ -167,23 +283,14 @@ fn disambiguate_with_context(
     Some(idx as u32 + 1) // 1-based
 }
 
-/// Read a file's content from a git tree, returning None if the file doesn't exist.
-fn read_file_from_tree(
-    repo: &Repository,
-    tree: &git2::Tree<'_>,
-    file_path: &Path,
-) -> Option<String> {
-    let entry = tree.get_path(file_path).ok()?;
-    let blob = repo.find_blob(entry.id()).ok()?;
-    std::str::from_utf8(blob.content()).ok().map(String::from)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::{AnchorContext, DiffSide};
+    use crate::model::{AnchorContext, AnchorStatus, DiffSide, NewComment};
     use test_repo::TestRepo;
 
+    const RENAME_THRESHOLD: u16 = 50;
+
     fn make_anchor(before: &[&str], target: &[&str], after: &[&str]) -> AnchorContext {
         AnchorContext {
             before: before.iter().map(|s| s.to_string()).collect(),
@@ -255,22 +362,26 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, sha).unwrap();
             cc.create_comment(
-                sha,
                 Path::new("main.rs"),
-                DiffSide::New,
-                2,
-                None,
-                "nice print".to_string(),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 2,
+                    start_line: None,
+                    body: "nice print".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.write().unwrap();
         }
 
-        let ported = get_all_ported_comments(&test_repo.repo, sha).unwrap();
+        let ported = get_all_ported_comments(&test_repo.repo, sha, RENAME_THRESHOLD).unwrap();
         let main_comments = &ported[Path::new("main.rs")];
         assert_eq!(main_comments.len(), 1);
         assert!(!main_comments[0].is_ported);
         assert_eq!(main_comments[0].ported_line, Some(2));
+        assert_eq!(main_comments[0].anchor_status, AnchorStatus::Exact);
     }
 
     #[test]
@@ -287,12 +398,15 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, old_sha).unwrap();
             cc.create_comment(
-                old_sha,
                 Path::new("main.rs"),
-                DiffSide::New,
-                2,
-                None,
-                "nice print".to_string(),
+                NewComment {
+                    sha: old_sha,
+                    side: DiffSide::New,
+                    line: 2,
+                    start_line: None,
+                    body: "nice print".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.write().unwrap();
@@ -311,11 +425,272 @@ mod tests {
         assert_eq!(new_info.change_id, change_id);
 
         // Port to new SHA — the println moved from line 2 to line 4.
-        let ported = get_all_ported_comments(&test_repo.repo, new_sha).unwrap();
+        let ported = get_all_ported_comments(&test_repo.repo, new_sha, RENAME_THRESHOLD).unwrap();
         let main_comments = &ported[Path::new("main.rs")];
         assert_eq!(main_comments.len(), 1);
         assert!(main_comments[0].is_ported);
         assert_eq!(main_comments[0].ported_line, Some(4));
+        assert_eq!(main_comments[0].anchor_status, AnchorStatus::Shifted(4));
+    }
+
+    #[test]
+    fn test_port_comments_follows_rename() {
+        let test_repo = TestRepo::new().unwrap();
+        let content = "fn main() {\n    println!(\"hello\");\n}\n";
+        test_repo.write_file("old.rs", content).unwrap();
+        let r1 = test_repo.commit("init").unwrap();
+        let old_sha = r1.created.commit_id;
+        let change_id = r1.created.change_id;
+
+        CommentCommit::with_session(&test_repo.repo, old_sha, |cc| {
+            cc.create_comment(
+                Path::new("old.rs"),
+                NewComment {
+                    sha: old_sha,
+                    side: DiffSide::New,
+                    line: 2,
+                    start_line: None,
+                    body: "nice print".to_string(),
+                    author: "alice".to_string(),
+                },
+            )
+        })
+        .unwrap();
+
+        // Rewrite the same change with the file renamed.
+        test_repo.edit(change_id).unwrap();
+        test_repo.rename_file("old.rs", "new.rs").unwrap();
+        let new_sha = test_repo.work_copy().unwrap().commit_id;
+
+        let ported = get_all_ported_comments(&test_repo.repo, new_sha, RENAME_THRESHOLD).unwrap();
+        assert!(!ported.contains_key(Path::new("old.rs")));
+        let new_comments = &ported[Path::new("new.rs")];
+        assert_eq!(new_comments.len(), 1);
+        assert!(new_comments[0].is_ported);
+        assert_eq!(new_comments[0].ported_line, Some(2));
+        assert_eq!(new_comments[0].anchor_status, AnchorStatus::Exact);
+    }
+
+    #[test]
+    fn test_port_comments_rename_threshold() {
+        let test_repo = TestRepo::new().unwrap();
+        let lines: Vec<String> = (1..=10).map(|i| format!("line {i}\n")).collect();
+        test_repo.write_file("old.txt", lines.concat()).unwrap();
+        let r1 = test_repo.commit("init").unwrap();
+        let old_sha = r1.created.commit_id;
+        let change_id = r1.created.change_id;
+
+        CommentCommit::with_session(&test_repo.repo, old_sha, |cc| {
+            cc.create_comment(
+                Path::new("old.txt"),
+                NewComment {
+                    sha: old_sha,
+                    side: DiffSide::New,
+                    line: 2,
+                    start_line: None,
+                    body: "check this".to_string(),
+                    author: "alice".to_string(),
+                },
+            )
+        })
+        .unwrap();
+
+        // Rename the file and edit one of its ten lines.
+        test_repo.edit(change_id).unwrap();
+        test_repo.rename_file("old.txt", "new.txt").unwrap();
+        let mut edited = lines.clone();
+        edited[9] = "changed\n".to_string();
+        test_repo.write_file("new.txt", edited.concat()).unwrap();
+        let new_sha = test_repo.work_copy().unwrap().commit_id;
+
+        let followed = get_all_ported_comments(&test_repo.repo, new_sha, RENAME_THRESHOLD).unwrap();
+        assert_eq!(followed[Path::new("new.txt")][0].ported_line, Some(2));
+
+        // Too strict a threshold sees a deletion and an addition, so the comment is left behind.
+        let strict = get_all_ported_comments(&test_repo.repo, new_sha, 100).unwrap();
+        assert!(!strict.contains_key(Path::new("new.txt")));
+        assert_eq!(
+            strict[Path::new("old.txt")][0].anchor_status,
+            AnchorStatus::Orphaned
+        );
+    }
+
+    #[test]
+    fn test_renamed_thread_accepts_actions_under_new_path() {
+        let test_repo = TestRepo::new().unwrap();
+        let content = "fn main() {\n    println!(\"hello\");\n}\n";
+        test_repo.write_file("old.rs", content).unwrap();
+        let r1 = test_repo.commit("init").unwrap();
+        let old_sha = r1.created.commit_id;
+        let change_id = r1.created.change_id;
+
+        CommentCommit::with_session(&test_repo.repo, old_sha, |cc| {
+            cc.create_comment(
+                Path::new("old.rs"),
+                NewComment {
+                    sha: old_sha,
+                    side: DiffSide::New,
+                    line: 2,
+                    start_line: None,
+                    body: "nice print".to_string(),
+                    author: "alice".to_string(),
+                },
+            )
+        })
+        .unwrap();
+
+        test_repo.edit(change_id).unwrap();
+        test_repo.rename_file("old.rs", "new.rs").unwrap();
+        let new_sha = test_repo.work_copy().unwrap().commit_id;
+
+        // Clients send back the path the thread was listed under.
+        let ported = get_all_ported_comments(&test_repo.repo, new_sha, RENAME_THRESHOLD).unwrap();
+        let comment_id = ported[Path::new("new.rs")][0].comment.id.clone();
+        CommentCommit::with_session(&test_repo.repo, new_sha, |cc| {
+            cc.reply_to_comment(
+                Path::new("new.rs"),
+                comment_id.clone(),
+                "thanks".to_string(),
+                "bob".to_string(),
+            )?;
+            cc.resolve_comment(Path::new("new.rs"), comment_id.clone())
+        })
+        .unwrap();
+
+        let ported = get_all_ported_comments(&test_repo.repo, new_sha, RENAME_THRESHOLD).unwrap();
+        let new_comments = &ported[Path::new("new.rs")];
+        assert_eq!(new_comments.len(), 1);
+        let thread = &new_comments[0].comment;
+        assert_eq!(thread.id, comment_id);
+        assert!(thread.resolved);
+        assert_eq!(thread.replies.len(), 1);
+        assert_eq!(thread.replies[0].body, "thanks");
+    }
+
+    #[test]
+    fn test_port_comments_unchanged_anchor_is_exact() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo
+            .write_file("main.rs", "fn main() {\n    println!(\"hello\");\n}\n")
+            .unwrap();
+        let r1 = test_repo.commit("init").unwrap();
+        let old_sha = r1.created.commit_id;
+        let change_id = r1.created.change_id;
+
+        {
+            let mut cc = CommentCommit::get(&test_repo.repo, old_sha).unwrap();
+            cc.create_comment(
+                Path::new("main.rs"),
+                NewComment {
+                    sha: old_sha,
+                    side: DiffSide::New,
+                    line: 2,
+                    start_line: None,
+                    body: "nice print".to_string(),
+                    author: "alice".to_string(),
+                },
+            )
+            .unwrap();
+            cc.write().unwrap();
+        }
+
+        // Rewrite the change below the anchored region only.
+        test_repo.edit(change_id).unwrap();
+        test_repo
+            .write_file(
+                "main.rs",
+                "fn main() {\n    println!(\"hello\");\n}\n\nfn helper() {}\n",
+            )
+            .unwrap();
+        let new_sha = test_repo.work_copy().unwrap().commit_id;
+
+        let ported = get_all_ported_comments(&test_repo.repo, new_sha, RENAME_THRESHOLD).unwrap();
+        let main_comments = &ported[Path::new("main.rs")];
+        assert!(main_comments[0].is_ported);
+        assert_eq!(main_comments[0].ported_line, Some(2));
+        assert_eq!(main_comments[0].anchor_status, AnchorStatus::Exact);
+    }
+
+    #[test]
+    fn test_port_comments_deleted_region_is_orphaned() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo
+            .write_file("main.rs", "fn main() {\n    println!(\"hello\");\n}\n")
+            .unwrap();
+        let r1 = test_repo.commit("init").unwrap();
+        let old_sha = r1.created.commit_id;
+        let change_id = r1.created.change_id;
+
+        {
+            let mut cc = CommentCommit::get(&test_repo.repo, old_sha).unwrap();
+            cc.create_comment(
+                Path::new("main.rs"),
+                NewComment {
+                    sha: old_sha,
+                    side: DiffSide::New,
+                    line: 2,
+                    start_line: None,
+                    body: "nice print".to_string(),
+                    author: "alice".to_string(),
+                },
+            )
+            .unwrap();
+            cc.write().unwrap();
+        }
+
+        // Rewrite the change, deleting the commented line but keeping the file.
+        test_repo.edit(change_id).unwrap();
+        test_repo.write_file("main.rs", "fn main() {\n}\n").unwrap();
+        let new_sha = test_repo.work_copy().unwrap().commit_id;
+
+        let ported = get_all_ported_comments(&test_repo.repo, new_sha, RENAME_THRESHOLD).unwrap();
+        let main_comments = &ported[Path::new("main.rs")];
+        assert_eq!(main_comments[0].ported_line, None);
+        assert_eq!(main_comments[0].anchor_status, AnchorStatus::Orphaned);
+    }
+
+    #[test]
+    fn test_port_old_side_comment_reads_parent_tree() {
+        let test_repo = TestRepo::new().unwrap();
+        test_repo
+            .write_file("main.rs", "fn main() {\n    old_call();\n}\n")
+            .unwrap();
+        test_repo.commit("base").unwrap();
+        test_repo
+            .write_file("main.rs", "fn main() {\n    new_call();\n}\n")
+            .unwrap();
+        let r2 = test_repo.commit("replace call").unwrap();
+        let old_sha = r2.created.commit_id;
+        let change_id = r2.created.change_id;
+
+        {
+            let mut cc = CommentCommit::get(&test_repo.repo, old_sha).unwrap();
+            cc.create_comment(
+                Path::new("main.rs"),
+                NewComment {
+                    sha: old_sha,
+                    side: DiffSide::Old,
+                    line: 2,
+                    start_line: None,
+                    body: "why remove this?".to_string(),
+                    author: "alice".to_string(),
+                },
+            )
+            .unwrap();
+            cc.write().unwrap();
+        }
+
+        // Rewrite the new side; the parent keeps the old call at line 2.
+        test_repo.edit(change_id).unwrap();
+        test_repo
+            .write_file("main.rs", "fn main() {\n    other_call();\n}\n")
+            .unwrap();
+        let new_sha = test_repo.work_copy().unwrap().commit_id;
+
+        let ported = get_all_ported_comments(&test_repo.repo, new_sha, RENAME_THRESHOLD).unwrap();
+        let main_comments = &ported[Path::new("main.rs")];
+        assert_eq!(main_comments[0].ported_line, Some(2));
+        assert_eq!(main_comments[0].anchor_status, AnchorStatus::Exact);
     }
 
     #[test]
@@ -330,12 +705,15 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, old_sha).unwrap();
             cc.create_comment(
-                old_sha,
                 Path::new("temp.rs"),
-                DiffSide::New,
-                1,
-                None,
-                "remove this".to_string(),
+                NewComment {
+                    sha: old_sha,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "remove this".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.write().unwrap();
@@ -349,11 +727,12 @@ mod tests {
         assert_eq!(new_info.change_id, change_id);
 
         // Port — file gone, comment degrades to file-level.
-        let ported = get_all_ported_comments(&test_repo.repo, new_sha).unwrap();
+        let ported = get_all_ported_comments(&test_repo.repo, new_sha, RENAME_THRESHOLD).unwrap();
         let temp_comments = &ported[Path::new("temp.rs")];
         assert_eq!(temp_comments.len(), 1);
         assert!(temp_comments[0].is_ported);
         assert_eq!(temp_comments[0].ported_line, None);
+        assert_eq!(temp_comments[0].anchor_status, AnchorStatus::Orphaned);
     }
 
     #[test]
@@ -370,12 +749,15 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, old_sha).unwrap();
             cc.create_comment(
-                old_sha,
                 Path::new("main.rs"),
-                DiffSide::New,
-                2,
-                None,
-                "comment".to_string(),
+                NewComment {
+                    sha: old_sha,
+                    side: DiffSide::New,
+                    line: 2,
+                    start_line: None,
+                    body: "comment".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.write().unwrap();
@@ -393,12 +775,13 @@ mod tests {
         let new_sha = new_info.commit_id;
         assert_eq!(new_info.change_id, change_id);
 
-        let ported = get_all_ported_comments(&test_repo.repo, new_sha).unwrap();
+        let ported = get_all_ported_comments(&test_repo.repo, new_sha, RENAME_THRESHOLD).unwrap();
         let main_comments = &ported[Path::new("main.rs")];
         assert_eq!(main_comments.len(), 1);
         assert!(main_comments[0].is_ported);
         // Anchor didn't match — degrades to file-level.
         assert_eq!(main_comments[0].ported_line, None);
+        assert_eq!(main_comments[0].anchor_status, AnchorStatus::Orphaned);
     }
 
     #[test]
@@ -418,12 +801,15 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, old_sha).unwrap();
             cc.create_comment(
-                old_sha,
                 Path::new("main.rs"),
-                DiffSide::New,
-                4,
-                Some(2),
-                "this block".to_string(),
+                NewComment {
+                    sha: old_sha,
+                    side: DiffSide::New,
+                    line: 4,
+                    start_line: Some(2),
+                    body: "this block".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.write().unwrap();
@@ -441,7 +827,7 @@ mod tests {
         let new_sha = new_info.commit_id;
         assert_eq!(new_info.change_id, change_id);
 
-        let ported = get_all_ported_comments(&test_repo.repo, new_sha).unwrap();
+        let ported = get_all_ported_comments(&test_repo.repo, new_sha, RENAME_THRESHOLD).unwrap();
         let main_comments = &ported[Path::new("main.rs")];
         assert_eq!(main_comments.len(), 1);
         assert!(main_comments[0].is_ported);
@@ -466,12 +852,15 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, sha_v1).unwrap();
             cc.create_comment(
-                sha_v1,
                 Path::new("main.rs"),
-                DiffSide::New,
-                2,
-                None,
-                "from v1".to_string(),
+                NewComment {
+                    sha: sha_v1,
+                    side: DiffSide::New,
+                    line: 2,
+                    start_line: None,
+                    body: "from v1".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.write().unwrap();
@@ -490,12 +879,15 @@ mod tests {
         {
             let mut cc = CommentCommit::get(&test_repo.repo, sha_v2).unwrap();
             cc.create_comment(
-                sha_v2,
                 Path::new("main.rs"),
-                DiffSide::New,
-                4,
-                None,
-                "from v2".to_string(),
+                NewComment {
+                    sha: sha_v2,
+                    side: DiffSide::New,
+                    line: 4,
+                    start_line: None,
+                    body: "from v2".to_string(),
+                    author: "alice".to_string(),
+                },
             )
             .unwrap();
             cc.write().unwrap();
@@ -513,7 +905,7 @@ mod tests {
         assert_eq!(v3_info.change_id, change_id);
 
         // Port both old comments to v3.
-        let ported = get_all_ported_comments(&test_repo.repo, sha_v3).unwrap();
+        let ported = get_all_ported_comments(&test_repo.repo, sha_v3, RENAME_THRESHOLD).unwrap();
         let main_comments = &ported[Path::new("main.rs")];
         assert_eq!(main_comments.len(), 2);
 
@@ -15,11 +15,17 @@ pub struct JjCommit {
     pub description: String,
     pub author: String,
     pub email: String,
+    /// CSS color derived from `email`, stable across runs
+    pub author_color: String,
     pub timestamp: String,
     pub is_immutable: bool,
     pub is_working_copy: bool,
+    /// The commit has unresolved conflicts
+    pub has_conflict: bool,
     /// Parent change_ids (for graph edges) - supports multiple parents for merges
     pub parents: Vec<ChangeId>,
+    /// Local bookmark names pointing at this commit
+    pub bookmarks: Vec<String>,
 }
 
 /// Response for get_jj_log command
@@ -78,6 +84,9 @@ pub struct CommitRow {
     /// These are edges from other branches passing through without
     /// connecting to this commit.
     pub passing_columns: Vec<usize>,
+    /// Intermediate fork columns (the `┬`s in `├─┬─╮`) below an octopus merge.
+    /// Empty for commits with fewer than three parents.
+    pub merge_spread: Vec<usize>,
 }
 
 /// Terminal elision marker — represents one or more hidden/elided revisions.
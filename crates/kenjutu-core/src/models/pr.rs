@@ -65,6 +65,10 @@ pub struct DiffHunk {
     pub new_lines: u32,
     pub header: String,
     pub lines: Vec<DiffLine>,
+    /// True if the removed and added lines only differ in whitespace
+    pub whitespace_only: bool,
+    /// True if the new side of the hunk contains jj conflict markers
+    pub has_conflict: bool,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -87,6 +91,8 @@ pub struct HighlightToken {
     pub color: Option<String>,
     /// True if this token is part of a character-level change (for inline diff highlighting)
     pub changed: bool,
+    /// True if the whole line was moved within its hunk rather than changed
+    pub moved: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
@@ -100,6 +106,18 @@ pub enum DiffLineType {
     DelEofnl,
 }
 
+/// Who last changed a line of the file before the reviewed commit.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct BlameLine {
+    /// 1-based line number in the base version of the file
+    pub line: u32,
+    /// Abbreviated id of the commit that last changed the line
+    pub short_id: String,
+    pub author: String,
+}
+
 /// Lightweight file entry for file list (no content/hunks)
 #[derive(Clone, Debug, Serialize)]
 #[cfg_attr(feature = "specta", derive(specta::Type))]
@@ -112,6 +130,9 @@ pub struct FileEntry {
     pub deletions: u32,
     pub is_binary: bool,
     pub review_status: ReviewStatus,
+    /// Matches a generated-file glob (lockfiles, build output). Set by
+    /// [`GeneratedFiles::tag`](crate::services::diff::GeneratedFiles::tag).
+    pub generated: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
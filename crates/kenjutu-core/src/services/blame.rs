@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use git2::{BlameOptions, Repository};
+use kenjutu_types::CommitId;
+
+use crate::models::BlameLine;
+use crate::services::diff::Result;
+use crate::services::git;
+use crate::services::lru::LruCache;
+
+const DEFAULT_CAPACITY: usize = 64;
+
+/// Blame the base version of a file, i.e. the lines a commit's diff shows as old or context.
+///
+/// The base is the same tree the diff is computed against: the parent, or for a merge the
+/// parents merged together. Each parent is blamed in turn and the result is mapped onto the
+/// base content, so a merge attributes each line to the parent it came from. Lines that exist
+/// in no parent, such as conflict markers, are left out.
+///
+/// `old_path` is the path before a rename in `sha`, so the blame follows the file back across
+/// it. Files added by `sha` and root commits have nothing to blame.
+pub fn get_blame(
+    repository: &Repository,
+    sha: CommitId,
+    file_path: &Path,
+    old_path: Option<&Path>,
+) -> Result<Vec<BlameLine>> {
+    let commit = repository
+        .find_commit(sha.oid())
+        .map_err(|_| git::Error::CommitNotFound(sha.to_string()))?;
+    if commit.parent_count() == 0 {
+        return Ok(Vec::new());
+    }
+    let path = old_path.unwrap_or(file_path);
+    let base_tree = marker_commit::calculate_base_tree(repository, &commit)?;
+    let Ok(entry) = base_tree.get_path(path) else {
+        return Ok(Vec::new());
+    };
+    let base_blob = repository.find_blob(entry.id())?;
+
+    let mut lines = BTreeMap::new();
+    for parent in commit.parents() {
+        if parent.tree()?.get_path(path).is_err() {
+            continue;
+        }
+        let mut opts = BlameOptions::new();
+        opts.newest_commit(parent.id());
+        let blame = repository.blame_file(path, Some(&mut opts))?;
+        let blame = blame.blame_buffer(base_blob.content())?;
+
+        for hunk in blame.iter() {
+            // Lines of the base that differ from this parent carry a zero id.
+            if hunk.final_commit_id().is_zero() {
+                continue;
+            }
+            let short_id = CommitId::from(hunk.final_commit_id()).short(CommitId::SHORT_LEN);
+            let signature = hunk.final_signature();
+            let author = String::from_utf8_lossy(signature.name_bytes()).into_owned();
+            let start = hunk.final_start_line() as u32;
+            for line in start..start + hunk.lines_in_hunk() as u32 {
+                lines.entry(line).or_insert_with(|| BlameLine {
+                    line,
+                    short_id: short_id.clone(),
+                    author: author.clone(),
+                });
+            }
+        }
+    }
+    Ok(lines.into_values().collect())
+}
+
+type BlameKey = (CommitId, PathBuf);
+
+/// Bounded LRU cache of blame results keyed by commit and file. Commits are immutable, so
+/// entries never go stale; the bound only keeps a long session from growing without limit.
+#[derive(Debug)]
+pub struct BlameCache(LruCache<BlameKey, Vec<BlameLine>>);
+
+impl Default for BlameCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl BlameCache {
+    pub fn new(capacity: usize) -> Self {
+        Self(LruCache::new(capacity))
+    }
+
+    /// Return the cached blame of `file_path` at `sha`, computing it on a miss.
+    pub fn get_or_compute(
+        &self,
+        repository: &Repository,
+        sha: CommitId,
+        file_path: &Path,
+        old_path: Option<&Path>,
+    ) -> Result<Vec<BlameLine>> {
+        self.0
+            .get_or_insert_with((sha, file_path.to_path_buf()), || {
+                get_blame(repository, sha, file_path, old_path)
+            })
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_repo::TestRepo;
+
+    fn blamed_ids(lines: &[BlameLine]) -> Vec<&str> {
+        lines.iter().map(|l| l.short_id.as_str()).collect()
+    }
+
+    fn short(sha: CommitId) -> String {
+        sha.short(CommitId::SHORT_LEN)
+    }
+
+    #[test]
+    fn attributes_lines_to_the_commits_that_last_changed_them() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("file.txt", "one\ntwo\nthree\n").unwrap();
+        let first = t.commit("first").unwrap().created.commit_id;
+        t.write_file("file.txt", "one\nTWO\nthree\n").unwrap();
+        let second = t.commit("second").unwrap().created.commit_id;
+        t.write_file("file.txt", "one\nTWO\nTHREE\n").unwrap();
+        let third = t.commit("third").unwrap().created.commit_id;
+
+        let lines = get_blame(&t.repo, third, Path::new("file.txt"), None).unwrap();
+
+        assert_eq!(
+            blamed_ids(&lines),
+            vec![short(first), short(second), short(first)]
+        );
+        assert_eq!(lines[0].line, 1);
+        assert_eq!(lines[0].author, "Test User");
+    }
+
+    #[test]
+    fn follows_rename_in_the_blamed_commit() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("old.txt", "one\ntwo\n").unwrap();
+        let first = t.commit("first").unwrap().created.commit_id;
+        t.write_file("old.txt", "one\nTWO\n").unwrap();
+        let second = t.commit("second").unwrap().created.commit_id;
+        t.rename_file("old.txt", "new.txt").unwrap();
+        let renamed = t.commit("rename").unwrap().created.commit_id;
+
+        let lines = get_blame(
+            &t.repo,
+            renamed,
+            Path::new("new.txt"),
+            Some(Path::new("old.txt")),
+        )
+        .unwrap();
+
+        assert_eq!(blamed_ids(&lines), vec![short(first), short(second)]);
+        assert_eq!(lines.iter().map(|l| l.line).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn merge_attributes_lines_to_the_parent_they_came_from() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("file.txt", "one\ntwo\nthree\nfour\nfive\n")
+            .unwrap();
+        let base = t.commit("base").unwrap().created;
+        t.write_file("file.txt", "ONE\ntwo\nthree\nfour\nfive\n")
+            .unwrap();
+        let left = t.commit("left").unwrap().created;
+        t.new_revision(base.change_id).unwrap();
+        t.write_file("file.txt", "one\ntwo\nthree\nfour\nFIVE\n")
+            .unwrap();
+        let right = t.work_copy().unwrap();
+        let merge = t
+            .merge(&[left.change_id, right.change_id], "merge")
+            .unwrap();
+
+        let lines = get_blame(&t.repo, merge.commit_id, Path::new("file.txt"), None).unwrap();
+
+        assert_eq!(
+            blamed_ids(&lines),
+            vec![
+                short(left.commit_id),
+                short(base.commit_id),
+                short(base.commit_id),
+                short(base.commit_id),
+                short(right.commit_id),
+            ]
+        );
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("a.txt", "a\n").unwrap();
+        t.write_file("b.txt", "b\n").unwrap();
+        t.commit("first").unwrap();
+        t.write_file("a.txt", "A\n").unwrap();
+        let second = t.commit("second").unwrap().created.commit_id;
+
+        let cache = BlameCache::new(1);
+        let a = cache
+            .get_or_compute(&t.repo, second, Path::new("a.txt"), None)
+            .unwrap();
+        cache
+            .get_or_compute(&t.repo, second, Path::new("b.txt"), None)
+            .unwrap();
+
+        assert_eq!(cache.len(), 1);
+        let again = cache
+            .get_or_compute(&t.repo, second, Path::new("a.txt"), None)
+            .unwrap();
+        assert_eq!(blamed_ids(&again), blamed_ids(&a));
+    }
+
+    #[test]
+    fn added_file_has_no_blame() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("a.txt", "a\n").unwrap();
+        t.commit("first").unwrap();
+        t.write_file("b.txt", "b\n").unwrap();
+        let second = t.commit("second").unwrap().created.commit_id;
+
+        let lines = get_blame(&t.repo, second, Path::new("b.txt"), None).unwrap();
+        assert!(lines.is_empty());
+    }
+}
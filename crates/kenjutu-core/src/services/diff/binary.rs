@@ -0,0 +1,129 @@
+use std::path::Path;
+
+use git2::Tree;
+use kenjutu_types::CommitId;
+use marker_commit::MarkerCommit;
+use serde::Serialize;
+
+use super::{Error, Result};
+
+/// Size and blob summary shown in place of a text diff for binary files.
+///
+/// Either side is `None` when the file does not exist there (added or deleted files).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct BinaryDiff {
+    /// Size in bytes of the base blob.
+    pub old_size: Option<u64>,
+    /// Size in bytes of the target blob.
+    pub new_size: Option<u64>,
+    pub old_oid: Option<String>,
+    pub new_oid: Option<String>,
+    /// Whether the base and target blobs differ.
+    pub changed: bool,
+}
+
+/// Summarize a binary file by comparing its blobs in the base and target trees.
+///
+/// For renamed files, pass the previous path as `old_path` so the base blob is found.
+pub fn generate_binary_diff(
+    repository: &git2::Repository,
+    sha: CommitId,
+    file_path: &Path,
+    old_path: Option<&Path>,
+) -> Result<BinaryDiff> {
+    let (base_tree, target_tree) = {
+        let marker = MarkerCommit::get(repository, sha)?;
+        (marker.base_tree().clone(), marker.target_tree().clone())
+    };
+
+    let old = blob_summary(repository, &base_tree, old_path.unwrap_or(file_path))?;
+    let new = blob_summary(repository, &target_tree, file_path)?;
+    if old.is_none() && new.is_none() {
+        return Err(Error::FileNotFound(file_path.display().to_string()));
+    }
+
+    let changed = old.as_ref().map(|(oid, _)| oid) != new.as_ref().map(|(oid, _)| oid);
+    Ok(BinaryDiff {
+        old_size: old.as_ref().map(|(_, size)| *size),
+        new_size: new.as_ref().map(|(_, size)| *size),
+        old_oid: old.map(|(oid, _)| oid.to_string()),
+        new_oid: new.map(|(oid, _)| oid.to_string()),
+        changed,
+    })
+}
+
+fn blob_summary(
+    repository: &git2::Repository,
+    tree: &Tree,
+    path: &Path,
+) -> Result<Option<(git2::Oid, u64)>> {
+    match tree.get_path(path) {
+        Ok(entry) => {
+            let blob = repository.find_blob(entry.id())?;
+            Ok(Some((blob.id(), blob.size() as u64)))
+        }
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(Error::from(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ReviewStatus;
+    use crate::services::diff::{DiffConfig, generate_file_list};
+    use test_repo::TestRepo;
+
+    const PNG_V1: &str = "\x00PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+    const PNG_V2: &str = "\x00PNG\r\n\x1a\n\x00\x00\x00\rIHDR\x00\x00\x01\x00\x00\x00\x01\x00";
+
+    #[test]
+    fn modified_binary_reports_both_sizes() {
+        let repo = TestRepo::new().unwrap();
+        repo.write_file("image.png", PNG_V1).unwrap();
+        repo.commit("add image").unwrap();
+        repo.write_file("image.png", PNG_V2).unwrap();
+        let sha = repo.commit("update image").unwrap().created.commit_id;
+
+        let diff = generate_binary_diff(&repo.repo, sha, Path::new("image.png"), None).unwrap();
+
+        assert_eq!(diff.old_size, Some(PNG_V1.len() as u64));
+        assert_eq!(diff.new_size, Some(PNG_V2.len() as u64));
+        assert!(diff.changed);
+        assert_ne!(diff.old_oid, diff.new_oid);
+    }
+
+    #[test]
+    fn added_binary_has_no_old_side() {
+        let repo = TestRepo::new().unwrap();
+        repo.write_file("image.png", PNG_V1).unwrap();
+        let sha = repo.commit("add image").unwrap().created.commit_id;
+
+        let diff = generate_binary_diff(&repo.repo, sha, Path::new("image.png"), None).unwrap();
+
+        assert_eq!(diff.old_size, None);
+        assert_eq!(diff.old_oid, None);
+        assert_eq!(diff.new_size, Some(PNG_V1.len() as u64));
+        assert!(diff.changed);
+    }
+
+    #[test]
+    fn binary_file_can_be_marked_reviewed() {
+        let repo = TestRepo::new().unwrap();
+        repo.write_file("image.png", PNG_V1).unwrap();
+        let sha = repo.commit("add image").unwrap().created.commit_id;
+
+        let mut marker = MarkerCommit::get(&repo.repo, sha).unwrap();
+        marker
+            .mark_file_reviewed(Path::new("image.png"), None)
+            .unwrap();
+        marker.write().unwrap();
+        drop(marker);
+
+        let (_, files) = generate_file_list(&repo.repo, sha, DiffConfig::default()).unwrap();
+        assert!(files[0].is_binary);
+        assert_eq!(files[0].review_status, ReviewStatus::Reviewed);
+    }
+}
@@ -0,0 +1,146 @@
+use std::path::Path;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use kenjutu_types::CommitId;
+use marker_commit::MarkerCommit;
+use serde::{Deserialize, Serialize};
+
+use super::{Error, Result};
+
+/// Blobs larger than this are refused unless the caller passes its own limit.
+pub const DEFAULT_MAX_BLOB_SIZE: u64 = 5 * 1024 * 1024;
+
+/// Which side of the change to read a blob from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum BlobSide {
+    /// The base tree (the parent of the commit).
+    Old,
+    /// The commit's own tree.
+    New,
+}
+
+/// A blob encoded for embedding in the UI as a `data:` URL.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct EncodedBlob {
+    /// MIME type guessed from the file extension.
+    pub mime: String,
+    pub base64: String,
+}
+
+/// Read a blob from one side of the change and encode it as base64.
+///
+/// Returns [`Error::BlobTooLarge`] instead of reading blobs over `max_size` bytes.
+pub fn get_blob_base64(
+    repository: &git2::Repository,
+    sha: CommitId,
+    file_path: &Path,
+    side: BlobSide,
+    max_size: u64,
+) -> Result<EncodedBlob> {
+    let tree = {
+        let marker = MarkerCommit::get(repository, sha)?;
+        match side {
+            BlobSide::Old => marker.base_tree().clone(),
+            BlobSide::New => marker.target_tree().clone(),
+        }
+    };
+
+    let entry = tree.get_path(file_path).map_err(|e| match e.code() {
+        git2::ErrorCode::NotFound => Error::FileNotFound(file_path.display().to_string()),
+        _ => Error::from(e),
+    })?;
+    let blob = repository.find_blob(entry.id())?;
+
+    let size = blob.size() as u64;
+    if size > max_size {
+        return Err(Error::BlobTooLarge {
+            size,
+            max: max_size,
+        });
+    }
+
+    Ok(EncodedBlob {
+        mime: mime_from_path(file_path).to_string(),
+        base64: STANDARD.encode(blob.content()),
+    })
+}
+
+fn mime_from_path(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("svg") => "image/svg+xml",
+        Some("webp") => "image/webp",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_repo::TestRepo;
+
+    const PNG: &str = "\x00PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+
+    #[test]
+    fn round_trips_png_as_base64() {
+        let repo = TestRepo::new().unwrap();
+        repo.write_file("logo.png", PNG).unwrap();
+        let sha = repo.commit("add logo").unwrap().created.commit_id;
+
+        let blob = get_blob_base64(
+            &repo.repo,
+            sha,
+            Path::new("logo.png"),
+            BlobSide::New,
+            DEFAULT_MAX_BLOB_SIZE,
+        )
+        .unwrap();
+
+        assert_eq!(blob.mime, "image/png");
+        assert_eq!(STANDARD.decode(blob.base64).unwrap(), PNG.as_bytes());
+    }
+
+    #[test]
+    fn old_side_reads_parent_tree() {
+        let repo = TestRepo::new().unwrap();
+        repo.write_file("logo.png", PNG).unwrap();
+        repo.commit("add logo").unwrap();
+        repo.write_file("logo.png", "\x00changed").unwrap();
+        let sha = repo.commit("update logo").unwrap().created.commit_id;
+
+        let blob = get_blob_base64(
+            &repo.repo,
+            sha,
+            Path::new("logo.png"),
+            BlobSide::Old,
+            DEFAULT_MAX_BLOB_SIZE,
+        )
+        .unwrap();
+
+        assert_eq!(STANDARD.decode(blob.base64).unwrap(), PNG.as_bytes());
+    }
+
+    #[test]
+    fn refuses_blobs_over_max_size() {
+        let repo = TestRepo::new().unwrap();
+        repo.write_file("logo.png", PNG).unwrap();
+        let sha = repo.commit("add logo").unwrap().created.commit_id;
+
+        let result = get_blob_base64(&repo.repo, sha, Path::new("logo.png"), BlobSide::New, 4);
+
+        assert!(matches!(
+            result,
+            Err(Error::BlobTooLarge { size, max: 4 }) if size == PNG.len() as u64
+        ));
+    }
+}
@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+
+use git2::Oid;
+
+use super::{DiffAlgorithm, DiffConfig, PartialReviewDiffs, Result};
+use crate::services::lru::LruCache;
+
+const DEFAULT_CAPACITY: usize = 64;
+
+/// Identifies a computed `PartialReviewDiffs` by the blobs it was built from.
+///
+/// Keying on the B/M/T blob ids rather than the commit means a marker write that touches the
+/// file produces a new key, so stale entries are never served and need no explicit invalidation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct DiffCacheKey {
+    pub file_path: PathBuf,
+    pub old_path: Option<PathBuf>,
+    pub marker_path: PathBuf,
+    pub base: Oid,
+    pub marker: Oid,
+    pub target: Oid,
+    pub ignore_whitespace: bool,
+    pub context_lines: u32,
+    pub algorithm: DiffAlgorithm,
+    pub plain: bool,
+    pub theme: Option<&'static str>,
+}
+
+impl DiffCacheKey {
+    pub fn new(
+        file_path: &Path,
+        old_path: Option<&Path>,
+        marker_path: &Path,
+        [base, marker, target]: [Option<Oid>; 3],
+        config: DiffConfig,
+    ) -> Self {
+        Self {
+            file_path: file_path.to_path_buf(),
+            old_path: old_path.map(Path::to_path_buf),
+            marker_path: marker_path.to_path_buf(),
+            base: base.unwrap_or_else(Oid::zero),
+            marker: marker.unwrap_or_else(Oid::zero),
+            target: target.unwrap_or_else(Oid::zero),
+            ignore_whitespace: config.ignore_whitespace,
+            context_lines: config.context_lines,
+            algorithm: config.algorithm,
+            plain: config.plain,
+            theme: config.theme,
+        }
+    }
+}
+
+/// Bounded LRU cache of highlighted per-file diffs, so moving back and forth between files
+/// doesn't re-diff and re-highlight them.
+///
+/// Highlight colors are baked into the cached tokens; call [`DiffCache::clear`] after changing
+/// the highlight theme.
+#[derive(Debug)]
+pub struct DiffCache(LruCache<DiffCacheKey, PartialReviewDiffs>);
+
+impl Default for DiffCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl DiffCache {
+    pub fn new(capacity: usize) -> Self {
+        Self(LruCache::new(capacity))
+    }
+
+    pub fn clear(&self) {
+        self.0.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(super) fn get_or_insert_with(
+        &self,
+        key: DiffCacheKey,
+        generate: impl FnOnce() -> Result<PartialReviewDiffs>,
+    ) -> Result<PartialReviewDiffs> {
+        self.0.get_or_insert_with(key, generate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::models::FileDiff;
+
+    fn key(path: &str, marker: u8) -> DiffCacheKey {
+        let oid = |b: u8| Some(Oid::from_bytes(&[b; 20]).unwrap());
+        DiffCacheKey::new(
+            Path::new(path),
+            None,
+            Path::new(path),
+            [oid(1), oid(marker), oid(3)],
+            DiffConfig::default(),
+        )
+    }
+
+    fn empty_diffs() -> PartialReviewDiffs {
+        let empty = FileDiff {
+            hunks: Vec::new(),
+            new_file_lines: 0,
+        };
+        PartialReviewDiffs {
+            remaining: empty.clone(),
+            reviewed: empty,
+            marker_path: String::new(),
+        }
+    }
+
+    #[test]
+    fn second_fetch_does_not_regenerate() {
+        let cache = DiffCache::default();
+        let calls = Cell::new(0);
+        let generate = || {
+            calls.set(calls.get() + 1);
+            Ok(empty_diffs())
+        };
+
+        cache.get_or_insert_with(key("a.rs", 2), generate).unwrap();
+        cache.get_or_insert_with(key("a.rs", 2), generate).unwrap();
+        assert_eq!(calls.get(), 1);
+
+        // A marker write changes M's blob, which must miss.
+        cache.get_or_insert_with(key("a.rs", 9), generate).unwrap();
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = DiffCache::new(2);
+        let calls = Cell::new(0);
+        let generate = || {
+            calls.set(calls.get() + 1);
+            Ok(empty_diffs())
+        };
+
+        cache.get_or_insert_with(key("a.rs", 2), generate).unwrap();
+        cache.get_or_insert_with(key("b.rs", 2), generate).unwrap();
+        // Touch a.rs so b.rs becomes the eviction candidate.
+        cache.get_or_insert_with(key("a.rs", 2), generate).unwrap();
+        cache.get_or_insert_with(key("c.rs", 2), generate).unwrap();
+        assert_eq!(calls.get(), 3);
+        assert_eq!(cache.len(), 2);
+
+        cache.get_or_insert_with(key("a.rs", 2), generate).unwrap();
+        assert_eq!(calls.get(), 3);
+        cache.get_or_insert_with(key("b.rs", 2), generate).unwrap();
+        assert_eq!(calls.get(), 4);
+    }
+
+    #[test]
+    fn errors_are_not_cached() {
+        let cache = DiffCache::default();
+        let result = cache.get_or_insert_with(key("a.rs", 2), || {
+            Err(super::super::Error::Internal("boom".to_string()))
+        });
+        assert!(result.is_err());
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+use git2::DiffFormat;
+use kenjutu_types::CommitId;
+use marker_commit::MarkerCommit;
+
+use super::file_list::diff_with_options;
+use super::{DiffConfig, Result};
+
+/// Render the full change as `diff --git` unified text, suitable for `git apply`.
+///
+/// Uses the same base tree and `config` as the file list, so the export matches what the
+/// reviewer sees. Whitespace changes are always kept, since a patch without them would not
+/// apply. Binary files are emitted as `Binary files ... differ` placeholders.
+pub fn export_unified_diff(
+    repository: &git2::Repository,
+    sha: CommitId,
+    config: DiffConfig,
+) -> Result<String> {
+    let (base_tree, target_tree) = {
+        let marker = MarkerCommit::get(repository, sha)?;
+        (marker.base_tree().clone(), marker.target_tree().clone())
+    };
+
+    let config = DiffConfig {
+        ignore_whitespace: false,
+        ..config
+    };
+    let diff = diff_with_options(repository, &base_tree, &target_tree, config)?;
+
+    let mut out = Vec::new();
+    diff.print(DiffFormat::Patch, |_delta, _hunk, line| {
+        // File headers and hunk headers carry their own text; content lines need their origin.
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            out.push(line.origin() as u8);
+        }
+        out.extend_from_slice(line.content());
+        true
+    })?;
+
+    Ok(String::from_utf8_lossy(&out).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    use super::*;
+    use test_repo::TestRepo;
+
+    fn git_apply(dir: &std::path::Path, patch: &str, extra: &[&str]) -> std::process::Output {
+        let mut child = Command::new("git")
+            .arg("apply")
+            .args(extra)
+            .current_dir(dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(patch.as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    }
+
+    #[test]
+    fn export_round_trips_through_git_apply() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("modified.txt", "one\ntwo\nthree\n").unwrap();
+        t.write_file("deleted.txt", "bye\n").unwrap();
+        t.write_file("old_name.txt", "a\nb\nc\nd\ne\nf\ng\nh\n")
+            .unwrap();
+        let a = t.commit("base").unwrap().created;
+
+        t.write_file("modified.txt", "one\nTWO\nthree\n").unwrap();
+        t.delete_file("deleted.txt").unwrap();
+        t.rename_file("old_name.txt", "new_name.txt").unwrap();
+        t.write_file("added.txt", "hello\n").unwrap();
+        let b = t.commit("change").unwrap().created;
+
+        let patch = export_unified_diff(&t.repo, b.commit_id, DiffConfig::default()).unwrap();
+        assert!(patch.contains("diff --git a/modified.txt b/modified.txt"));
+        assert!(patch.contains("rename from old_name.txt"));
+        assert!(patch.contains("rename to new_name.txt"));
+
+        // Check the patch against a working copy of the base revision.
+        t.new_revision(a.change_id).unwrap();
+        let check = git_apply(t.path(), &patch, &["--check"]);
+        assert!(
+            check.status.success(),
+            "git apply --check failed: {}\n{patch}",
+            String::from_utf8_lossy(&check.stderr)
+        );
+
+        let apply = git_apply(t.path(), &patch, &[]);
+        assert!(apply.status.success());
+        let applied = std::fs::read_to_string(t.path().join("modified.txt")).unwrap();
+        assert_eq!(applied, "one\nTWO\nthree\n");
+        assert!(t.path().join("new_name.txt").exists());
+        assert!(!t.path().join("deleted.txt").exists());
+    }
+
+    #[test]
+    fn export_keeps_whitespace_changes_when_ignored() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("a.txt", "fn a() {\nbody\n}\n").unwrap();
+        t.commit("base").unwrap();
+        t.write_file("a.txt", "fn a() {\n    body\n}\n").unwrap();
+        let b = t.commit("indent").unwrap().created;
+
+        let config = DiffConfig {
+            ignore_whitespace: true,
+            ..Default::default()
+        };
+        let patch = export_unified_diff(&t.repo, b.commit_id, config).unwrap();
+        assert!(patch.contains("+    body"), "{patch}");
+    }
+}
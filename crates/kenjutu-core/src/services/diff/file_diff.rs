@@ -5,10 +5,12 @@ use serde::Serialize;
 use std::path::Path;
 use two_face::re_exports::syntect::parsing::SyntaxReference;
 
-use super::{Error, Result};
+use super::cache::{DiffCache, DiffCacheKey};
+use super::file_list::commit_tree;
+use super::{DiffConfig, Error, Result};
 use crate::models::{DiffHunk, DiffLine, DiffLineType, FileDiff, HighlightToken};
 use crate::services::git;
-use crate::services::highlight::{self, HighlightService};
+use crate::services::highlight::{self, HighlightService, ParseAndHighlight};
 use crate::services::word_diff::{Block, HunkLines, SideLine, compute_word_diff};
 
 #[derive(Debug)]
@@ -110,28 +112,128 @@ fn map_line_type(line_type: Git2DiffLineType) -> DiffLineType {
     }
 }
 
-fn process_hunk(hunk: &Hunk, syntax: &SyntaxReference) -> Result<DiffHunk> {
-    let word_diff = compute_word_diff(hunk);
+/// Markers jj writes when materializing a conflict into a file.
+const CONFLICT_MARKER_CHARS: [char; 6] = ['<', '%', '+', '-', '|', '>'];
 
-    let highlight_service = HighlightService::global();
-    let mut old_state = highlight_service.parse_and_highlight(syntax);
-    let mut new_state = highlight_service.parse_and_highlight(syntax);
+const MIN_CONFLICT_MARKER_LEN: usize = 7;
+
+/// jj writes a conflict marker as a run of at least seven identical marker characters, a space
+/// and a label. It uses longer runs when the file already has marker-like lines, and always
+/// adds the label, so a bare run such as a heading underline is not a marker.
+fn is_conflict_marker(line: &str) -> bool {
+    let line = line.trim_end_matches(['\n', '\r']);
+    let Some(marker) = line.chars().next() else {
+        return false;
+    };
+    if !CONFLICT_MARKER_CHARS.contains(&marker) {
+        return false;
+    }
+    let label = line.trim_start_matches(marker);
+    let run = line.len() - label.len();
+    run >= MIN_CONFLICT_MARKER_LEN
+        && label
+            .strip_prefix(' ')
+            .is_some_and(|label| !label.trim().is_empty())
+}
+
+/// Refuse to mark `file_path` reviewed while the version in `tree` (a marker's target) still has
+/// conflict markers in it. The marking functions in [`crate::services::review`] call this, so
+/// every front end gets the same check.
+pub fn check_no_conflict_markers(
+    repository: &git2::Repository,
+    tree: &git2::Tree,
+    file_path: &Path,
+) -> Result<()> {
+    let Some(blob) = resolve_blob(repository, tree, file_path)? else {
+        return Ok(());
+    };
+    check_content_has_no_conflict_markers(file_path, blob.content())
+}
+
+/// Refuse to record `content` as the reviewed version of `file_path` while it has conflict
+/// markers in it.
+pub fn check_content_has_no_conflict_markers(file_path: &Path, content: &[u8]) -> Result<()> {
+    if String::from_utf8_lossy(content)
+        .lines()
+        .any(is_conflict_marker)
+    {
+        return Err(Error::UnresolvedConflict(file_path.display().to_string()));
+    }
+    Ok(())
+}
+
+/// Highlighter for one side of a patch that keeps its parse state across hunks.
+/// Lines elided between hunks are fed from the full file content, so constructs
+/// spanning a hunk boundary (block comments, multi-line strings) stay correct.
+///
+/// Without a syntax (plain diffs) no parsing happens and each line is a single uncolored token.
+struct SideHighlighter<'a> {
+    state: Option<ParseAndHighlight<'a>>,
+    lines: Vec<&'a str>,
+    next_lineno: u32,
+}
+
+impl<'a> SideHighlighter<'a> {
+    fn new(syntax: Option<&'a SyntaxReference>, theme: Option<&str>, content: &'a str) -> Self {
+        Self {
+            state: syntax
+                .map(|syntax| HighlightService::global().parse_and_highlight(syntax, theme)),
+            lines: content.split_inclusive('\n').collect(),
+            next_lineno: 1,
+        }
+    }
+
+    /// Highlight `line` as line `lineno` (1-based) of this side.
+    fn highlight(&mut self, lineno: Option<u32>, line: &str) -> Vec<highlight::Token> {
+        let Some(state) = self.state.as_mut() else {
+            return vec![highlight::Token {
+                content: line.to_string(),
+                color: None,
+            }];
+        };
+        if let Some(lineno) = lineno {
+            let start = self.next_lineno.saturating_sub(1) as usize;
+            let end = (lineno.saturating_sub(1) as usize).max(start);
+            for skipped in self.lines.get(start..end).unwrap_or_default() {
+                let _ = state.highlight_line(skipped);
+            }
+            self.next_lineno = lineno + 1;
+        }
+        state.highlight_line(line)
+    }
+}
+
+fn process_hunk(
+    hunk: &Hunk,
+    old_state: &mut SideHighlighter,
+    new_state: &mut SideHighlighter,
+) -> Result<DiffHunk> {
+    let word_diff = compute_word_diff(hunk);
 
     let mut lines = Vec::new();
+    let mut old_text = Vec::new();
+    let mut new_text = Vec::new();
+    let mut has_changes = false;
+    let mut has_conflict = false;
 
     for line in hunk.lines() {
         let line = line?;
         let line_str = String::from_utf8_lossy(line.content()).to_string();
-        match map_line_type(line.origin_value()) {
+        let line_type = map_line_type(line.origin_value());
+        if matches!(line_type, DiffLineType::Context | DiffLineType::Addition) {
+            has_conflict |= is_conflict_marker(&line_str);
+        }
+        match line_type {
             DiffLineType::Context => {
-                let _ = old_state.highlight_line(&line_str);
-                let tokens = new_state.highlight_line(&line_str);
+                let _ = old_state.highlight(line.old_lineno(), &line_str);
+                let tokens = new_state.highlight(line.new_lineno(), &line_str);
                 let tokens = tokens
                     .into_iter()
                     .map(|t| HighlightToken {
                         content: t.content,
                         color: t.color,
                         changed: false,
+                        moved: false,
                     })
                     .collect();
                 let tokens = merge_same_color_tokens(tokens);
@@ -145,16 +247,22 @@ fn process_hunk(hunk: &Hunk, syntax: &SyntaxReference) -> Result<DiffHunk> {
                             content: t.content,
                             color: t.color,
                             changed: false,
+                            moved: false,
                         })
                         .collect(),
                 });
             }
             DiffLineType::Deletion => {
-                let tokens = old_state.highlight_line(&line_str);
+                has_changes = true;
+                old_text.push(collapse_whitespace(&line_str));
+                let tokens = old_state.highlight(line.old_lineno(), &line_str);
                 let info = line.old_lineno().and_then(|n| word_diff.deletions.get(&n));
                 let ranges = info.map(|(_paired, ranges)| ranges);
                 let tokens = apply_change_ranges_to_tokens(tokens, ranges);
-                let tokens = merge_same_color_tokens(tokens);
+                let moved = line
+                    .old_lineno()
+                    .is_some_and(|n| word_diff.moved_deletions.contains(&n));
+                let tokens = merge_same_color_tokens(mark_moved(tokens, moved));
                 let new_lineno = info.map(|(paired, _)| *paired);
                 lines.push(DiffLine {
                     line_type: DiffLineType::Deletion,
@@ -164,11 +272,16 @@ fn process_hunk(hunk: &Hunk, syntax: &SyntaxReference) -> Result<DiffHunk> {
                 });
             }
             DiffLineType::Addition => {
-                let tokens = new_state.highlight_line(&line_str);
+                has_changes = true;
+                new_text.push(collapse_whitespace(&line_str));
+                let tokens = new_state.highlight(line.new_lineno(), &line_str);
                 let info = line.new_lineno().and_then(|n| word_diff.insertions.get(&n));
                 let ranges = info.map(|(_paired, ranges)| ranges);
                 let tokens = apply_change_ranges_to_tokens(tokens, ranges);
-                let tokens = merge_same_color_tokens(tokens);
+                let moved = line
+                    .new_lineno()
+                    .is_some_and(|n| word_diff.moved_insertions.contains(&n));
+                let tokens = merge_same_color_tokens(mark_moved(tokens, moved));
                 let old_lineno = info.map(|(paired, _)| *paired);
                 lines.push(DiffLine {
                     line_type: DiffLineType::Addition,
@@ -190,9 +303,31 @@ fn process_hunk(hunk: &Hunk, syntax: &SyntaxReference) -> Result<DiffHunk> {
         new_lines: hunk.new_lines(),
         header,
         lines,
+        whitespace_only: has_changes && old_text == new_text,
+        has_conflict,
     })
 }
 
+/// Trim a line and collapse inner whitespace runs, so lines compare equal only when they differ
+/// in indentation or spacing, not when a space between tokens was dropped.
+fn collapse_whitespace(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn mark_moved(tokens: Vec<HighlightToken>, moved: bool) -> Vec<HighlightToken> {
+    if !moved {
+        return tokens;
+    }
+    tokens
+        .into_iter()
+        .map(|t| HighlightToken {
+            changed: false,
+            moved: true,
+            ..t
+        })
+        .collect()
+}
+
 fn merge_same_color_tokens(tokens: Vec<HighlightToken>) -> Vec<HighlightToken> {
     let mut merged: Vec<HighlightToken> = Vec::new();
 
@@ -210,7 +345,14 @@ fn merge_same_color_tokens(tokens: Vec<HighlightToken>) -> Vec<HighlightToken> {
     merged
 }
 
-fn process_patch(patch: &git2::Patch) -> Result<Vec<DiffHunk>> {
+/// Turn a patch into hunks of tokens highlighted in `config.theme`. With `config.plain`, the
+/// highlighter is skipped.
+fn process_patch(
+    patch: &git2::Patch,
+    old_content: &[u8],
+    new_content: &[u8],
+    config: DiffConfig,
+) -> Result<Vec<DiffHunk>> {
     let delta = patch.delta();
     let old_file = delta.old_file();
     let new_file = delta.new_file();
@@ -218,18 +360,31 @@ fn process_patch(patch: &git2::Patch) -> Result<Vec<DiffHunk>> {
     let old_path = old_file.path().map(|p| p.to_string_lossy().to_string());
     let new_path = new_file.path().map(|p| p.to_string_lossy().to_string());
 
+    let old_content = String::from_utf8_lossy(old_content);
+    let new_content = String::from_utf8_lossy(new_content);
+
     let mut hunks = Vec::new();
 
+    // The first line lets a shebang identify the language when the path does not.
+    let first_line = [&new_content, &old_content]
+        .into_iter()
+        .find(|content| !content.is_empty())
+        .and_then(|content| content.lines().next());
     let highlight_service = HighlightService::global();
-    let syntax = new_path
-        .as_ref()
-        .or(old_path.as_ref())
-        .and_then(|path| highlight_service.detect_syntax(path))
-        .unwrap_or_else(|| highlight_service.default_syntax());
+    let syntax = (!config.plain).then(|| {
+        new_path
+            .as_ref()
+            .or(old_path.as_ref())
+            .and_then(|path| highlight_service.detect_syntax(path, first_line))
+            .unwrap_or_else(|| highlight_service.default_syntax())
+    });
+
+    let mut old_state = SideHighlighter::new(syntax, config.theme, &old_content);
+    let mut new_state = SideHighlighter::new(syntax, config.theme, &new_content);
 
     for hunk_idx in 0..patch.num_hunks() {
         let hunk = Hunk::new(patch, hunk_idx)?;
-        let hunk = process_hunk(&hunk, syntax)?;
+        let hunk = process_hunk(&hunk, &mut old_state, &mut new_state)?;
         hunks.push(hunk);
     }
 
@@ -245,6 +400,7 @@ fn apply_change_ranges_to_tokens(
             .into_iter()
             .map(|t| HighlightToken {
                 changed: false,
+                moved: false,
                 content: t.content,
                 color: t.color,
             })
@@ -271,6 +427,7 @@ fn apply_change_ranges_to_tokens(
                     content: token.content[slice_start..slice_end].to_string(),
                     color: token.color.clone(),
                     changed: is_changed,
+                    moved: false,
                 });
             }
 
@@ -309,12 +466,9 @@ fn diff_blobs(
     old_path: Option<&Path>,
     new_content: &[u8],
     new_path: Option<&Path>,
+    config: DiffConfig,
 ) -> Result<Vec<DiffHunk>> {
-    let mut diff_opts = git2::DiffOptions::new();
-    diff_opts
-        .context_lines(3)
-        .interhunk_lines(0)
-        .ignore_whitespace(false);
+    let mut diff_opts = config.diff_options();
 
     let patch = Patch::from_buffers(
         old_content,
@@ -324,7 +478,24 @@ fn diff_blobs(
         Some(&mut diff_opts),
     )?;
 
-    process_patch(&patch)
+    let mut hunks = process_patch(&patch, old_content, new_content, config)?;
+    if config.plain {
+        for line in hunks.iter_mut().flat_map(|hunk| hunk.lines.iter_mut()) {
+            strip_styling(&mut line.tokens);
+        }
+    }
+    Ok(hunks)
+}
+
+/// Drop word-level change marks, merging what is left into one token.
+fn strip_styling(tokens: &mut Vec<HighlightToken>) {
+    let content: String = tokens.iter().map(|t| t.content.as_str()).collect();
+    *tokens = vec![HighlightToken {
+        content,
+        color: None,
+        changed: false,
+        moved: false,
+    }];
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -335,6 +506,9 @@ pub struct PartialReviewDiffs {
     pub remaining: FileDiff,
     /// diff(B→M) — already reviewed changes
     pub reviewed: FileDiff,
+    /// Where the file lives in the marker tree. For a rename this stays the old path until the
+    /// first region is reviewed, so `reviewed` is old→marker path and `remaining` marker→new.
+    pub marker_path: String,
 }
 
 fn resolve_blob<'repo>(
@@ -349,66 +523,270 @@ fn resolve_blob<'repo>(
     }
 }
 
+/// Refuse to highlight a file whose blobs are over [`DiffConfig::max_diff_bytes`]. Plain diffs
+/// skip the highlighter, so they are let through.
+fn check_diff_size(blobs: &[&Option<git2::Blob>], config: DiffConfig) -> Result<()> {
+    if config.plain {
+        return Ok(());
+    }
+    let size = blobs
+        .iter()
+        .filter_map(|b| b.as_ref().map(|b| b.size() as u64))
+        .max()
+        .unwrap_or(0);
+    if size > config.max_diff_bytes {
+        return Err(Error::DiffTooLarge {
+            size,
+            max: config.max_diff_bytes,
+        });
+    }
+    Ok(())
+}
+
 /// Generate two diffs for a partially reviewed file:
 /// - remaining: diff(M→T) — what's left to review
 /// - reviewed: diff(B→M) — what's already been reviewed
+///
+/// Fails with [`Error::DiffTooLarge`] when a blob is over [`DiffConfig::max_diff_bytes`] and
+/// the diff would be highlighted.
 pub fn generate_partial_review_diffs(
     repository: &git2::Repository,
     sha: CommitId,
     file_path: &Path,
     old_path: Option<&Path>,
+    config: DiffConfig,
 ) -> Result<PartialReviewDiffs> {
-    let marker = MarkerCommit::get(repository, sha)?;
-    let base_tree = marker.base_tree();
-    let marker_tree = marker.marker_tree();
-    let target_tree = marker.target_tree();
+    partial_review_diffs(repository, sha, file_path, old_path, config, None)
+}
 
-    let empty: &[u8] = b"";
+/// Like [`generate_partial_review_diffs`], but reuses a previous result from `cache` when the
+/// file's base, marker and target blobs are unchanged.
+pub fn generate_partial_review_diffs_cached(
+    repository: &git2::Repository,
+    cache: &DiffCache,
+    sha: CommitId,
+    file_path: &Path,
+    old_path: Option<&Path>,
+    config: DiffConfig,
+) -> Result<PartialReviewDiffs> {
+    partial_review_diffs(repository, sha, file_path, old_path, config, Some(cache))
+}
 
-    let target_blob = resolve_blob(repository, target_tree, file_path)?;
-    let target_content = target_blob.as_ref().map(|b| b.content()).unwrap_or(empty);
+/// The base, marker and target blobs of one file, and the path the file has in the marker tree.
+struct ReviewBlobs<'repo, 'p> {
+    base: Option<git2::Blob<'repo>>,
+    marker: Option<git2::Blob<'repo>>,
+    target: Option<git2::Blob<'repo>>,
+    marker_path: &'p Path,
+}
+
+impl ReviewBlobs<'_, '_> {
+    fn ids(&self) -> [Option<git2::Oid>; 3] {
+        [&self.base, &self.marker, &self.target].map(|b| b.as_ref().map(|b| b.id()))
+    }
+}
+
+fn blob_content<'b>(blob: &'b Option<git2::Blob>) -> &'b [u8] {
+    blob.as_ref().map(|b| b.content()).unwrap_or(b"")
+}
 
-    // For renamed files, M may have the file at old_path (not yet reviewed)
-    // or file_path (after review started)
-    let marker_blob = resolve_blob(repository, marker_tree, file_path)?.or(old_path
-        .map(|op| resolve_blob(repository, marker_tree, op))
+fn resolve_review_blobs<'repo, 'p>(
+    repository: &'repo git2::Repository,
+    marker: &MarkerCommit,
+    file_path: &'p Path,
+    old_path: Option<&'p Path>,
+) -> Result<ReviewBlobs<'repo, 'p>> {
+    let target = resolve_blob(repository, marker.target_tree(), file_path)?;
+
+    // For renamed files, M keeps the file at old_path until a region is reviewed, then at
+    // file_path. Look it up in the same order as `MarkerCommit::mark_regions_reviewed` so the
+    // regions sent back are computed against the blob the marker will splice.
+    let pending_rename_blob = old_path
+        .map(|op| resolve_blob(repository, marker.marker_tree(), op))
         .transpose()?
-        .flatten());
-    let marker_content = marker_blob.as_ref().map(|b| b.content()).unwrap_or(empty);
+        .flatten();
+    let (marker_path, marker_blob) = match (old_path, pending_rename_blob) {
+        (Some(op), Some(blob)) => (op, Some(blob)),
+        _ => (
+            file_path,
+            resolve_blob(repository, marker.marker_tree(), file_path)?,
+        ),
+    };
 
     let base_lookup = old_path.unwrap_or(file_path);
-    let base_blob = resolve_blob(repository, base_tree, base_lookup)?;
-    let base_content = base_blob.as_ref().map(|b| b.content()).unwrap_or(empty);
+    let base = resolve_blob(repository, marker.base_tree(), base_lookup)?;
 
-    // Remaining: diff(M→T)
-    let remaining_hunks = diff_blobs(marker_content, old_path, target_content, Some(file_path))?;
-    let remaining_new_file_lines = target_blob
-        .as_ref()
-        .map(|blob| String::from_utf8_lossy(blob.content()).lines().count() as u32)
-        .unwrap_or(0);
+    Ok(ReviewBlobs {
+        base,
+        marker: marker_blob,
+        target,
+        marker_path,
+    })
+}
 
-    // Reviewed: diff(B→M)
-    let reviewed_hunks = diff_blobs(base_content, old_path, marker_content, Some(file_path))?;
-    let reviewed_new_file_lines = marker_blob
-        .as_ref()
-        .map(|blob| String::from_utf8_lossy(blob.content()).lines().count() as u32)
-        .unwrap_or(0);
+fn partial_review_diffs(
+    repository: &git2::Repository,
+    sha: CommitId,
+    file_path: &Path,
+    old_path: Option<&Path>,
+    config: DiffConfig,
+    cache: Option<&DiffCache>,
+) -> Result<PartialReviewDiffs> {
+    let marker = MarkerCommit::get(repository, sha)?;
+    let blobs = resolve_review_blobs(repository, &marker, file_path, old_path)?;
+
+    check_diff_size(&[&blobs.base, &blobs.marker, &blobs.target], config)?;
+
+    let generate = || {
+        build_partial_review_diffs(
+            blob_content(&blobs.base),
+            blob_content(&blobs.marker),
+            blob_content(&blobs.target),
+            file_path,
+            old_path,
+            blobs.marker_path,
+            config,
+        )
+    };
+
+    match cache {
+        Some(cache) => {
+            let key =
+                DiffCacheKey::new(file_path, old_path, blobs.marker_path, blobs.ids(), config);
+            cache.get_or_insert_with(key, generate)
+        }
+        None => generate(),
+    }
+}
+
+/// What has been marked reviewed in one file so far: diff(B→M), the `reviewed` half of
+/// [`generate_partial_review_diffs`] on its own.
+///
+/// Works for a file in any review state; the diff is empty when nothing has been marked and
+/// equals the full diff once the file is reviewed.
+pub fn generate_reviewed_diff(
+    repository: &git2::Repository,
+    sha: CommitId,
+    file_path: &Path,
+    old_path: Option<&Path>,
+    config: DiffConfig,
+) -> Result<FileDiff> {
+    let marker = MarkerCommit::get(repository, sha)?;
+    let blobs = resolve_review_blobs(repository, &marker, file_path, old_path)?;
+
+    check_diff_size(&[&blobs.base, &blobs.marker], config)?;
+
+    reviewed_file_diff(
+        blob_content(&blobs.base),
+        blob_content(&blobs.marker),
+        file_path,
+        old_path,
+        blobs.marker_path,
+        config,
+    )
+}
+
+/// diff(B→M): the changes already marked reviewed.
+fn reviewed_file_diff(
+    base_content: &[u8],
+    marker_content: &[u8],
+    file_path: &Path,
+    old_path: Option<&Path>,
+    marker_path: &Path,
+    config: DiffConfig,
+) -> Result<FileDiff> {
+    Ok(FileDiff {
+        hunks: diff_blobs(
+            base_content,
+            Some(old_path.unwrap_or(file_path)),
+            marker_content,
+            Some(marker_path),
+            config,
+        )?,
+        new_file_lines: count_lines(marker_content),
+    })
+}
+
+fn count_lines(content: &[u8]) -> u32 {
+    String::from_utf8_lossy(content).lines().count() as u32
+}
+
+fn build_partial_review_diffs(
+    base_content: &[u8],
+    marker_content: &[u8],
+    target_content: &[u8],
+    file_path: &Path,
+    old_path: Option<&Path>,
+    marker_path: &Path,
+    config: DiffConfig,
+) -> Result<PartialReviewDiffs> {
+    // Remaining: diff(M→T)
+    let remaining_hunks = diff_blobs(
+        marker_content,
+        Some(marker_path),
+        target_content,
+        Some(file_path),
+        config,
+    )?;
+    let remaining_new_file_lines = count_lines(target_content);
 
     Ok(PartialReviewDiffs {
         remaining: FileDiff {
             hunks: remaining_hunks,
             new_file_lines: remaining_new_file_lines,
         },
-        reviewed: FileDiff {
-            hunks: reviewed_hunks,
-            new_file_lines: reviewed_new_file_lines,
-        },
+        reviewed: reviewed_file_diff(
+            base_content,
+            marker_content,
+            file_path,
+            old_path,
+            marker_path,
+            config,
+        )?,
+        marker_path: marker_path.to_string_lossy().into_owned(),
+    })
+}
+
+/// Diff one file between the trees of `base` and `sha`, for the file list from
+/// [`generate_range_file_list`](super::generate_range_file_list).
+///
+/// Unlike [`generate_partial_review_diffs`] there is no marker, so the result can't be marked.
+pub fn generate_range_file_diff(
+    repository: &git2::Repository,
+    base: CommitId,
+    sha: CommitId,
+    file_path: &Path,
+    old_path: Option<&Path>,
+    config: DiffConfig,
+) -> Result<FileDiff> {
+    let base_tree = commit_tree(repository, base)?;
+    let target_tree = commit_tree(repository, sha)?;
+
+    let empty: &[u8] = b"";
+    let base_path = old_path.unwrap_or(file_path);
+    let base_blob = resolve_blob(repository, &base_tree, base_path)?;
+    let target_blob = resolve_blob(repository, &target_tree, file_path)?;
+    check_diff_size(&[&base_blob, &target_blob], config)?;
+    let base_content = base_blob.as_ref().map(|b| b.content()).unwrap_or(empty);
+    let target_content = target_blob.as_ref().map(|b| b.content()).unwrap_or(empty);
+
+    Ok(FileDiff {
+        hunks: diff_blobs(
+            base_content,
+            Some(base_path),
+            target_content,
+            Some(file_path),
+            config,
+        )?,
+        new_file_lines: count_lines(target_content),
     })
 }
 
 /// Fetch context lines from a file blob at a given commit with syntax highlighting.
 /// `start_line` and `end_line` are 1-based inclusive line numbers in the new file.
 /// `old_start_line` is the corresponding 1-based line number in the old file for the first returned line.
+/// The lines are styled like hunks built with the same `config`: uncolored if it is plain, in
+/// its theme otherwise.
 pub fn get_context_lines(
     repository: &git2::Repository,
     sha: CommitId,
@@ -416,6 +794,7 @@ pub fn get_context_lines(
     start_line: u32,
     end_line: u32,
     old_start_line: u32,
+    config: DiffConfig,
 ) -> Result<Vec<DiffLine>> {
     let commit = repository
         .find_commit(sha.oid())
@@ -444,12 +823,28 @@ pub fn get_context_lines(
         return Ok(Vec::new());
     }
 
+    if config.plain {
+        return Ok(all_lines[start_idx..end_idx]
+            .iter()
+            .enumerate()
+            .map(|(i, line)| DiffLine {
+                line_type: DiffLineType::Context,
+                old_lineno: Some(old_start_line + i as u32),
+                new_lineno: Some(start_line + i as u32),
+                tokens: vec![HighlightToken {
+                    content: format!("{line}\n"),
+                    color: None,
+                    changed: false,
+                    moved: false,
+                }],
+            })
+            .collect());
+    }
+
     // Set up syntax highlighting - feed all lines from start to build correct parse state
     let highlight_service = HighlightService::global();
-    let syntax = highlight_service
-        .detect_syntax(file_path)
-        .unwrap_or_else(|| highlight_service.default_syntax());
-    let mut state = highlight_service.parse_and_highlight(syntax);
+    let syntax = highlight_service.syntax_for(file_path, all_lines.first().copied());
+    let mut state = highlight_service.parse_and_highlight(syntax, config.theme);
 
     // Feed lines before the requested range to build up parse state
     for line in &all_lines[..start_idx] {
@@ -465,20 +860,468 @@ pub fn get_context_lines(
         let line_num = start_line + i as u32;
         let old_line_num = old_start_line + i as u32;
 
+        let tokens: Vec<HighlightToken> = tokens
+            .into_iter()
+            .map(|t| HighlightToken {
+                content: t.content,
+                color: t.color,
+                changed: false,
+                moved: false,
+            })
+            .collect();
+
         lines.push(DiffLine {
             line_type: DiffLineType::Context,
             old_lineno: Some(old_line_num),
             new_lineno: Some(line_num),
-            tokens: tokens
-                .into_iter()
-                .map(|t| HighlightToken {
-                    content: t.content,
-                    color: t.color,
-                    changed: false,
-                })
-                .collect(),
+            tokens,
         });
     }
 
     Ok(lines)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::diff::DiffAlgorithm;
+    use marker_commit::RegionId;
+    use test_repo::TestRepo;
+
+    fn hunks(old: &str, new: &str, ignore_whitespace: bool) -> Vec<DiffHunk> {
+        hunks_with_config(
+            old,
+            new,
+            DiffConfig {
+                ignore_whitespace,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn hunks_with_config(old: &str, new: &str, config: DiffConfig) -> Vec<DiffHunk> {
+        diff_blobs(
+            old.as_bytes(),
+            Some(Path::new("file.txt")),
+            new.as_bytes(),
+            Some(Path::new("file.txt")),
+            config,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn reindent_is_whitespace_only() {
+        let result = hunks("fn a() {\nfoo();\n}\n", "fn a() {\n    foo();\n}\n", false);
+        assert_eq!(result.len(), 1);
+        assert!(result[0].whitespace_only);
+    }
+
+    #[test]
+    fn content_change_is_not_whitespace_only() {
+        let result = hunks("foo();\n", "  bar();\n", false);
+        assert_eq!(result.len(), 1);
+        assert!(!result[0].whitespace_only);
+    }
+
+    #[test]
+    fn dropped_space_is_not_whitespace_only() {
+        let result = hunks("int x;\n", "intx;\n", false);
+        assert_eq!(result.len(), 1);
+        assert!(!result[0].whitespace_only);
+    }
+
+    #[test]
+    fn ignore_whitespace_hides_reindent() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let new = "a\n  b\nc\nd\ne\nf\ng\nh\ni\nJ\n";
+        assert_eq!(hunks(old, new, false).len(), 2);
+
+        let result = hunks(old, new, true);
+        assert_eq!(result.len(), 1, "only the real change should remain");
+        assert!(!result[0].whitespace_only);
+    }
+
+    #[test]
+    fn conflict_markers_are_flagged() {
+        let old = "a\nb\nc\n";
+        let new = concat!(
+            "a\n",
+            "<<<<<<< Conflict 1 of 1\n",
+            "%%%%%%% Changes from base to side #1\n",
+            "-b\n",
+            "+B\n",
+            "+++++++ Contents of side #2\n",
+            "b2\n",
+            ">>>>>>> Conflict 1 of 1 ends\n",
+            "c\n",
+        );
+        let result = hunks(old, new, false);
+        assert_eq!(result.len(), 1);
+        assert!(result[0].has_conflict);
+    }
+
+    #[test]
+    fn base_and_diff3_markers_are_conflict_markers() {
+        assert!(is_conflict_marker("------- Contents of base"));
+        assert!(is_conflict_marker("||||||| Base"));
+        assert!(!is_conflict_marker("--------------"));
+    }
+
+    #[test]
+    fn heading_underline_is_not_a_conflict_marker() {
+        assert!(!is_conflict_marker("-------"));
+        assert!(!is_conflict_marker("-------\n"));
+        assert!(!is_conflict_marker("------- "));
+
+        let result = hunks("a\n", "a\n\nSummary\n-------\n", false);
+        assert_eq!(result.len(), 1);
+        assert!(!result[0].has_conflict);
+    }
+
+    #[test]
+    fn longer_conflict_markers_are_flagged() {
+        assert!(is_conflict_marker("<<<<<<<<<<< Conflict 1 of 1"));
+        assert!(is_conflict_marker("+++++++++++ Contents of side #2\n"));
+
+        let new = concat!(
+            "<<<<<<<<<<< Conflict 1 of 1\n",
+            "+++++++++++ Contents of side #1\n",
+            "a\n",
+            "+++++++++++ Contents of side #2\n",
+            "b\n",
+            ">>>>>>>>>>> Conflict 1 of 1 ends\n",
+        );
+        let result = hunks("a\n", new, false);
+        assert_eq!(result.len(), 1);
+        assert!(result[0].has_conflict);
+    }
+
+    #[test]
+    fn whole_file_mark_is_refused_while_conflicted() {
+        let repo = TestRepo::new().unwrap();
+        repo.write_file("clean.txt", "a\n").unwrap();
+        repo.write_file(
+            "conflicted.txt",
+            "<<<<<<< Conflict 1 of 1\na\n>>>>>>> Conflict 1 of 1 ends\n",
+        )
+        .unwrap();
+        let sha = repo.commit("add files").unwrap().created.commit_id;
+
+        let tree = commit_tree(&repo.repo, sha).unwrap();
+        check_no_conflict_markers(&repo.repo, &tree, Path::new("clean.txt")).unwrap();
+        let err = check_no_conflict_markers(&repo.repo, &tree, Path::new("conflicted.txt"));
+        assert!(matches!(err, Err(Error::UnresolvedConflict(_))));
+    }
+
+    #[test]
+    fn ordinary_change_has_no_conflict() {
+        let result = hunks("a\n", "<<<< not a marker\n", false);
+        assert_eq!(result.len(), 1);
+        assert!(!result[0].has_conflict);
+    }
+
+    #[test]
+    fn context_lines_controls_hunk_merging() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let new = "a\nB\nc\nd\ne\nf\nG\nh\n";
+        let with_context = |context_lines| {
+            hunks_with_config(
+                old,
+                new,
+                DiffConfig {
+                    context_lines,
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_eq!(with_context(1).len(), 2);
+        assert_eq!(with_context(10).len(), 1);
+    }
+
+    #[test]
+    fn patience_keeps_moved_function_as_context() {
+        let old = concat!(
+            "#include <stdio.h>\n",
+            "\n",
+            "// Frobs foo heartily\n",
+            "int frobnitz(int foo)\n",
+            "{\n",
+            "    int i;\n",
+            "    for(i = 0; i < 10; i++)\n",
+            "    {\n",
+            "        printf(\"Your answer is: \");\n",
+            "        printf(\"%d\\n\", foo);\n",
+            "    }\n",
+            "}\n",
+            "\n",
+            "int fact(int n)\n",
+            "{\n",
+            "    if(n > 1)\n",
+            "    {\n",
+            "        return fact(n-1) * n;\n",
+            "    }\n",
+            "    return 1;\n",
+            "}\n",
+            "\n",
+            "int main(int argc, char **argv)\n",
+            "{\n",
+            "    frobnitz(fact(10));\n",
+            "}\n",
+        );
+        let new = concat!(
+            "#include <stdio.h>\n",
+            "\n",
+            "int fib(int n)\n",
+            "{\n",
+            "    if(n > 2)\n",
+            "    {\n",
+            "        return fib(n-1) + fib(n-2);\n",
+            "    }\n",
+            "    return 1;\n",
+            "}\n",
+            "\n",
+            "// Frobs foo heartily\n",
+            "int frobnitz(int foo)\n",
+            "{\n",
+            "    int i;\n",
+            "    for(i = 0; i < 10; i++)\n",
+            "    {\n",
+            "        printf(\"%d\\n\", foo);\n",
+            "    }\n",
+            "}\n",
+            "\n",
+            "int main(int argc, char **argv)\n",
+            "{\n",
+            "    frobnitz(fib(10));\n",
+            "}\n",
+        );
+        let deletes_frobnitz = |algorithm| {
+            let config = DiffConfig {
+                algorithm,
+                ..Default::default()
+            };
+            hunks_with_config(old, new, config).iter().any(|hunk| {
+                hunk.lines.iter().any(|line| {
+                    let text: String = line.tokens.iter().map(|t| t.content.as_str()).collect();
+                    line.line_type == DiffLineType::Deletion && text.contains("frobnitz(int")
+                })
+            })
+        };
+
+        assert!(deletes_frobnitz(DiffAlgorithm::Myers));
+        assert!(!deletes_frobnitz(DiffAlgorithm::Patience));
+    }
+
+    #[test]
+    fn plain_config_emits_no_styling() {
+        let config = DiffConfig {
+            plain: true,
+            ..Default::default()
+        };
+        let result = hunks_with_config(
+            "fn main() {\n    let x = 1;\n}\n",
+            "fn main() {\n    let x = 2;\n}\n",
+            config,
+        );
+        assert!(!result.is_empty());
+        for line in result.iter().flat_map(|hunk| &hunk.lines) {
+            for token in &line.tokens {
+                assert_eq!(token.color, None, "{token:?}");
+                assert!(!token.changed && !token.moved, "{token:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn block_comment_carries_across_hunks() {
+        let body: String = (1..=20).map(|i| format!("text {i}\n")).collect();
+        let old = format!("fn a() {{}}\n/*\n{body}*/\n");
+        let new = old
+            .replace("fn a()", "fn b()")
+            .replace("text 18\n", "still a comment\n");
+        let result = diff_blobs(
+            old.as_bytes(),
+            Some(Path::new("file.rs")),
+            new.as_bytes(),
+            Some(Path::new("file.rs")),
+            DiffConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(result.len(), 2);
+
+        let colors = |line: &DiffLine| {
+            line.tokens
+                .iter()
+                .filter(|t| !t.content.trim().is_empty())
+                .map(|t| t.color.clone())
+                .collect::<Vec<_>>()
+        };
+        let comment_line = result[0]
+            .lines
+            .iter()
+            .find(|l| l.new_lineno == Some(3))
+            .unwrap();
+        let comment_color = colors(comment_line)[0].clone();
+        assert!(comment_color.is_some());
+
+        let added = result[1]
+            .lines
+            .iter()
+            .find(|l| l.line_type == DiffLineType::Addition)
+            .unwrap();
+        let added_colors = colors(added);
+        assert!(!added_colors.is_empty());
+        assert!(
+            added_colors.iter().all(|c| *c == comment_color),
+            "{added:?}"
+        );
+    }
+
+    fn region_of(hunk: &DiffHunk) -> RegionId {
+        RegionId {
+            old_start: hunk.old_start,
+            old_lines: hunk.old_lines,
+            new_start: hunk.new_start,
+            new_lines: hunk.new_lines,
+        }
+    }
+
+    fn hunk_text(hunk: &DiffHunk) -> String {
+        hunk.lines
+            .iter()
+            .flat_map(|line| line.tokens.iter().map(|t| t.content.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn renamed_file_splits_across_old_and_new_paths() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("old.txt", "head\na1\nmid1\nmid2\nmid3\nb1\ntail\n")
+            .unwrap();
+        t.commit("commit A").unwrap();
+        t.rename_file("old.txt", "new.txt").unwrap();
+        t.write_file("new.txt", "head\nA1\nmid1\nmid2\nmid3\nB1\ntail\n")
+            .unwrap();
+        let sha = t.commit("commit B").unwrap().created.commit_id;
+
+        let file_path = Path::new("new.txt");
+        let old_path = Some(Path::new("old.txt"));
+        // One line of context keeps the two edits in separate hunks.
+        let config = DiffConfig {
+            context_lines: 1,
+            ..Default::default()
+        };
+        let diffs =
+            || generate_partial_review_diffs(&t.repo, sha, file_path, old_path, config).unwrap();
+
+        let before = diffs();
+        assert_eq!(before.marker_path, "old.txt");
+        assert_eq!(before.remaining.hunks.len(), 2);
+        assert!(before.reviewed.hunks.is_empty());
+
+        let mut marker = MarkerCommit::get(&t.repo, sha).unwrap();
+        marker
+            .mark_region_reviewed(file_path, old_path, &region_of(&before.remaining.hunks[0]))
+            .unwrap();
+        marker.write().unwrap();
+        drop(marker);
+
+        let after = diffs();
+        assert_eq!(after.marker_path, "new.txt");
+        assert_eq!(after.reviewed.hunks.len(), 1);
+        assert!(hunk_text(&after.reviewed.hunks[0]).contains("A1"));
+        assert_eq!(after.remaining.hunks.len(), 1);
+        assert!(hunk_text(&after.remaining.hunks[0]).contains("B1"));
+    }
+
+    #[test]
+    fn range_diff_includes_edits_from_every_commit_since_base() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("old.txt", "a\nmid1\nmid2\nmid3\nb\n").unwrap();
+        let base = t.commit("base").unwrap().created.commit_id;
+        t.write_file("old.txt", "A\nmid1\nmid2\nmid3\nb\n").unwrap();
+        t.commit("edit a").unwrap();
+        t.rename_file("old.txt", "new.txt").unwrap();
+        t.write_file("new.txt", "A\nmid1\nmid2\nmid3\nB\n").unwrap();
+        let tip = t.commit("rename and edit b").unwrap().created.commit_id;
+
+        let config = DiffConfig {
+            context_lines: 1,
+            ..Default::default()
+        };
+        let diff = generate_range_file_diff(
+            &t.repo,
+            base,
+            tip,
+            Path::new("new.txt"),
+            Some(Path::new("old.txt")),
+            config,
+        )
+        .unwrap();
+
+        assert_eq!(diff.hunks.len(), 2);
+        assert!(hunk_text(&diff.hunks[0]).contains("A\n"));
+        assert!(hunk_text(&diff.hunks[1]).contains("B\n"));
+        assert_eq!(diff.new_file_lines, 5);
+    }
+
+    #[test]
+    fn reviewed_diff_contains_only_marked_hunks() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("file.txt", "a\nmid1\nmid2\nmid3\nb\n")
+            .unwrap();
+        t.commit("base").unwrap();
+        t.write_file("file.txt", "A\nmid1\nmid2\nmid3\nB\n")
+            .unwrap();
+        let sha = t.commit("edit").unwrap().created.commit_id;
+
+        let file_path = Path::new("file.txt");
+        let config = DiffConfig {
+            context_lines: 1,
+            ..Default::default()
+        };
+        let reviewed = || generate_reviewed_diff(&t.repo, sha, file_path, None, config).unwrap();
+        assert!(reviewed().hunks.is_empty());
+
+        let full = generate_partial_review_diffs(&t.repo, sha, file_path, None, config).unwrap();
+        assert_eq!(full.remaining.hunks.len(), 2);
+        let mut marker = MarkerCommit::get(&t.repo, sha).unwrap();
+        marker
+            .mark_region_reviewed(file_path, None, &region_of(&full.remaining.hunks[1]))
+            .unwrap();
+        marker.write().unwrap();
+        drop(marker);
+
+        let after = reviewed();
+        assert_eq!(after.hunks.len(), 1);
+        assert!(hunk_text(&after.hunks[0]).contains('B'));
+        assert!(!hunk_text(&after.hunks[0]).contains('A'));
+    }
+
+    #[test]
+    fn oversized_blob_is_refused_unless_plain() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("big.txt", "x\n".repeat(64)).unwrap();
+        let sha = t.commit("add big file").unwrap().created.commit_id;
+        let file_path = Path::new("big.txt");
+        let config = DiffConfig {
+            max_diff_bytes: 16,
+            ..Default::default()
+        };
+
+        let result = generate_partial_review_diffs(&t.repo, sha, file_path, None, config);
+        assert!(matches!(
+            result,
+            Err(Error::DiffTooLarge { size: 128, max: 16 })
+        ));
+
+        let plain = DiffConfig {
+            plain: true,
+            ..config
+        };
+        let diffs = generate_partial_review_diffs(&t.repo, sha, file_path, None, plain).unwrap();
+        assert_eq!(diffs.remaining.hunks.len(), 1);
+    }
+}
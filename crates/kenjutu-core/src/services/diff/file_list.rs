@@ -5,7 +5,7 @@ use git2::{Delta, Repository, Tree};
 use kenjutu_types::{ChangeId, CommitChangeIdExt, CommitId};
 use marker_commit::MarkerCommit;
 
-use super::{Error, Result};
+use super::{DiffConfig, Error, Result};
 use crate::models::{FileChangeStatus, FileEntry, ReviewStatus};
 use crate::services::git;
 
@@ -21,8 +21,14 @@ fn map_delta_status(status: Delta) -> FileChangeStatus {
     }
 }
 
-/// Extract metadata from a patch without fetching blob contents or syntax highlighting.
-fn process_patch_metadata(patch: &git2::Patch, marker_tree: &Tree) -> Result<FileEntry> {
+/// Extract metadata from a patch without syntax highlighting.
+///
+/// Without a `marker_tree` there is no review to compare against and every file is unreviewed.
+fn process_patch_metadata(
+    repository: &git2::Repository,
+    patch: &git2::Patch,
+    marker_tree: Option<&Tree>,
+) -> Result<FileEntry> {
     let delta = patch.delta();
     let old_file = delta.old_file();
     let new_file = delta.new_file();
@@ -39,12 +45,48 @@ fn process_patch_metadata(patch: &git2::Patch, marker_tree: &Tree) -> Result<Fil
     };
 
     let status = map_delta_status(delta.status());
-    let is_binary = old_file.is_binary() || new_file.is_binary();
+    // libgit2 only looks for NUL bytes. Text in another encoding can't be shown or spliced
+    // line by line either, so it is listed as binary too.
+    let is_binary = old_file.is_binary()
+        || new_file.is_binary()
+        || !is_utf8_blob(repository, &old_file)?
+        || !is_utf8_blob(repository, &new_file)?;
 
     let (_context, additions, deletions) = patch.line_stats()?;
     let (additions, deletions) = (additions as u32, deletions as u32);
 
-    let review_status = if is_deletion {
+    let review_status = if let Some(marker_tree) = marker_tree {
+        marker_review_status(&delta, marker_tree)?
+    } else {
+        ReviewStatus::Unreviewed
+    };
+
+    Ok(FileEntry {
+        old_path,
+        new_path,
+        status,
+        additions,
+        deletions,
+        is_binary,
+        review_status,
+        generated: false,
+    })
+}
+
+/// Whether one side of a delta is valid UTF-8. The missing side of an addition or deletion and
+/// submodules, which have no blob, count as text.
+fn is_utf8_blob(repository: &git2::Repository, file: &git2::DiffFile) -> Result<bool> {
+    if file.id().is_zero() || file.mode() == git2::FileMode::Commit {
+        return Ok(true);
+    }
+    let blob = repository.find_blob(file.id())?;
+    Ok(std::str::from_utf8(blob.content()).is_ok())
+}
+
+fn marker_review_status(delta: &git2::DiffDelta, marker_tree: &Tree) -> Result<ReviewStatus> {
+    let old_file = delta.old_file();
+    let new_file = delta.new_file();
+    let status = if delta.status() == Delta::Deleted {
         // Deletion: binary choice — M still has the file (Unreviewed) or doesn't (Reviewed).
         match marker_tree.get_path(old_file.path().unwrap()) {
             Ok(_) => ReviewStatus::Unreviewed,
@@ -52,16 +94,21 @@ fn process_patch_metadata(patch: &git2::Patch, marker_tree: &Tree) -> Result<Fil
             Err(e) => return Err(e.into()),
         }
     } else {
-        // Addition, modification, rename, copy:
+        // Addition, modification, rename, copy, typechange:
         // Compare M's blob at new_path against B's blob (old_file.id) and T's blob (new_file.id).
         // For additions, old_file.id() is the null OID — M can never hold a null-ID blob,
         // so the blob_b equality check is a no-op there and falls through to PartiallyReviewed.
+        // The mode is compared too: a file turned into a symlink to its own content keeps the
+        // blob id and only the mode tells B and T apart.
         let target_path = new_file.path().unwrap();
+        let same = |entry: &git2::TreeEntry, file: &git2::DiffFile| {
+            entry.id() == file.id() && entry.filemode() == i32::from(file.mode())
+        };
         match marker_tree.get_path(target_path) {
             Ok(content) => {
-                if content.id() == new_file.id() {
+                if same(&content, &new_file) {
                     ReviewStatus::Reviewed
-                } else if content.id() == old_file.id() {
+                } else if same(&content, &old_file) {
                     ReviewStatus::Unreviewed
                 } else {
                     ReviewStatus::PartiallyReviewed
@@ -71,23 +118,16 @@ fn process_patch_metadata(patch: &git2::Patch, marker_tree: &Tree) -> Result<Fil
             Err(e) => return Err(e.into()),
         }
     };
-
-    Ok(FileEntry {
-        old_path,
-        new_path,
-        status,
-        additions,
-        deletions,
-        is_binary,
-        review_status,
-    })
+    Ok(status)
 }
 
 /// Generate a lightweight file list without blob fetching or syntax highlighting.
 /// This is fast because it only iterates over diff deltas and counts lines from patches.
+/// `config` only affects the line counts; review status always compares raw blobs.
 pub fn generate_file_list(
     repository: &git2::Repository,
     sha: CommitId,
+    config: DiffConfig,
 ) -> Result<(ChangeId, Vec<FileEntry>)> {
     let commit = repository
         .find_commit(sha.oid())
@@ -108,8 +148,9 @@ pub fn generate_file_list(
         )
     };
 
-    let diff = diff_with_options(repository, &base_tree, &commit_tree)?;
-    let base_to_marker_diff = diff_with_options(repository, &base_tree, &marker_tree)?;
+    let diff = diff_with_options(repository, &base_tree, &commit_tree, config)?;
+    let base_to_marker_diff =
+        diff_with_options(repository, &base_tree, &marker_tree, DiffConfig::default())?;
 
     // Process all file deltas to extract metadata only.
     // Collect all paths touched by diff(B, T) so we can skip them in the ReviewedReverted pass.
@@ -124,7 +165,11 @@ pub fn generate_file_list(
         }
         let patch = git2::Patch::from_diff(&diff, delta_idx)?;
         if let Some(patch) = patch {
-            files.push(process_patch_metadata(&patch, &marker_tree)?);
+            files.push(process_patch_metadata(
+                repository,
+                &patch,
+                Some(&marker_tree),
+            )?);
         }
     }
 
@@ -152,25 +197,55 @@ pub fn generate_file_list(
             deletions: 0,
             is_binary: delta.old_file().is_binary() || delta.new_file().is_binary(),
             review_status: ReviewStatus::ReviewedReverted,
+            generated: false,
         });
     }
 
     Ok((change_id, files))
 }
 
-fn diff_with_options<'repo>(
+/// File list for everything that changed between `base` and `sha`, e.g. "all changes since
+/// main" rather than a single commit's own diff.
+///
+/// Review progress is tracked per change against its parents, so a range has no marker to
+/// compare with: every entry is [`ReviewStatus::Unreviewed`] and the view is read-only.
+pub fn generate_range_file_list(
+    repository: &git2::Repository,
+    base: CommitId,
+    sha: CommitId,
+    config: DiffConfig,
+) -> Result<Vec<FileEntry>> {
+    let base_tree = commit_tree(repository, base)?;
+    let target_tree = commit_tree(repository, sha)?;
+    let diff = diff_with_options(repository, &base_tree, &target_tree, config)?;
+
+    let mut files = Vec::new();
+    for delta_idx in 0..diff.deltas().len() {
+        if let Some(patch) = git2::Patch::from_diff(&diff, delta_idx)? {
+            files.push(process_patch_metadata(repository, &patch, None)?);
+        }
+    }
+    Ok(files)
+}
+
+/// The tree of `sha` with any conflicts materialized, as the review diffs show it.
+pub(crate) fn commit_tree(repository: &git2::Repository, sha: CommitId) -> Result<Tree<'_>> {
+    let commit = repository
+        .find_commit(sha.oid())
+        .map_err(|_| git::Error::CommitNotFound(sha.to_string()))?;
+    Ok(marker_commit::materialize_tree(repository, &commit)?)
+}
+
+pub(crate) fn diff_with_options<'repo>(
     repo: &'repo Repository,
     old_tree: &Tree<'repo>,
     new_tree: &Tree<'repo>,
+    config: DiffConfig,
 ) -> Result<git2::Diff<'repo>> {
-    let mut opts = git2::DiffOptions::new();
-    opts.context_lines(3)
-        .interhunk_lines(0)
-        .ignore_whitespace(false);
+    let mut opts = config.diff_options();
 
     let mut diff = repo.diff_tree_to_tree(Some(old_tree), Some(new_tree), Some(&mut opts))?;
-    let mut find_opts = git2::DiffFindOptions::new();
-    find_opts.renames(true);
+    let mut find_opts = config.find_options();
     diff.find_similar(Some(&mut find_opts))?;
     Ok(diff)
 }
@@ -183,13 +258,91 @@ mod tests {
     use crate::models::FileChangeStatus;
     use test_repo::TestRepo;
 
+    #[test]
+    fn file_list_clean_octopus_merge_is_empty() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("shared", "base\n").unwrap();
+        let base = t.commit("base").unwrap().created;
+        let mut parents = Vec::new();
+        for name in ["a", "b", "c"] {
+            t.new_revision(base.change_id).unwrap();
+            t.write_file(name, "content\n").unwrap();
+            parents.push(t.commit(name).unwrap().created.change_id);
+        }
+
+        t.merge(&parents, "merge").unwrap();
+        let merge = t.work_copy().unwrap();
+        assert_eq!(t.repo.find_commit(merge.oid()).unwrap().parent_count(), 3);
+
+        let (_, files) =
+            generate_file_list(&t.repo, merge.commit_id, DiffConfig::default()).unwrap();
+        assert!(
+            files.is_empty(),
+            "every file comes from a parent: {files:?}"
+        );
+    }
+
+    #[test]
+    fn range_file_list_includes_changes_from_every_commit_since_base() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("a.txt", "one\n").unwrap();
+        let base = t.commit("base").unwrap().created;
+        t.write_file("b.txt", "new\n").unwrap();
+        t.commit("add b").unwrap();
+        t.write_file("a.txt", "two\n").unwrap();
+        let tip = t.commit("modify a").unwrap().created;
+
+        let files = generate_range_file_list(
+            &t.repo,
+            base.commit_id,
+            tip.commit_id,
+            DiffConfig::default(),
+        )
+        .unwrap();
+
+        let status_of = |path: &str| {
+            files
+                .iter()
+                .find(|f| f.new_path.as_deref() == Some(path))
+                .map(|f| f.status.clone())
+        };
+        assert_eq!(files.len(), 2, "{files:?}");
+        assert_eq!(status_of("a.txt"), Some(FileChangeStatus::Modified));
+        assert_eq!(status_of("b.txt"), Some(FileChangeStatus::Added));
+        assert!(
+            files
+                .iter()
+                .all(|f| f.review_status == ReviewStatus::Unreviewed)
+        );
+    }
+
+    #[test]
+    fn file_list_classifies_non_utf8_text_as_binary() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("latin1.txt", b"caf\xe9\n").unwrap();
+        t.write_file("utf8.txt", "café\n").unwrap();
+        let sha = t.commit("add files").unwrap().created.commit_id;
+
+        let (_, files) = generate_file_list(&t.repo, sha, DiffConfig::default()).unwrap();
+        let is_binary = |path: &str| {
+            files
+                .iter()
+                .find(|f| f.new_path.as_deref() == Some(path))
+                .unwrap()
+                .is_binary
+        };
+        assert!(is_binary("latin1.txt"));
+        assert!(!is_binary("utf8.txt"));
+    }
+
     #[test]
     fn file_list_added_file() {
         let t = TestRepo::new().unwrap();
         t.write_file("hello.rs", "fn main() {}\n").unwrap();
         let commit = t.commit("add hello.rs").unwrap().created;
 
-        let (change_id, files) = generate_file_list(&t.repo, commit.commit_id).unwrap();
+        let (change_id, files) =
+            generate_file_list(&t.repo, commit.commit_id, DiffConfig::default()).unwrap();
 
         assert_eq!(change_id, commit.change_id);
         assert_eq!(files.len(), 1);
@@ -210,7 +363,7 @@ mod tests {
             .unwrap();
         let sha = t.commit("modify").unwrap().created.commit_id;
 
-        let (_, files) = generate_file_list(&t.repo, sha).unwrap();
+        let (_, files) = generate_file_list(&t.repo, sha, DiffConfig::default()).unwrap();
 
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].status, FileChangeStatus::Modified);
@@ -228,7 +381,7 @@ mod tests {
         t.delete_file("temp.rs").unwrap();
         let sha = t.commit("delete").unwrap().created.commit_id;
 
-        let (_, files) = generate_file_list(&t.repo, sha).unwrap();
+        let (_, files) = generate_file_list(&t.repo, sha, DiffConfig::default()).unwrap();
 
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].status, FileChangeStatus::Deleted);
@@ -250,7 +403,7 @@ mod tests {
         t.rename_file("old_name.rs", "new_name.rs").unwrap();
         let sha = t.commit("rename").unwrap().created.commit_id;
 
-        let (_, files) = generate_file_list(&t.repo, sha).unwrap();
+        let (_, files) = generate_file_list(&t.repo, sha, DiffConfig::default()).unwrap();
 
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].status, FileChangeStatus::Renamed);
@@ -271,7 +424,7 @@ mod tests {
         t.write_file("c.rs", "cc\n").unwrap();
         let sha = t.commit("modify all").unwrap().created.commit_id;
 
-        let (_, files) = generate_file_list(&t.repo, sha).unwrap();
+        let (_, files) = generate_file_list(&t.repo, sha, DiffConfig::default()).unwrap();
 
         assert_eq!(files.len(), 3);
         let mut paths: Vec<_> = files.iter().filter_map(|f| f.new_path.as_deref()).collect();
@@ -291,7 +444,7 @@ mod tests {
             .unwrap();
         let sha = t.commit("modify").unwrap().created.commit_id;
 
-        let (_, files) = generate_file_list(&t.repo, sha).unwrap();
+        let (_, files) = generate_file_list(&t.repo, sha, DiffConfig::default()).unwrap();
 
         assert_eq!(files[0].additions, 3);
         assert_eq!(files[0].deletions, 2);
@@ -307,7 +460,7 @@ mod tests {
         let commit = t.repo.find_commit(sha.oid()).unwrap();
         let change_id = commit.change_id();
 
-        let (change_id_, files) = generate_file_list(&t.repo, sha).unwrap();
+        let (change_id_, files) = generate_file_list(&t.repo, sha, DiffConfig::default()).unwrap();
         assert_eq!(change_id_, change_id);
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].status, FileChangeStatus::Modified);
@@ -333,7 +486,7 @@ mod tests {
             .unwrap()
             .commit_id;
 
-        let (_, files) = generate_file_list(&t.repo, merge_sha).unwrap();
+        let (_, files) = generate_file_list(&t.repo, merge_sha, DiffConfig::default()).unwrap();
 
         assert!(
             files.is_empty(),
@@ -343,6 +496,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unresolved_merge_materializes_conflict_markers() {
+        let t = TestRepo::new().unwrap();
+        let merge = t
+            .create_conflict("file.txt", "base\n", "left\n", "right\n")
+            .unwrap();
+
+        let tree = commit_tree(&t.repo, merge.commit_id).unwrap();
+        let entry = tree.get_path(Path::new("file.txt")).unwrap();
+        let blob = t.repo.find_blob(entry.id()).unwrap();
+        let content = String::from_utf8_lossy(blob.content());
+
+        assert!(
+            content.contains("<<<<<<<"),
+            "no conflict markers in {content:?}"
+        );
+        assert!(
+            content.contains(">>>>>>>"),
+            "no conflict markers in {content:?}"
+        );
+    }
+
     #[test]
     fn merge_with_conflicting_parents_produces_file_list() {
         // Both parents modify the same file in conflicting ways.
@@ -367,7 +542,8 @@ mod tests {
         t.write_file("file.txt", "resolved\n").unwrap();
         let merge = t.work_copy().unwrap();
 
-        let (_, files) = generate_file_list(&t.repo, merge.commit_id).unwrap();
+        let (_, files) =
+            generate_file_list(&t.repo, merge.commit_id, DiffConfig::default()).unwrap();
 
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].new_path.as_deref(), Some("file.txt"));
@@ -403,7 +579,8 @@ mod tests {
         // Merge M: parents=[B, C], tree = auto-merged (both changes)
         let merge = t.merge(&[b.change_id, c.change_id], "merge").unwrap();
 
-        let (_, files) = generate_file_list(&t.repo, merge.commit_id).unwrap();
+        let (_, files) =
+            generate_file_list(&t.repo, merge.commit_id, DiffConfig::default()).unwrap();
 
         assert!(
             files.is_empty(),
@@ -429,12 +606,51 @@ mod tests {
         marker.write().unwrap();
         drop(marker);
 
-        let (_, files) = generate_file_list(&t.repo, b.commit_id).unwrap();
+        let (_, files) = generate_file_list(&t.repo, b.commit_id, DiffConfig::default()).unwrap();
 
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].review_status, ReviewStatus::Reviewed);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn review_status_typechange_to_symlink_round_trips() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("link", "target.txt").unwrap();
+        t.write_file("target.txt", "hello\n").unwrap();
+        t.commit("initial").unwrap();
+        // Same bytes as the regular file, so only the mode differs between B and T.
+        t.symlink("link", "target.txt").unwrap();
+        let b = t.commit("make symlink").unwrap().created;
+
+        let (_, files) = generate_file_list(&t.repo, b.commit_id, DiffConfig::default()).unwrap();
+        assert_eq!(files.len(), 1, "a typechange is a single entry: {files:?}");
+        assert_eq!(files[0].status, FileChangeStatus::Typechange);
+        assert_eq!(files[0].old_path.as_deref(), Some("link"));
+        assert_eq!(files[0].new_path.as_deref(), Some("link"));
+        assert_eq!(files[0].review_status, ReviewStatus::Unreviewed);
+
+        let mut marker = marker_commit::MarkerCommit::get(&t.repo, b.commit_id).unwrap();
+        marker.mark_file_reviewed(Path::new("link"), None).unwrap();
+        let entry = marker.marker_tree().get_path(Path::new("link")).unwrap();
+        assert_eq!(entry.filemode(), i32::from(git2::FileMode::Link));
+        marker.write().unwrap();
+        drop(marker);
+
+        let (_, files) = generate_file_list(&t.repo, b.commit_id, DiffConfig::default()).unwrap();
+        assert_eq!(files[0].review_status, ReviewStatus::Reviewed);
+
+        let mut marker = marker_commit::MarkerCommit::get(&t.repo, b.commit_id).unwrap();
+        marker
+            .unmark_file_reviewed(Path::new("link"), None)
+            .unwrap();
+        marker.write().unwrap();
+        drop(marker);
+
+        let (_, files) = generate_file_list(&t.repo, b.commit_id, DiffConfig::default()).unwrap();
+        assert_eq!(files[0].review_status, ReviewStatus::Unreviewed);
+    }
+
     #[test]
     fn review_status_partially_reviewed_after_one_hunk() {
         // Base: a1..a5, b1..b5 (10 lines); target: A1..a5, b1..B4..b5 (two hunks changed)
@@ -461,7 +677,7 @@ mod tests {
         marker.write().unwrap();
         drop(marker);
 
-        let (_, files) = generate_file_list(&t.repo, b.commit_id).unwrap();
+        let (_, files) = generate_file_list(&t.repo, b.commit_id, DiffConfig::default()).unwrap();
 
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].review_status, ReviewStatus::PartiallyReviewed);
@@ -482,7 +698,7 @@ mod tests {
         marker.write().unwrap();
         drop(marker);
 
-        let (_, files) = generate_file_list(&t.repo, b.commit_id).unwrap();
+        let (_, files) = generate_file_list(&t.repo, b.commit_id, DiffConfig::default()).unwrap();
 
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].review_status, ReviewStatus::Reviewed);
@@ -511,7 +727,7 @@ mod tests {
         t.write_file("foo.rs", "fn old() {}\n").unwrap();
         let b2 = t.work_copy().unwrap();
 
-        let (_, files) = generate_file_list(&t.repo, b2.commit_id).unwrap();
+        let (_, files) = generate_file_list(&t.repo, b2.commit_id, DiffConfig::default()).unwrap();
 
         // diff(B, T) is now empty (no changes), but diff(B, M) still has foo.rs
         let reverted: Vec<_> = files
@@ -529,4 +745,53 @@ mod tests {
             "all entries should be ReviewedReverted when the only change was reverted"
         );
     }
+
+    #[test]
+    fn ignore_whitespace_only_affects_line_counts() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("foo.rs", "fn a() {\nfoo();\n}\n").unwrap();
+        t.commit("initial").unwrap();
+        t.write_file("foo.rs", "fn a() {\n    foo();\n}\n").unwrap();
+        let b = t.commit("reindent").unwrap().created;
+
+        let (_, raw) = generate_file_list(&t.repo, b.commit_id, DiffConfig::default()).unwrap();
+        let config = DiffConfig {
+            ignore_whitespace: true,
+            ..Default::default()
+        };
+        let (_, ignored) = generate_file_list(&t.repo, b.commit_id, config).unwrap();
+
+        assert_eq!((raw[0].additions, raw[0].deletions), (1, 1));
+        assert_eq!((ignored[0].additions, ignored[0].deletions), (0, 0));
+        assert_eq!(ignored[0].review_status, ReviewStatus::Unreviewed);
+    }
+
+    #[test]
+    fn rename_threshold_pairs_heavily_edited_rename() {
+        let line = |i: usize| format!("let value_{i} = compute_something_long({i});\n");
+        let t = TestRepo::new().unwrap();
+        t.write_file("old.rs", (0..20).map(line).collect::<String>())
+            .unwrap();
+        t.commit("initial").unwrap();
+        t.delete_file("old.rs").unwrap();
+        let edited: String = (0..20)
+            .map(|i| if i < 6 { line(i) } else { line(i + 100) })
+            .collect();
+        t.write_file("new.rs", &edited).unwrap();
+        let b = t.commit("rename and rewrite").unwrap().created;
+
+        let (_, strict) = generate_file_list(&t.repo, b.commit_id, DiffConfig::default()).unwrap();
+        assert_eq!(strict.len(), 2, "{strict:?}");
+        assert!(strict.iter().all(|f| f.status != FileChangeStatus::Renamed));
+
+        let config = DiffConfig {
+            rename_threshold: 10,
+            ..Default::default()
+        };
+        let (_, loose) = generate_file_list(&t.repo, b.commit_id, config).unwrap();
+        assert_eq!(loose.len(), 1, "{loose:?}");
+        assert_eq!(loose[0].status, FileChangeStatus::Renamed);
+        assert_eq!(loose[0].old_path.as_deref(), Some("old.rs"));
+        assert_eq!(loose[0].new_path.as_deref(), Some("new.rs"));
+    }
 }
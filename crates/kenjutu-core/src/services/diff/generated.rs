@@ -0,0 +1,105 @@
+use glob::{MatchOptions, Pattern};
+
+use crate::models::FileEntry;
+
+/// Lockfiles and build output that rarely need a line-by-line review.
+pub const DEFAULT_GENERATED_GLOBS: &[&str] = &[
+    "**/Cargo.lock",
+    "**/package-lock.json",
+    "**/pnpm-lock.yaml",
+    "**/yarn.lock",
+    "**/*.generated.*",
+    "**/dist/**",
+];
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Glob set deciding which files in a file list are generated.
+#[derive(Debug, Clone)]
+pub struct GeneratedFiles {
+    patterns: Vec<Pattern>,
+}
+
+impl Default for GeneratedFiles {
+    fn default() -> Self {
+        Self::new(DEFAULT_GENERATED_GLOBS)
+    }
+}
+
+impl GeneratedFiles {
+    /// Compile `globs`, logging and skipping any that are invalid.
+    pub fn new<S: AsRef<str>>(globs: &[S]) -> Self {
+        let patterns = globs
+            .iter()
+            .filter_map(|glob| {
+                let glob = glob.as_ref();
+                Pattern::new(glob)
+                    .inspect_err(|e| {
+                        log::warn!("Ignoring invalid generated-file glob {glob:?}: {e}")
+                    })
+                    .ok()
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    pub fn is_generated(&self, path: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| pattern.matches_with(path, MATCH_OPTIONS))
+    }
+
+    /// Set [`FileEntry::generated`] on every entry whose new (or, for deletions, old) path
+    /// matches.
+    pub fn tag(&self, files: &mut [FileEntry]) {
+        for file in files {
+            let path = file.new_path.as_deref().or(file.old_path.as_deref());
+            file.generated = path.is_some_and(|path| self.is_generated(path));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::diff::{DiffConfig, generate_file_list};
+    use test_repo::TestRepo;
+
+    #[test]
+    fn default_globs_flag_lockfile() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("Cargo.lock", "# lock\n").unwrap();
+        t.write_file("src/main.rs", "fn main() {}\n").unwrap();
+        let sha = t.commit("initial").unwrap().created.commit_id;
+
+        let (_, mut files) = generate_file_list(&t.repo, sha, DiffConfig::default()).unwrap();
+        GeneratedFiles::default().tag(&mut files);
+
+        let generated = |path: &str| {
+            files
+                .iter()
+                .find(|f| f.new_path.as_deref() == Some(path))
+                .unwrap()
+                .generated
+        };
+        assert!(generated("Cargo.lock"));
+        assert!(!generated("src/main.rs"));
+    }
+
+    #[test]
+    fn globs_match_nested_paths() {
+        let generated = GeneratedFiles::default();
+        assert!(generated.is_generated("web/package-lock.json"));
+        assert!(generated.is_generated("src/api.generated.ts"));
+        assert!(generated.is_generated("app/dist/bundle.js"));
+        assert!(!generated.is_generated("src/distance.rs"));
+
+        let custom = GeneratedFiles::new(&["*.pb.go", "[invalid"]);
+        assert!(custom.is_generated("api.pb.go"));
+        assert!(!custom.is_generated("Cargo.lock"));
+    }
+}
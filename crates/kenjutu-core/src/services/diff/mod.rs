@@ -1,27 +1,154 @@
+use serde::{Deserialize, Serialize};
+
 use super::git;
 
-pub use file_diff::{PartialReviewDiffs, generate_partial_review_diffs, get_context_lines};
-pub use file_list::generate_file_list;
+pub use binary::{BinaryDiff, generate_binary_diff};
+pub use blob::{BlobSide, DEFAULT_MAX_BLOB_SIZE, EncodedBlob, get_blob_base64};
+pub use cache::DiffCache;
+pub use export::export_unified_diff;
+pub use file_diff::{
+    PartialReviewDiffs, check_content_has_no_conflict_markers, check_no_conflict_markers,
+    generate_partial_review_diffs, generate_partial_review_diffs_cached, generate_range_file_diff,
+    generate_reviewed_diff, get_context_lines,
+};
+pub(crate) use file_list::diff_with_options;
+pub use file_list::{generate_file_list, generate_range_file_list};
+pub use generated::{DEFAULT_GENERATED_GLOBS, GeneratedFiles};
+pub use sort::{FileSort, sort_files};
 
+mod binary;
+mod blob;
+mod cache;
+mod export;
 mod file_diff;
 mod file_list;
+mod generated;
+mod sort;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Unchanged lines shown around each hunk when no override is configured.
+pub const DEFAULT_CONTEXT_LINES: u32 = 3;
+
+/// Largest blob, in bytes, diffed with highlighting when no override is configured.
+pub const DEFAULT_MAX_DIFF_BYTES: u64 = 1024 * 1024;
+
+/// Minimum similarity (percent) for an add/delete pair to be shown as a rename, git's default.
+pub const DEFAULT_RENAME_THRESHOLD: u16 = 50;
+
+/// Line-matching algorithm used to compute hunks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub enum DiffAlgorithm {
+    /// git's default.
+    #[default]
+    Myers,
+    /// Myers, spending extra time to find the smallest possible diff.
+    Minimal,
+    /// Anchors on lines that are unique to both sides, which keeps reformatted or moved code
+    /// from being matched against unrelated braces and blank lines.
+    Patience,
+}
+
+impl DiffAlgorithm {
+    fn apply(self, opts: &mut git2::DiffOptions) {
+        opts.minimal(self == Self::Minimal)
+            .patience(self == Self::Patience);
+    }
+}
+
+/// Options shared by the file list and per-file diffs.
+#[derive(Debug, Clone, Copy)]
+pub struct DiffConfig {
+    /// Hide whitespace-only changes. Review status is always derived from the raw blobs, so
+    /// toggling this never changes what counts as reviewed.
+    pub ignore_whitespace: bool,
+    /// Unchanged lines shown around each hunk. Hunks whose context overlaps are merged, so this
+    /// also decides how changes are grouped into review regions; region ids must come from a
+    /// diff built with the same value.
+    pub context_lines: u32,
+    /// Like `context_lines`, this changes the hunks and therefore the region ids.
+    pub algorithm: DiffAlgorithm,
+    /// Emit tokens without syntax colors or word-level change marks, leaving only the line
+    /// types. The highlighter is not run at all, so this is also the cheap way to show a large
+    /// file. Used for screenshots and for readers who find the colors distracting.
+    pub plain: bool,
+    /// Highlight theme to use instead of the highlighter's current one, e.g. a repository's own.
+    pub theme: Option<&'static str>,
+    /// Minimum similarity (0-100) for a deleted and an added file to be paired as a rename.
+    /// Lower it to keep heavily edited renames together instead of an add and a delete.
+    pub rename_threshold: u16,
+    /// Also pair added files with a similar modified file as copies.
+    pub find_copies: bool,
+    /// Per-file diffs whose blobs exceed this many bytes fail with [`Error::DiffTooLarge`]
+    /// rather than freezing the highlighter. Plain diffs are not checked, so a caller can still
+    /// show the file without colors.
+    pub max_diff_bytes: u64,
+}
+
+impl Default for DiffConfig {
+    fn default() -> Self {
+        Self {
+            ignore_whitespace: false,
+            context_lines: DEFAULT_CONTEXT_LINES,
+            algorithm: DiffAlgorithm::default(),
+            plain: false,
+            theme: None,
+            rename_threshold: DEFAULT_RENAME_THRESHOLD,
+            find_copies: false,
+            max_diff_bytes: DEFAULT_MAX_DIFF_BYTES,
+        }
+    }
+}
+
+impl DiffConfig {
+    fn diff_options(&self) -> git2::DiffOptions {
+        let mut opts = git2::DiffOptions::new();
+        // Without this libgit2 splits a file that became a symlink (or the reverse) into a
+        // deletion and an addition of the same path.
+        opts.context_lines(self.context_lines)
+            .interhunk_lines(0)
+            .include_typechange(true)
+            .ignore_whitespace(self.ignore_whitespace);
+        self.algorithm.apply(&mut opts);
+        opts
+    }
+
+    fn find_options(&self) -> git2::DiffFindOptions {
+        let threshold = self.rename_threshold.min(100);
+        let mut opts = git2::DiffFindOptions::new();
+        opts.renames(true)
+            .rename_threshold(threshold)
+            .copies(self.find_copies)
+            .copy_threshold(threshold);
+        opts
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("File not found in tree: {0}")]
     FileNotFound(String),
 
+    #[error("Blob is too large to load: {size} bytes (max {max})")]
+    BlobTooLarge { size: u64, max: u64 },
+
     #[error("Git error: {0}")]
     Git(#[from] git::Error),
 
+    #[error("File is too large to highlight: {size} bytes (max {max})")]
+    DiffTooLarge { size: u64, max: u64 },
+
     #[error("git2 error: {0}")]
     Git2(#[from] git2::Error),
 
     #[error("Marker commit error: {0}")]
     MarkerCommit(#[from] marker_commit::Error),
 
+    #[error("{0} has unresolved conflicts; resolve them before marking it reviewed")]
+    UnresolvedConflict(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -0,0 +1,177 @@
+use std::cmp::{Ordering, Reverse};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{FileChangeStatus, FileEntry};
+
+/// Order of the file list. libgit2 emits deltas in its own order, which reviewers rarely want.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub enum FileSort {
+    /// Alphabetical by path.
+    #[default]
+    Path,
+    /// Added, modified, renamed and copied, type changes, then deletions.
+    Status,
+    /// Most changed lines first.
+    ChangeSize,
+}
+
+/// Path a file is listed under. Renames and copies use their new path, so the entry stays
+/// next to its new neighbours rather than splitting from them.
+fn display_path(file: &FileEntry) -> &str {
+    file.new_path
+        .as_deref()
+        .or(file.old_path.as_deref())
+        .unwrap_or_default()
+}
+
+fn directory(file: &FileEntry) -> &str {
+    let path = display_path(file);
+    path.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
+
+fn status_rank(status: &FileChangeStatus) -> u8 {
+    match status {
+        FileChangeStatus::Added => 0,
+        FileChangeStatus::Modified => 1,
+        FileChangeStatus::Renamed | FileChangeStatus::Copied => 2,
+        FileChangeStatus::Typechange => 3,
+        FileChangeStatus::Deleted => 4,
+    }
+}
+
+fn compare(a: &FileEntry, b: &FileEntry, sort: FileSort) -> Ordering {
+    let by_key = match sort {
+        FileSort::Path => Ordering::Equal,
+        FileSort::Status => status_rank(&a.status).cmp(&status_rank(&b.status)),
+        FileSort::ChangeSize => {
+            Reverse(a.additions + a.deletions).cmp(&Reverse(b.additions + b.deletions))
+        }
+    };
+    by_key.then_with(|| display_path(a).cmp(display_path(b)))
+}
+
+/// Sort `files` in place. With `group_by_dir`, files are first grouped by their directory
+/// (in path order) and sorted within each group, ready to render under collapsible headers.
+///
+/// The sort is stable, so entries that compare equal keep their relative order.
+pub fn sort_files(files: &mut [FileEntry], sort: FileSort, group_by_dir: bool) {
+    files.sort_by(|a, b| {
+        let by_dir = if group_by_dir {
+            directory(a).cmp(directory(b))
+        } else {
+            Ordering::Equal
+        };
+        by_dir.then_with(|| compare(a, b, sort))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ReviewStatus;
+
+    fn entry(
+        old_path: Option<&str>,
+        new_path: Option<&str>,
+        status: FileChangeStatus,
+        additions: u32,
+        deletions: u32,
+    ) -> FileEntry {
+        FileEntry {
+            old_path: old_path.map(str::to_string),
+            new_path: new_path.map(str::to_string),
+            status,
+            additions,
+            deletions,
+            is_binary: false,
+            review_status: ReviewStatus::Unreviewed,
+            generated: false,
+        }
+    }
+
+    fn fixture() -> Vec<FileEntry> {
+        vec![
+            entry(Some("z.rs"), None, FileChangeStatus::Deleted, 0, 3),
+            entry(
+                Some("src/b.rs"),
+                Some("src/b.rs"),
+                FileChangeStatus::Modified,
+                1,
+                1,
+            ),
+            entry(None, Some("src/new.rs"), FileChangeStatus::Added, 12, 0),
+            entry(
+                Some("old.rs"),
+                Some("src/a.rs"),
+                FileChangeStatus::Renamed,
+                2,
+                2,
+            ),
+            entry(
+                Some("lib.rs"),
+                Some("lib.rs"),
+                FileChangeStatus::Modified,
+                5,
+                4,
+            ),
+        ]
+    }
+
+    fn paths(files: &[FileEntry]) -> Vec<&str> {
+        files.iter().map(display_path).collect()
+    }
+
+    #[test]
+    fn sort_by_path() {
+        let mut files = fixture();
+        sort_files(&mut files, FileSort::Path, false);
+        assert_eq!(
+            paths(&files),
+            ["lib.rs", "src/a.rs", "src/b.rs", "src/new.rs", "z.rs"]
+        );
+    }
+
+    #[test]
+    fn sort_by_status_breaks_ties_by_path() {
+        let mut files = fixture();
+        sort_files(&mut files, FileSort::Status, false);
+        assert_eq!(
+            paths(&files),
+            ["src/new.rs", "lib.rs", "src/b.rs", "src/a.rs", "z.rs"]
+        );
+    }
+
+    #[test]
+    fn sort_by_change_size_puts_largest_first() {
+        let mut files = fixture();
+        sort_files(&mut files, FileSort::ChangeSize, false);
+        assert_eq!(
+            paths(&files),
+            ["src/new.rs", "lib.rs", "src/a.rs", "z.rs", "src/b.rs"]
+        );
+    }
+
+    #[test]
+    fn group_by_dir_sorts_within_each_directory() {
+        let mut files = fixture();
+        sort_files(&mut files, FileSort::ChangeSize, true);
+        assert_eq!(
+            paths(&files),
+            ["lib.rs", "z.rs", "src/new.rs", "src/a.rs", "src/b.rs"]
+        );
+    }
+
+    #[test]
+    fn equal_entries_keep_their_order() {
+        let mut files = vec![
+            entry(Some("a.rs"), None, FileChangeStatus::Deleted, 0, 1),
+            entry(Some("a.rs"), Some("a.rs"), FileChangeStatus::Added, 1, 0),
+        ];
+        sort_files(&mut files, FileSort::Path, false);
+        assert_eq!(files[0].status, FileChangeStatus::Deleted);
+        assert_eq!(files[1].status, FileChangeStatus::Added);
+    }
+}
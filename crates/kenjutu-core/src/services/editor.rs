@@ -0,0 +1,93 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::jj;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("No editor command is configured")]
+    NoEditor,
+
+    #[error("File does not exist in the working copy: {0}")]
+    FileNotFound(PathBuf),
+
+    #[error("Failed to launch editor: {0}")]
+    Spawn(#[from] std::io::Error),
+}
+
+/// Build the argv for opening `path` at `line` with a configured editor command.
+///
+/// `{file}` and `{line}` in the command are replaced, e.g. `code --goto {file}:{line}`;
+/// `{line}` is 1 when no line is given. A command without `{file}` gets the path appended.
+pub fn editor_args(editor: &str, path: &Path, line: Option<u32>) -> Vec<String> {
+    let file = path.to_string_lossy();
+    let line = line.unwrap_or(1).to_string();
+    let mut args: Vec<String> = editor
+        .split_whitespace()
+        .map(|arg| arg.replace("{file}", &file).replace("{line}", &line))
+        .collect();
+    if !editor.contains("{file}") {
+        args.push(file.into_owned());
+    }
+    args
+}
+
+/// Absolute path of `file_path` in the working copy of the workspace containing `local_dir`.
+///
+/// Files deleted by the change have nothing on disk to open and return
+/// [`Error::FileNotFound`].
+pub fn working_copy_path(local_dir: &Path, file_path: &Path) -> Result<PathBuf> {
+    let root = jj::find_workspace_root(local_dir).unwrap_or_else(|| local_dir.to_path_buf());
+    let path = root.join(file_path);
+    if !path.is_file() {
+        return Err(Error::FileNotFound(file_path.to_path_buf()));
+    }
+    Ok(path)
+}
+
+/// Open `file_path` from the working copy with the `editor` command, without waiting for
+/// it to exit. See [`editor_args`] for how the command is expanded.
+pub fn open_in_editor(
+    local_dir: &Path,
+    file_path: &Path,
+    line: Option<u32>,
+    editor: &str,
+) -> Result<()> {
+    let path = working_copy_path(local_dir, file_path)?;
+    let args = editor_args(editor, &path, line);
+    let (program, rest) = args.split_first().ok_or(Error::NoEditor)?;
+    Command::new(program)
+        .args(rest)
+        .current_dir(path.parent().unwrap_or(local_dir))
+        .spawn()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn editor_args_fills_placeholders() {
+        let args = editor_args(
+            "code --goto {file}:{line}",
+            Path::new("/repo/src/main.rs"),
+            Some(42),
+        );
+        assert_eq!(args, vec!["code", "--goto", "/repo/src/main.rs:42"]);
+    }
+
+    #[test]
+    fn editor_args_defaults_line_to_one() {
+        let args = editor_args("subl {file}:{line}", Path::new("a.txt"), None);
+        assert_eq!(args, vec!["subl", "a.txt:1"]);
+    }
+
+    #[test]
+    fn editor_args_appends_path_without_placeholder() {
+        let args = editor_args("zed --new", Path::new("a.txt"), Some(3));
+        assert_eq!(args, vec!["zed", "--new", "a.txt"]);
+    }
+}
@@ -157,6 +157,8 @@ fn build_remote_callbacks<'a>(
     callbacks
 }
 
+/// Read the change id from the commit's `change-id` header. Commits written outside jj have
+/// none; [`CommitChangeIdExt::change_id`] derives the id jj gives those.
 pub fn get_change_id(commit: &Commit<'_>) -> Option<ChangeId> {
     commit
         .header_field_bytes("change-id")
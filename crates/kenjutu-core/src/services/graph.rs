@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use kenjutu_types::ChangeId;
+use kenjutu_types::{ChangeId, CommitId};
 
 use crate::models::{CommitGraph, CommitRow, EdgeType, ElisionRow, GraphEdge, GraphRow, JjCommit};
 use crate::services::jj::{self, Error};
@@ -8,24 +8,67 @@ use crate::services::jj::{self, Error};
 /// Node characters that jj uses in graph gutters.
 const NODE_CHARS: &[char] = &['@', '○', '◆', '●', '◉'];
 
+/// Colors for tinting authors; readable on both light and dark backgrounds.
+const AUTHOR_PALETTE: &[&str] = &[
+    "#e5534b", "#d4762c", "#c69026", "#57ab5a", "#2f9e8f", "#39a0c8", "#539bf5", "#6c7fd8",
+    "#986ee2", "#c96198", "#b0755c", "#768390",
+];
+
+/// Stable CSS color for an author, so each person keeps the same tint across runs.
+///
+/// Uses FNV-1a rather than `DefaultHasher`, whose output isn't guaranteed between Rust releases.
+pub fn author_color(email: &str) -> &'static str {
+    let hash = email
+        .trim()
+        .to_lowercase()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    AUTHOR_PALETTE[(hash % AUTHOR_PALETTE.len() as u64) as usize]
+}
+
+/// Revset shown when none is given: the mutable stack plus a little immutable context.
+pub const DEFAULT_REVSET: &str = "mutable() | ancestors(mutable(), 2)";
+
 /// Fetch jj log with graph output and parse it into a structured `CommitGraph`.
-pub fn get_log_graph(local_dir: &Path) -> jj::Result<CommitGraph> {
+///
+/// `revset` selects the commits to show; `None` or a blank revset uses [`DEFAULT_REVSET`].
+pub fn get_log_graph(local_dir: &Path, revset: Option<&str>) -> jj::Result<CommitGraph> {
+    let stdout = run_log(local_dir, revset, None)?;
+    parse_graph_output(&stdout)
+}
+
+/// Like [`get_log_graph`], but returns at most the first `limit` commits.
+///
+/// Parents below the last returned commit are treated as off-screen: their edges are omitted,
+/// the same as at the bottom of an unpaged log. Callers load more by asking again with a larger
+/// limit, so every row always comes from a single, consistent layout.
+pub fn get_log_graph_limited(
+    local_dir: &Path,
+    revset: Option<&str>,
+    limit: usize,
+) -> jj::Result<CommitGraph> {
+    let stdout = run_log(local_dir, revset, Some(limit))?;
+    parse_graph_output(&stdout)
+}
+
+fn run_log(local_dir: &Path, revset: Option<&str>, limit: Option<usize>) -> jj::Result<String> {
+    let revset = revset
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .unwrap_or(DEFAULT_REVSET);
+
     // Use explicit \x00 concatenation instead of separate() because
     // separate() skips empty fields, changing the field count.
-    let template = r#""\x01" ++ change_id ++ "\x00" ++ commit_id ++ "\x00" ++ description.escape_json() ++ "\x00" ++ author.name() ++ "\x00" ++ author.email() ++ "\x00" ++ author.timestamp() ++ "\x00" ++ immutable ++ "\x00" ++ current_working_copy ++ "\x00" ++ parents.map(|p| p.change_id()).join(",") ++ "\n""#;
+    let template = r#""\x01" ++ change_id ++ "\x00" ++ commit_id ++ "\x00" ++ description.escape_json() ++ "\x00" ++ author.name() ++ "\x00" ++ author.email() ++ "\x00" ++ author.timestamp() ++ "\x00" ++ immutable ++ "\x00" ++ current_working_copy ++ "\x00" ++ parents.map(|p| p.change_id()).join(",") ++ "\x00" ++ local_bookmarks.map(|b| b.name()).join(",") ++ "\x00" ++ conflict ++ "\n""#;
 
-    let mut cmd =
-        jj::jj_command().ok_or_else(|| Error::Command("jj executable not found".to_string()))?;
+    let mut cmd = jj::jj_command().ok_or(Error::NotInstalled)?;
+    cmd.args(["log", "--color", "never", "-r", revset, "-T", template]);
+    if let Some(limit) = limit {
+        cmd.args(["--limit", &limit.to_string()]);
+    }
     let output = cmd
-        .args([
-            "log",
-            "--color",
-            "never",
-            "-r",
-            "mutable() | ancestors(mutable(), 2)",
-            "-T",
-            template,
-        ])
         .current_dir(local_dir)
         .output()
         .map_err(|e| Error::Command(e.to_string()))?;
@@ -35,8 +78,7 @@ pub fn get_log_graph(local_dir: &Path) -> jj::Result<CommitGraph> {
         return Err(Error::JjFailed(stderr.to_string()));
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    parse_graph_output(&stdout)
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
 
 // ── Raw line classification ─────────────────────────────────────────
@@ -89,21 +131,28 @@ fn parse_raw_lines(output: &str) -> jj::Result<Vec<RawLine>> {
 /// Parse the \x00-separated commit data after the \x01 marker.
 fn parse_commit_fields(data: &str) -> jj::Result<JjCommit> {
     let parts: Vec<&str> = data.split('\x00').collect();
-    if parts.len() < 9 {
+    if parts.len() < 11 {
         return Err(Error::Parse(format!(
-            "Expected 9 fields, got {}",
+            "Expected 11 fields, got {}",
             parts.len()
         )));
     }
 
-    let change_id = parts[0].parse()?;
+    let change_id = ChangeId::parse(parts[0])?;
+    let commit_id = CommitId::parse(parts[1])?;
 
     let parents: Vec<ChangeId> = parts[8]
         .split(',')
         .filter(|s| !s.is_empty())
-        .map(|s| s.parse().map_err(Error::from))
+        .map(|s| ChangeId::parse(s).map_err(Error::from))
         .collect::<jj::Result<Vec<ChangeId>>>()?;
 
+    let bookmarks: Vec<String> = parts[9]
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect();
+
     let full_description =
         serde_json::from_str::<String>(parts[2]).map_err(|e| Error::Parse(e.to_string()))?;
 
@@ -114,15 +163,18 @@ fn parse_commit_fields(data: &str) -> jj::Result<JjCommit> {
 
     Ok(JjCommit {
         change_id,
-        commit_id: parts[1].to_string(),
+        commit_id: commit_id.to_string(),
         summary,
         description,
         author: parts[3].to_string(),
         email: parts[4].to_string(),
+        author_color: author_color(parts[4]).to_string(),
         timestamp: parts[5].to_string(),
         is_immutable: parts[6] == "true",
         is_working_copy: parts[7] == "true",
+        has_conflict: parts[10] == "true",
         parents,
+        bookmarks,
     })
 }
 
@@ -293,6 +345,19 @@ enum ContinuationInfo {
     },
 }
 
+/// Find the intermediate `┬` columns of a fork line such as `├─┬─╮`.
+///
+/// Together with the `├` and `╮` ends these give the horizontal span of an octopus merge.
+fn fork_junction_columns(line: &str) -> Vec<usize> {
+    if !line.contains('├') {
+        return Vec::new();
+    }
+    line.char_indices()
+        .filter(|&(_, ch)| ch == '┬')
+        .map(|(byte_pos, _)| char_position_to_column(line, byte_pos))
+        .collect()
+}
+
 /// Find columns with pass-through │ on a continuation line.
 fn continuation_passing_columns(line: &str) -> Vec<usize> {
     let mut columns = Vec::new();
@@ -369,12 +434,26 @@ fn build_graph(raw_lines: Vec<RawLine>) -> jj::Result<CommitGraph> {
                     // Don't overwrite — the active edge is from an earlier commit.
                 }
 
+                let merge_spread = if commit.parents.len() > 2 {
+                    let mut spread: Vec<usize> = block
+                        .continuations
+                        .iter()
+                        .flat_map(|cont| fork_junction_columns(cont))
+                        .collect();
+                    spread.sort();
+                    spread.dedup();
+                    spread
+                } else {
+                    Vec::new()
+                };
+
                 rows.push(GraphRow::Commit(Box::new(CommitRow {
                     commit: *commit.clone(),
                     column,
                     row: row_index,
                     edges: Vec::new(), // filled in below
                     passing_columns: passing,
+                    merge_spread,
                 })));
             }
             BlockHead::Elision { column } => {
@@ -671,7 +750,7 @@ mod tests {
 
     /// Helper: get the commit graph for a test repo.
     fn graph_for(repo: &TestRepo) -> CommitGraph {
-        get_log_graph(repo.path()).expect("get_log_graph should succeed")
+        get_log_graph(repo.path(), None).expect("get_log_graph should succeed")
     }
 
     /// Helper: collect all CommitRows from a graph.
@@ -737,6 +816,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn describe_updates_summary_in_log_graph() {
+        let repo = TestRepo::new().unwrap();
+        repo.write_file("a.txt", "a").unwrap();
+        let created = repo.commit("before").unwrap().created;
+
+        jj::describe(repo.path(), created.change_id, "after\n\nbody").unwrap();
+
+        let graph = graph_for(&repo);
+        let commit = commit_rows(&graph)
+            .into_iter()
+            .map(|cr| &cr.commit)
+            .find(|c| c.change_id == created.change_id)
+            .expect("described commit should still be in the graph");
+        assert_eq!(commit.summary, "after");
+        assert_ne!(commit.commit_id, created.commit_id.to_string());
+    }
+
+    #[test]
+    fn working_copy_moves_after_edit_and_new() {
+        let repo = TestRepo::new().unwrap();
+        repo.write_file("a.txt", "a").unwrap();
+        let first = repo.commit("first").unwrap().created;
+        repo.write_file("b.txt", "b").unwrap();
+        repo.commit("second").unwrap();
+
+        let working_copy = |repo: &TestRepo| {
+            let graph = graph_for(repo);
+            commit_rows(&graph)
+                .into_iter()
+                .find(|cr| cr.commit.is_working_copy)
+                .map(|cr| (cr.commit.change_id, cr.commit.parents.clone()))
+                .expect("graph should show the working copy")
+        };
+
+        jj::edit(repo.path(), first.change_id).unwrap();
+        assert_eq!(working_copy(&repo).0, first.change_id);
+
+        jj::new_revision(repo.path(), first.change_id).unwrap();
+        let (change_id, parents) = working_copy(&repo);
+        assert_ne!(change_id, first.change_id);
+        assert_eq!(parents, vec![first.change_id]);
+    }
+
     #[test]
     fn single_branch() {
         let repo = TestRepo::new().unwrap();
@@ -818,6 +941,10 @@ mod tests {
             .iter()
             .any(|e| matches!(e.edge_type, EdgeType::Merge));
         assert!(has_merge, "merge commit should have a Merge edge");
+        assert!(
+            merge.merge_spread.is_empty(),
+            "a two-parent merge has no octopus spread"
+        );
     }
 
     #[test]
@@ -892,6 +1019,102 @@ mod tests {
         );
     }
 
+    #[test]
+    fn conflicted_commit_is_flagged() {
+        let repo = TestRepo::new().unwrap();
+        let merge = repo
+            .create_conflict("file.txt", "base\n", "left\n", "right\n")
+            .unwrap();
+
+        let graph = graph_for(&repo);
+        let commits = commit_rows(&graph);
+
+        let conflicted: Vec<_> = commits
+            .iter()
+            .filter(|cr| cr.commit.has_conflict)
+            .map(|cr| cr.commit.change_id)
+            .collect();
+        assert_eq!(conflicted, vec![merge.change_id]);
+    }
+
+    #[test]
+    fn bookmarks_attached_to_commit() {
+        let repo = TestRepo::new().unwrap();
+        repo.write_file("a.txt", "a").unwrap();
+        let first = repo.commit("first").unwrap();
+        repo.write_file("b.txt", "b").unwrap();
+        repo.commit("second").unwrap();
+
+        repo.bookmark("feature", first.created.change_id).unwrap();
+        repo.bookmark("topic", first.created.change_id).unwrap();
+
+        let graph = graph_for(&repo);
+        let commits = commit_rows(&graph);
+
+        let first_row = commits
+            .iter()
+            .find(|cr| cr.commit.summary == "first")
+            .unwrap();
+        let mut bookmarks = first_row.commit.bookmarks.clone();
+        bookmarks.sort();
+        assert_eq!(bookmarks, vec!["feature", "topic"]);
+
+        let second_row = commits
+            .iter()
+            .find(|cr| cr.commit.summary == "second")
+            .unwrap();
+        assert!(second_row.commit.bookmarks.is_empty());
+    }
+
+    #[test]
+    fn custom_revset_selects_single_commit() {
+        let repo = TestRepo::new().unwrap();
+        repo.write_file("a.txt", "a").unwrap();
+        let first = repo.commit("first").unwrap();
+        repo.write_file("b.txt", "b").unwrap();
+        repo.commit("second").unwrap();
+
+        let revset = first.created.change_id.to_string();
+        let graph = get_log_graph(repo.path(), Some(&revset)).unwrap();
+        let commits = commit_rows(&graph);
+
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].commit.change_id, first.created.change_id);
+        assert_eq!(commits[0].column, 0);
+    }
+
+    #[test]
+    fn blank_revset_uses_default() {
+        let repo = TestRepo::new().unwrap();
+        repo.write_file("a.txt", "a").unwrap();
+        repo.commit("first").unwrap();
+
+        let default = commit_rows(&graph_for(&repo)).len();
+        let blank = get_log_graph(repo.path(), Some("  ")).unwrap();
+        assert_eq!(commit_rows(&blank).len(), default);
+    }
+
+    #[test]
+    fn author_color_is_stable() {
+        assert_eq!(
+            author_color("alice@example.com"),
+            author_color("alice@example.com")
+        );
+        assert_eq!(
+            author_color("Alice@Example.com "),
+            author_color("alice@example.com")
+        );
+        assert!(AUTHOR_PALETTE.contains(&author_color("")));
+    }
+
+    #[test]
+    fn author_color_spreads_across_palette() {
+        let colors: std::collections::HashSet<_> = (0..50)
+            .map(|i| author_color(&format!("user{i}@example.com")))
+            .collect();
+        assert!(colors.len() > AUTHOR_PALETTE.len() / 2);
+    }
+
     #[test]
     fn row_indices_sequential() {
         let repo = TestRepo::new().unwrap();
@@ -1068,6 +1291,9 @@ mod tests {
             merge_edges.len()
         );
 
+        // The ┬ between ├ and ╮ is recorded as the merge spread
+        assert_eq!(merge.merge_spread, vec![merge.column + 1]);
+
         // max_columns should be at least 3 (columns 0, 1, 2 for the fork)
         assert!(
             graph.max_columns >= 3,
@@ -1104,4 +1330,32 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn limited_log_returns_top_rows() {
+        let repo = TestRepo::new().unwrap();
+        repo.write_file("a.txt", "a").unwrap();
+        repo.commit("first").unwrap();
+        repo.write_file("b.txt", "b").unwrap();
+        let second = repo.commit("second").unwrap();
+
+        let full = graph_for(&repo);
+        let graph = get_log_graph_limited(repo.path(), None, 2).unwrap();
+        let commits = commit_rows(&graph);
+
+        assert_eq!(commits.len(), 2);
+        assert_eq!(
+            commits[0].commit.change_id,
+            commit_rows(&full)[0].commit.change_id
+        );
+        assert_eq!(commits[1].commit.change_id, second.created.change_id);
+        assert_eq!(commits[0].edges.len(), 1);
+        assert_eq!(commits[0].edges[0].to_row, commits[1].row);
+        assert!(matches!(commits[0].edges[0].edge_type, EdgeType::Straight));
+        for cr in &commits {
+            for edge in &cr.edges {
+                assert!(edge.to_row < graph.rows.len());
+            }
+        }
+    }
 }
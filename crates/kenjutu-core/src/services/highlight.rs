@@ -1,4 +1,6 @@
-use std::sync::OnceLock;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
 
 use two_face::re_exports::syntect::easy::HighlightLines;
 use two_face::re_exports::syntect::highlighting::{Color, Theme};
@@ -14,9 +16,23 @@ pub struct Token {
 /// Global singleton for HighlightService to avoid repeated initialization.
 static HIGHLIGHTER: OnceLock<HighlightService> = OnceLock::new();
 
+// Works well on the colored diff backgrounds
+const DEFAULT_THEME: two_face::theme::EmbeddedThemeName =
+    two_face::theme::EmbeddedThemeName::Base16OceanDark;
+
+/// File names and suffixes that syntect's own extension lookup misses.
+const DEFAULT_EXTENSIONS: &[(&str, &str)] = &[
+    ("Dockerfile", "Dockerfile"),
+    ("Containerfile", "Dockerfile"),
+    ("rs.in", "Rust"),
+];
+
 pub struct HighlightService {
     syntax_set: SyntaxSet,
-    theme: Theme,
+    themes: BTreeMap<&'static str, Theme>,
+    theme_name: RwLock<&'static str>,
+    /// File name or dotted suffix (without the leading dot) -> syntax name.
+    extensions: RwLock<HashMap<String, String>>,
 }
 
 impl HighlightService {
@@ -27,42 +43,157 @@ impl HighlightService {
     fn new() -> Self {
         let syntax_set = two_face::syntax::extra_newlines();
         let theme_set = two_face::theme::extra();
+        let themes = two_face::theme::EmbeddedLazyThemeSet::theme_names()
+            .iter()
+            .map(|name| (name.as_name(), theme_set[*name].clone()))
+            .collect();
+
+        let extensions = DEFAULT_EXTENSIONS
+            .iter()
+            .map(|(ext, name)| (ext.to_string(), name.to_string()))
+            .collect();
+
+        Self {
+            syntax_set,
+            themes,
+            theme_name: RwLock::new(DEFAULT_THEME.as_name()),
+            extensions: RwLock::new(extensions),
+        }
+    }
+
+    /// Create a service using `theme_name`, falling back to the default theme if it is unknown.
+    pub fn with_theme(theme_name: &str) -> Self {
+        let service = Self::new();
+        service.set_theme(theme_name);
+        service
+    }
+
+    /// Switch the theme used by subsequent highlighters.
+    /// Returns `false` and keeps the current theme if `theme_name` is unknown.
+    pub fn set_theme(&self, theme_name: &str) -> bool {
+        let Some((&name, _)) = self.themes.get_key_value(theme_name) else {
+            log::warn!("Unknown highlight theme: {theme_name}");
+            return false;
+        };
+        *self.theme_name.write().unwrap_or_else(|e| e.into_inner()) = name;
+        true
+    }
+
+    pub fn default_theme_name() -> &'static str {
+        DEFAULT_THEME.as_name()
+    }
+
+    pub fn theme_name(&self) -> &'static str {
+        *self.theme_name.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The name of the theme called `theme_name`, or `None` if there is no such theme.
+    pub fn find_theme(&self, theme_name: &str) -> Option<&'static str> {
+        self.themes.get_key_value(theme_name).map(|(&name, _)| name)
+    }
+
+    pub fn list_themes(&self) -> Vec<&'static str> {
+        self.themes.keys().copied().collect()
+    }
+
+    /// Map a file name (e.g. `Dockerfile`) or extension (e.g. `rs.in`) to a syntax.
+    /// Returns `false` and leaves the mapping untouched if `syntax_name` is unknown.
+    pub fn register_extension(&self, ext: &str, syntax_name: &str) -> bool {
+        if self.syntax_set.find_syntax_by_name(syntax_name).is_none() {
+            log::warn!("Unknown syntax: {syntax_name}");
+            return false;
+        }
+        let ext = ext.trim_start_matches('.');
+        self.extensions
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(ext.to_string(), syntax_name.to_string());
+        true
+    }
+
+    /// Detect the syntax from the file name, then registered extensions, then a
+    /// shebang on `first_line`.
+    pub fn detect_syntax(
+        &self,
+        file_path: &str,
+        first_line: Option<&str>,
+    ) -> Option<&SyntaxReference> {
+        let path = Path::new(file_path);
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(file_path);
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+        self.syntax_set
+            .find_syntax_by_extension(file_name)
+            .or_else(|| self.syntax_set.find_syntax_by_extension(extension))
+            .or_else(|| self.registered_syntax(file_name))
+            .or_else(|| first_line.and_then(|line| self.shebang_syntax(line)))
+    }
 
-        // Use a theme that works well on colored backgrounds
-        let theme = theme_set[two_face::theme::EmbeddedThemeName::Base16OceanDark].clone();
+    /// Like [`Self::detect_syntax`], falling back to plain text.
+    pub fn syntax_for(&self, file_path: &str, first_line: Option<&str>) -> &SyntaxReference {
+        self.detect_syntax(file_path, first_line)
+            .unwrap_or_else(|| self.default_syntax())
+    }
 
-        Self { syntax_set, theme }
+    /// Look up the file name, then each dotted suffix from longest to shortest.
+    fn registered_syntax(&self, file_name: &str) -> Option<&SyntaxReference> {
+        let extensions = self.extensions.read().unwrap_or_else(|e| e.into_inner());
+        let suffixes = file_name
+            .match_indices('.')
+            .map(|(i, _)| &file_name[i + 1..]);
+        std::iter::once(file_name)
+            .chain(suffixes)
+            .find_map(|key| extensions.get(key))
+            .and_then(|name| self.syntax_set.find_syntax_by_name(name))
     }
 
-    pub fn detect_syntax(&self, file_path: &str) -> Option<&SyntaxReference> {
+    fn shebang_syntax(&self, first_line: &str) -> Option<&SyntaxReference> {
+        let interpreter = shebang_interpreter(first_line)?;
         self.syntax_set
-            .find_syntax_for_file(file_path)
-            .unwrap_or(None)
+            .find_syntax_by_first_line(first_line)
+            .or_else(|| self.syntax_set.find_syntax_by_extension(interpreter))
+            .or_else(|| {
+                let name = interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+                self.syntax_set.find_syntax_by_extension(name)
+            })
     }
 
     pub fn default_syntax(&self) -> &SyntaxReference {
         self.syntax_set.find_syntax_plain_text()
     }
 
-    pub fn parse_and_highlight<'a>(&'a self, syntax: &'a SyntaxReference) -> ParseAndHighlight<'a> {
-        ParseAndHighlight::new(syntax, &self.theme)
+    /// Highlighter using `theme`, or the current theme if it is `None` or unknown.
+    pub fn parse_and_highlight<'a>(
+        &'a self,
+        syntax: &'a SyntaxReference,
+        theme: Option<&str>,
+    ) -> ParseAndHighlight<'a> {
+        let theme = theme
+            .and_then(|name| self.themes.get(name))
+            .unwrap_or_else(|| &self.themes[self.theme_name()]);
+        ParseAndHighlight::new(syntax, theme, &self.syntax_set)
     }
 }
 
 pub struct ParseAndHighlight<'a> {
     highlighter: HighlightLines<'a>,
+    syntax_set: &'a SyntaxSet,
 }
 
 impl<'a> ParseAndHighlight<'a> {
-    fn new(syntax: &'a SyntaxReference, theme: &'a Theme) -> Self {
+    fn new(syntax: &'a SyntaxReference, theme: &'a Theme, syntax_set: &'a SyntaxSet) -> Self {
         let highlighter = HighlightLines::new(syntax, theme);
-        Self { highlighter }
+        Self {
+            highlighter,
+            syntax_set,
+        }
     }
 
     pub fn highlight_line(&mut self, line: &str) -> Vec<Token> {
-        let res = self
-            .highlighter
-            .highlight_line(line, &HighlightService::global().syntax_set);
+        let res = self.highlighter.highlight_line(line, self.syntax_set);
         let res = match res {
             Ok(v) => v,
             Err(err) => {
@@ -83,6 +214,105 @@ impl<'a> ParseAndHighlight<'a> {
     }
 }
 
+/// Interpreter named by a `#!` line, skipping `env` and its flags:
+/// `#!/usr/bin/env python3` -> `python3`.
+fn shebang_interpreter(line: &str) -> Option<&str> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = words.find(|w| !w.starts_with('-') && !w.contains('='))?;
+    }
+    Some(program)
+}
+
 fn color_to_hex(color: Color) -> String {
     format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colors(service: &HighlightService) -> Vec<Option<String>> {
+        let syntax = service.detect_syntax("main.rs", None).unwrap();
+        let mut state = service.parse_and_highlight(syntax, None);
+        state
+            .highlight_line("fn main() { let x = \"hi\"; }\n")
+            .into_iter()
+            .map(|t| t.color)
+            .collect()
+    }
+
+    #[test]
+    fn different_themes_produce_different_colors() {
+        let dark = HighlightService::with_theme(DEFAULT_THEME.as_name());
+        let light = HighlightService::with_theme(
+            two_face::theme::EmbeddedThemeName::InspiredGithub.as_name(),
+        );
+        assert_ne!(colors(&dark), colors(&light));
+    }
+
+    #[test]
+    fn theme_override_leaves_current_theme_alone() {
+        let service = HighlightService::with_theme(DEFAULT_THEME.as_name());
+        let light = two_face::theme::EmbeddedThemeName::InspiredGithub.as_name();
+        assert_eq!(service.find_theme(light), Some(light));
+
+        let syntax = service.detect_syntax("main.rs", None).unwrap();
+        let overridden: Vec<_> = service
+            .parse_and_highlight(syntax, Some(light))
+            .highlight_line("fn main() { let x = \"hi\"; }\n")
+            .into_iter()
+            .map(|t| t.color)
+            .collect();
+        assert_eq!(overridden, colors(&HighlightService::with_theme(light)));
+        assert_eq!(service.theme_name(), DEFAULT_THEME.as_name());
+    }
+
+    #[test]
+    fn unknown_theme_falls_back_to_default() {
+        let service = HighlightService::with_theme("no-such-theme");
+        assert_eq!(service.theme_name(), DEFAULT_THEME.as_name());
+        assert!(!service.set_theme("no-such-theme"));
+        assert!(service.list_themes().contains(&DEFAULT_THEME.as_name()));
+    }
+
+    #[test]
+    fn dockerfile_is_detected_by_file_name() {
+        let service = HighlightService::global();
+        let syntax = service.syntax_for("docker/Dockerfile", Some("FROM rust:1"));
+        assert_eq!(syntax.name, "Dockerfile");
+    }
+
+    #[test]
+    fn shebang_detects_extensionless_script() {
+        let service = HighlightService::global();
+        let syntax = service.syntax_for("scripts/release", Some("#!/usr/bin/env python3"));
+        assert_eq!(syntax.name, "Python");
+        assert_eq!(
+            shebang_interpreter("#!/usr/bin/env -S bash -e"),
+            Some("bash")
+        );
+        assert_eq!(shebang_interpreter("#!/bin/sh"), Some("sh"));
+    }
+
+    #[test]
+    fn unknown_extension_falls_back_to_plain_text() {
+        let service = HighlightService::global();
+        let syntax = service.syntax_for("notes.zzunknown", Some("hello"));
+        assert_eq!(syntax.name, service.default_syntax().name);
+    }
+
+    #[test]
+    fn registered_extension_overrides_unknown_suffix() {
+        let service = HighlightService::with_theme(DEFAULT_THEME.as_name());
+        assert!(service.detect_syntax("build.rs.tmpl", None).is_none());
+        assert!(service.register_extension(".rs.tmpl", "Rust"));
+        assert_eq!(
+            service.detect_syntax("build.rs.tmpl", None).unwrap().name,
+            "Rust"
+        );
+        assert!(!service.register_extension("foo", "No Such Syntax"));
+        assert_eq!(service.syntax_for("generated.rs.in", None).name, "Rust");
+    }
+}
@@ -1,4 +1,5 @@
-use kenjutu_types::{ChangeId, InvalidChangeIdError};
+use kenjutu_types::{ChangeId, InvalidChangeIdError, InvalidCommitIdError};
+use std::fmt;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::OnceLock;
@@ -9,6 +10,11 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+    #[error(
+        "jj executable not found. Install Jujutsu (https://jj-vcs.github.io/jj/latest/install-and-setup/) and make sure `jj` is on your PATH"
+    )]
+    NotInstalled,
+
     #[error("Failed to run jj command: {0}")]
     Command(String),
 
@@ -17,11 +23,20 @@ pub enum Error {
 
     #[error("Failed to parse output: {0}")]
     Parse(String),
+
+    #[error("{0}")]
+    InvalidId(String),
 }
 
 impl From<InvalidChangeIdError> for Error {
     fn from(err: InvalidChangeIdError) -> Self {
-        Error::Parse(err.to_string())
+        Error::InvalidId(err.to_string())
+    }
+}
+
+impl From<InvalidCommitIdError> for Error {
+    fn from(err: InvalidCommitIdError) -> Self {
+        Error::InvalidId(err.to_string())
     }
 }
 
@@ -74,6 +89,82 @@ pub fn is_installed() -> bool {
     find_jj_executable().is_some()
 }
 
+/// Oldest jj release with the `json()` template function used by the graph and test templates.
+pub const MIN_JJ_VERSION: JjVersion = JjVersion {
+    major: 0,
+    minor: 31,
+    patch: 0,
+};
+
+/// A jj release number, compared numerically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct JjVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl fmt::Display for JjVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// The jj executable in use and its version.
+#[derive(Clone, Debug)]
+pub struct JjInfo {
+    pub path: PathBuf,
+    pub version: JjVersion,
+}
+
+/// Parse `jj --version` output such as `jj 0.31.0` or `jj 0.32.0-4c1d5e6f...`.
+pub fn parse_version(output: &str) -> Option<JjVersion> {
+    let version = output.trim().strip_prefix("jj")?.trim_start();
+    let mut parts = version.splitn(3, '.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next()?;
+    let patch_end = patch
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(patch.len());
+    let patch = patch[..patch_end].parse().ok()?;
+    Some(JjVersion {
+        major,
+        minor,
+        patch,
+    })
+}
+
+/// Locate jj and read its version, warning when it predates [`MIN_JJ_VERSION`].
+///
+/// Fails with [`Error::NotInstalled`] when no executable is found, so callers can show an install
+/// hint instead of a generic command failure.
+pub fn detect() -> Result<JjInfo> {
+    let path = find_jj_executable().ok_or(Error::NotInstalled)?;
+    let output = Command::new(&path)
+        .arg("--version")
+        .output()
+        .map_err(|e| Error::Command(e.to_string()))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::JjFailed(format!(
+            "jj --version failed with status {}: {}",
+            output.status,
+            stderr.trim()
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let version = parse_version(&stdout)
+        .ok_or_else(|| Error::Parse(format!("unrecognized jj version: {}", stdout.trim())))?;
+    if version < MIN_JJ_VERSION {
+        log::warn!(
+            "jj {version} is older than {MIN_JJ_VERSION}; some log templates may not work. Please upgrade jj."
+        );
+    }
+    Ok(JjInfo { path, version })
+}
+
 /// Check if directory is a jj repository
 pub fn is_jj_repo(local_dir: &Path) -> bool {
     jj_command()
@@ -87,6 +178,15 @@ pub fn is_jj_repo(local_dir: &Path) -> bool {
         .unwrap_or(false)
 }
 
+/// Find the root of the jj workspace containing `start` by walking up to the nearest `.jj`
+/// directory, so tools launched from a subdirectory open the whole workspace.
+pub fn find_workspace_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(".jj").is_dir())
+        .map(Path::to_path_buf)
+}
+
 /// Get jj status for a directory
 pub fn get_status(local_dir: &Path) -> JjStatus {
     JjStatus {
@@ -95,12 +195,57 @@ pub fn get_status(local_dir: &Path) -> JjStatus {
     }
 }
 
+/// Read the configured jj identity as `Name <email>`, or whichever half is set.
+///
+/// Returns `None` when jj is unavailable or neither `user.name` nor `user.email` is set.
+pub fn get_author(local_dir: &Path) -> Option<String> {
+    let name = config_get(local_dir, "user.name");
+    let email = config_get(local_dir, "user.email");
+    match (name, email) {
+        (Some(name), Some(email)) => Some(format!("{name} <{email}>")),
+        (Some(name), None) => Some(name),
+        (None, Some(email)) => Some(email),
+        (None, None) => None,
+    }
+}
+
+/// Read a single jj config value. Unset and empty values are both `None`.
+fn config_get(local_dir: &Path, key: &str) -> Option<String> {
+    let output = jj_command()?
+        .args(["config", "get", key])
+        .current_dir(local_dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
 /// Describe (set the commit message of) a jj revision.
 pub fn describe(local_dir: &Path, change_id: ChangeId, message: &str) -> Result<()> {
-    let mut cmd = jj_command().ok_or_else(|| Error::Command("jj executable not found".into()))?;
-    let change_id_str = change_id.to_string();
+    run(
+        local_dir,
+        &["describe", "-r", &change_id.to_string(), "-m", message],
+    )
+}
+
+/// Make `change_id` the working-copy commit, like `jj edit`.
+pub fn edit(local_dir: &Path, change_id: ChangeId) -> Result<()> {
+    run(local_dir, &["edit", &change_id.to_string()])
+}
+
+/// Start a new, empty working-copy commit on top of `change_id`, like `jj new`.
+pub fn new_revision(local_dir: &Path, change_id: ChangeId) -> Result<()> {
+    run(local_dir, &["new", &change_id.to_string()])
+}
+
+/// Run a jj subcommand that modifies the repository, discarding its output.
+fn run(local_dir: &Path, args: &[&str]) -> Result<()> {
+    let mut cmd = jj_command().ok_or(Error::NotInstalled)?;
     let output = cmd
-        .args(["describe", "-r", &change_id_str, "-m", message])
+        .args(args)
         .current_dir(local_dir)
         .output()
         .map_err(|e| Error::Command(e.to_string()))?;
@@ -108,7 +253,8 @@ pub fn describe(local_dir: &Path, change_id: ChangeId, message: &str) -> Result<
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(Error::JjFailed(format!(
-            "jj describe failed with status {}: {}",
+            "jj {} failed with status {}: {}",
+            args[0],
             output.status,
             stderr.trim()
         )));
@@ -116,3 +262,59 @@ pub fn describe(local_dir: &Path, change_id: ChangeId, message: &str) -> Result<
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_repo::TestRepo;
+
+    #[test]
+    fn find_workspace_root_from_nested_dir() {
+        let repo = TestRepo::new().unwrap();
+        let nested = repo.path().join("src/deeply/nested");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(find_workspace_root(&nested).as_deref(), Some(repo.path()));
+        assert_eq!(
+            find_workspace_root(repo.path()).as_deref(),
+            Some(repo.path())
+        );
+    }
+
+    #[test]
+    fn parse_version_reads_release_and_dev_builds() {
+        assert_eq!(
+            parse_version("jj 0.31.0\n"),
+            Some(JjVersion {
+                major: 0,
+                minor: 31,
+                patch: 0
+            })
+        );
+        assert_eq!(
+            parse_version("jj 0.32.1-4c1d5e6f0a9b8c7d"),
+            Some(JjVersion {
+                major: 0,
+                minor: 32,
+                patch: 1
+            })
+        );
+        assert_eq!(parse_version("git version 2.43.0"), None);
+        assert!(parse_version("jj 0.24.0").unwrap() < MIN_JJ_VERSION);
+    }
+
+    #[test]
+    fn get_author_reads_jj_config() {
+        let repo = TestRepo::new().unwrap();
+        assert_eq!(
+            get_author(repo.path()).as_deref(),
+            Some("Test User <test@test.com>")
+        );
+
+        repo.jj_config_set("user.name", "Alice").unwrap();
+        assert_eq!(
+            get_author(repo.path()).as_deref(),
+            Some("Alice <test@test.com>")
+        );
+    }
+}
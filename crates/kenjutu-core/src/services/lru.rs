@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Bounded least-recently-used cache behind the diff and blame caches.
+///
+/// Values are computed without holding the lock, so a slow diff or blame never blocks lookups of
+/// other entries. Two callers missing the same key may both compute it; the first result is
+/// kept.
+#[derive(Debug)]
+pub(crate) struct LruCache<K, V> {
+    capacity: usize,
+    entries: Mutex<VecDeque<(K, V)>>,
+}
+
+impl<K: PartialEq, V: Clone> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Return the cached value for `key`, or compute and cache it. Errors are not cached.
+    pub fn get_or_insert_with<E>(
+        &self,
+        key: K,
+        compute: impl FnOnce() -> Result<V, E>,
+    ) -> Result<V, E> {
+        {
+            let mut entries = self.lock();
+            if let Some(pos) = entries.iter().position(|(k, _)| *k == key) {
+                let entry = entries.remove(pos).unwrap();
+                let value = entry.1.clone();
+                entries.push_back(entry);
+                return Ok(value);
+            }
+        }
+
+        let value = compute()?;
+
+        let mut entries = self.lock();
+        if !entries.iter().any(|(k, _)| *k == key) {
+            if entries.len() >= self.capacity {
+                entries.pop_front();
+            }
+            entries.push_back((key, value.clone()));
+        }
+        Ok(value)
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// A panic while holding the lock can't leave the queue half-updated in a way that
+    /// matters for a cache, so a poisoned lock is used as is.
+    fn lock(&self) -> MutexGuard<'_, VecDeque<(K, V)>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = LruCache::new(2);
+        let calls = Cell::new(0);
+        let get = |key: &'static str| {
+            cache
+                .get_or_insert_with(key, || {
+                    calls.set(calls.get() + 1);
+                    Ok::<_, ()>(key.len())
+                })
+                .unwrap()
+        };
+
+        get("a");
+        get("b");
+        // Touch a so b becomes the eviction candidate.
+        get("a");
+        get("c");
+        assert_eq!(calls.get(), 3);
+        assert_eq!(cache.len(), 2);
+
+        get("a");
+        assert_eq!(calls.get(), 3);
+        get("b");
+        assert_eq!(calls.get(), 4);
+    }
+
+    #[test]
+    fn errors_are_not_cached() {
+        let cache = LruCache::<u8, u8>::new(4);
+        assert_eq!(cache.get_or_insert_with(1, || Err("boom")), Err("boom"));
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn poisoned_lock_keeps_working() {
+        let cache = LruCache::new(4);
+        cache.get_or_insert_with(1, || Ok::<_, ()>(1)).unwrap();
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = cache.entries.lock().unwrap();
+            panic!("poison the lock");
+        }));
+        assert!(cache.entries.is_poisoned());
+
+        assert_eq!(cache.get_or_insert_with(1, || Err(())), Ok(1));
+        cache.clear();
+        assert_eq!(cache.len(), 0);
+    }
+}
@@ -1,6 +1,11 @@
+pub mod blame;
 pub mod diff;
+pub mod editor;
 pub mod git;
 pub mod graph;
 pub mod highlight;
 pub mod jj;
+pub(crate) mod lru;
+pub mod repo_settings;
+pub mod review;
 pub(crate) mod word_diff;
@@ -0,0 +1,191 @@
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::diff::{DiffAlgorithm, DiffConfig, GeneratedFiles};
+use super::highlight::HighlightService;
+use super::jj;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Not inside a jj workspace: {0}")]
+    NotAWorkspace(PathBuf),
+
+    #[error("Failed to access repository settings: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid repository settings: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("Failed to serialize repository settings: {0}")]
+    Serialize(#[from] toml::ser::Error),
+}
+
+/// Workspace-relative location of the settings file. It lives under `.jj` so it is never
+/// snapshotted into a commit.
+const SETTINGS_PATH: &str = ".jj/kenjutu/settings.toml";
+
+/// Per-repository overrides for the global settings. Every field is optional; anything left
+/// out falls back to the global value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(default)]
+pub struct RepoSettings {
+    /// Unchanged lines shown around each hunk.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_lines: Option<u32>,
+    /// Line-matching algorithm used to compute hunks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<DiffAlgorithm>,
+    /// Hide changes that only touch whitespace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_whitespace: Option<bool>,
+    /// Syntax highlighting theme name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Globs for generated files, replacing the global list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generated_globs: Option<Vec<String>>,
+    /// Largest blob, in bytes, diffed with highlighting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_diff_bytes: Option<u64>,
+}
+
+impl RepoSettings {
+    /// `global` with this repository's overrides applied.
+    pub fn diff_config(&self, global: DiffConfig) -> DiffConfig {
+        DiffConfig {
+            context_lines: self.context_lines.unwrap_or(global.context_lines),
+            algorithm: self.algorithm.unwrap_or(global.algorithm),
+            ignore_whitespace: self.ignore_whitespace.unwrap_or(global.ignore_whitespace),
+            max_diff_bytes: self.max_diff_bytes.unwrap_or(global.max_diff_bytes),
+            theme: self.theme.as_deref().and_then(find_theme).or(global.theme),
+            ..global
+        }
+    }
+
+    /// The repository's generated-file globs, or `global` if it does not set any.
+    pub fn generated_files<S: AsRef<str>>(&self, global: &[S]) -> GeneratedFiles {
+        match &self.generated_globs {
+            Some(globs) => GeneratedFiles::new(globs),
+            None => GeneratedFiles::new(global),
+        }
+    }
+}
+
+/// An unknown theme, e.g. one dropped from two-face, falls back to the global theme.
+fn find_theme(name: &str) -> Option<&'static str> {
+    let theme = HighlightService::global().find_theme(name);
+    if theme.is_none() {
+        log::warn!("Unknown highlight theme in repository settings: {name}");
+    }
+    theme
+}
+
+fn settings_path(local_dir: &Path) -> Result<PathBuf> {
+    let root = jj::find_workspace_root(local_dir)
+        .ok_or_else(|| Error::NotAWorkspace(local_dir.to_path_buf()))?;
+    Ok(root.join(SETTINGS_PATH))
+}
+
+/// Read the settings of the workspace containing `local_dir`. A missing file means no
+/// overrides.
+pub fn load(local_dir: &Path) -> Result<RepoSettings> {
+    let path = settings_path(local_dir)?;
+    match std::fs::read_to_string(&path) {
+        Ok(content) => Ok(toml::from_str(&content)?),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(RepoSettings::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn save(local_dir: &Path, settings: &RepoSettings) -> Result<()> {
+    let path = settings_path(local_dir)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, toml::to_string(settings)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_repo::TestRepo;
+
+    #[test]
+    fn save_then_load_round_trips() {
+        let t = TestRepo::new().unwrap();
+        let settings = RepoSettings {
+            context_lines: Some(8),
+            algorithm: Some(DiffAlgorithm::Patience),
+            ignore_whitespace: Some(true),
+            theme: Some("InspiredGitHub".to_string()),
+            generated_globs: Some(vec!["**/*.pb.go".to_string()]),
+            max_diff_bytes: Some(4096),
+        };
+
+        save(t.path(), &settings).unwrap();
+        assert_eq!(load(t.path()).unwrap(), settings);
+        assert!(t.path().join(SETTINGS_PATH).is_file());
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_global() {
+        let t = TestRepo::new().unwrap();
+        assert_eq!(load(t.path()).unwrap(), RepoSettings::default());
+
+        let path = t.path().join(SETTINGS_PATH);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "context_lines = 1\n").unwrap();
+        let settings = load(t.path()).unwrap();
+        assert_eq!(settings.context_lines, Some(1));
+        assert_eq!(settings.algorithm, None);
+
+        let global = DiffConfig {
+            algorithm: DiffAlgorithm::Minimal,
+            ignore_whitespace: true,
+            ..Default::default()
+        };
+        let config = settings.diff_config(global);
+        assert_eq!(config.context_lines, 1);
+        assert_eq!(config.algorithm, DiffAlgorithm::Minimal);
+        assert!(config.ignore_whitespace);
+        assert_eq!(config.max_diff_bytes, global.max_diff_bytes);
+
+        let generated = settings.generated_files(&["**/Cargo.lock"]);
+        assert!(generated.is_generated("Cargo.lock"));
+    }
+
+    #[test]
+    fn repo_overrides_ignore_whitespace() {
+        let global = DiffConfig {
+            ignore_whitespace: true,
+            ..Default::default()
+        };
+        let settings = RepoSettings {
+            ignore_whitespace: Some(false),
+            ..Default::default()
+        };
+        assert!(!settings.diff_config(global).ignore_whitespace);
+    }
+
+    #[test]
+    fn repo_theme_applies_unless_unknown() {
+        let global = DiffConfig::default();
+        let settings = RepoSettings {
+            theme: Some("InspiredGitHub".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(settings.diff_config(global).theme, Some("InspiredGitHub"));
+
+        let settings = RepoSettings {
+            theme: Some("no-such-theme".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(settings.diff_config(global).theme, None);
+    }
+}
@@ -0,0 +1,713 @@
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use comment_commit::{CommentCommit, CommentLog};
+use git2::{Delta, Repository};
+use kenjutu_types::{ChangeId, CommitChangeIdExt, CommitId};
+use marker_commit::{MarkerCommit, MarkerEntry, RegionId, ReviewProgress};
+use serde::{Deserialize, Serialize};
+
+use crate::models::ReviewStatus;
+use crate::services::diff::{self, DiffConfig};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to mark {sha} reviewed: {source}")]
+    Revision {
+        sha: CommitId,
+        #[source]
+        source: diff::Error,
+    },
+
+    #[error("{first} and {second} are both revisions of change {change_id}")]
+    DuplicateChange {
+        change_id: ChangeId,
+        first: CommitId,
+        second: CommitId,
+    },
+
+    #[error("Review state belongs to change {expected}, not {found}")]
+    ChangeMismatch { expected: ChangeId, found: ChangeId },
+
+    #[error("Invalid review state: {0}")]
+    InvalidState(String),
+
+    #[error("{} is not changed in {sha}", path.display())]
+    FileNotInChange { sha: CommitId, path: PathBuf },
+
+    #[error("Diff error: {0}")]
+    Diff(#[from] diff::Error),
+
+    #[error("git2 error: {0}")]
+    Git2(#[from] git2::Error),
+
+    #[error("Marker commit error: {0}")]
+    MarkerCommit(#[from] marker_commit::Error),
+
+    #[error("Comment commit error: {0}")]
+    CommentCommit(#[from] comment_commit::Error),
+}
+
+/// Portable review state of one change: what has been reviewed and every comment action.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewStateJson {
+    pub change_id: ChangeId,
+    pub reviewed: Vec<ReviewedEntry>,
+    pub comments: CommentLog,
+}
+
+/// One file of the marker tree that differs from the base.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewedEntry {
+    pub path: PathBuf,
+    /// Base64-encoded reviewed content, or `None` when the deletion was reviewed.
+    pub content: Option<String>,
+    pub filemode: i32,
+}
+
+/// Review progress of one change, for dashboards that summarize a whole stack.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeProgress {
+    pub change_id: ChangeId,
+    pub commit_id: CommitId,
+    /// First line of the commit message.
+    pub summary: String,
+    pub reviewed_files: usize,
+    pub total_files: usize,
+    /// Unresolved comment threads across all files.
+    pub open_comments: usize,
+}
+
+/// Summarize review progress for each revision in `shas`, e.g. every change in a jj stack.
+///
+/// Changes nobody has started reviewing report zero reviewed files. Partially reviewed files
+/// don't count as reviewed. Duplicate revisions are reported once.
+pub fn overall_progress(repository: &Repository, shas: &[CommitId]) -> Result<Vec<ChangeProgress>> {
+    let mut progress: Vec<ChangeProgress> = Vec::with_capacity(shas.len());
+    for &sha in shas {
+        let commit = repository.find_commit(sha.oid())?;
+        let change_id = commit.change_id();
+        if progress.iter().any(|p| p.change_id == change_id) {
+            continue;
+        }
+        let review = MarkerCommit::get(repository, sha)?.review_progress()?;
+        let open_comments = CommentCommit::get(repository, sha)?
+            .unresolved_counts()
+            .values()
+            .sum();
+        progress.push(ChangeProgress {
+            change_id,
+            commit_id: sha,
+            summary: commit.summary().unwrap_or_default().to_string(),
+            reviewed_files: review.reviewed_files,
+            total_files: review.total_files(),
+            open_comments,
+        });
+    }
+    Ok(progress)
+}
+
+/// Review state of one change, file by file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeReviewState {
+    pub change_id: ChangeId,
+    pub progress: ReviewProgress,
+    pub reviewed: Vec<String>,
+    pub partially_reviewed: Vec<String>,
+    pub unreviewed: Vec<String>,
+}
+
+impl ChangeReviewState {
+    /// Whether nothing in the change is left to review.
+    pub fn is_complete(&self) -> bool {
+        self.partially_reviewed.is_empty() && self.unreviewed.is_empty()
+    }
+}
+
+/// Review progress of `sha` together with the files in each review state, e.g. to gate a merge
+/// on a fully reviewed change.
+///
+/// Files whose reviewed change was reverted are left out: there is nothing in them to review.
+pub fn change_review_state(repository: &Repository, sha: CommitId) -> Result<ChangeReviewState> {
+    let progress = MarkerCommit::get(repository, sha)?.review_progress()?;
+    let (change_id, files) = diff::generate_file_list(repository, sha, DiffConfig::default())?;
+
+    let mut state = ChangeReviewState {
+        change_id,
+        progress,
+        reviewed: Vec::new(),
+        partially_reviewed: Vec::new(),
+        unreviewed: Vec::new(),
+    };
+    for file in files {
+        let Some(path) = file.new_path.or(file.old_path) else {
+            continue;
+        };
+        match file.review_status {
+            ReviewStatus::Reviewed => state.reviewed.push(path),
+            ReviewStatus::PartiallyReviewed => state.partially_reviewed.push(path),
+            ReviewStatus::Unreviewed => state.unreviewed.push(path),
+            ReviewStatus::ReviewedReverted => {}
+        }
+    }
+    Ok(state)
+}
+
+/// Mark every file of every revision in `shas` as reviewed, e.g. a whole jj stack.
+///
+/// All marker commits are prepared before any is written, so a revision that can't be marked
+/// leaves the whole range untouched. The writes themselves are not atomic: if one fails, the
+/// revisions written before it stay marked. The error names the revision that failed.
+///
+/// Markers are locked per change, so two different revisions of one change (a divergent change,
+/// or an old and a new commit of it) are refused rather than marked against each other.
+pub fn mark_range_reviewed(repository: &Repository, shas: &[CommitId]) -> Result<()> {
+    let mut seen: Vec<(ChangeId, CommitId)> = Vec::with_capacity(shas.len());
+    for &sha in shas {
+        let change_id = repository
+            .find_commit(sha.oid())
+            .map_err(|e| Error::Revision {
+                sha,
+                source: e.into(),
+            })?
+            .change_id();
+        match seen
+            .iter()
+            .find(|(seen_change, _)| *seen_change == change_id)
+        {
+            Some((_, first)) if *first == sha => continue,
+            Some(&(_, first)) => {
+                return Err(Error::DuplicateChange {
+                    change_id,
+                    first,
+                    second: sha,
+                });
+            }
+            None => seen.push((change_id, sha)),
+        }
+    }
+
+    let mut markers: Vec<(CommitId, MarkerCommit)> = Vec::with_capacity(seen.len());
+    for (_, sha) in seen {
+        let marker =
+            mark_all_files(repository, sha).map_err(|source| Error::Revision { sha, source })?;
+        markers.push((sha, marker));
+    }
+
+    for (sha, marker) in &markers {
+        marker.write().map_err(|e| Error::Revision {
+            sha: *sha,
+            source: e.into(),
+        })?;
+    }
+    Ok(())
+}
+
+/// Serialize the review state of the change `sha` belongs to.
+pub fn export_state(repository: &Repository, sha: CommitId) -> Result<ReviewStateJson> {
+    let change_id = repository.find_commit(sha.oid())?.change_id();
+    let reviewed = MarkerCommit::get(repository, sha)?
+        .entries()?
+        .into_iter()
+        .map(|entry| ReviewedEntry {
+            path: entry.path,
+            content: entry.content.map(|content| STANDARD.encode(content)),
+            filemode: entry.filemode,
+        })
+        .collect();
+    let comments = CommentCommit::get(repository, sha)?.export_log();
+    Ok(ReviewStateJson {
+        change_id,
+        reviewed,
+        comments,
+    })
+}
+
+/// Restore review state exported by [`export_state`] onto `sha`.
+///
+/// The marker is rebuilt from the exported entries, replacing any local review progress.
+/// Comment actions are appended idempotently, so importing the same state twice is harmless.
+pub fn import_state(repository: &Repository, sha: CommitId, state: ReviewStateJson) -> Result<()> {
+    let found = repository.find_commit(sha.oid())?.change_id();
+    if found != state.change_id {
+        return Err(Error::ChangeMismatch {
+            expected: state.change_id,
+            found,
+        });
+    }
+
+    let entries = state
+        .reviewed
+        .into_iter()
+        .map(|entry| {
+            let content = entry
+                .content
+                .map(|content| STANDARD.decode(content))
+                .transpose()
+                .map_err(|e| Error::InvalidState(format!("{}: {e}", entry.path.display())))?;
+            Ok(MarkerEntry {
+                path: entry.path,
+                content,
+                filemode: entry.filemode,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut marker = MarkerCommit::get(repository, sha)?;
+    marker.restore_entries(&entries)?;
+    let mut comments = CommentCommit::get(repository, sha)?;
+    comments.import_log(state.comments);
+
+    marker.write()?;
+    comments.write()?;
+    Ok(())
+}
+
+/// Mark (or with `reviewed == false`, unmark) whole files of `sha` and write the marker once.
+///
+/// Paths are checked against the change before anything is marked, so a typo leaves the review
+/// state untouched. A renamed file can be named by either its old or its new path.
+pub fn set_files_reviewed(
+    repository: &Repository,
+    sha: CommitId,
+    paths: &[PathBuf],
+    reviewed: bool,
+) -> Result<()> {
+    let mut marker = MarkerCommit::get(repository, sha)?;
+    let changed = changed_files(repository, &marker)?;
+
+    let mut targets = Vec::with_capacity(paths.len());
+    for path in paths {
+        let target = changed
+            .iter()
+            .find(|(file_path, old_path)| {
+                file_path == path || old_path.as_deref() == Some(path.as_path())
+            })
+            .ok_or_else(|| Error::FileNotInChange {
+                sha,
+                path: path.clone(),
+            })?;
+        targets.push(target);
+    }
+
+    for (file_path, old_path) in targets {
+        if reviewed {
+            mark_file_reviewed(repository, &mut marker, file_path, old_path.as_deref())?;
+        } else {
+            marker.unmark_file_reviewed(file_path, old_path.as_deref())?;
+        }
+    }
+    marker.write()?;
+    Ok(())
+}
+
+/// Files of the marker's change as `(file_path, old_path)`, the arguments of
+/// [`MarkerCommit::mark_file_reviewed`]. `old_path` is only set for renames.
+fn changed_files(
+    repository: &Repository,
+    marker: &MarkerCommit,
+) -> diff::Result<Vec<(PathBuf, Option<PathBuf>)>> {
+    let diff = diff::diff_with_options(
+        repository,
+        marker.base_tree(),
+        marker.target_tree(),
+        DiffConfig::default(),
+    )?;
+
+    let mut files = Vec::new();
+    for delta in diff.deltas() {
+        let old_path = delta.old_file().path().map(|p| p.to_path_buf());
+        let new_path = delta.new_file().path().map(|p| p.to_path_buf());
+        match delta.status() {
+            Delta::Deleted => files.push((old_path, None)),
+            Delta::Renamed => files.push((new_path, old_path)),
+            _ => files.push((new_path, None)),
+        }
+    }
+
+    Ok(files
+        .into_iter()
+        .filter_map(|(file_path, old_path)| Some((file_path?, old_path)))
+        .collect())
+}
+
+fn mark_all_files(repository: &Repository, sha: CommitId) -> diff::Result<MarkerCommit<'_>> {
+    let mut marker = MarkerCommit::get(repository, sha)?;
+    for (file_path, old_path) in changed_files(repository, &marker)? {
+        mark_file_reviewed(repository, &mut marker, &file_path, old_path.as_deref())?;
+    }
+    Ok(marker)
+}
+
+/// Mark `file_path` reviewed as a whole, without writing the marker.
+///
+/// Every way of marking goes through this module, so an unresolved conflict is never recorded
+/// as reviewed content whichever front end asked.
+pub fn mark_file_reviewed(
+    repository: &Repository,
+    marker: &mut MarkerCommit,
+    file_path: &Path,
+    old_path: Option<&Path>,
+) -> diff::Result<()> {
+    diff::check_no_conflict_markers(repository, marker.target_tree(), file_path)?;
+    marker.mark_file_reviewed(file_path, old_path)?;
+    Ok(())
+}
+
+/// Mark one hunk of `file_path` reviewed, without writing the marker. Refused while the file
+/// has conflict markers, like [`mark_file_reviewed`].
+pub fn mark_region_reviewed(
+    repository: &Repository,
+    marker: &mut MarkerCommit,
+    file_path: &Path,
+    old_path: Option<&Path>,
+    region: &RegionId,
+) -> diff::Result<()> {
+    diff::check_no_conflict_markers(repository, marker.target_tree(), file_path)?;
+    marker.mark_region_reviewed(file_path, old_path, region)?;
+    Ok(())
+}
+
+/// Store `content` as the reviewed version of `file_path`, without writing the marker. Refused
+/// when `content` has conflict markers in it.
+pub fn set_reviewed_content(
+    marker: &mut MarkerCommit,
+    file_path: &Path,
+    old_path: Option<&Path>,
+    content: &[u8],
+) -> diff::Result<()> {
+    diff::check_content_has_no_conflict_markers(file_path, content)?;
+    marker.set_blob(file_path, old_path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use comment_commit::{DiffSide, NewComment};
+    use std::path::Path;
+    use test_repo::TestRepo;
+
+    #[test]
+    fn overall_progress_counts_each_change() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("a.txt", "a\n").unwrap();
+        t.write_file("b.txt", "b\n").unwrap();
+        let first = t.commit("first").unwrap().created;
+        t.write_file("c.txt", "c\n").unwrap();
+        let second = t.commit("second").unwrap().created;
+
+        let mut marker = MarkerCommit::get(&t.repo, first.commit_id).unwrap();
+        marker.mark_file_reviewed(Path::new("a.txt"), None).unwrap();
+        marker.write().unwrap();
+        drop(marker);
+        let mut comments = CommentCommit::get(&t.repo, second.commit_id).unwrap();
+        comments
+            .create_comment(
+                Path::new("c.txt"),
+                NewComment {
+                    sha: second.commit_id,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "why c?".into(),
+                    author: "alice".into(),
+                },
+            )
+            .unwrap();
+        comments.write().unwrap();
+        drop(comments);
+
+        let progress = overall_progress(&t.repo, &[first.commit_id, second.commit_id]).unwrap();
+        assert_eq!(
+            progress,
+            vec![
+                ChangeProgress {
+                    change_id: first.change_id,
+                    commit_id: first.commit_id,
+                    summary: "first".into(),
+                    reviewed_files: 1,
+                    total_files: 2,
+                    open_comments: 0,
+                },
+                ChangeProgress {
+                    change_id: second.change_id,
+                    commit_id: second.commit_id,
+                    summary: "second".into(),
+                    reviewed_files: 0,
+                    total_files: 1,
+                    open_comments: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn marks_every_file_in_a_linear_stack() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("a.txt", "a\n").unwrap();
+        t.write_file("gone.txt", "bye\n").unwrap();
+        t.write_file("old.txt", "1\n2\n3\n4\n5\n6\n7\n8\n").unwrap();
+        t.commit("base").unwrap();
+
+        t.write_file("a.txt", "a\nb\n").unwrap();
+        t.write_file("new_file.txt", "hello\n").unwrap();
+        let first = t.commit("first").unwrap().created;
+
+        t.delete_file("gone.txt").unwrap();
+        let second = t.commit("second").unwrap().created;
+
+        t.rename_file("old.txt", "renamed.txt").unwrap();
+        let third = t.commit("third").unwrap().created;
+
+        let shas = [first.commit_id, second.commit_id, third.commit_id];
+        mark_range_reviewed(&t.repo, &shas).unwrap();
+
+        for sha in shas {
+            let (_, files) = diff::generate_file_list(&t.repo, sha, DiffConfig::default()).unwrap();
+            assert!(!files.is_empty());
+            for file in files {
+                assert_eq!(
+                    file.review_status,
+                    ReviewStatus::Reviewed,
+                    "{sha}: {file:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn two_revisions_of_one_change_are_refused() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("a.txt", "a\n").unwrap();
+        let old = t.commit("first").unwrap().created;
+        t.edit(old.change_id).unwrap();
+        t.write_file("a.txt", "a\nb\n").unwrap();
+        let new = t.work_copy().unwrap();
+        assert_eq!(new.change_id, old.change_id);
+
+        let err = mark_range_reviewed(&t.repo, &[old.commit_id, new.commit_id]).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                Error::DuplicateChange { change_id, first, second }
+                    if change_id == old.change_id
+                        && first == old.commit_id
+                        && second == new.commit_id
+            ),
+            "{err:?}"
+        );
+        assert!(!MarkerCommit::exists(&t.repo, old.change_id).unwrap());
+
+        // The same revision listed twice is only marked once.
+        mark_range_reviewed(&t.repo, &[new.commit_id, new.commit_id]).unwrap();
+    }
+
+    #[test]
+    fn failure_in_one_revision_writes_nothing() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("a.txt", "a\n").unwrap();
+        let first = t.commit("first").unwrap().created;
+        t.write_file("b.txt", "b\n").unwrap();
+        let second = t.commit("second").unwrap().created;
+
+        let missing = CommitId::from(git2::Oid::from_bytes(&[0xab; 20]).unwrap());
+        let err = mark_range_reviewed(&t.repo, &[first.commit_id, missing, second.commit_id])
+            .unwrap_err();
+        let Error::Revision { sha, .. } = err else {
+            panic!("expected a revision error, got {err:?}");
+        };
+        assert_eq!(sha, missing);
+
+        for sha in [first.commit_id, second.commit_id] {
+            let (_, files) = diff::generate_file_list(&t.repo, sha, DiffConfig::default()).unwrap();
+            for file in files {
+                assert_eq!(file.review_status, ReviewStatus::Unreviewed);
+            }
+        }
+    }
+
+    #[test]
+    fn set_files_reviewed_marks_and_unmarks() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("old.txt", "1\n2\n3\n4\n5\n6\n7\n8\n").unwrap();
+        t.commit("base").unwrap();
+        t.write_file("a.txt", "a\n").unwrap();
+        t.write_file("b.txt", "b\n").unwrap();
+        t.rename_file("old.txt", "new.txt").unwrap();
+        let sha = t.commit("change").unwrap().created.commit_id;
+        let status = |path: &str| {
+            let (_, files) = diff::generate_file_list(&t.repo, sha, DiffConfig::default()).unwrap();
+            files
+                .into_iter()
+                .find(|f| f.new_path.as_deref() == Some(path))
+                .unwrap()
+                .review_status
+        };
+
+        let paths = [PathBuf::from("a.txt"), PathBuf::from("old.txt")];
+        set_files_reviewed(&t.repo, sha, &paths, true).unwrap();
+        assert_eq!(status("a.txt"), ReviewStatus::Reviewed);
+        assert_eq!(status("new.txt"), ReviewStatus::Reviewed);
+        assert_eq!(status("b.txt"), ReviewStatus::Unreviewed);
+
+        set_files_reviewed(&t.repo, sha, &[PathBuf::from("a.txt")], false).unwrap();
+        assert_eq!(status("a.txt"), ReviewStatus::Unreviewed);
+        assert_eq!(status("new.txt"), ReviewStatus::Reviewed);
+    }
+
+    #[test]
+    fn set_files_reviewed_rejects_unchanged_paths() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("a.txt", "a\n").unwrap();
+        let change = t.commit("change").unwrap().created;
+
+        let paths = [PathBuf::from("a.txt"), PathBuf::from("missing.txt")];
+        let err = set_files_reviewed(&t.repo, change.commit_id, &paths, true).unwrap_err();
+        assert!(
+            matches!(&err, Error::FileNotInChange { path, .. } if path == Path::new("missing.txt")),
+            "{err:?}"
+        );
+        assert!(!MarkerCommit::exists(&t.repo, change.change_id).unwrap());
+    }
+
+    #[test]
+    fn conflicted_files_are_never_marked() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("a.txt", "a\n").unwrap();
+        t.write_file(
+            "conflicted.txt",
+            "<<<<<<< Conflict 1 of 1\na\n>>>>>>> Conflict 1 of 1 ends\n",
+        )
+        .unwrap();
+        let change = t.commit("change").unwrap().created;
+        let sha = change.commit_id;
+
+        let paths = [PathBuf::from("a.txt"), PathBuf::from("conflicted.txt")];
+        let err = set_files_reviewed(&t.repo, sha, &paths, true).unwrap_err();
+        assert!(
+            matches!(err, Error::Diff(diff::Error::UnresolvedConflict(_))),
+            "{err:?}"
+        );
+        let err = mark_range_reviewed(&t.repo, &[sha]).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                Error::Revision {
+                    source: diff::Error::UnresolvedConflict(_),
+                    ..
+                }
+            ),
+            "{err:?}"
+        );
+        assert!(!MarkerCommit::exists(&t.repo, change.change_id).unwrap());
+
+        let mut marker = MarkerCommit::get(&t.repo, sha).unwrap();
+        let err = set_reviewed_content(
+            &mut marker,
+            Path::new("conflicted.txt"),
+            None,
+            b"<<<<<<< Conflict 1 of 1\n",
+        );
+        assert!(matches!(err, Err(diff::Error::UnresolvedConflict(_))));
+        set_reviewed_content(&mut marker, Path::new("conflicted.txt"), None, b"a\n").unwrap();
+    }
+
+    #[test]
+    fn review_state_lists_files_by_status() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("c.txt", "1\n2\n").unwrap();
+        t.commit("base").unwrap();
+        t.write_file("a.txt", "a\n").unwrap();
+        t.write_file("b.txt", "b\n").unwrap();
+        t.write_file("c.txt", "one\ntwo\n").unwrap();
+        let sha = t.commit("change").unwrap().created.commit_id;
+
+        let mut marker = MarkerCommit::get(&t.repo, sha).unwrap();
+        marker.mark_file_reviewed(Path::new("a.txt"), None).unwrap();
+        marker
+            .set_blob(Path::new("c.txt"), None, b"one\n2\n")
+            .unwrap();
+        marker.write().unwrap();
+        drop(marker);
+
+        let state = change_review_state(&t.repo, sha).unwrap();
+        assert_eq!(state.reviewed, ["a.txt"]);
+        assert_eq!(state.partially_reviewed, ["c.txt"]);
+        assert_eq!(state.unreviewed, ["b.txt"]);
+        assert_eq!(state.progress.reviewed_files, 1);
+        assert_eq!(state.progress.total_files(), 3);
+        assert!(!state.is_complete());
+
+        set_files_reviewed(
+            &t.repo,
+            sha,
+            &[PathBuf::from("b.txt"), PathBuf::from("c.txt")],
+            true,
+        )
+        .unwrap();
+        assert!(change_review_state(&t.repo, sha).unwrap().is_complete());
+    }
+
+    #[test]
+    fn exported_state_round_trips_after_refs_are_deleted() {
+        let t = TestRepo::new().unwrap();
+        t.write_file("a.txt", "a\n").unwrap();
+        t.write_file("b.txt", "b\n").unwrap();
+        let created = t.commit("first").unwrap().created;
+        let sha = created.commit_id;
+
+        let mut marker = MarkerCommit::get(&t.repo, sha).unwrap();
+        marker.mark_file_reviewed(Path::new("a.txt"), None).unwrap();
+        marker.write().unwrap();
+        drop(marker);
+        let mut comments = CommentCommit::get(&t.repo, sha).unwrap();
+        comments
+            .create_comment(
+                Path::new("a.txt"),
+                NewComment {
+                    sha,
+                    side: DiffSide::New,
+                    line: 1,
+                    start_line: None,
+                    body: "nit".into(),
+                    author: "alice".into(),
+                },
+            )
+            .unwrap();
+        comments.write().unwrap();
+        drop(comments);
+
+        let json = serde_json::to_string(&export_state(&t.repo, sha).unwrap()).unwrap();
+
+        MarkerCommit::delete(&t.repo, created.change_id).unwrap();
+        t.repo
+            .find_reference(&format!("refs/kenjutu/{}/comments", created.change_id))
+            .unwrap()
+            .delete()
+            .unwrap();
+
+        let state: ReviewStateJson = serde_json::from_str(&json).unwrap();
+        import_state(&t.repo, sha, state.clone()).unwrap();
+        // A second import must not duplicate comments.
+        import_state(&t.repo, sha, state).unwrap();
+
+        let (_, files) = diff::generate_file_list(&t.repo, sha, DiffConfig::default()).unwrap();
+        let status = |path: &str| {
+            files
+                .iter()
+                .find(|f| f.new_path.as_deref() == Some(path))
+                .map(|f| f.review_status.clone())
+        };
+        assert_eq!(status("a.txt"), Some(ReviewStatus::Reviewed));
+        assert_eq!(status("b.txt"), Some(ReviewStatus::Unreviewed));
+
+        let comments = CommentCommit::get(&t.repo, sha).unwrap();
+        assert_eq!(comments.get_file_comments(Path::new("a.txt")).len(), 1);
+    }
+}
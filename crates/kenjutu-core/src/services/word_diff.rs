@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 
 use similar::{Algorithm, ChangeTag, DiffOp, TextDiff, capture_diff_slices};
 
@@ -27,6 +27,10 @@ pub struct WordDiffResult {
     pub deletions: BTreeMap<u32, LineDiffInfo>,
     /// new line number → (paired old line number, byte ranges inserted)
     pub insertions: BTreeMap<u32, LineDiffInfo>,
+    /// Old line numbers whose content reappears unchanged as an addition in the same hunk.
+    pub moved_deletions: BTreeSet<u32>,
+    /// New line numbers whose content was deleted elsewhere in the same hunk.
+    pub moved_insertions: BTreeSet<u32>,
 }
 
 /// Change ranges (byte offsets) for old and new lines.
@@ -212,15 +216,53 @@ fn match_block_lines(old_lines: &[SideLine], new_lines: &[SideLine]) -> Vec<(usi
     pairs
 }
 
+/// Match deleted lines to added lines with identical content anywhere in the hunk.
+///
+/// Each added line consumes at most one deleted line, earliest first. Lines without any
+/// alphanumeric character (blank lines, lone braces) are too common to count as moves.
+fn find_moved_lines(blocks: &[Block]) -> (BTreeSet<u32>, BTreeSet<u32>) {
+    let is_significant = |content: &str| content.chars().any(char::is_alphanumeric);
+
+    let mut deleted: HashMap<&str, VecDeque<u32>> = HashMap::new();
+    for line in blocks.iter().flat_map(|b| &b.old_lines) {
+        let content = line.content.trim_end();
+        if is_significant(content) {
+            deleted.entry(content).or_default().push_back(line.lineno);
+        }
+    }
+
+    let mut moved_deletions = BTreeSet::new();
+    let mut moved_insertions = BTreeSet::new();
+    for line in blocks.iter().flat_map(|b| &b.new_lines) {
+        if let Some(old_lineno) = deleted
+            .get_mut(line.content.trim_end())
+            .and_then(VecDeque::pop_front)
+        {
+            moved_deletions.insert(old_lineno);
+            moved_insertions.insert(line.lineno);
+        }
+    }
+    (moved_deletions, moved_insertions)
+}
+
 pub fn compute_word_diff(source: &impl HunkLines) -> WordDiffResult {
     let mut deletions: BTreeMap<u32, LineDiffInfo> = BTreeMap::new();
     let mut insertions: BTreeMap<u32, LineDiffInfo> = BTreeMap::new();
 
-    for block in source.blocks() {
+    let blocks = source.blocks();
+    let (moved_deletions, moved_insertions) = find_moved_lines(&blocks);
+
+    for block in &blocks {
         let pairs = match_block_lines(&block.old_lines, &block.new_lines);
         for (old_idx, new_idx) in pairs {
             let old_line = &block.old_lines[old_idx];
             let new_line = &block.new_lines[new_idx];
+            // A moved line is shown whole, not word-diffed against a neighbour.
+            if moved_deletions.contains(&old_line.lineno)
+                || moved_insertions.contains(&new_line.lineno)
+            {
+                continue;
+            }
             let ranges = compute_inline_diff(&old_line.content, &new_line.content);
             deletions.insert(old_line.lineno, (new_line.lineno, ranges.old_ranges));
             insertions.insert(new_line.lineno, (old_line.lineno, ranges.new_ranges));
@@ -230,6 +272,8 @@ pub fn compute_word_diff(source: &impl HunkLines) -> WordDiffResult {
     WordDiffResult {
         deletions,
         insertions,
+        moved_deletions,
+        moved_insertions,
     }
 }
 
@@ -548,4 +592,52 @@ mod tests {
             "new line 21 ('zzz') is a pure insertion, no word diff"
         );
     }
+
+    #[test]
+    fn word_diff_reordered_lines_are_moved() {
+        // Swapping two lines: git shows `-let a`, ` let b`, `+let a`.
+        let mock = MockHunk {
+            blocks: vec![
+                Block {
+                    old_lines: vec![line(1, "let a = 1;\n")],
+                    new_lines: vec![],
+                },
+                Block {
+                    old_lines: vec![],
+                    new_lines: vec![line(2, "let a = 1;\n")],
+                },
+            ],
+        };
+        let result = compute_word_diff(&mock);
+        assert!(result.moved_deletions.contains(&1));
+        assert!(result.moved_insertions.contains(&2));
+    }
+
+    #[test]
+    fn word_diff_swapped_pair_in_one_block_is_moved() {
+        let mock = MockHunk {
+            blocks: vec![Block {
+                old_lines: vec![line(1, "first();"), line(2, "second();")],
+                new_lines: vec![line(1, "second();"), line(2, "first();")],
+            }],
+        };
+        let result = compute_word_diff(&mock);
+        assert_eq!(result.moved_deletions, BTreeSet::from([1, 2]));
+        assert_eq!(result.moved_insertions, BTreeSet::from([1, 2]));
+        assert!(result.deletions.is_empty());
+        assert!(result.insertions.is_empty());
+    }
+
+    #[test]
+    fn word_diff_braces_are_not_moved() {
+        let mock = MockHunk {
+            blocks: vec![Block {
+                old_lines: vec![line(1, "}")],
+                new_lines: vec![line(3, "}")],
+            }],
+        };
+        let result = compute_word_diff(&mock);
+        assert!(result.moved_deletions.is_empty());
+        assert!(result.moved_insertions.is_empty());
+    }
 }
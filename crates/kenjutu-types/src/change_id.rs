@@ -2,18 +2,27 @@ use std::str::FromStr;
 
 use git2::Commit;
 
-#[derive(Debug)]
-pub struct InvalidChangeIdError {
-    received: String,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidChangeIdError {
+    WrongLength { received: String },
+    InvalidCharacter { received: String, character: char },
 }
 
 impl std::fmt::Display for InvalidChangeIdError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Invalid ChangeId: expected a 32-character string, got '{}'",
-            self.received
-        )
+        match self {
+            Self::WrongLength { received } => write!(
+                f,
+                "Invalid ChangeId: expected a 32-character string, got '{received}'"
+            ),
+            Self::InvalidCharacter {
+                received,
+                character,
+            } => write!(
+                f,
+                "Invalid ChangeId: '{character}' is not in jj's change id alphabet (k-z), got '{received}'"
+            ),
+        }
     }
 }
 
@@ -49,14 +58,22 @@ impl std::fmt::Display for ChangeId {
     }
 }
 
-impl FromStr for ChangeId {
-    type Err = InvalidChangeIdError;
+impl ChangeId {
+    /// Abbreviation used in lists and headers; the commit graph shows change ids this long.
+    pub const SHORT_LEN: usize = 8;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    /// Parse a full-length jj change id, rejecting anything outside jj's reverse-hex alphabet.
+    pub fn parse(s: &str) -> Result<Self, InvalidChangeIdError> {
         let bytes = s.as_bytes();
         if bytes.len() != 32 {
-            return Err(InvalidChangeIdError {
+            return Err(InvalidChangeIdError::WrongLength {
+                received: s.to_string(),
+            });
+        }
+        if let Some(character) = s.chars().find(|c| !matches!(c, 'k'..='z')) {
+            return Err(InvalidChangeIdError::InvalidCharacter {
                 received: s.to_string(),
+                character,
             });
         }
 
@@ -64,6 +81,19 @@ impl FromStr for ChangeId {
         arr.copy_from_slice(bytes);
         Ok(Self(arr))
     }
+
+    /// The first `len` reverse-hex characters, or the whole id if it is shorter.
+    pub fn short(&self, len: usize) -> String {
+        String::from_utf8_lossy(&self.0[..len.min(self.0.len())]).into_owned()
+    }
+}
+
+impl FromStr for ChangeId {
+    type Err = InvalidChangeIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
 }
 
 impl TryFrom<&str> for ChangeId {
@@ -133,3 +163,20 @@ fn synthetic_change_id(sha: git2::Oid) -> ChangeId {
     }
     ChangeId::from(encoded)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_is_a_prefix_of_the_full_id() {
+        let full = "kmzvxtqxqlvsoyrzrnnuptkpqvoozwqx";
+        let id = ChangeId::parse(full).unwrap();
+        for len in [0, ChangeId::SHORT_LEN, 12, 32] {
+            let short = id.short(len);
+            assert_eq!(short.len(), len);
+            assert!(full.starts_with(&short));
+        }
+        assert_eq!(id.short(100), full);
+    }
+}
@@ -2,6 +2,23 @@ use std::str::FromStr;
 
 use git2::Oid;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidCommitIdError {
+    received: String,
+}
+
+impl std::fmt::Display for InvalidCommitIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid CommitId: expected a 40-character hex string, got '{}'",
+            self.received
+        )
+    }
+}
+
+impl std::error::Error for InvalidCommitIdError {}
+
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
 pub struct CommitId(Oid);
 
@@ -18,9 +35,31 @@ impl std::fmt::Display for CommitId {
 }
 
 impl CommitId {
+    /// Abbreviation used in lists and headers, matching jj's `short()` for commit ids.
+    pub const SHORT_LEN: usize = 12;
+
+    /// Parse a full-length hex SHA. Unlike `Oid::from_str`, abbreviated ids are rejected rather
+    /// than zero-padded.
+    pub fn parse(s: &str) -> Result<Self, InvalidCommitIdError> {
+        let invalid = || InvalidCommitIdError {
+            received: s.to_string(),
+        };
+        if s.len() != 40 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        Oid::from_str(s).map(Self).map_err(|_| invalid())
+    }
+
     pub fn oid(self) -> Oid {
         self.0
     }
+
+    /// The first `len` hex characters, or the whole id if it is shorter.
+    pub fn short(self, len: usize) -> String {
+        let mut hex = self.0.to_string();
+        hex.truncate(len);
+        hex
+    }
 }
 
 impl From<Oid> for CommitId {
@@ -36,10 +75,10 @@ impl From<CommitId> for Oid {
 }
 
 impl FromStr for CommitId {
-    type Err = git2::Error;
+    type Err = InvalidCommitIdError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Oid::from_str(s).map(Self)
+        Self::parse(s)
     }
 }
 
@@ -54,9 +93,7 @@ impl serde::Serialize for CommitId {
 impl<'de> serde::Deserialize<'de> for CommitId {
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         let s = String::deserialize(deserializer)?;
-        Oid::from_str(&s)
-            .map(Self)
-            .map_err(serde::de::Error::custom)
+        Self::parse(&s).map_err(serde::de::Error::custom)
     }
 }
 
@@ -69,3 +106,20 @@ impl specta::Type for CommitId {
         specta::datatype::DataType::Primitive(specta::datatype::PrimitiveType::String)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_is_a_prefix_of_the_full_id() {
+        let full = "0123456789abcdef0123456789abcdef01234567";
+        let id = CommitId::parse(full).unwrap();
+        for len in [0, 8, CommitId::SHORT_LEN, 40] {
+            let short = id.short(len);
+            assert_eq!(short.len(), len);
+            assert!(full.starts_with(&short));
+        }
+        assert_eq!(id.short(100), full);
+    }
+}
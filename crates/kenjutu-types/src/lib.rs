@@ -2,4 +2,4 @@ mod change_id;
 mod commit_id;
 
 pub use change_id::{ChangeId, CommitChangeIdExt, InvalidChangeIdError};
-pub use commit_id::CommitId;
+pub use commit_id::{CommitId, InvalidCommitIdError};
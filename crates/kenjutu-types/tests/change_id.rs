@@ -1,6 +1,6 @@
 use std::{path::Path, process::Command};
 
-use kenjutu_types::{ChangeId, CommitChangeIdExt};
+use kenjutu_types::{ChangeId, CommitChangeIdExt, InvalidChangeIdError};
 use test_repo::TestRepo;
 
 fn jj_change_id(dir: &Path, sha: &str) -> ChangeId {
@@ -36,3 +36,24 @@ fn test_fallback_change_id_matches_jj_generates() {
     let ours = commit.change_id();
     assert_eq!(from_jj, ours);
 }
+
+#[test]
+fn test_parse_accepts_jj_change_id() {
+    let id = ChangeId::parse("zyxwvutsrqponmlkzyxwvutsrqponmlk").unwrap();
+    assert_eq!(id.to_string(), "zyxwvutsrqponmlkzyxwvutsrqponmlk");
+}
+
+#[test]
+fn test_parse_rejects_wrong_length() {
+    let err = ChangeId::parse("zyxwvuts").unwrap_err();
+    assert!(matches!(err, InvalidChangeIdError::WrongLength { .. }));
+}
+
+#[test]
+fn test_parse_rejects_characters_outside_alphabet() {
+    let err = ChangeId::parse("zyxwvutsrqponmlkzyxwvutsrqponmla").unwrap_err();
+    assert!(matches!(
+        err,
+        InvalidChangeIdError::InvalidCharacter { character: 'a', .. }
+    ));
+}
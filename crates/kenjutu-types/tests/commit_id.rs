@@ -0,0 +1,17 @@
+use kenjutu_types::CommitId;
+
+#[test]
+fn test_parse_accepts_full_sha() {
+    let sha = "0123456789abcdef0123456789abcdef01234567";
+    assert_eq!(CommitId::parse(sha).unwrap().to_string(), sha);
+}
+
+#[test]
+fn test_parse_rejects_abbreviated_sha() {
+    assert!(CommitId::parse("0123456").is_err());
+}
+
+#[test]
+fn test_parse_rejects_non_hex() {
+    assert!(CommitId::parse("0123456789abcdef0123456789abcdef0123456g").is_err());
+}
@@ -0,0 +1,23 @@
+use kenjutu_types::{ChangeId, CommitId};
+
+#[test]
+fn test_change_id_serializes_as_string() {
+    let id = ChangeId::parse("zyxwvutsrqponmlkzyxwvutsrqponmlk").unwrap();
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(json, r#""zyxwvutsrqponmlkzyxwvutsrqponmlk""#);
+    assert_eq!(serde_json::from_str::<ChangeId>(&json).unwrap(), id);
+}
+
+#[test]
+fn test_change_id_rejects_invalid_string() {
+    assert!(serde_json::from_str::<ChangeId>(r#""abc""#).is_err());
+}
+
+#[test]
+fn test_commit_id_serializes_as_string() {
+    let sha = "0123456789abcdef0123456789abcdef01234567";
+    let id = CommitId::parse(sha).unwrap();
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(json, format!("\"{sha}\""));
+    assert_eq!(serde_json::from_str::<CommitId>(&json).unwrap(), id);
+}
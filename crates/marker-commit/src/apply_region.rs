@@ -1,3 +1,7 @@
+use std::cmp::Reverse;
+
+use crate::{Error, Result};
+
 /// Identifies a region in a diff by its header coordinates.
 /// Coordinates are 1-based, matching the `@@ -old_start,old_lines +new_start,new_lines @@` header.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -84,6 +88,101 @@ pub(crate) fn unapply_region(m_content: &str, b_content: &str, region: &RegionId
     result
 }
 
+/// Apply several regions from the same `diff(M→T)` to M in one pass.
+///
+/// All regions must come from a single snapshot of `diff(M, T)`. They are spliced bottom-to-top
+/// so earlier splices don't shift the M coordinates of the regions above them.
+pub(crate) fn apply_regions(
+    m_content: &str,
+    t_content: &str,
+    regions: &[RegionId],
+) -> Result<String> {
+    let ordered = order_bottom_up(
+        regions,
+        |r| (r.old_start, r.old_lines),
+        |r| (r.new_start, r.new_lines),
+        split_lines_inclusive(m_content).len(),
+        split_lines_inclusive(t_content).len(),
+    )?;
+    let mut content = m_content.to_owned();
+    for region in ordered {
+        content = apply_region(&content, t_content, region);
+    }
+    Ok(content)
+}
+
+/// Reverse several regions from the same `diff(B→M)` out of M in one pass.
+///
+/// The counterpart of [`apply_regions`]; regions are ordered bottom-to-top by their M coordinates.
+pub(crate) fn unapply_regions(
+    m_content: &str,
+    b_content: &str,
+    regions: &[RegionId],
+) -> Result<String> {
+    let ordered = order_bottom_up(
+        regions,
+        |r| (r.new_start, r.new_lines),
+        |r| (r.old_start, r.old_lines),
+        split_lines_inclusive(m_content).len(),
+        split_lines_inclusive(b_content).len(),
+    )?;
+    let mut content = m_content.to_owned();
+    for region in ordered {
+        content = unapply_region(&content, b_content, region);
+    }
+    Ok(content)
+}
+
+/// Sort regions bottom-to-top in M, rejecting any that overlap or fall outside either blob.
+///
+/// `m_side` and `src_side` select the `(start, lines)` header pair for M and for the blob the
+/// replacement lines are taken from.
+fn order_bottom_up(
+    regions: &[RegionId],
+    m_side: fn(&RegionId) -> (u32, u32),
+    src_side: fn(&RegionId) -> (u32, u32),
+    m_len: usize,
+    src_len: usize,
+) -> Result<Vec<&RegionId>> {
+    let mut spans = Vec::with_capacity(regions.len());
+    for region in regions {
+        let (m_start, m_lines) = m_side(region);
+        let (src_start, src_lines) = src_side(region);
+        match (
+            line_span(m_start, m_lines, true),
+            line_span(src_start, src_lines, false),
+        ) {
+            (Some(m), Some(src)) if m.1 <= m_len && src.1 <= src_len => spans.push((m, region)),
+            _ => {
+                return Err(Error::Internal(format!(
+                    "region {region:?} is out of range"
+                )));
+            }
+        }
+    }
+
+    // Equal starts put the longer span first so a pure insertion lands above a replacement.
+    spans.sort_by_key(|(span, _)| Reverse(*span));
+    for pair in spans.windows(2) {
+        let ((upper, a), (lower, b)) = (pair[0], pair[1]);
+        if lower.1 > upper.0 {
+            return Err(Error::Internal(format!("regions {a:?} and {b:?} overlap")));
+        }
+    }
+    Ok(spans.into_iter().map(|(_, region)| region).collect())
+}
+
+/// 0-based half-open line range covered by a `start,lines` header pair.
+/// On the side being spliced into, an empty range sits *after* line `start`.
+fn line_span(start: u32, lines: u32, insert_after: bool) -> Option<(usize, usize)> {
+    let begin = match (lines, insert_after) {
+        (0, true) => start as usize,
+        (0, false) => 0,
+        _ => start.checked_sub(1)? as usize,
+    };
+    Some((begin, begin + lines as usize))
+}
+
 /// Splits `s` into lines preserving their terminators (`\n` or `\r\n`).
 /// Returns an empty vec for an empty string.
 fn split_lines_inclusive(s: &str) -> Vec<&str> {
@@ -289,4 +388,61 @@ mod tests {
         );
         assert_eq!(lines[5], "b1", "region2 should be reverted");
     }
+
+    #[test]
+    fn apply_regions_reaches_target_in_any_order() {
+        let forward = apply_regions(BASE, TARGET, &[region1(), region2()]).unwrap();
+        let reverse = apply_regions(BASE, TARGET, &[region2(), region1()]).unwrap();
+        assert_eq!(forward, TARGET);
+        assert_eq!(reverse, TARGET);
+    }
+
+    #[test]
+    fn apply_regions_insertion_and_replacement_at_same_line() {
+        // diff(M→T): @@ -2,0 +3,1 @@ inserts "ins" after line 2,
+        //            @@ -3,1 +4,1 @@ replaces line 3 right below it.
+        let m = "line1\nline2\nline3\n";
+        let t = "line1\nline2\nins\nLINE3\n";
+        let insertion = RegionId {
+            old_start: 2,
+            old_lines: 0,
+            new_start: 3,
+            new_lines: 1,
+        };
+        let replacement = RegionId {
+            old_start: 3,
+            old_lines: 1,
+            new_start: 4,
+            new_lines: 1,
+        };
+        assert_eq!(apply_regions(m, t, &[insertion, replacement]).unwrap(), t);
+    }
+
+    #[test]
+    fn unapply_regions_restores_base() {
+        let restored = unapply_regions(TARGET, BASE, &[region1(), region2()]).unwrap();
+        assert_eq!(restored, BASE);
+    }
+
+    #[test]
+    fn apply_regions_rejects_overlap() {
+        let overlapping = RegionId {
+            old_start: 3,
+            old_lines: 3,
+            new_start: 3,
+            new_lines: 3,
+        };
+        assert!(apply_regions(BASE, TARGET, &[region1(), overlapping]).is_err());
+    }
+
+    #[test]
+    fn apply_regions_rejects_out_of_range() {
+        let past_end = RegionId {
+            old_start: 6,
+            old_lines: 3,
+            new_start: 6,
+            new_lines: 3,
+        };
+        assert!(apply_regions(BASE, TARGET, &[past_end]).is_err());
+    }
 }
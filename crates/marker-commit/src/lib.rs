@@ -4,12 +4,14 @@ mod marker_commit;
 mod marker_commit_lock;
 mod materialize_tree;
 mod octopus_merge;
+mod review_progress;
 mod tree_builder_ext;
 
 pub use apply_region::RegionId;
 pub use kenjutu_types::{ChangeId, CommitId};
-pub use marker_commit::MarkerCommit;
+pub use marker_commit::{MarkerCommit, MarkerEntry, calculate_base_tree, enumerate_marker_refs};
 pub use materialize_tree::materialize_tree;
+pub use review_progress::ReviewProgress;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -32,6 +34,8 @@ pub enum Error {
         path: String,
         old_path: Option<String>,
     },
+    #[error("Cannot mark regions of {path}: content is not valid UTF-8; mark the whole file")]
+    NotUtf8 { path: String },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -1,15 +1,27 @@
 use crate::{
     ChangeId, CommitId, Error, RegionId, Result,
-    apply_region::{apply_region, unapply_region},
+    apply_region::{apply_regions, unapply_regions},
     conflict::resolve_conflict_prefer_our,
     marker_commit_lock::MarkerCommitLock,
     materialize_tree::materialize_tree,
     octopus_merge::octopus_merge,
+    review_progress::{ReviewProgress, review_progress},
     tree_builder_ext::TreeBuilderExt,
 };
 use git2::{Commit, Oid, Repository, Signature, Tree};
 use kenjutu_types::CommitChangeIdExt;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// A file whose marker content differs from the base tree, i.e. something the reviewer has
+/// accepted. Together the entries of a marker describe its review state independently of
+/// the objects in any one repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkerEntry {
+    pub path: PathBuf,
+    /// Blob content in the marker tree, or `None` when the reviewer accepted a deletion.
+    pub content: Option<Vec<u8>>,
+    pub filemode: i32,
+}
 
 /// Commit for tracking review state for a specific revision.
 /// Stored at refs/kenjutu/{change_id}/marker pointing to the commit being reviewed.
@@ -35,14 +47,33 @@ impl<'a> std::fmt::Debug for MarkerCommit<'a> {
 impl<'a> MarkerCommit<'a> {
     pub fn get(repo: &'a Repository, sha: CommitId) -> Result<Self> {
         let target_commit = repo.find_commit(sha.oid())?;
+        let lock_file = MarkerCommitLock::new(repo, target_commit.change_id())?;
+        Self::load(repo, sha, &target_commit, lock_file)
+    }
+
+    /// Like [`MarkerCommit::get`], but returns `Ok(None)` instead of blocking when another
+    /// review operation holds the lock for this change.
+    pub fn try_get(repo: &'a Repository, sha: CommitId) -> Result<Option<Self>> {
+        let target_commit = repo.find_commit(sha.oid())?;
+        let Some(lock_file) = MarkerCommitLock::try_new(repo, target_commit.change_id())? else {
+            return Ok(None);
+        };
+        Self::load(repo, sha, &target_commit, lock_file).map(Some)
+    }
+
+    fn load(
+        repo: &'a Repository,
+        sha: CommitId,
+        target_commit: &Commit<'a>,
+        lock_file: MarkerCommitLock,
+    ) -> Result<Self> {
         let change_id = target_commit.change_id();
-        let lock_file = MarkerCommitLock::new(repo, change_id)?;
         log::info!(
             "acquired lock for marker commit for revision: {}",
             change_id
         );
 
-        let new_base_tree = calculate_base_tree(repo, &target_commit)?;
+        let new_base_tree = calculate_base_tree(repo, target_commit)?;
 
         let ref_name = marker_commit_ref_name(change_id);
         let marker_tree = match repo.find_reference(&ref_name) {
@@ -89,7 +120,7 @@ impl<'a> MarkerCommit<'a> {
             _guard: lock_file,
             tree: marker_tree,
             base_tree: new_base_tree,
-            target_tree: materialize_tree(repo, &target_commit)?,
+            target_tree: materialize_tree(repo, target_commit)?,
             repo,
             change_id,
             commit_id: sha,
@@ -108,6 +139,12 @@ impl<'a> MarkerCommit<'a> {
         &self.target_tree
     }
 
+    /// Summarize how much of the revision has been reviewed, comparing the marker tree against
+    /// the base and target trees.
+    pub fn review_progress(&self) -> Result<ReviewProgress> {
+        review_progress(self.repo, &self.base_tree, &self.tree, &self.target_tree)
+    }
+
     /// Mark a single region as reviewed by splicing the corresponding target lines into the marker blob.
     ///
     /// `region` coordinates must be in M/T space, as they appear in `diff(marker, target)`.
@@ -121,6 +158,20 @@ impl<'a> MarkerCommit<'a> {
         file_path: &Path,
         old_path: Option<&Path>,
         region: &RegionId,
+    ) -> Result<()> {
+        self.mark_regions_reviewed(file_path, old_path, std::slice::from_ref(region))
+    }
+
+    /// Mark several regions of one file as reviewed in a single splice.
+    ///
+    /// All `regions` must be taken from the same `diff(marker, target)` snapshot; there is no
+    /// need to recompute coordinates between them. The marker tree is left untouched if any
+    /// region overlaps another or falls outside the file.
+    pub fn mark_regions_reviewed(
+        &mut self,
+        file_path: &Path,
+        old_path: Option<&Path>,
+        regions: &[RegionId],
     ) -> Result<()> {
         let ext = TreeBuilderExt::new(self.repo);
 
@@ -139,10 +190,11 @@ impl<'a> MarkerCommit<'a> {
 
         let m_content_mode = blob_content_and_mode(&self.tree, m_lookup, self.repo)?;
         let t_content_mode = blob_content_and_mode(&self.target_tree, file_path, self.repo)?;
-        let (m_content, t_content, filemode) = match (m_content_mode, t_content_mode) {
-            (Some((m_blob, filemode)), Some((t_blob, _))) => (m_blob, t_blob, filemode),
-            (None, Some((t_blob, filemode))) => (String::new(), t_blob, filemode),
-            (Some((m_blob, filemode)), None) => (m_blob, String::new(), filemode),
+        let (m_content, t_content, m_filemode, t_filemode) = match (m_content_mode, t_content_mode)
+        {
+            (Some((m_blob, m_mode)), Some((t_blob, t_mode))) => (m_blob, t_blob, m_mode, t_mode),
+            (None, Some((t_blob, t_mode))) => (String::new(), t_blob, t_mode, t_mode),
+            (Some((m_blob, m_mode)), None) => (m_blob, String::new(), m_mode, m_mode),
             (None, None) => {
                 return Err(Error::FileNotFound {
                     path: file_path.to_string_lossy().to_string(),
@@ -151,7 +203,14 @@ impl<'a> MarkerCommit<'a> {
             }
         };
 
-        let new_content = apply_region(&m_content, &t_content, region);
+        let new_content = apply_regions(&m_content, &t_content, regions)?;
+        // Once every region is in, take the target's mode as well so a typechange (e.g. a file
+        // that became a symlink) is fully reviewed rather than stuck with the base mode.
+        let filemode = if new_content == t_content {
+            t_filemode
+        } else {
+            m_filemode
+        };
         let new_oid = self.repo.blob(new_content.as_bytes())?;
 
         if rename_pending {
@@ -179,6 +238,19 @@ impl<'a> MarkerCommit<'a> {
         file_path: &Path,
         old_path: Option<&Path>,
         region: &RegionId,
+    ) -> Result<()> {
+        self.unmark_regions_reviewed(file_path, old_path, std::slice::from_ref(region))
+    }
+
+    /// Unmark several regions of one file in a single splice.
+    ///
+    /// All `regions` must be taken from the same `diff(base, marker)` snapshot. The marker tree
+    /// is left untouched if any region overlaps another or falls outside the file.
+    pub fn unmark_regions_reviewed(
+        &mut self,
+        file_path: &Path,
+        old_path: Option<&Path>,
+        regions: &[RegionId],
     ) -> Result<()> {
         let ext = TreeBuilderExt::new(self.repo);
 
@@ -193,17 +265,14 @@ impl<'a> MarkerCommit<'a> {
             match self.base_tree.get_path(b_lookup) {
                 Ok(entry) => {
                     let blob = self.repo.find_blob(entry.id())?;
-                    let content = std::str::from_utf8(blob.content())
-                        .map_err(|e| Error::Internal(e.to_string()))?
-                        .to_owned();
-                    (content, true)
+                    (utf8_content(&blob, b_lookup)?, true)
                 }
                 Err(e) if e.code() == git2::ErrorCode::NotFound => (String::new(), false),
                 Err(e) => return Err(Error::Git(e)),
             }
         };
 
-        let new_content = unapply_region(&m_content, &b_content, region);
+        let new_content = unapply_regions(&m_content, &b_content, regions)?;
         if new_content.is_empty() && !file_in_base {
             let new_tree_oid = ext.remove_path(&self.tree, file_path)?;
             self.tree = self.repo.find_tree(new_tree_oid)?;
@@ -376,6 +445,65 @@ impl<'a> MarkerCommit<'a> {
         Ok(())
     }
 
+    /// Every file whose marker content differs from the base tree.
+    pub fn entries(&self) -> Result<Vec<MarkerEntry>> {
+        let diff = self
+            .repo
+            .diff_tree_to_tree(Some(&self.base_tree), Some(&self.tree), None)?;
+        let mut entries = Vec::new();
+        for delta in diff.deltas() {
+            let entry = if delta.status() == git2::Delta::Deleted {
+                let Some(path) = delta.old_file().path() else {
+                    continue;
+                };
+                MarkerEntry {
+                    path: path.to_path_buf(),
+                    content: None,
+                    filemode: delta.old_file().mode().into(),
+                }
+            } else {
+                let Some(path) = delta.new_file().path() else {
+                    continue;
+                };
+                let blob = self.repo.find_blob(delta.new_file().id())?;
+                MarkerEntry {
+                    path: path.to_path_buf(),
+                    content: Some(blob.content().to_vec()),
+                    filemode: delta.new_file().mode().into(),
+                }
+            };
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+
+    /// Replace the marker tree with the base tree plus `entries`, as produced by
+    /// [`MarkerCommit::entries`]. Any review state not described by `entries` is dropped.
+    pub fn restore_entries(&mut self, entries: &[MarkerEntry]) -> Result<()> {
+        let ext = TreeBuilderExt::new(self.repo);
+        let mut tree = self.base_tree.clone();
+        for entry in entries {
+            let tree_oid = match &entry.content {
+                Some(content) => {
+                    let blob_oid = self.repo.blob(content)?;
+                    ext.insert_file(&tree, &entry.path, blob_oid, entry.filemode)?
+                }
+                None => ext.remove_path(&tree, &entry.path)?,
+            };
+            tree = self.repo.find_tree(tree_oid)?;
+        }
+        self.tree = tree;
+        Ok(())
+    }
+
+    /// Replace the marker tree with an earlier one, e.g. [`MarkerCommit::marker_tree`] recorded
+    /// before a mark, to undo it. Writing afterwards appends a new marker commit; the ref's
+    /// history is never rewound.
+    pub fn restore_tree(&mut self, tree_id: Oid) -> Result<()> {
+        self.tree = self.repo.find_tree(tree_id)?;
+        Ok(())
+    }
+
     /// Write the review status to the repository. Should be called after marking files as
     /// reviewed.
     /// Return the `CommitId` of the marker commit.
@@ -405,13 +533,39 @@ impl<'a> MarkerCommit<'a> {
         Ok(CommitId::from(oid))
     }
 
+    /// Whether a marker commit has been written for `change_id`.
+    pub fn exists(repo: &Repository, change_id: ChangeId) -> Result<bool> {
+        match repo.find_reference(&marker_commit_ref_name(change_id)) {
+            Ok(_) => Ok(true),
+            Err(err) if err.code() == git2::ErrorCode::NotFound => Ok(false),
+            Err(err) => Err(Error::Git(err)),
+        }
+    }
+
+    /// Delete the marker commit ref for `change_id`, discarding its review state.
+    ///
+    /// Waits for the marker lock so an in-flight review isn't clobbered; the lock file is
+    /// removed when the guard drops. Deleting a change that has no marker is not an error.
+    pub fn delete(repo: &Repository, change_id: ChangeId) -> Result<()> {
+        let _guard = MarkerCommitLock::new(repo, change_id)?;
+        match repo.find_reference(&marker_commit_ref_name(change_id)) {
+            Ok(mut reference) => reference.delete()?,
+            Err(err) if err.code() == git2::ErrorCode::NotFound => {}
+            Err(err) => return Err(Error::Git(err)),
+        }
+        log::info!("deleted marker commit for {}", change_id);
+        Ok(())
+    }
+
     fn signature() -> Result<Signature<'static>> {
         let sig = Signature::now("kenjutu", "kenjutu@gmail.com")?;
         Ok(sig)
     }
 }
 
-fn calculate_base_tree<'a>(repo: &'a Repository, commit: &Commit<'a>) -> Result<Tree<'a>> {
+/// The tree a revision is reviewed against: the empty tree for a root commit, the parent's tree
+/// for a normal commit, and the octopus merge of all parents for a merge.
+pub fn calculate_base_tree<'a>(repo: &'a Repository, commit: &Commit<'a>) -> Result<Tree<'a>> {
     match commit.parent_count() {
         0 => {
             let empty_tree_oid = empty_tree(repo)?;
@@ -446,16 +600,48 @@ fn blob_content_and_mode(
     };
     let filemode = entry.filemode();
     let blob = repo.find_blob(entry.id())?;
-    let content = std::str::from_utf8(blob.content())
-        .map_err(|e| Error::Internal(e.to_string()))?
-        .to_owned();
-    Ok(Some((content, filemode)))
+    Ok(Some((utf8_content(&blob, path)?, filemode)))
+}
+
+/// Region splicing works on lines of text. Refuse anything else rather than writing a lossily
+/// converted copy of the file into the marker.
+fn utf8_content(blob: &git2::Blob<'_>, path: &Path) -> Result<String> {
+    std::str::from_utf8(blob.content())
+        .map(str::to_owned)
+        .map_err(|_| Error::NotUtf8 {
+            path: path.to_string_lossy().to_string(),
+        })
 }
 
 fn marker_commit_ref_name(change_id: ChangeId) -> String {
     format!("refs/kenjutu/{}/marker", change_id)
 }
 
+/// List every marker commit ref in the repository as `(change_id, marker commit oid)`.
+///
+/// Refs whose change_id segment doesn't parse are skipped, so stray refs under
+/// `refs/kenjutu/` never block cleanup of the valid ones.
+pub fn enumerate_marker_refs(repo: &Repository) -> Result<Vec<(ChangeId, Oid)>> {
+    let mut markers = Vec::new();
+    for reference in repo.references_glob("refs/kenjutu/*/marker")? {
+        let reference = reference?;
+        let Some(name) = reference.name() else {
+            continue;
+        };
+        let Some(oid) = reference.target() else {
+            continue;
+        };
+        let segment = name
+            .strip_prefix("refs/kenjutu/")
+            .and_then(|rest| rest.strip_suffix("/marker"));
+        match segment.map(str::parse::<ChangeId>) {
+            Some(Ok(change_id)) => markers.push((change_id, oid)),
+            _ => log::warn!("skipping unrecognized marker ref: {}", name),
+        }
+    }
+    Ok(markers)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -624,6 +810,139 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_try_get_returns_none_while_locked() -> Result {
+        let (repo, _, b) = setup_two_commits()?;
+
+        let held = MarkerCommit::get(&repo.repo, b.commit_id)?;
+        assert!(MarkerCommit::try_get(&repo.repo, b.commit_id)?.is_none());
+
+        drop(held);
+        assert!(MarkerCommit::try_get(&repo.repo, b.commit_id)?.is_some());
+        Ok(())
+    }
+
+    // ── enumerate / delete tests ──────────────────────────────────────
+
+    #[test]
+    fn enumerate_marker_refs_lists_written_markers() -> Result {
+        let (repo, a, b) = setup_two_commits()?;
+        assert!(enumerate_marker_refs(&repo.repo)?.is_empty());
+
+        let a_oid = MarkerCommit::get(&repo.repo, a.commit_id)?.write()?;
+        let b_oid = MarkerCommit::get(&repo.repo, b.commit_id)?.write()?;
+
+        let mut markers = enumerate_marker_refs(&repo.repo)?;
+        markers.sort_by_key(|(change_id, _)| change_id.to_string());
+        let mut expected = vec![(a.change_id, a_oid.oid()), (b.change_id, b_oid.oid())];
+        expected.sort_by_key(|(change_id, _)| change_id.to_string());
+        assert_eq!(markers, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn delete_removes_ref_and_lock_file() -> Result {
+        let (repo, a, b) = setup_two_commits()?;
+        MarkerCommit::get(&repo.repo, a.commit_id)?.write()?;
+        MarkerCommit::get(&repo.repo, b.commit_id)?.write()?;
+
+        MarkerCommit::delete(&repo.repo, b.change_id)?;
+
+        let markers = enumerate_marker_refs(&repo.repo)?;
+        assert_eq!(markers.len(), 1);
+        assert_eq!(markers[0].0, a.change_id);
+        assert!(
+            !MarkerCommitLock::lock_path(&repo.repo, b.change_id).exists(),
+            "lock file should be removed after delete"
+        );
+
+        // Deleting again is a no-op.
+        MarkerCommit::delete(&repo.repo, b.change_id)?;
+        Ok(())
+    }
+
+    #[test]
+    fn restore_entries_reproduces_marker_tree() -> Result {
+        let (repo, _, b) = setup_two_commits()?;
+        let mut marker = MarkerCommit::get(&repo.repo, b.commit_id)?;
+        marker.mark_file_reviewed(Path::new("test2"), None)?;
+        let entries = marker.entries()?;
+        let reviewed_tree = marker.marker_tree().id();
+        drop(marker);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, Path::new("test2"));
+        assert_eq!(entries[0].content.as_deref(), Some(&b"hello world"[..]));
+
+        let mut restored = MarkerCommit::get(&repo.repo, b.commit_id)?;
+        assert!(restored.entries()?.is_empty());
+        restored.restore_entries(&entries)?;
+        assert_eq!(restored.marker_tree().id(), reviewed_tree);
+        Ok(())
+    }
+
+    #[test]
+    fn restore_tree_undoes_mark() -> Result {
+        let (repo, _, b) = setup_two_commits()?;
+        let mut marker = MarkerCommit::get(&repo.repo, b.commit_id)?;
+        let before = marker.marker_tree().id();
+        marker.mark_file_reviewed(Path::new("test2"), None)?;
+        let first_write = marker.write()?;
+        drop(marker);
+
+        let mut marker = MarkerCommit::get(&repo.repo, b.commit_id)?;
+        assert_eq!(marker.review_progress()?.reviewed_files, 1);
+        marker.restore_tree(before)?;
+        let undo_write = marker.write()?;
+        drop(marker);
+
+        let marker = MarkerCommit::get(&repo.repo, b.commit_id)?;
+        assert_eq!(marker.marker_tree().id(), before);
+        assert_eq!(marker.review_progress()?.reviewed_files, 0);
+        assert_ne!(undo_write, first_write);
+        Ok(())
+    }
+
+    #[test]
+    fn mark_region_refuses_non_utf8_content() -> Result {
+        let repo = TestRepo::new()?;
+        repo.write_file("latin1.txt", b"caf\xe9\n")?;
+        repo.commit("base")?;
+        repo.write_file("latin1.txt", b"caf\xe9\nna\xefve\n")?;
+        let sha = repo.commit("append")?.created.commit_id;
+
+        let mut marker = MarkerCommit::get(&repo.repo, sha)?;
+        let before = marker.marker_tree().id();
+        let region = RegionId {
+            old_start: 1,
+            old_lines: 0,
+            new_start: 2,
+            new_lines: 1,
+        };
+        let err = marker
+            .mark_region_reviewed(Path::new("latin1.txt"), None, &region)
+            .unwrap_err();
+        assert!(matches!(err, Error::NotUtf8 { ref path } if path == "latin1.txt"));
+        assert_eq!(marker.marker_tree().id(), before);
+
+        // Whole-file marking copies the blob as is.
+        marker.mark_file_reviewed(Path::new("latin1.txt"), None)?;
+        assert!(does_oid_match(&marker, Path::new("latin1.txt")));
+        Ok(())
+    }
+
+    #[test]
+    fn exists_reflects_written_marker() -> Result {
+        let (repo, a, b) = setup_two_commits()?;
+        assert!(!MarkerCommit::exists(&repo.repo, b.change_id)?);
+
+        MarkerCommit::get(&repo.repo, b.commit_id)?.write()?;
+
+        assert!(MarkerCommit::exists(&repo.repo, b.change_id)?);
+        assert!(!MarkerCommit::exists(&repo.repo, a.change_id)?);
+        Ok(())
+    }
+
     // ── mark_file_reviewed tests ────────────────────────────────────────
     #[test]
     fn state_persists_after_write() -> Result {
@@ -687,6 +1006,30 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn mark_region_reviewed_takes_target_mode_for_typechange() -> Result {
+        let repo = TestRepo::new()?;
+        repo.write_file("link", "old\n")?;
+        repo.commit("commit A")?;
+        repo.symlink("link", "target.txt")?;
+        let b = repo.commit("commit B")?.created;
+
+        let mut marker = MarkerCommit::get(&repo.repo, b.commit_id)?;
+        let region = RegionId {
+            old_start: 1,
+            old_lines: 1,
+            new_start: 1,
+            new_lines: 1,
+        };
+        marker.mark_region_reviewed(Path::new("link"), None, &region)?;
+
+        let entry = marker.marker_tree().get_path(Path::new("link"))?;
+        assert!(does_oid_match(&marker, Path::new("link")));
+        assert_eq!(entry.filemode(), i32::from(git2::FileMode::Link));
+        Ok(())
+    }
+
     #[test]
     fn mark_deleted_file_reviewed() -> Result {
         let repo = TestRepo::new()?;
@@ -823,6 +1166,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn survive_editing_the_working_copy() -> Result {
+        // @ is rewritten on every snapshot, so its commit id changes as files are edited.
+        let repo = TestRepo::new()?;
+        repo.write_file("test", "hello")?;
+        repo.commit("commit A")?;
+        repo.write_file("test2", "hello world")?;
+        let wc = repo.work_copy()?;
+
+        let mut marker = MarkerCommit::get(&repo.repo, wc.commit_id)?;
+        marker.mark_file_reviewed(Path::new("test2"), None)?;
+        marker.write()?;
+        drop(marker);
+
+        repo.write_file("test3", "added later")?;
+        let wc_2 = repo.work_copy()?;
+        assert_eq!(wc_2.change_id, wc.change_id);
+        assert_ne!(wc_2.commit_id, wc.commit_id);
+
+        let marker = MarkerCommit::get(&repo.repo, wc_2.commit_id)?;
+        assert!(
+            does_oid_match(&marker, Path::new("test2")),
+            "reviewed state should survive editing the working copy"
+        );
+        assert!(
+            !does_oid_match(&marker, Path::new("test3")),
+            "a file added to the working copy after review should not be reviewed"
+        );
+        Ok(())
+    }
+
     #[test]
     fn changing_diff_revert_reviewed() -> Result {
         let (repo, _, b) = setup_two_commits()?;
@@ -1038,6 +1412,85 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn mark_non_adjacent_regions_at_once() -> Result {
+        let (repo, _, sha, region1, region2) = setup_two_region_commit()?;
+
+        let mut marker = MarkerCommit::get(&repo.repo, sha)?;
+        marker.mark_regions_reviewed(Path::new("test"), None, &[region1, region2])?;
+
+        assert!(
+            does_oid_match(&marker, Path::new("test")),
+            "both regions marked in one call → M should equal T"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn mark_adjacent_regions_at_once() -> Result {
+        let repo = TestRepo::new()?;
+        repo.write_file("test", "a\nb\nc\nd\n")?;
+        let _a = repo.commit("commit A")?.created;
+        repo.write_file("test", "A\nB\nB2\nc\nd\n")?;
+        let b = repo.commit("commit B")?.created;
+
+        // Zero-context regions touching each other: a→A, then b→B/B2.
+        let first = RegionId {
+            old_start: 1,
+            old_lines: 1,
+            new_start: 1,
+            new_lines: 1,
+        };
+        let second = RegionId {
+            old_start: 2,
+            old_lines: 1,
+            new_start: 2,
+            new_lines: 2,
+        };
+
+        let mut marker = MarkerCommit::get(&repo.repo, b.commit_id)?;
+        marker.mark_regions_reviewed(Path::new("test"), None, &[first, second])?;
+
+        let m_content = blob_content_at(&repo.repo, marker.marker_tree(), Path::new("test"));
+        assert_eq!(m_content, "A\nB\nB2\nc\nd\n");
+        Ok(())
+    }
+
+    #[test]
+    fn unmark_regions_at_once_reverts_to_base() -> Result {
+        let (repo, _, sha, region1, region2) = setup_two_region_commit()?;
+
+        let mut marker = MarkerCommit::get(&repo.repo, sha)?;
+        marker.mark_file_reviewed(Path::new("test"), None)?;
+        marker.unmark_regions_reviewed(Path::new("test"), None, &[region2, region1])?;
+
+        assert_eq!(
+            marker.marker_tree().id(),
+            marker.base_tree().id(),
+            "unmarking every region should restore the base tree"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn mark_overlapping_regions_leaves_marker_untouched() -> Result {
+        let (repo, _, sha, region1, _region2) = setup_two_region_commit()?;
+
+        let mut marker = MarkerCommit::get(&repo.repo, sha)?;
+        let before = marker.marker_tree().id();
+        let overlapping = RegionId {
+            old_start: 2,
+            old_lines: 3,
+            new_start: 2,
+            new_lines: 3,
+        };
+
+        let result = marker.mark_regions_reviewed(Path::new("test"), None, &[region1, overlapping]);
+        assert!(result.is_err(), "overlapping regions should be rejected");
+        assert_eq!(marker.marker_tree().id(), before);
+        Ok(())
+    }
+
     #[test]
     fn mark_added_file_region_reviewed() -> Result {
         let repo = TestRepo::new()?;
@@ -1217,6 +1670,114 @@ mod tests {
         Ok(())
     }
 
+    // ── review_progress tests ─────────────────────────────────────────
+
+    #[test]
+    fn review_progress_counts_reviewed_partial_and_unreviewed() -> Result {
+        let repo = TestRepo::new()?;
+        repo.write_file("regions", "a1\na2\na3\na4\na5\nb1\nb2\nb3\nb4\nb5\n")?;
+        repo.write_file("whole", "old\n")?;
+        repo.write_file("untouched", "old\n")?;
+        let _a = repo.commit("commit A")?.created;
+        repo.write_file("regions", "A1\na2\na3\na4\na5\nb1\nb2\nb3\nB4\nb5\n")?;
+        repo.write_file("whole", "new\n")?;
+        repo.write_file("untouched", "new\n")?;
+        let b = repo.commit("commit B")?.created;
+
+        let mut marker = MarkerCommit::get(&repo.repo, b.commit_id)?;
+        let fresh = marker.review_progress()?;
+        assert_eq!(fresh.unreviewed_files, 3);
+        assert_eq!(fresh.total_lines, 8);
+        assert_eq!(fresh.reviewed_lines(), 0);
+
+        marker.mark_file_reviewed(Path::new("whole"), None)?;
+        let region1 = RegionId {
+            old_start: 1,
+            old_lines: 3,
+            new_start: 1,
+            new_lines: 3,
+        };
+        marker.mark_region_reviewed(Path::new("regions"), None, &region1)?;
+
+        let progress = marker.review_progress()?;
+        assert_eq!(
+            progress,
+            ReviewProgress {
+                reviewed_files: 1,
+                partially_reviewed_files: 1,
+                unreviewed_files: 1,
+                total_lines: 8,
+                remaining_lines: 4,
+            }
+        );
+        assert_eq!(progress.total_files(), 3);
+        assert_eq!(progress.reviewed_lines(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn review_progress_deleted_file_reviewed_when_absent() -> Result {
+        let repo = TestRepo::new()?;
+        repo.write_file("gone", "bye\n")?;
+        let _a = repo.commit("commit A")?.created;
+        repo.delete_file("gone")?;
+        let b = repo.commit("commit B")?.created;
+
+        let mut marker = MarkerCommit::get(&repo.repo, b.commit_id)?;
+        assert_eq!(marker.review_progress()?.unreviewed_files, 1);
+
+        marker.mark_file_reviewed(Path::new("gone"), None)?;
+        let progress = marker.review_progress()?;
+        assert_eq!(progress.reviewed_files, 1);
+        assert_eq!(progress.remaining_lines, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn review_progress_matches_renamed_file_by_new_path() -> Result {
+        let (repo, _, sha, region1, region2) = setup_rename_two_region_commit()?;
+
+        let mut marker = MarkerCommit::get(&repo.repo, sha)?;
+        let fresh = marker.review_progress()?;
+        assert_eq!(fresh.total_files(), 1, "a rename is a single file");
+        assert_eq!(fresh.unreviewed_files, 1);
+
+        marker.mark_region_reviewed(Path::new("new.txt"), Some(Path::new("old.txt")), &region1)?;
+        assert_eq!(marker.review_progress()?.partially_reviewed_files, 1);
+
+        marker.mark_region_reviewed(Path::new("new.txt"), Some(Path::new("old.txt")), &region2)?;
+        assert_eq!(marker.review_progress()?.reviewed_files, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn review_progress_uses_octopus_base_for_merge() -> Result {
+        let repo = TestRepo::new()?;
+        repo.write_file("shared", "base\n")?;
+        let a = repo.commit("commit A")?.created;
+        repo.write_file("left", "left\n")?;
+        let b = repo.commit("commit B")?.created;
+        repo.new_revision(a.change_id)?;
+        repo.write_file("right", "right\n")?;
+        let c = repo.commit("commit C")?.created;
+
+        repo.merge(&[b.change_id, c.change_id], "merge")?;
+        repo.write_file("shared", "resolved\n")?;
+        let merge = repo.work_copy()?;
+
+        let mut marker = MarkerCommit::get(&repo.repo, merge.commit_id)?;
+        let fresh = marker.review_progress()?;
+        assert_eq!(
+            fresh.total_files(),
+            1,
+            "files brought in by either parent are part of the base, not the change"
+        );
+
+        marker.mark_file_reviewed(Path::new("shared"), None)?;
+        assert_eq!(marker.review_progress()?.reviewed_files, 1);
+        Ok(())
+    }
+
     // ── set_blob tests ────────────────────────────────────────────────
 
     #[test]
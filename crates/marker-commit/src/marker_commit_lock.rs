@@ -1,7 +1,7 @@
 use fs2::FileExt;
 use std::{
     fs::{self, File, OpenOptions},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use git2::Repository;
@@ -18,13 +18,7 @@ pub struct MarkerCommitLock {
 impl MarkerCommitLock {
     pub fn new(repo: &Repository, change_id: ChangeId) -> Result<Self> {
         let path = Self::lock_path(repo, change_id);
-        fs::create_dir_all(path.parent().unwrap())?;
-        let file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .truncate(false)
-            .open(&path)?;
+        let file = Self::open_lock_file(&path)?;
         file.lock_exclusive()?;
 
         log::info!("created lock file at {}", path.to_str().unwrap_or(""));
@@ -35,6 +29,37 @@ impl MarkerCommitLock {
         })
     }
 
+    /// Non-blocking variant of [`MarkerCommitLock::new`]. Returns `Ok(None)` if another
+    /// holder already has the lock.
+    pub fn try_new(repo: &Repository, change_id: ChangeId) -> Result<Option<Self>> {
+        let path = Self::lock_path(repo, change_id);
+        let file = Self::open_lock_file(&path)?;
+        if let Err(err) = file.try_lock_exclusive() {
+            if err.raw_os_error() == fs2::lock_contended_error().raw_os_error() {
+                log::info!("lock file at {} is held", path.to_str().unwrap_or(""));
+                return Ok(None);
+            }
+            return Err(err.into());
+        }
+
+        log::info!("created lock file at {}", path.to_str().unwrap_or(""));
+        Ok(Some(Self {
+            _lock_file: file,
+            change_id,
+            path,
+        }))
+    }
+
+    fn open_lock_file(path: &Path) -> Result<File> {
+        fs::create_dir_all(path.parent().unwrap())?;
+        Ok(OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?)
+    }
+
     pub fn lock_path(repo: &Repository, change_id: ChangeId) -> PathBuf {
         repo.path()
             .join("info/kenjutu/lock/")
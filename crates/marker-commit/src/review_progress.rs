@@ -0,0 +1,88 @@
+use std::path::Path;
+
+use git2::{Oid, Repository, Tree};
+
+use crate::Result;
+
+/// Summary of how much of a revision has been reviewed.
+///
+/// File counts cover every file changed in `diff(base, target)`. Line counts are additions plus
+/// deletions: `total_lines` from `diff(base, target)` and `remaining_lines` from
+/// `diff(marker, target)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReviewProgress {
+    pub reviewed_files: usize,
+    pub partially_reviewed_files: usize,
+    pub unreviewed_files: usize,
+    pub total_lines: usize,
+    pub remaining_lines: usize,
+}
+
+impl ReviewProgress {
+    pub fn total_files(&self) -> usize {
+        self.reviewed_files + self.partially_reviewed_files + self.unreviewed_files
+    }
+
+    pub fn reviewed_lines(&self) -> usize {
+        self.total_lines.saturating_sub(self.remaining_lines)
+    }
+}
+
+pub(crate) fn review_progress<'repo>(
+    repo: &'repo Repository,
+    base: &Tree<'repo>,
+    marker: &Tree<'repo>,
+    target: &Tree<'repo>,
+) -> Result<ReviewProgress> {
+    let changes = diff_trees(repo, base, target)?;
+    let mut progress = ReviewProgress {
+        total_lines: line_count(&changes)?,
+        remaining_lines: line_count(&diff_trees(repo, marker, target)?)?,
+        ..Default::default()
+    };
+
+    for delta in changes.deltas() {
+        let (Some(old_path), Some(new_path)) = (delta.old_file().path(), delta.new_file().path())
+        else {
+            continue;
+        };
+        let renamed = old_path != new_path;
+
+        // A pending rename leaves the file at old_path in M, so new_path alone isn't enough.
+        let at_target = entry_id(marker, new_path) == entry_id(target, new_path)
+            && (!renamed || entry_id(marker, old_path).is_none());
+        let at_base = entry_id(marker, old_path) == entry_id(base, old_path)
+            && (!renamed || entry_id(marker, new_path).is_none());
+
+        if at_target {
+            progress.reviewed_files += 1;
+        } else if at_base {
+            progress.unreviewed_files += 1;
+        } else {
+            progress.partially_reviewed_files += 1;
+        }
+    }
+
+    Ok(progress)
+}
+
+fn diff_trees<'repo>(
+    repo: &'repo Repository,
+    old_tree: &Tree<'repo>,
+    new_tree: &Tree<'repo>,
+) -> Result<git2::Diff<'repo>> {
+    let mut diff = repo.diff_tree_to_tree(Some(old_tree), Some(new_tree), None)?;
+    let mut find_opts = git2::DiffFindOptions::new();
+    find_opts.renames(true);
+    diff.find_similar(Some(&mut find_opts))?;
+    Ok(diff)
+}
+
+fn line_count(diff: &git2::Diff<'_>) -> Result<usize> {
+    let stats = diff.stats()?;
+    Ok(stats.insertions() + stats.deletions())
+}
+
+fn entry_id(tree: &Tree<'_>, path: &Path) -> Option<Oid> {
+    tree.get_path(path).ok().map(|entry| entry.id())
+}
@@ -69,7 +69,7 @@ impl TestRepo {
         Ok(())
     }
 
-    pub fn write_file(&self, path: &str, content: &str) -> Result<()> {
+    pub fn write_file(&self, path: &str, content: impl AsRef<[u8]>) -> Result<()> {
         let file_path = self._dir.path().join(path);
         std::fs::create_dir_all(file_path.parent().unwrap())?;
         std::fs::write(&file_path, content)?;
@@ -82,6 +82,18 @@ impl TestRepo {
         Ok(())
     }
 
+    /// Replace whatever is at `path` with a symlink pointing at `target`.
+    #[cfg(unix)]
+    pub fn symlink(&self, path: &str, target: &str) -> Result<()> {
+        let link_path = self._dir.path().join(path);
+        std::fs::create_dir_all(link_path.parent().unwrap())?;
+        if link_path.symlink_metadata().is_ok() {
+            std::fs::remove_file(&link_path)?;
+        }
+        std::os::unix::fs::symlink(target, &link_path)?;
+        Ok(())
+    }
+
     pub fn rename_file(&self, old_path: &str, new_path: &str) -> Result<()> {
         let old_file_path = self._dir.path().join(old_path);
         let new_file_path = self._dir.path().join(new_path);
@@ -106,6 +118,28 @@ impl TestRepo {
         Ok(commit)
     }
 
+    /// Commit `base` to `file`, edit it to `left` and `right` in two sibling revisions, then
+    /// merge them without resolving. Returns the merge, left as the working copy, whose
+    /// `file` is conflicted.
+    pub fn create_conflict(
+        &self,
+        file: &str,
+        base: &str,
+        left: &str,
+        right: &str,
+    ) -> Result<CommitInfo> {
+        self.write_file(file, base)?;
+        let base = self.commit("conflict base")?.created;
+        self.write_file(file, left)?;
+        let left = self.commit("conflict left")?.created;
+
+        self.new_revision(base.change_id)?;
+        self.write_file(file, right)?;
+        let right = self.work_copy()?;
+
+        self.merge(&[left.change_id, right.change_id], "conflict")
+    }
+
     pub fn new_revision(&self, revision: ChangeId) -> Result<CommitInfo> {
         self.jj().args(["new", "-r", &revision.to_string()]).run()?;
         self.work_copy()
@@ -127,6 +161,27 @@ impl TestRepo {
         Ok(())
     }
 
+    /// Create or move bookmark `name` to `revision`.
+    pub fn bookmark(&self, name: &str, revision: ChangeId) -> Result<()> {
+        self.jj()
+            .args(["bookmark", "set", name, "-r", &revision.to_string()])
+            .run()?;
+        Ok(())
+    }
+
+    pub fn abandon(&self, revision: ChangeId) -> Result<()> {
+        self.jj().args(["abandon", &revision.to_string()]).run()?;
+        Ok(())
+    }
+
+    /// Replace the description of `revision`, rewriting its commit id.
+    pub fn describe(&self, revision: ChangeId, message: &str) -> Result<()> {
+        self.jj()
+            .args(["describe", &revision.to_string(), "-m", message])
+            .run()?;
+        Ok(())
+    }
+
     pub fn commit(&self, message: &str) -> Result<CommitResult> {
         self.jj().args(["commit", "-m", message]).run()?;
 
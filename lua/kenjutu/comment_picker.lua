@@ -1,4 +1,5 @@
 local kjn = require("kenjutu.kjn")
+local utils = require("kenjutu.utils")
 
 local M = {}
 
@@ -44,13 +45,14 @@ local function render_preview(bufnr, winnr, comment, file_path)
     table.insert(lines, body_line)
   end
 
-  for _, reply in ipairs(comment.replies or {}) do
+  for _, item in ipairs(utils.flatten_replies(comment.replies)) do
+    local indent = string.rep("  ", item.depth + 1)
     table.insert(lines, "")
     table.insert(lines, string.rep("─", width))
     table.insert(highlights, { line = #lines - 1, hl = "Comment" })
     table.insert(lines, "")
-    for _, body_line in ipairs(vim.split(reply.body, "\n", { plain = true })) do
-      table.insert(lines, "  " .. body_line)
+    for _, body_line in ipairs(vim.split(item.reply.body, "\n", { plain = true })) do
+      table.insert(lines, indent .. body_line)
     end
   end
 
@@ -95,6 +97,7 @@ end
 ---@field dir string
 ---@field commit_id string
 ---@field on_select fun(file_path: string, pc: kenjutu.PortedComment)
+---@field on_resolve fun()|nil
 
 ---@param opts kenjutu.CommentPickerOpts
 function M.open(opts)
@@ -194,6 +197,9 @@ function M.open(opts)
         c.resolved = not c.resolved
         local picker = action_state.get_current_picker(prompt_bufnr)
         picker:refresh(make_finder(), { reset_prompt = false })
+        if opts.on_resolve then
+          opts.on_resolve()
+        end
       end)
     end
 
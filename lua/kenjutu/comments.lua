@@ -1,4 +1,5 @@
 local kjn = require("kenjutu.kjn")
+local utils = require("kenjutu.utils")
 
 local M = {}
 
@@ -211,17 +212,19 @@ function M.open_thread(opts)
     for _, body_line in ipairs(vim.split(comment.body, "\n", { plain = true })) do
       table.insert(lines, body_line)
     end
-    local date = format_date(comment.created_at)
+    local date = comment.author .. "  " .. format_date(comment.created_at)
     table.insert(lines, string.rep(" ", math.max(0, width - #date - 2)) .. date)
     table.insert(highlights, { line = #lines - 1, hl = "KenjutuCommentTimestamp" })
 
-    for _, reply in ipairs(comment.replies or {}) do
+    for _, item in ipairs(utils.flatten_replies(comment.replies)) do
+      local reply = item.reply
+      local indent = string.rep("  ", item.depth + 1)
       table.insert(lines, separator)
       table.insert(highlights, { line = #lines - 1, hl = "KenjutuCommentSeparator" })
       for _, body_line in ipairs(vim.split(reply.body, "\n", { plain = true })) do
-        table.insert(lines, "  " .. body_line)
+        table.insert(lines, indent .. body_line)
       end
-      local reply_date = format_date(reply.created_at)
+      local reply_date = reply.author .. "  " .. format_date(reply.created_at)
       table.insert(lines, string.rep(" ", math.max(0, width - #reply_date - 2)) .. reply_date)
       table.insert(highlights, { line = #lines - 1, hl = "KenjutuCommentTimestamp" })
     end
@@ -409,8 +412,9 @@ function M.build_comment_list(comments, width)
       table.insert(highlights, { line = #lines - 1, col = 5, end_col = #rendered, hl = "KenjutuCommentCodeSnippet" })
     end
 
-    local reply_list = c.replies or {}
-    for _, reply in ipairs(reply_list) do
+    for _, item in ipairs(utils.flatten_replies(c.replies)) do
+      local reply = item.reply
+      local indent = string.rep("  ", item.depth)
       local reply_sep = "  "
         .. string.rep("\xe2\x94\x80", math.max(1, width - 12))
         .. " Reply "
@@ -421,12 +425,12 @@ function M.build_comment_list(comments, width)
       table.insert(highlights, { line = #lines - 1, hl = "KenjutuCommentReplyHeader" })
 
       for _, body_line in ipairs(vim.split(reply.body, "\n", { plain = true })) do
-        table.insert(lines, "    " .. body_line)
+        table.insert(lines, "    " .. indent .. body_line)
         fold_levels[#lines] = "1"
         line_to_comment[#lines] = pc
       end
 
-      local reply_date = format_date(reply.created_at)
+      local reply_date = reply.author .. "  " .. format_date(reply.created_at)
       table.insert(lines, string.rep(" ", math.max(0, width - #reply_date)) .. reply_date)
       fold_levels[#lines] = "1"
       line_to_comment[#lines] = pc
@@ -96,8 +96,10 @@ end
 ---@class kenjutu.DiffCallbacks
 ---@field focus_file_list fun()
 ---@field move_selection fun(direction: "up"|"down")
+---@field jump_to_unreviewed fun(direction: "up"|"down")
 ---@field close fun()
 ---@field on_mark fun()
+---@field on_comments_changed fun()
 ---@field navigate_to fun(file_path: string, line: integer|nil, side: "New"|"Old")
 
 ---@class kenjutu.DiffState
@@ -110,6 +112,9 @@ end
 ---@field commit_id string
 ---@field callbacks kenjutu.DiffCallbacks|nil
 ---@field created_buffers integer[]
+---@field saved_views table<string, kenjutu.DiffState.SavedView> views of files navigated away from
+---@field load_generation integer bumped on every load so late results of a superseded load are dropped
+---@field loading boolean true while the panes wait for blobs
 local DiffState = {}
 DiffState.__index = DiffState
 
@@ -136,6 +141,9 @@ function DiffState:new(opts)
     file = nil,
     callbacks = nil,
     created_buffers = {},
+    saved_views = {},
+    load_generation = 0,
+    loading = false,
   }
   setmetatable(obj, self)
   return obj
@@ -217,10 +225,22 @@ function DiffState:install_keymaps(bufnr)
     cb.move_selection("up")
   end, opts)
 
+  vim.keymap.set("n", "]u", function()
+    cb.jump_to_unreviewed("down")
+  end, opts)
+
+  vim.keymap.set("n", "[u", function()
+    cb.jump_to_unreviewed("up")
+  end, opts)
+
   vim.keymap.set("n", "t", function()
     self:cycle_mode()
   end, opts)
 
+  vim.keymap.set("n", "gf", function()
+    self:open_in_editor()
+  end, opts)
+
   vim.keymap.set({ "n", "v" }, "gc", function()
     self:new_comment()
   end, opts)
@@ -254,9 +274,60 @@ end
 ---@field line integer|nil
 ---@field side "Old"|"New"|nil
 
+---@class kenjutu.DiffState.SavedView
+---@field left vim.fn.winsaveview.ret|nil
+---@field right vim.fn.winsaveview.ret|nil
+
+---@param winnr integer
+---@return vim.fn.winsaveview.ret|nil
+local function save_win_view(winnr)
+  if not vim.api.nvim_win_is_valid(winnr) then
+    return nil
+  end
+  return vim.api.nvim_win_call(winnr, function()
+    return vim.fn.winsaveview()
+  end)
+end
+
+---@param winnr integer
+---@param view vim.fn.winsaveview.ret|nil
+local function restore_win_view(winnr, view)
+  if not view or not vim.api.nvim_win_is_valid(winnr) then
+    return
+  end
+  local line_count = vim.api.nvim_buf_line_count(vim.api.nvim_win_get_buf(winnr))
+  vim.api.nvim_win_call(winnr, function()
+    vim.fn.winrestview({
+      lnum = math.min(view.lnum, line_count),
+      col = view.col,
+      topline = math.min(view.topline, line_count),
+    })
+  end)
+end
+
+--- Remember where the current file's panes are scrolled to so returning to it restores them.
+function DiffState:save_view()
+  if not self.file then
+    return
+  end
+  self.saved_views[utils.file_path(self.file)] = {
+    left = save_win_view(self.left_winnr),
+    right = save_win_view(self.right_winnr),
+  }
+end
+
+--- Forget the saved position of a file whose diff has changed underneath it.
+---@param file_path string
+function DiffState:forget_view(file_path)
+  self.saved_views[file_path] = nil
+end
+
 ---@param file kenjutu.FileEntry
 ---@param jump_opts kenjutu.SetFileOpts|nil
 function DiffState:set_file(file, jump_opts)
+  if self.file and utils.file_path(self.file) ~= utils.file_path(file) then
+    self:save_view()
+  end
   self.file = file
   self.mode = file.reviewStatus == "reviewed" and "reviewed" or "remaining"
   self:update_wins(false, jump_opts)
@@ -343,6 +414,15 @@ function DiffState:update_wins(ignore_cache, jump_opts)
   local left_tree = tree_for_side(self.mode, "left")
   local right_tree = tree_for_side(self.mode, "right")
 
+  self.load_generation = self.load_generation + 1
+  local generation = self.load_generation
+  self.loading = true
+  for _, winnr in ipairs({ self.left_winnr, self.right_winnr }) do
+    if vim.api.nvim_win_is_valid(winnr) then
+      vim.wo[winnr].winbar = "Loading…"
+    end
+  end
+
   utils.await_all({
     left = function(cb)
       setup_buffer(left_tree, cb)
@@ -351,7 +431,14 @@ function DiffState:update_wins(ignore_cache, jump_opts)
       setup_buffer(right_tree, cb)
     end,
   }, function(err, results)
+    -- Another file or mode was requested while this one loaded.
+    if generation ~= self.load_generation then
+      return
+    end
+    self.loading = false
     if err then
+      vim.wo[self.left_winnr].winbar = tree_labels[left_tree]
+      vim.wo[self.right_winnr].winbar = tree_labels[right_tree]
       vim.notify("Error loading buffers: " .. err, vim.log.levels.ERROR)
       return
     end
@@ -376,6 +463,10 @@ function DiffState:update_wins(ignore_cache, jump_opts)
     vim.wo[self.left_winnr].winbar = tree_labels[left_tree]
     vim.wo[self.right_winnr].winbar = tree_labels[right_tree]
 
+    local file_path = utils.file_path(file)
+    local saved_view = self.saved_views[file_path]
+    self.saved_views[file_path] = nil
+
     if jump_opts and jump_opts.line then
       local winnr
       if jump_opts.side == "New" then
@@ -389,12 +480,36 @@ function DiffState:update_wins(ignore_cache, jump_opts)
         local target_line = math.min(jump_opts.line, line_count)
         vim.api.nvim_win_set_cursor(winnr, { target_line, 0 })
       end
+    elseif saved_view then
+      restore_win_view(self.left_winnr, saved_view.left)
+      restore_win_view(self.right_winnr, saved_view.right)
     end
 
     self:refresh_signs()
   end)
 end
 
+--- Open the working-copy file in a new tab. The cursor line carries over when the
+--- cursor is on the target side, whose lines match the file on disk.
+function DiffState:open_in_editor()
+  local file = self.file
+  if not file then
+    return
+  end
+  local path = vim.fs.joinpath(self.dir, utils.file_path(file))
+  if file.status == "deleted" or vim.fn.filereadable(path) == 0 then
+    vim.notify("File does not exist in the working copy: " .. utils.file_path(file), vim.log.levels.WARN)
+    return
+  end
+
+  local line = 1
+  local side = self:current_side()
+  if side and side.tree == "target" then
+    line = vim.api.nvim_win_get_cursor(0)[1]
+  end
+  vim.cmd(string.format("tabedit +%d %s", line, vim.fn.fnameescape(path)))
+end
+
 ---@param is_visual boolean
 function DiffState:mark_action(is_visual)
   local file = self.file
@@ -409,6 +524,10 @@ function DiffState:mark_action(is_visual)
     vim.notify("Cannot mark binary file", vim.log.levels.WARN)
     return
   end
+  if self.loading then
+    vim.notify("Diff is still loading", vim.log.levels.WARN)
+    return
+  end
 
   local bufnr = vim.api.nvim_get_current_buf()
   local left_bufnr = self:buf("left")
@@ -440,12 +559,14 @@ function DiffState:mark_action(is_visual)
 
   local maker_contents = vim.api.nvim_buf_get_lines(marker_bufnr, 0, -1, false)
   local content_str = table.concat(maker_contents, "\n") .. "\n"
+  local file_path = utils.file_path(file)
+  self:forget_view(file_path)
 
   kjn.set_blob(
     {
       dir = self.dir,
       commit_id = self.commit_id,
-      file_path = utils.file_path(file),
+      file_path = file_path,
     },
     content_str,
     function(err, _)
@@ -463,6 +584,7 @@ end
 ---@param new_status "reviewed" | "unreviewed"
 function DiffState:on_file_toggled(file, new_status)
   local file_path = utils.file_path(file)
+  self:forget_view(file_path)
   local marker_bufname = diff_buf_name(self.change_id, file_path, "marker")
   local marker_bufnr = vim.fn.bufnr(marker_bufname)
   if marker_bufnr == -1 then
@@ -506,6 +628,9 @@ function DiffState:new_comment()
     side = tree == "base" and "Old" or "New",
     on_create = function()
       self:refresh_signs()
+      if self.callbacks then
+        self.callbacks.on_comments_changed()
+      end
     end,
   })
 end
@@ -554,6 +679,12 @@ function DiffState:open_all_comments()
       end
       cb.navigate_to(file_path, pc.ported_line, pc.comment.side)
     end,
+    on_resolve = function()
+      self:refresh_signs()
+      if self.callbacks then
+        self.callbacks.on_comments_changed()
+      end
+    end,
   })
 end
 
@@ -572,6 +703,9 @@ function DiffState:open_comment_list()
       commit_id = self.commit_id,
       on_resolve = function()
         self:refresh_signs()
+        if self.callbacks then
+          self.callbacks.on_comments_changed()
+        end
       end,
       on_select = function(pc)
         if not pc.ported_line then
@@ -635,9 +769,12 @@ function DiffState:close()
   self:cleanup()
 end
 
+--- Refetch the current file's diff for a rewritten commit, keeping its panes where they were.
 ---@param commit_id string
 function DiffState:reload(commit_id)
   self.commit_id = commit_id
+  self.saved_views = {}
+  self:save_view()
   ---@type integer[]
   local kept_bufnr = {}
   for _, bufnr in ipairs(self.created_buffers) do
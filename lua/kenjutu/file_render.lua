@@ -13,6 +13,7 @@ local hl_defs = {
   KenjutuStatusC = { fg = "#94e2d5" },
   KenjutuStatusT = { fg = "#cba6f7" },
   KenjutuStats = { fg = "#6c7086" },
+  KenjutuComments = { fg = "#89b4fa" },
   KenjutuHeader = { default = true, link = "Title" },
   KenjutuDir = { default = true, link = "Directory" },
   KenjutuCommitSummary = { default = true, link = "Title" },
@@ -242,6 +243,13 @@ function M.format_file_line(file, indent)
     end
     table.insert(parts, stats)
     table.insert(highlights, { col, col + #stats, "KenjutuStats" })
+    col = col + #stats
+  end
+
+  if file.unresolvedComments and file.unresolvedComments > 0 then
+    local badge = " 💬" .. file.unresolvedComments
+    table.insert(parts, badge)
+    table.insert(highlights, { col, col + #badge, "KenjutuComments" })
   end
 
   return { text = table.concat(parts), highlights = highlights }
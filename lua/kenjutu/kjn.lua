@@ -171,6 +171,7 @@ end
 ---@field files kenjutu.FileEntry[]
 ---@field commitId string
 ---@field changeId string
+---@field unresolvedComments table<string, integer> unresolved thread count keyed by file path
 
 ---@class kenjutu.FileEntry
 ---@field oldPath string|nil
@@ -180,12 +181,21 @@ end
 ---@field deletions integer
 ---@field isBinary boolean
 ---@field reviewStatus "reviewed"|"partiallyReviewed"|"unreviewed"|"reviewedReverted"
+---@field unresolvedComments integer|nil
 
 ---@param dir string
 ---@param change_id string
 ---@param cb fun(err: string|nil, result: kenjutu.FilesResult|nil)
 function M.files(dir, change_id, cb)
-  send_request(dir, "files", { change_id = change_id }, cb)
+  send_request(dir, "files", { change_id = change_id }, function(err, result)
+    if result and result.files then
+      local counts = result.unresolvedComments or {}
+      for _, file in ipairs(result.files) do
+        file.unresolvedComments = counts[file.newPath or file.oldPath]
+      end
+    end
+    cb(err, result)
+  end)
 end
 
 ---@class kenjutu.SetBlobOptions
@@ -241,6 +251,7 @@ end
 ---@field ported_line integer|nil
 ---@field ported_start_line integer|nil
 ---@field is_ported boolean
+---@field anchor_status "Exact"|{Shifted: integer}|"Orphaned"
 
 ---@class kenjutu.MaterializedComment
 ---@field id string
@@ -250,6 +261,7 @@ end
 ---@field start_line integer|nil
 ---@field body string
 ---@field anchor { before: string[], target: string[], after: string[] }
+---@field author string
 ---@field resolved boolean
 ---@field created_at string
 ---@field updated_at string
@@ -259,9 +271,11 @@ end
 ---@class kenjutu.MaterializedReply
 ---@field id string
 ---@field body string
+---@field author string
 ---@field created_at string
 ---@field updated_at string
 ---@field edit_count integer
+---@field replies kenjutu.MaterializedReply[]|nil
 
 ---@class kenjutu.FileComments
 ---@field file_path string
@@ -5,6 +5,18 @@ local utils = require("kenjutu.utils")
 
 local M = {}
 
+-- Width of the file list pane; it gives up columns on narrow screens, down to the minimum.
+local FILE_LIST_WIDTH = 40
+local FILE_LIST_MIN_WIDTH = 20
+
+--- Width of the file list for an editor `columns` wide: at most a third of the screen so the
+--- diff panes stay usable.
+---@param columns integer
+---@return integer
+function M.file_list_width(columns)
+  return math.max(FILE_LIST_MIN_WIDTH, math.min(FILE_LIST_WIDTH, math.floor(columns / 3)))
+end
+
 ---@class kenjutu.ReviewState
 ---@field dir string
 ---@field change_id string
@@ -77,6 +89,48 @@ function ReviewState:move_selection(direction)
   end
 end
 
+--- Find the next file line, after `cur_line` in the given direction and wrapping around, whose
+--- file still needs review. Partially reviewed files count as needing review.
+---@param line_map table<integer, kenjutu.FileEntry>
+---@param cur_line integer
+---@param direction "up" | "down"
+---@return integer|nil line
+function M.find_unreviewed_line(line_map, cur_line, direction)
+  local lines = vim.tbl_keys(line_map)
+  table.sort(lines)
+  if direction == "up" then
+    lines = vim.fn.reverse(lines)
+  end
+
+  local wrapped = nil
+  for _, line in ipairs(lines) do
+    if line_map[line].reviewStatus ~= "reviewed" then
+      local ahead = direction == "down" and line > cur_line or direction == "up" and line < cur_line
+      if ahead then
+        return line
+      end
+      wrapped = wrapped or line
+    end
+  end
+  return wrapped
+end
+
+--- Move file selection to the next file that still needs review, wrapping around.
+---@param direction "up" | "down"
+function ReviewState:jump_to_unreviewed(direction)
+  if not vim.api.nvim_win_is_valid(self.file_list_winnr) then
+    return
+  end
+  local cur_line = vim.api.nvim_win_get_cursor(self.file_list_winnr)[1]
+  local line = M.find_unreviewed_line(self.line_map, cur_line, direction)
+  if not line then
+    vim.notify("All files reviewed", vim.log.levels.INFO)
+    return
+  end
+  vim.api.nvim_win_set_cursor(self.file_list_winnr, { line, 0 })
+  self:update_diff_view()
+end
+
 ---@param file_path string
 ---@param line integer|nil
 ---@param side "New"|"Old"
@@ -89,6 +143,50 @@ function ReviewState:navigate_to(file_path, line, side)
   end
 end
 
+--- Line of the file list row showing `file_path`.
+---@param line_map table<integer, kenjutu.FileEntry>
+---@param file_path string
+---@return integer|nil line
+function M.find_file_line(line_map, file_path)
+  for line, file in pairs(line_map) do
+    if utils.file_path(file) == file_path then
+      return line
+    end
+  end
+  return nil
+end
+
+--- Re-render the file list, keeping the cursor on the selected file and the window scrolled
+--- where it was. Replacing every line of the buffer would otherwise move the cursor to the top.
+function ReviewState:render_file_list()
+  local winnr = self.file_list_winnr
+  local view = nil
+  local selected = self:selected_file()
+  if vim.api.nvim_win_is_valid(winnr) then
+    view = vim.api.nvim_win_call(winnr, function()
+      return vim.fn.winsaveview()
+    end)
+  end
+
+  self.line_map = file_list.render(self.file_list_bufnr, self.files, winnr)
+  if not view then
+    return
+  end
+
+  local lnum = view.lnum
+  if selected then
+    lnum = M.find_file_line(self.line_map, utils.file_path(selected)) or lnum
+  end
+  local line_count = vim.api.nvim_buf_line_count(self.file_list_bufnr)
+  vim.api.nvim_win_call(winnr, function()
+    vim.fn.winrestview({
+      lnum = math.min(lnum, line_count),
+      col = view.col,
+      topline = math.min(view.topline, line_count),
+    })
+  end)
+end
+
 function ReviewState:refresh_file_list()
   kjn.files(self.dir, self.change_id, function(err, result)
     if err then
@@ -101,7 +199,7 @@ function ReviewState:refresh_file_list()
     local commit_changed = self.commit_id ~= result.commitId
     self.commit_id = result.commitId
     self.files = result.files or {}
-    self.line_map = file_list.render(self.file_list_bufnr, self.files, self.file_list_winnr)
+    self:render_file_list()
     if commit_changed then
       self.diff_state:reload(self.commit_id)
     end
@@ -139,6 +237,19 @@ function ReviewState:toggle_file_reviewed()
   end)
 end
 
+--- Re-apply the layout after the editor was resized.
+--- Neovim keeps the file list's fixed width when the screen shrinks, leaving the diff panes
+--- squeezed and unevenly split, so recompute the width and equalize the diff panes.
+function ReviewState:relayout()
+  if not vim.api.nvim_win_is_valid(self.file_list_winnr) then
+    return
+  end
+  vim.api.nvim_win_set_width(self.file_list_winnr, M.file_list_width(vim.o.columns))
+  vim.api.nvim_win_call(self.file_list_winnr, function()
+    vim.cmd("wincmd =")
+  end)
+end
+
 --- Close the review screen and restore the log buffer.
 function ReviewState:close()
   local log_bufnr = self.log_bufnr
@@ -172,13 +283,25 @@ function ReviewState:close()
   self.on_close()
 end
 
+function ReviewState:focus_diff()
+  if vim.api.nvim_win_is_valid(self.diff_state.right_winnr) then
+    vim.api.nvim_set_current_win(self.diff_state.right_winnr)
+  end
+end
+
 function ReviewState:setup_file_list_keymaps()
   local bufnr = self.file_list_bufnr
   local opts = { buffer = bufnr, silent = true }
 
   vim.keymap.set("n", "<CR>", function()
-    if vim.api.nvim_win_is_valid(self.diff_state.right_winnr) then
-      vim.api.nvim_set_current_win(self.diff_state.right_winnr)
+    self:focus_diff()
+  end, opts)
+
+  -- The first click already moved the cursor, and with it the selected file. Mouse events only
+  -- arrive when the user has enabled 'mouse', so this stays out of the way otherwise.
+  vim.keymap.set("n", "<2-LeftMouse>", function()
+    if self:selected_file() then
+      self:focus_diff()
     end
   end, opts)
 
@@ -196,6 +319,14 @@ function ReviewState:setup_file_list_keymaps()
     end
   end, opts)
 
+  vim.keymap.set("n", "]u", function()
+    self:jump_to_unreviewed("down")
+  end, opts)
+
+  vim.keymap.set("n", "[u", function()
+    self:jump_to_unreviewed("up")
+  end, opts)
+
   vim.keymap.set("n", "q", function()
     self:close()
   end, opts)
@@ -242,7 +373,7 @@ function M.open(dir, commit, log_bufnr, on_close)
 
   local file_list_winnr = cur_win
   vim.api.nvim_set_current_win(file_list_winnr)
-  vim.api.nvim_win_set_width(file_list_winnr, 40)
+  vim.api.nvim_win_set_width(file_list_winnr, M.file_list_width(vim.o.columns))
 
   -- File list window options
   vim.wo[file_list_winnr].cursorline = true
@@ -279,12 +410,18 @@ function M.open(dir, commit, log_bufnr, on_close)
     move_selection = function(direction)
       s:move_selection(direction)
     end,
+    jump_to_unreviewed = function(direction)
+      s:jump_to_unreviewed(direction)
+    end,
     close = function()
       s:close()
     end,
     on_mark = function()
       s:refresh_file_list()
     end,
+    on_comments_changed = function()
+      s:refresh_file_list()
+    end,
     navigate_to = function(file_path, line, side)
       s:navigate_to(file_path, line, side)
     end,
@@ -292,7 +429,7 @@ function M.open(dir, commit, log_bufnr, on_close)
 
   -- Restore focus to file list after diff layout creation
   vim.api.nvim_set_current_win(file_list_winnr)
-  vim.api.nvim_win_set_width(file_list_winnr, 40)
+  vim.api.nvim_win_set_width(file_list_winnr, M.file_list_width(vim.o.columns))
 
   s:setup_file_list_keymaps()
 
@@ -315,6 +452,15 @@ function M.open(dir, commit, log_bufnr, on_close)
     end,
   })
 
+  vim.api.nvim_create_autocmd("VimResized", {
+    callback = function()
+      if not vim.api.nvim_win_is_valid(s.file_list_winnr) then
+        return true
+      end
+      s:relayout()
+    end,
+  })
+
   local tab_page = vim.api.nvim_tabpage_get_number(0)
   vim.api.nvim_create_autocmd("TabClosed", {
     once = true,
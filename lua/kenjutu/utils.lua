@@ -8,6 +8,21 @@ function M.file_path(file)
   return p
 end
 
+--- Flatten a reply tree into reading order: each reply followed by its own replies.
+--- `depth` is 0 for a direct reply to the thread root.
+---@param replies kenjutu.MaterializedReply[]|nil
+---@param depth integer|nil
+---@return { reply: kenjutu.MaterializedReply, depth: integer }[]
+function M.flatten_replies(replies, depth)
+  depth = depth or 0
+  local flat = {}
+  for _, reply in ipairs(replies or {}) do
+    table.insert(flat, { reply = reply, depth = depth })
+    vim.list_extend(flat, M.flatten_replies(reply.replies, depth + 1))
+  end
+  return flat
+end
+
 --- Run async functions in parallel and collect their results.
 --- Each task calls `cb(err, result)` when done. If any task errors,
 --- the callback fires immediately with that error and remaining tasks
@@ -2,6 +2,7 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use comment_commit::get_all_ported_comments;
+use kenjutu_core::services::diff::DEFAULT_RENAME_THRESHOLD;
 use kenjutu_types::ChangeId;
 use serde::Serialize;
 
@@ -62,11 +63,19 @@ impl From<&comment_commit::PortedComment> for CommentOutput {
             target_sha: c.target_sha.to_string(),
             resolved: c.resolved,
             context: ContextOutput::from(&c.anchor),
-            replies: c.replies.iter().map(|r| r.body.clone()).collect(),
+            replies: reply_bodies(&c.replies),
         }
     }
 }
 
+/// Reply bodies in reading order, each reply followed by its own replies.
+fn reply_bodies(replies: &[comment_commit::MaterializedReply]) -> Vec<String> {
+    replies
+        .iter()
+        .flat_map(|r| std::iter::once(r.body.clone()).chain(reply_bodies(&r.replies)))
+        .collect()
+}
+
 pub fn run(
     local_dir: &Path,
     dir: &str,
@@ -88,7 +97,7 @@ pub fn run(
     let repo = git2::Repository::open(local_dir)
         .with_context(|| format!("failed to open git repository at {}", dir))?;
 
-    let all_ported = get_all_ported_comments(&repo, commit_sha)
+    let all_ported = get_all_ported_comments(&repo, commit_sha, DEFAULT_RENAME_THRESHOLD)
         .map_err(|e| anyhow::anyhow!("failed to read comments: {e}"))?;
 
     let mut files: Vec<FileComments> = Vec::new();
@@ -1,5 +1,7 @@
 mod get;
+mod mark;
 mod resolve;
+mod review;
 mod status;
 
 use std::process;
@@ -38,6 +40,32 @@ enum Command {
         all: bool,
     },
 
+    /// Mark whole files of a change as reviewed, without opening a review UI
+    Mark {
+        /// Jujutsu change ID (auto-detected from working copy if omitted)
+        #[arg(short, long, visible_alias = "change")]
+        change_id: Option<String>,
+
+        /// File to mark; repeat for several files
+        #[arg(short, long = "file", required = true)]
+        files: Vec<String>,
+
+        /// Mark the files unreviewed instead
+        #[arg(long, default_value_t = false)]
+        unmark: bool,
+    },
+
+    /// Show review progress of a change (JSON output)
+    Review {
+        /// Jujutsu change ID (auto-detected from working copy if omitted)
+        #[arg(short, long, visible_alias = "change")]
+        change_id: Option<String>,
+
+        /// Exit non-zero unless every file is fully reviewed
+        #[arg(long, default_value_t = false)]
+        require_complete: bool,
+    },
+
     /// Show comment counts per change (JSON output)
     Status {
         /// Jujutsu revset (omit to use jj's default revset)
@@ -67,6 +95,15 @@ fn run(cli: Cli) -> Result<()> {
             file,
             all,
         } => get::run(&local_dir, &cli.dir, change_id, file, all),
+        Command::Mark {
+            change_id,
+            files,
+            unmark,
+        } => mark::run(&local_dir, &cli.dir, change_id, files, unmark),
+        Command::Review {
+            change_id,
+            require_complete,
+        } => review::run(&local_dir, &cli.dir, change_id, require_complete),
         Command::Status { revset, all } => status::run(&local_dir, revset, all),
     }
 }
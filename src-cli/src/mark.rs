@@ -0,0 +1,50 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use kenjutu_core::services::review;
+use kenjutu_types::ChangeId;
+use serde::Serialize;
+
+use crate::resolve;
+
+#[derive(Debug, Serialize)]
+struct Output {
+    change_id: String,
+    reviewed: bool,
+    files: Vec<String>,
+}
+
+pub fn run(
+    local_dir: &Path,
+    dir: &str,
+    change_id: Option<String>,
+    files: Vec<String>,
+    unmark: bool,
+) -> Result<()> {
+    let change_id: ChangeId = match change_id {
+        Some(raw) => raw
+            .parse()
+            .map_err(|e| anyhow::anyhow!("invalid --change-id: {e}"))?,
+        None => resolve::auto_detect_change_id(local_dir)
+            .context("failed to auto-detect change_id from working copy")?,
+    };
+
+    let commit_sha = resolve::resolve_commit_sha(local_dir, change_id)
+        .with_context(|| format!("change {change_id} not found"))?;
+
+    let repo = git2::Repository::open(local_dir)
+        .with_context(|| format!("failed to open git repository at {}", dir))?;
+
+    let paths: Vec<PathBuf> = files.iter().map(PathBuf::from).collect();
+    review::set_files_reviewed(&repo, commit_sha, &paths, !unmark)
+        .map_err(|e| anyhow::anyhow!("failed to update review state: {e}"))?;
+
+    let output = Output {
+        change_id: change_id.to_string(),
+        reviewed: !unmark,
+        files,
+    };
+    println!("{}", serde_json::to_string_pretty(&output)?);
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use kenjutu_core::services::review;
+use kenjutu_types::ChangeId;
+use serde::Serialize;
+
+use crate::resolve;
+
+#[derive(Debug, Serialize)]
+struct Output {
+    change_id: String,
+    complete: bool,
+    progress: ProgressOutput,
+    reviewed: Vec<String>,
+    partially_reviewed: Vec<String>,
+    unreviewed: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ProgressOutput {
+    reviewed_files: usize,
+    partially_reviewed_files: usize,
+    unreviewed_files: usize,
+    total_lines: usize,
+    remaining_lines: usize,
+}
+
+impl From<marker_commit::ReviewProgress> for ProgressOutput {
+    fn from(p: marker_commit::ReviewProgress) -> Self {
+        Self {
+            reviewed_files: p.reviewed_files,
+            partially_reviewed_files: p.partially_reviewed_files,
+            unreviewed_files: p.unreviewed_files,
+            total_lines: p.total_lines,
+            remaining_lines: p.remaining_lines,
+        }
+    }
+}
+
+pub fn run(
+    local_dir: &Path,
+    dir: &str,
+    change_id: Option<String>,
+    require_complete: bool,
+) -> Result<()> {
+    let change_id: ChangeId = match change_id {
+        Some(raw) => raw
+            .parse()
+            .map_err(|e| anyhow::anyhow!("invalid --change-id: {e}"))?,
+        None => resolve::auto_detect_change_id(local_dir)
+            .context("failed to auto-detect change_id from working copy")?,
+    };
+
+    let commit_sha = resolve::resolve_commit_sha(local_dir, change_id)
+        .with_context(|| format!("change {change_id} not found"))?;
+
+    let repo = git2::Repository::open(local_dir)
+        .with_context(|| format!("failed to open git repository at {}", dir))?;
+
+    let state = review::change_review_state(&repo, commit_sha)
+        .map_err(|e| anyhow::anyhow!("failed to read review state: {e}"))?;
+    let complete = state.is_complete();
+
+    let output = Output {
+        change_id: state.change_id.to_string(),
+        complete,
+        progress: state.progress.into(),
+        reviewed: state.reviewed,
+        partially_reviewed: state.partially_reviewed,
+        unreviewed: state.unreviewed,
+    };
+    println!("{}", serde_json::to_string_pretty(&output)?);
+
+    if require_complete && !complete {
+        bail!("change {change_id} is not fully reviewed");
+    }
+    Ok(())
+}
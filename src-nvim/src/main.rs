@@ -1,14 +1,18 @@
 mod serve;
 
-use std::{path::PathBuf, process};
+use std::{
+    path::{Path, PathBuf},
+    process,
+};
 
 use anyhow::{Context, Result, anyhow, bail};
+use kenjutu_core::services::jj;
 
 fn main() -> Result<()> {
     let args = parse_args()?;
     match args {
         Args::Server { dir } => {
-            if let Err(e) = serve::run(&dir) {
+            if let Err(e) = run_server(&dir) {
                 let err = serde_json::json!({ "error": format!("{e:#}") });
                 eprintln!("{}", serde_json::to_string(&err).unwrap());
                 process::exit(1);
@@ -23,6 +27,12 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Check for a usable jj before serving so a missing install is reported up front.
+fn run_server(dir: &Path) -> Result<()> {
+    jj::detect()?;
+    serve::run(dir)
+}
+
 enum Args {
     Server { dir: PathBuf },
     Version,
@@ -31,8 +41,9 @@ enum Args {
 fn parse_args() -> Result<Args> {
     let mut args = std::env::args().skip(1);
     let Some(first_arg) = args.next() else {
+        let cwd = std::env::current_dir().context("invalid current directory")?;
         return Ok(Args::Server {
-            dir: PathBuf::from("."),
+            dir: workspace_root(cwd),
         });
     };
 
@@ -43,8 +54,15 @@ fn parse_args() -> Result<Args> {
                 .next()
                 .ok_or(anyhow!("--dir requires a value"))
                 .and_then(|dir| std::fs::canonicalize(&dir).context("invalid directory"))?;
-            Ok(Args::Server { dir })
+            Ok(Args::Server {
+                dir: workspace_root(dir),
+            })
         }
         _ => bail!("unknown argument {}", first_arg),
     }
 }
+
+/// Open the whole workspace when launched from a subdirectory of it.
+fn workspace_root(dir: PathBuf) -> PathBuf {
+    jj::find_workspace_root(&dir).unwrap_or(dir)
+}
@@ -3,7 +3,12 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::{Context, Result};
-use comment_commit::{CommentCommit, DiffSide, get_all_ported_comments};
+use comment_commit::{
+    CommentCommit, DiffSide, NewComment, UNKNOWN_AUTHOR, get_all_ported_comments,
+};
+use kenjutu_core::services::diff::{self, DiffConfig};
+use kenjutu_core::services::jj;
+use kenjutu_core::services::review;
 use kenjutu_types::{ChangeId, CommitId};
 use marker_commit::MarkerCommit;
 use serde::{Deserialize, Serialize};
@@ -87,8 +92,8 @@ fn dispatch(repo: &git2::Repository, local_dir: &Path, req: &Request) -> Respons
         "unmark-file" => handle_unmark(req.id, repo, &req.params),
         "set-blob" => handle_set_blob(req.id, repo, &req.params),
         "get-comments" => handle_get_comments(req.id, repo, &req.params),
-        "add-comment" => handle_add_comment(req.id, repo, &req.params),
-        "reply-to-comment" => handle_reply_to_comment(req.id, repo, &req.params),
+        "add-comment" => handle_add_comment(req.id, repo, local_dir, &req.params),
+        "reply-to-comment" => handle_reply_to_comment(req.id, repo, local_dir, &req.params),
         "edit-comment" => handle_edit_comment(req.id, repo, &req.params),
         "resolve-comment" => handle_resolve_comment(req.id, repo, &req.params),
         "unresolve-comment" => handle_unresolve_comment(req.id, repo, &req.params),
@@ -117,12 +122,18 @@ fn handle_files(
         Err(e) => return Response::err(id, format!("failed to find commit ID: {e:#}")),
     };
 
-    match kenjutu_core::services::diff::generate_file_list(repo, commit_id) {
+    // Counts only decorate the list, so an unreadable comment commit shouldn't hide the files.
+    let unresolved_comments = CommentCommit::get(repo, commit_id)
+        .map(|cc| cc.unresolved_counts())
+        .unwrap_or_default();
+
+    match diff::generate_file_list(repo, commit_id, DiffConfig::default()) {
         Ok((change_id, files)) => {
             let output = serde_json::json!({
                 "commitId": commit_id,
                 "changeId": change_id,
                 "files": files,
+                "unresolvedComments": unresolved_comments,
             });
             Response::ok(id, output)
         }
@@ -224,7 +235,9 @@ fn handle_mark(id: u64, repo: &git2::Repository, params: &serde_json::Value) ->
         Err(e) => return Response::err(id, format!("failed to get marker commit: {e}")),
     };
 
-    if let Err(e) = marker.mark_file_reviewed(&params.file, params.old_path.as_deref()) {
+    if let Err(e) =
+        review::mark_file_reviewed(repo, &mut marker, &params.file, params.old_path.as_deref())
+    {
         return Response::err(id, format!("failed to mark file reviewed: {e}"));
     }
 
@@ -278,7 +291,12 @@ fn handle_set_blob(id: u64, repo: &git2::Repository, params: &serde_json::Value)
         Err(e) => return Response::err(id, format!("failed to get marker commit: {e}")),
     };
 
-    if let Err(e) = marker.set_blob(&params.file, params.old_path.as_deref(), content) {
+    if let Err(e) = review::set_reviewed_content(
+        &mut marker,
+        &params.file,
+        params.old_path.as_deref(),
+        content,
+    ) {
         return Response::err(id, format!("failed to set blob: {e}"));
     }
 
@@ -300,7 +318,11 @@ fn handle_get_comments(id: u64, repo: &git2::Repository, params: &serde_json::Va
         Err(e) => return Response::err(id, format!("invalid params: {e}")),
     };
 
-    let ported = match get_all_ported_comments(repo, params.commit) {
+    let ported = match get_all_ported_comments(
+        repo,
+        params.commit,
+        DiffConfig::default().rename_threshold,
+    ) {
         Ok(p) => p,
         Err(e) => return Response::err(id, format!("failed to get comments: {e}")),
     };
@@ -334,7 +356,12 @@ struct AddCommentParams {
     body: String,
 }
 
-fn handle_add_comment(id: u64, repo: &git2::Repository, params: &serde_json::Value) -> Response {
+fn handle_add_comment(
+    id: u64,
+    repo: &git2::Repository,
+    local_dir: &Path,
+    params: &serde_json::Value,
+) -> Response {
     let params: AddCommentParams = match serde_json::from_value(params.clone()) {
         Ok(p) => p,
         Err(e) => return Response::err(id, format!("invalid params: {e}")),
@@ -346,12 +373,15 @@ fn handle_add_comment(id: u64, repo: &git2::Repository, params: &serde_json::Val
     };
 
     if let Err(e) = cc.create_comment(
-        params.commit,
         &params.file,
-        params.side,
-        params.line,
-        params.start_line,
-        params.body,
+        NewComment {
+            sha: params.commit,
+            side: params.side,
+            line: params.line,
+            start_line: params.start_line,
+            body: params.body,
+            author: comment_author(local_dir),
+        },
     ) {
         return Response::err(id, format!("failed to create comment: {e}"));
     }
@@ -374,6 +404,7 @@ struct ReplyToCommentParams {
 fn handle_reply_to_comment(
     id: u64,
     repo: &git2::Repository,
+    local_dir: &Path,
     params: &serde_json::Value,
 ) -> Response {
     let params: ReplyToCommentParams = match serde_json::from_value(params.clone()) {
@@ -386,7 +417,12 @@ fn handle_reply_to_comment(
         Err(e) => return Response::err(id, format!("failed to get comment commit: {e}")),
     };
 
-    if let Err(e) = cc.reply_to_comment(&params.file, params.parent_comment_id, params.body) {
+    if let Err(e) = cc.reply_to_comment(
+        &params.file,
+        params.parent_comment_id,
+        params.body,
+        comment_author(local_dir),
+    ) {
         return Response::err(id, format!("failed to reply to comment: {e}"));
     }
 
@@ -486,6 +522,10 @@ fn handle_unresolve_comment(
     Response::ok(id, serde_json::json!({ "success": true }))
 }
 
+fn comment_author(local_dir: &Path) -> String {
+    jj::get_author(local_dir).unwrap_or_else(|| UNKNOWN_AUTHOR.to_string())
+}
+
 fn find_commit_from_change_id(dir: &Path, change_id: &ChangeId) -> Result<CommitId> {
     let output = Command::new("jj")
         .args([
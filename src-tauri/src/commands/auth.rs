@@ -8,3 +8,10 @@ use crate::{models::DeviceFlowInfo, services::auth};
 pub async fn auth_github(app_handle: AppHandle) -> Result<DeviceFlowInfo> {
     Ok(auth::init_auth_flow(&app_handle).await?)
 }
+
+/// GitHub token from the `GITHUB_TOKEN` environment variable, if set.
+#[command]
+#[specta::specta]
+pub async fn get_env_github_token() -> Result<Option<String>> {
+    Ok(auth::env_token())
+}
@@ -1,13 +1,17 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use comment_commit::{CommentCommit, DiffSide, PortedComment, get_all_ported_comments};
-use kenjutu_types::CommitId;
+use comment_commit::{
+    CommentCommit, DiffSide, MaterializedComment, NewComment, PortedComment, UNKNOWN_AUTHOR,
+    get_all_ported_comments, get_stack_comments as get_stack_comment_map,
+};
+use kenjutu_types::{ChangeId, CommitId};
 use serde::Deserialize;
 use specta::Type;
-use tauri::command;
+use tauri::{State, command};
 
 use super::{Error, Result};
-use kenjutu_core::services::git;
+use crate::services::diff_settings::DiffSettingsState;
+use kenjutu_core::services::{git, jj};
 
 #[derive(Deserialize, Type)]
 pub struct AddCommentInput {
@@ -66,93 +70,111 @@ pub struct FileComments {
     pub comments: Vec<PortedComment>,
 }
 
+#[derive(Deserialize, Type)]
+pub struct GetStackCommentsInput {
+    pub local_dir: PathBuf,
+    pub change_ids: Vec<ChangeId>,
+}
+
+#[derive(serde::Serialize, Type)]
+pub struct StackFileComments {
+    pub file_path: String,
+    pub comments: Vec<MaterializedComment>,
+}
+
+#[derive(serde::Serialize, Type)]
+pub struct ChangeComments {
+    pub change_id: ChangeId,
+    pub files: Vec<StackFileComments>,
+}
+
+/// The jj identity of the local user, or [`UNKNOWN_AUTHOR`] when none is configured.
+fn comment_author(local_dir: &Path) -> String {
+    jj::get_author(local_dir).unwrap_or_else(|| UNKNOWN_AUTHOR.to_string())
+}
+
 #[command]
 #[specta::specta]
 pub async fn add_comment(input: AddCommentInput) -> Result<()> {
     let repo = git::open_repository(&input.local_dir)?;
-    let mut cc = CommentCommit::get(&repo, input.commit_id).map_err(map_comment_err)?;
-
     let file_path = PathBuf::from(&input.file_path);
-
-    cc.create_comment(
-        input.commit_id,
-        &file_path,
-        input.side,
-        input.line,
-        input.start_line,
-        input.body,
-    )
-    .map_err(map_comment_err)?;
-
-    cc.write().map_err(map_comment_err)?;
-    Ok(())
+    let author = comment_author(&input.local_dir);
+
+    CommentCommit::with_session(&repo, input.commit_id, |cc| {
+        cc.create_comment(
+            &file_path,
+            NewComment {
+                sha: input.commit_id,
+                side: input.side,
+                line: input.line,
+                start_line: input.start_line,
+                body: input.body,
+                author,
+            },
+        )
+    })
+    .map_err(map_comment_err)
 }
 
 #[command]
 #[specta::specta]
 pub async fn reply_to_comment(input: ReplyToCommentInput) -> Result<()> {
     let repo = git::open_repository(&input.local_dir)?;
-    let mut cc = CommentCommit::get(&repo, input.commit_id).map_err(map_comment_err)?;
-
     let file_path = PathBuf::from(&input.file_path);
+    let author = comment_author(&input.local_dir);
 
-    cc.reply_to_comment(&file_path, input.parent_comment_id, input.body)
-        .map_err(map_comment_err)?;
-
-    cc.write().map_err(map_comment_err)?;
-    Ok(())
+    CommentCommit::with_session(&repo, input.commit_id, |cc| {
+        cc.reply_to_comment(&file_path, input.parent_comment_id, input.body, author)
+    })
+    .map_err(map_comment_err)
 }
 
 #[command]
 #[specta::specta]
 pub async fn edit_comment(input: EditCommentInput) -> Result<()> {
     let repo = git::open_repository(&input.local_dir)?;
-    let mut cc = CommentCommit::get(&repo, input.commit_id).map_err(map_comment_err)?;
-
     let file_path = PathBuf::from(&input.file_path);
 
-    cc.edit_comment(&file_path, input.comment_id, input.body)
-        .map_err(map_comment_err)?;
-
-    cc.write().map_err(map_comment_err)?;
-    Ok(())
+    CommentCommit::with_session(&repo, input.commit_id, |cc| {
+        cc.edit_comment(&file_path, input.comment_id, input.body)
+    })
+    .map_err(map_comment_err)
 }
 
 #[command]
 #[specta::specta]
 pub async fn resolve_comment(input: ResolveCommentInput) -> Result<()> {
     let repo = git::open_repository(&input.local_dir)?;
-    let mut cc = CommentCommit::get(&repo, input.commit_id).map_err(map_comment_err)?;
-
     let file_path = PathBuf::from(&input.file_path);
 
-    cc.resolve_comment(&file_path, input.comment_id)
-        .map_err(map_comment_err)?;
-
-    cc.write().map_err(map_comment_err)?;
-    Ok(())
+    CommentCommit::with_session(&repo, input.commit_id, |cc| {
+        cc.resolve_comment(&file_path, input.comment_id)
+    })
+    .map_err(map_comment_err)
 }
 
 #[command]
 #[specta::specta]
 pub async fn unresolve_comment(input: UnresolveCommentInput) -> Result<()> {
     let repo = git::open_repository(&input.local_dir)?;
-    let mut cc = CommentCommit::get(&repo, input.commit_id).map_err(map_comment_err)?;
-
     let file_path = PathBuf::from(&input.file_path);
 
-    cc.unresolve_comment(&file_path, input.comment_id)
-        .map_err(map_comment_err)?;
-
-    cc.write().map_err(map_comment_err)?;
-    Ok(())
+    CommentCommit::with_session(&repo, input.commit_id, |cc| {
+        cc.unresolve_comment(&file_path, input.comment_id)
+    })
+    .map_err(map_comment_err)
 }
 
 #[command]
 #[specta::specta]
-pub async fn get_comments(input: GetCommentsInput) -> Result<Vec<FileComments>> {
+pub async fn get_comments(
+    settings: State<'_, DiffSettingsState>,
+    input: GetCommentsInput,
+) -> Result<Vec<FileComments>> {
     let repo = git::open_repository(&input.local_dir)?;
-    let ported = get_all_ported_comments(&repo, input.commit_id).map_err(map_comment_err)?;
+    let rename_threshold = settings.config_for(&input.local_dir).rename_threshold;
+    let ported = get_all_ported_comments(&repo, input.commit_id, rename_threshold)
+        .map_err(map_comment_err)?;
 
     let mut result: Vec<FileComments> = ported
         .into_iter()
@@ -167,6 +189,37 @@ pub async fn get_comments(input: GetCommentsInput) -> Result<Vec<FileComments>>
     Ok(result)
 }
 
+/// Comment threads of every change in a stack, in the order of `change_ids`. Comments are not
+/// ported; each stays anchored to the revision it was written on.
+#[command]
+#[specta::specta]
+pub async fn get_stack_comments(input: GetStackCommentsInput) -> Result<Vec<ChangeComments>> {
+    let repo = git::open_repository(&input.local_dir)?;
+    let mut by_change = get_stack_comment_map(&repo, &input.change_ids).map_err(map_comment_err)?;
+
+    Ok(input
+        .change_ids
+        .iter()
+        .map(|change_id| {
+            let mut files: Vec<StackFileComments> = by_change
+                .remove(change_id)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|(_, comments)| !comments.is_empty())
+                .map(|(path, comments)| StackFileComments {
+                    file_path: path.to_string_lossy().to_string(),
+                    comments,
+                })
+                .collect();
+            files.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+            ChangeComments {
+                change_id: *change_id,
+                files,
+            }
+        })
+        .collect())
+}
+
 fn map_comment_err(err: comment_commit::Error) -> Error {
     Error::CommentCommit {
         message: err.to_string(),
@@ -14,17 +14,41 @@ pub async fn get_jj_status(local_dir: PathBuf) -> Result<JjStatus> {
     Ok(jj::get_status(&local_dir))
 }
 
-/// Get mutable commits from jj log with graph layout
+/// Get commits from jj log with graph layout. Defaults to the mutable stack when `revset` is
+/// empty or omitted.
 #[command]
 #[specta::specta]
-pub async fn get_jj_log(local_dir: PathBuf) -> Result<CommitGraph> {
+pub async fn get_jj_log(local_dir: PathBuf, revset: Option<String>) -> Result<CommitGraph> {
     if !jj::is_installed() {
         return Err(Error::bad_input("Jujutsu (jj) is not installed"));
     }
     if !jj::is_jj_repo(&local_dir) {
         return Err(Error::bad_input("Directory is not a jj repository"));
     }
-    Ok(graph::get_log_graph(&local_dir)?)
+    Ok(graph::get_log_graph(&local_dir, revset.as_deref())?)
+}
+
+/// Like `get_jj_log`, but returns at most the first `limit` commits.
+///
+/// The log view pages by asking again with a larger limit.
+#[command]
+#[specta::specta]
+pub async fn get_jj_log_paged(
+    local_dir: PathBuf,
+    revset: Option<String>,
+    limit: u32,
+) -> Result<CommitGraph> {
+    if !jj::is_installed() {
+        return Err(Error::bad_input("Jujutsu (jj) is not installed"));
+    }
+    if !jj::is_jj_repo(&local_dir) {
+        return Err(Error::bad_input("Directory is not a jj repository"));
+    }
+    Ok(graph::get_log_graph_limited(
+        &local_dir,
+        revset.as_deref(),
+        limit as usize,
+    )?)
 }
 
 /// Describe (set the commit message of) a jj revision.
@@ -43,3 +67,29 @@ pub async fn describe_commit(
     }
     Ok(jj::describe(&local_dir, change_id, &message)?)
 }
+
+/// Make a jj revision the working-copy commit (`jj edit`).
+#[command]
+#[specta::specta]
+pub async fn edit_commit(local_dir: PathBuf, change_id: ChangeId) -> Result<()> {
+    if !jj::is_installed() {
+        return Err(Error::bad_input("Jujutsu (jj) is not installed"));
+    }
+    if !jj::is_jj_repo(&local_dir) {
+        return Err(Error::bad_input("Directory is not a jj repository"));
+    }
+    Ok(jj::edit(&local_dir, change_id)?)
+}
+
+/// Start a new working-copy commit on top of a jj revision (`jj new`).
+#[command]
+#[specta::specta]
+pub async fn new_commit(local_dir: PathBuf, change_id: ChangeId) -> Result<()> {
+    if !jj::is_installed() {
+        return Err(Error::bad_input("Jujutsu (jj) is not installed"));
+    }
+    if !jj::is_jj_repo(&local_dir) {
+        return Err(Error::bad_input("Directory is not a jj repository"));
+    }
+    Ok(jj::new_revision(&local_dir, change_id)?)
+}
@@ -10,14 +10,17 @@ pub use comments::*;
 pub use jj::*;
 pub use pr::*;
 pub use repo::*;
-pub use settings::{get_ssh_settings, set_ssh_settings};
+pub use settings::{
+    get_diff_settings, get_keybindings, get_repo_settings, get_ssh_settings, list_highlight_themes,
+    set_diff_settings, set_repo_settings, set_ssh_settings,
+};
 
 use serde::Serialize;
 use specta::Type;
 
 use crate::services::auth as auth_svc;
-use kenjutu_core::services::{diff, git, jj as jj_svc};
-use kenjutu_types::InvalidChangeIdError;
+use kenjutu_core::services::{diff, editor, git, jj as jj_svc, repo_settings, review};
+use kenjutu_types::{InvalidChangeIdError, InvalidCommitIdError};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -39,6 +42,12 @@ pub enum Error {
     #[error("File not found: {path}")]
     FileNotFound { path: String },
 
+    #[error("File is too large to preview: {size} bytes (max {max})")]
+    BlobTooLarge { size: u64, max: u64 },
+
+    #[error("File is too large to highlight: {size} bytes (max {max})")]
+    DiffTooLarge { size: u64, max: u64 },
+
     #[error("Internal error")]
     Internal,
 
@@ -83,6 +92,8 @@ impl From<diff::Error> for Error {
         log::error!("Diff error: {err}");
         match err {
             diff::Error::FileNotFound(path) => Error::FileNotFound { path },
+            diff::Error::BlobTooLarge { size, max } => Error::BlobTooLarge { size, max },
+            diff::Error::DiffTooLarge { size, max } => Error::DiffTooLarge { size, max },
             diff::Error::Git(e) => e.into(),
             diff::Error::Git2(e) => Error::Git {
                 message: e.message().to_string(),
@@ -90,6 +101,7 @@ impl From<diff::Error> for Error {
             diff::Error::MarkerCommit(e) => Error::MarkerCommit {
                 message: e.to_string(),
             },
+            e @ diff::Error::UnresolvedConflict(_) => Error::bad_input(e.to_string()),
             diff::Error::Internal(msg) => {
                 log::error!("Internal diff error: {msg}");
                 Error::Internal
@@ -98,6 +110,19 @@ impl From<diff::Error> for Error {
     }
 }
 
+impl From<editor::Error> for Error {
+    fn from(err: editor::Error) -> Self {
+        log::error!("Editor error: {err}");
+        match err {
+            editor::Error::NoEditor => Error::bad_input(err.to_string()),
+            editor::Error::FileNotFound(path) => Error::FileNotFound {
+                path: path.display().to_string(),
+            },
+            editor::Error::Spawn(_) => Error::Internal,
+        }
+    }
+}
+
 impl From<auth_svc::Error> for Error {
     fn from(err: auth_svc::Error) -> Self {
         log::error!("Auth error: {err}");
@@ -111,6 +136,9 @@ impl From<jj_svc::Error> for Error {
     fn from(err: jj_svc::Error) -> Self {
         log::error!("Jj error: {err}");
         match err {
+            jj_svc::Error::NotInstalled => Error::Jj {
+                message: jj_svc::Error::NotInstalled.to_string(),
+            },
             jj_svc::Error::Command(msg) => Error::Jj {
                 message: format!("Failed to run jj: {msg}"),
             },
@@ -118,6 +146,48 @@ impl From<jj_svc::Error> for Error {
                 message: format!("Failed to run jj: {msg}"),
             },
             jj_svc::Error::Parse(_) => Error::Internal,
+            jj_svc::Error::InvalidId(message) => Error::BadInput { message },
+        }
+    }
+}
+
+impl From<repo_settings::Error> for Error {
+    fn from(err: repo_settings::Error) -> Self {
+        log::error!("Repository settings error: {err}");
+        match err {
+            repo_settings::Error::NotAWorkspace(_) => Error::Repository {
+                message: err.to_string(),
+            },
+            repo_settings::Error::Parse(_) => Error::bad_input(err.to_string()),
+            repo_settings::Error::Io(_) | repo_settings::Error::Serialize(_) => Error::Internal,
+        }
+    }
+}
+
+impl From<review::Error> for Error {
+    fn from(err: review::Error) -> Self {
+        match err {
+            review::Error::Revision { source, .. } | review::Error::Diff(source) => source.into(),
+            review::Error::ChangeMismatch { .. }
+            | review::Error::DuplicateChange { .. }
+            | review::Error::InvalidState(_)
+            | review::Error::FileNotInChange { .. } => {
+                log::error!("Review error: {err}");
+                Error::bad_input(err.to_string())
+            }
+            review::Error::Git2(e) => {
+                log::error!("Review error: {e}");
+                Error::Git {
+                    message: e.message().to_string(),
+                }
+            }
+            review::Error::MarkerCommit(e) => e.into(),
+            review::Error::CommentCommit(e) => {
+                log::error!("Comment commit error: {e}");
+                Error::CommentCommit {
+                    message: e.to_string(),
+                }
+            }
         }
     }
 }
@@ -131,6 +201,15 @@ impl From<InvalidChangeIdError> for Error {
     }
 }
 
+impl From<InvalidCommitIdError> for Error {
+    fn from(err: InvalidCommitIdError) -> Self {
+        log::error!("Invalid commit ID error: {err}");
+        Error::BadInput {
+            message: format!("Invalid commit ID: {err}"),
+        }
+    }
+}
+
 impl From<marker_commit::Error> for Error {
     fn from(err: marker_commit::Error) -> Self {
         log::error!("Marker commit error: {err}");
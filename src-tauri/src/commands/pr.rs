@@ -1,14 +1,23 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use kenjutu_types::{ChangeId, CommitId};
+use kenjutu_types::{ChangeId, CommitChangeIdExt, CommitId};
 use marker_commit::MarkerCommit;
-use tauri::{AppHandle, command};
+use tauri::{AppHandle, State, command};
 
-use super::Result;
-use crate::models::{CommitFileList, DiffLine, RegionId};
+use super::{Error, Result};
+use crate::models::{
+    BlameLine, CommitFileList, DiffLine, FileChangeStatus, GeneratedFilesMode, PrLink, RegionId,
+    ReviewStatus,
+};
+use crate::services::diff_settings::DiffSettingsState;
+use crate::services::pr_links::{find_pr_for_change, save_pr_link};
 use crate::services::ssh::AppSshCredentials;
-use kenjutu_core::services::diff::PartialReviewDiffs;
+use kenjutu_core::services::blame::BlameCache;
+use kenjutu_core::services::diff::{
+    BinaryDiff, BlobSide, DEFAULT_MAX_BLOB_SIZE, DiffCache, EncodedBlob, PartialReviewDiffs,
+};
 use kenjutu_core::services::git::get_or_fetch_commit;
+use kenjutu_core::services::review::{self, ChangeProgress};
 use kenjutu_core::services::{diff, git};
 
 #[command]
@@ -38,33 +47,56 @@ pub async fn toggle_file_reviewed(
     file_path: String,
     old_path: Option<String>,
     is_reviewed: bool,
-) -> Result<()> {
+) -> Result<String> {
     let repo = git::open_repository(&local_dir)?;
     let mut marker_commit = MarkerCommit::get(&repo, sha)?;
+    let previous_tree = marker_commit.marker_tree().id();
 
     let file_path = PathBuf::from(file_path);
     let old_path = old_path.map(PathBuf::from);
     let old_path = old_path.as_ref().map(|path| path.as_ref());
 
     if is_reviewed {
-        marker_commit.mark_file_reviewed(&file_path, old_path)?;
+        review::mark_file_reviewed(&repo, &mut marker_commit, &file_path, old_path)?;
     } else {
         marker_commit.unmark_file_reviewed(&file_path, old_path)?;
     }
     marker_commit.write()?;
 
-    Ok(())
+    Ok(previous_tree.to_string())
+}
+
+#[command]
+#[specta::specta]
+pub async fn export_unified_diff(
+    settings: State<'_, DiffSettingsState>,
+    local_dir: PathBuf,
+    commit_sha: CommitId,
+) -> Result<String> {
+    let repository = git::open_repository(&local_dir)?;
+    let config = settings.config_for(&local_dir);
+    Ok(diff::export_unified_diff(&repository, commit_sha, config)?)
 }
 
 #[command]
 #[specta::specta]
 pub async fn get_commit_file_list(
+    settings: State<'_, DiffSettingsState>,
     local_dir: PathBuf,
     commit_sha: CommitId,
 ) -> Result<CommitFileList> {
     let repository = git::open_repository(&local_dir)?;
 
-    let (change_id, files) = diff::generate_file_list(&repository, commit_sha)?;
+    let (change_id, mut files) =
+        diff::generate_file_list(&repository, commit_sha, settings.config_for(&local_dir))?;
+
+    let (generated, mode) = settings.generated_files_for(&local_dir);
+    generated.tag(&mut files);
+    if mode == GeneratedFilesMode::Hide {
+        files.retain(|file| !file.generated);
+    }
+    let (sort, group_by_dir) = settings.file_sort();
+    diff::sort_files(&mut files, sort, group_by_dir);
 
     Ok(CommitFileList {
         commit_sha,
@@ -73,6 +105,47 @@ pub async fn get_commit_file_list(
     })
 }
 
+/// Mark every generated file that still has unreviewed changes as reviewed, returning the
+/// marker tree from before so the marks can be undone in one step.
+#[command]
+#[specta::specta]
+pub async fn mark_generated_files_reviewed(
+    settings: State<'_, DiffSettingsState>,
+    local_dir: PathBuf,
+    commit_sha: CommitId,
+) -> Result<String> {
+    let repository = git::open_repository(&local_dir)?;
+
+    let (_, mut files) =
+        diff::generate_file_list(&repository, commit_sha, settings.config_for(&local_dir))?;
+    let (generated, _) = settings.generated_files_for(&local_dir);
+    generated.tag(&mut files);
+
+    let mut marker_commit = MarkerCommit::get(&repository, commit_sha)?;
+    let previous_tree = marker_commit.marker_tree().id();
+
+    let pending = files.iter().filter(|file| {
+        file.generated
+            && matches!(
+                file.review_status,
+                ReviewStatus::Unreviewed | ReviewStatus::PartiallyReviewed
+            )
+    });
+    for file in pending {
+        let Some(path) = file.new_path.as_deref().or(file.old_path.as_deref()) else {
+            continue;
+        };
+        let old_path = match file.status {
+            FileChangeStatus::Renamed => file.old_path.as_deref().map(Path::new),
+            _ => None,
+        };
+        review::mark_file_reviewed(&repository, &mut marker_commit, Path::new(path), old_path)?;
+    }
+    marker_commit.write()?;
+
+    Ok(previous_tree.to_string())
+}
+
 #[command]
 #[specta::specta]
 pub async fn get_change_id_from_sha(
@@ -85,22 +158,117 @@ pub async fn get_change_id_from_sha(
     let remote_urls: Vec<&str> = remote_urls.iter().map(|s| s.as_str()).collect();
     let creds = AppSshCredentials::from_state(&app);
     let commit = get_or_fetch_commit(&repository, sha, &remote_urls, &creds)?;
-    Ok(git::get_change_id(&commit))
+    Ok(Some(commit.change_id()))
+}
+
+/// Remember which jj change a GitHub PR is reviewed as, so the PR can be found again from
+/// the change.
+#[command]
+#[specta::specta]
+pub async fn link_pr_to_change(
+    app: AppHandle,
+    owner: String,
+    repo: String,
+    pr_number: u32,
+    change_id: ChangeId,
+) -> Result<()> {
+    let link = PrLink {
+        owner,
+        repo,
+        pr_number,
+        change_id,
+    };
+    save_pr_link(&app, &link).map_err(|_| Error::Internal)?;
+    Ok(())
+}
+
+#[command]
+#[specta::specta]
+pub async fn get_pr_for_change(app: AppHandle, change_id: ChangeId) -> Result<Option<PrLink>> {
+    find_pr_for_change(&app, change_id).map_err(|_| Error::Internal)
+}
+
+/// Whether a review has been started locally for `commit_sha`. Commits that haven't been
+/// fetched yet have no review, so this never touches the network.
+#[command]
+#[specta::specta]
+pub async fn has_local_review(local_dir: PathBuf, commit_sha: CommitId) -> Result<bool> {
+    let repository = git::open_repository(&local_dir)?;
+    let Ok(commit) = repository.find_commit(commit_sha.oid()) else {
+        return Ok(false);
+    };
+    Ok(MarkerCommit::exists(&repository, commit.change_id())?)
+}
+
+/// Files reviewed and open comment threads for each revision in `shas`, in order.
+#[command]
+#[specta::specta]
+pub async fn get_review_summary(
+    local_dir: PathBuf,
+    shas: Vec<CommitId>,
+) -> Result<Vec<ChangeProgress>> {
+    let repository = git::open_repository(&local_dir)?;
+    Ok(review::overall_progress(&repository, &shas)?)
 }
 
 #[command]
 #[specta::specta]
 pub async fn get_partial_review_diffs(
+    cache: State<'_, DiffCache>,
+    settings: State<'_, DiffSettingsState>,
     local_dir: PathBuf,
     commit_sha: CommitId,
     file_path: String,
     old_path: Option<String>,
+    force_plain: bool,
 ) -> Result<PartialReviewDiffs> {
     let repository = git::open_repository(&local_dir)?;
     let file_path = PathBuf::from(file_path);
     let old_path = old_path.map(PathBuf::from);
+    let mut config = settings.config_for(&local_dir);
+    // Set when the user asks to see a file that was too large to highlight.
+    config.plain |= force_plain;
+
+    Ok(diff::generate_partial_review_diffs_cached(
+        &repository,
+        &cache,
+        commit_sha,
+        &file_path,
+        old_path.as_deref(),
+        config,
+    )?)
+}
+
+/// Who last changed each line of the base version of a file, for annotating context lines.
+#[command]
+#[specta::specta]
+pub async fn get_blame(
+    cache: State<'_, BlameCache>,
+    local_dir: PathBuf,
+    commit_sha: CommitId,
+    file_path: String,
+    old_path: Option<String>,
+) -> Result<Vec<BlameLine>> {
+    let repository = git::open_repository(&local_dir)?;
+    let file_path = PathBuf::from(file_path);
+    let old_path = old_path.map(PathBuf::from);
 
-    Ok(diff::generate_partial_review_diffs(
+    Ok(cache.get_or_compute(&repository, commit_sha, &file_path, old_path.as_deref())?)
+}
+
+#[command]
+#[specta::specta]
+pub async fn get_binary_diff(
+    local_dir: PathBuf,
+    commit_sha: CommitId,
+    file_path: String,
+    old_path: Option<String>,
+) -> Result<BinaryDiff> {
+    let repository = git::open_repository(&local_dir)?;
+    let file_path = PathBuf::from(file_path);
+    let old_path = old_path.map(PathBuf::from);
+
+    Ok(diff::generate_binary_diff(
         &repository,
         commit_sha,
         &file_path,
@@ -108,17 +276,45 @@ pub async fn get_partial_review_diffs(
     )?)
 }
 
+/// Load one side of a file as base64 for previews. `max_size` defaults to
+/// [`DEFAULT_MAX_BLOB_SIZE`] bytes.
+#[command]
+#[specta::specta]
+pub async fn get_blob_base64(
+    local_dir: PathBuf,
+    commit_sha: CommitId,
+    file_path: String,
+    side: BlobSide,
+    max_size: Option<u64>,
+) -> Result<EncodedBlob> {
+    let repository = git::open_repository(&local_dir)?;
+    let file_path = PathBuf::from(file_path);
+
+    Ok(diff::get_blob_base64(
+        &repository,
+        commit_sha,
+        &file_path,
+        side,
+        max_size.unwrap_or(DEFAULT_MAX_BLOB_SIZE),
+    )?)
+}
+
 #[command]
 #[specta::specta]
 pub async fn get_context_lines(
+    settings: State<'_, DiffSettingsState>,
     local_dir: PathBuf,
     commit_sha: CommitId,
     file_path: String,
     start_line: u32,
     end_line: u32,
     old_start_line: u32,
+    force_plain: bool,
 ) -> Result<Vec<DiffLine>> {
     let repository = git::open_repository(&local_dir)?;
+    let mut config = settings.config_for(&local_dir);
+    // Matches the hunks around them when the file was shown plain for being too large.
+    config.plain |= force_plain;
 
     Ok(diff::get_context_lines(
         &repository,
@@ -127,6 +323,7 @@ pub async fn get_context_lines(
         start_line,
         end_line,
         old_start_line,
+        config,
     )?)
 }
 
@@ -138,18 +335,25 @@ pub async fn mark_region_reviewed(
     file_path: String,
     old_path: Option<String>,
     region: RegionId,
-) -> Result<()> {
+) -> Result<String> {
     let repo = git::open_repository(&local_dir)?;
     let mut marker_commit = MarkerCommit::get(&repo, sha)?;
+    let previous_tree = marker_commit.marker_tree().id();
 
     let file_path = PathBuf::from(file_path);
     let old_path = old_path.map(PathBuf::from);
     let region: marker_commit::RegionId = region.into();
 
-    marker_commit.mark_region_reviewed(&file_path, old_path.as_deref(), &region)?;
+    review::mark_region_reviewed(
+        &repo,
+        &mut marker_commit,
+        &file_path,
+        old_path.as_deref(),
+        &region,
+    )?;
     marker_commit.write()?;
 
-    Ok(())
+    Ok(previous_tree.to_string())
 }
 
 #[command]
@@ -160,9 +364,10 @@ pub async fn unmark_region_reviewed(
     file_path: String,
     old_path: Option<String>,
     region: RegionId,
-) -> Result<()> {
+) -> Result<String> {
     let repo = git::open_repository(&local_dir)?;
     let mut marker_commit = MarkerCommit::get(&repo, sha)?;
+    let previous_tree = marker_commit.marker_tree().id();
 
     let file_path = PathBuf::from(file_path);
     let old_path = old_path.map(PathBuf::from);
@@ -171,5 +376,19 @@ pub async fn unmark_region_reviewed(
     marker_commit.unmark_region_reviewed(&file_path, old_path.as_deref(), &region)?;
     marker_commit.write()?;
 
+    Ok(previous_tree.to_string())
+}
+
+/// Put back a marker tree returned by one of the marking commands, undoing that mark. The
+/// restored tree is written as a new marker commit.
+#[command]
+#[specta::specta]
+pub async fn restore_marker_tree(local_dir: PathBuf, sha: CommitId, tree_id: String) -> Result<()> {
+    let repo = git::open_repository(&local_dir)?;
+    let tree_id = git2::Oid::from_str(&tree_id)
+        .map_err(|_| Error::bad_input(format!("Invalid tree id: {tree_id}")))?;
+    let mut marker_commit = MarkerCommit::get(&repo, sha)?;
+    marker_commit.restore_tree(tree_id)?;
+    marker_commit.write()?;
     Ok(())
 }
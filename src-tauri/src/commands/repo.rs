@@ -1,8 +1,11 @@
 use std::path::PathBuf;
 
-use tauri::command;
+use kenjutu_core::services::editor;
+use tauri::{AppHandle, Manager, command};
+use tauri_plugin_opener::OpenerExt;
 
 use super::{Error, Result};
+use crate::services::diff_settings::DiffSettingsState;
 
 /// Validate that a directory is a git repository.
 /// This is called from the frontend before saving the local path.
@@ -17,3 +20,39 @@ pub async fn validate_git_repo(local_dir: PathBuf) -> Result<()> {
     }
     Ok(())
 }
+
+/// Open a working-copy file in the configured editor command, at `line` when given.
+///
+/// Without a configured command the file goes to the OS default application, since a GUI
+/// app has no terminal to run `$EDITOR` in.
+#[command]
+#[specta::specta]
+pub async fn open_in_editor(
+    app: AppHandle,
+    local_dir: PathBuf,
+    file_path: String,
+    line: Option<u32>,
+) -> Result<()> {
+    let file_path = PathBuf::from(file_path);
+    let command = app
+        .state::<DiffSettingsState>()
+        .0
+        .lock()
+        .map_err(|_| Error::Internal)?
+        .editor_command
+        .trim()
+        .to_string();
+    if !command.is_empty() {
+        editor::open_in_editor(&local_dir, &file_path, line, &command)?;
+        return Ok(());
+    }
+
+    let path = editor::working_copy_path(&local_dir, &file_path)?;
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|e| {
+            log::error!("Failed to open {}: {e}", path.display());
+            Error::Internal
+        })?;
+    Ok(())
+}
@@ -1,7 +1,15 @@
-use tauri::{AppHandle, Manager, command};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use kenjutu_core::services::diff::DiffCache;
+use kenjutu_core::services::highlight::HighlightService;
+use kenjutu_core::services::repo_settings::{self, RepoSettings};
+use tauri::{AppHandle, Manager, State, command};
 
 use super::{Error, Result};
-use crate::models::SshSettings;
+use crate::models::{DiffSettings, SshSettings};
+use crate::services::diff_settings::{DiffSettingsState, save_diff_settings};
+use crate::services::keybindings::load_keybindings;
 use crate::services::ssh::{SshSettingsState, save_ssh_settings};
 
 #[command]
@@ -18,3 +26,55 @@ pub async fn set_ssh_settings(app: AppHandle, settings: SshSettings) -> Result<(
     save_ssh_settings(&app, &settings).map_err(|_| Error::Internal)?;
     Ok(())
 }
+
+#[command]
+#[specta::specta]
+pub async fn get_diff_settings(app: AppHandle) -> Result<DiffSettings> {
+    let state = app.state::<DiffSettingsState>();
+    let settings = state.0.lock().map_err(|_| Error::Internal)?;
+    Ok(settings.clone())
+}
+
+#[command]
+#[specta::specta]
+pub async fn set_diff_settings(app: AppHandle, settings: DiffSettings) -> Result<()> {
+    save_diff_settings(&app, &settings).map_err(|_| Error::Internal)?;
+    Ok(())
+}
+
+#[command]
+#[specta::specta]
+pub async fn list_highlight_themes() -> Result<Vec<String>> {
+    Ok(HighlightService::global()
+        .list_themes()
+        .into_iter()
+        .map(String::from)
+        .collect())
+}
+
+/// Overrides from the repository's `.jj/kenjutu/settings.toml`.
+#[command]
+#[specta::specta]
+pub async fn get_repo_settings(local_dir: PathBuf) -> Result<RepoSettings> {
+    Ok(repo_settings::load(&local_dir)?)
+}
+
+#[command]
+#[specta::specta]
+pub async fn set_repo_settings(
+    cache: State<'_, DiffCache>,
+    local_dir: PathBuf,
+    settings: RepoSettings,
+) -> Result<()> {
+    repo_settings::save(&local_dir, &settings)?;
+    // Cached entries are keyed on the config, so they would never be hit again.
+    cache.clear();
+    Ok(())
+}
+
+/// Keybinding overrides from `keybindings.toml`, re-read on every call so edits apply on reload.
+#[command]
+#[specta::specta]
+pub async fn get_keybindings(app: AppHandle) -> Result<HashMap<String, String>> {
+    Ok(load_keybindings(&app))
+}
@@ -1,14 +1,24 @@
 use std::sync::Mutex;
 
+use kenjutu_core::services::blame::BlameCache;
+use kenjutu_core::services::diff::DiffCache;
 use tauri::Manager;
 
 use crate::commands::{
-    add_comment, auth_github, describe_commit, edit_comment, get_change_id_from_sha, get_comments,
-    get_commit_file_list, get_commits_in_range, get_context_lines, get_jj_log, get_jj_status,
-    get_partial_review_diffs, get_ssh_settings, mark_region_reviewed, reply_to_comment,
-    resolve_comment, set_ssh_settings, toggle_file_reviewed, unmark_region_reviewed,
+    add_comment, auth_github, describe_commit, edit_comment, edit_commit, export_unified_diff,
+    get_binary_diff, get_blame, get_blob_base64, get_change_id_from_sha, get_comments,
+    get_commit_file_list, get_commits_in_range, get_context_lines, get_diff_settings,
+    get_env_github_token, get_jj_log, get_jj_log_paged, get_jj_status, get_keybindings,
+    get_partial_review_diffs, get_pr_for_change, get_repo_settings, get_review_summary,
+    get_ssh_settings, get_stack_comments, has_local_review, link_pr_to_change,
+    list_highlight_themes, mark_generated_files_reviewed, mark_region_reviewed, new_commit,
+    open_in_editor, reply_to_comment, resolve_comment, restore_marker_tree, set_diff_settings,
+    set_repo_settings, set_ssh_settings, toggle_file_reviewed, unmark_region_reviewed,
     unresolve_comment, validate_git_repo,
 };
+use crate::services::app_data::ensure_app_data_dir;
+use crate::services::diff_settings::{DiffSettingsState, load_diff_settings};
+use crate::services::logging::log_builder;
 use crate::services::ssh::{SshSettingsState, load_ssh_settings};
 
 mod commands;
@@ -28,23 +38,21 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
 
     builder
         .plugin(tauri_plugin_opener::init())
-        .plugin(
-            tauri_plugin_log::Builder::new()
-                .level(tauri_plugin_log::log::LevelFilter::Info)
-                .build(),
-        )
+        .plugin(log_builder().build())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_store::Builder::new().build())
         .setup(|app| {
             log::info!("Application starting up - logging initialized");
-            let app_dir = app.path().app_data_dir()?;
-
-            std::fs::create_dir_all(&app_dir)
-                .map_err(|err| format!("Failed to create data directory: {}", err))?;
+            ensure_app_data_dir(&app.path().app_data_dir()?);
 
             let ssh_settings = load_ssh_settings(app.handle());
             log::info!("Loaded SSH settings: {:?}", ssh_settings);
             app.manage(SshSettingsState(Mutex::new(ssh_settings)));
+            let diff_settings = load_diff_settings(app.handle());
+            diff_settings.apply_theme();
+            app.manage(DiffSettingsState(Mutex::new(diff_settings)));
+            app.manage(DiffCache::default());
+            app.manage(BlameCache::default());
 
             Ok(())
         })
@@ -53,18 +61,40 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
             auth_github,
             describe_commit,
             edit_comment,
+            edit_commit,
+            export_unified_diff,
+            get_binary_diff,
+            get_blame,
+            get_blob_base64,
             get_change_id_from_sha,
             get_commit_file_list,
             get_comments,
             get_commits_in_range,
             get_context_lines,
+            get_diff_settings,
+            get_env_github_token,
             get_jj_log,
+            get_jj_log_paged,
             get_jj_status,
+            get_keybindings,
             get_partial_review_diffs,
+            get_pr_for_change,
+            get_repo_settings,
+            get_review_summary,
             get_ssh_settings,
+            get_stack_comments,
+            has_local_review,
+            link_pr_to_change,
+            list_highlight_themes,
+            mark_generated_files_reviewed,
             mark_region_reviewed,
+            new_commit,
+            open_in_editor,
             reply_to_comment,
             resolve_comment,
+            restore_marker_tree,
+            set_diff_settings,
+            set_repo_settings,
             set_ssh_settings,
             toggle_file_reviewed,
             unmark_region_reviewed,
@@ -84,18 +114,40 @@ pub fn gen_ts_bindings() {
             auth_github,
             describe_commit,
             edit_comment,
+            edit_commit,
+            export_unified_diff,
+            get_binary_diff,
+            get_blame,
+            get_blob_base64,
             get_change_id_from_sha,
             get_commit_file_list,
             get_comments,
             get_commits_in_range,
             get_context_lines,
+            get_diff_settings,
+            get_env_github_token,
             get_jj_log,
+            get_jj_log_paged,
             get_jj_status,
+            get_keybindings,
             get_partial_review_diffs,
+            get_pr_for_change,
+            get_repo_settings,
+            get_review_summary,
             get_ssh_settings,
+            get_stack_comments,
+            has_local_review,
+            link_pr_to_change,
+            list_highlight_themes,
+            mark_generated_files_reviewed,
             mark_region_reviewed,
+            new_commit,
+            open_in_editor,
             reply_to_comment,
             resolve_comment,
+            restore_marker_tree,
+            set_diff_settings,
+            set_repo_settings,
             set_ssh_settings,
             toggle_file_reviewed,
             unmark_region_reviewed,
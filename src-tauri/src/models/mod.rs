@@ -1,6 +1,8 @@
 mod auth;
+mod pr_link;
 mod settings;
 
 pub use auth::*;
 pub use kenjutu_core::models::*;
+pub use pr_link::*;
 pub use settings::*;
@@ -0,0 +1,13 @@
+use kenjutu_types::ChangeId;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// A GitHub pull request linked to the jj change it was reviewed as.
+#[derive(Debug, Serialize, Deserialize, Type, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrLink {
+    pub owner: String,
+    pub repo: String,
+    pub pr_number: u32,
+    pub change_id: ChangeId,
+}
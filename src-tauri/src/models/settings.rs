@@ -1,3 +1,8 @@
+use kenjutu_core::services::diff::{
+    DEFAULT_CONTEXT_LINES, DEFAULT_GENERATED_GLOBS, DEFAULT_RENAME_THRESHOLD, DiffAlgorithm,
+    DiffConfig, FileSort,
+};
+use kenjutu_core::services::highlight::HighlightService;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
@@ -8,3 +13,96 @@ pub struct SshSettings {
     /// If set, this private key path is tried first before auto-detection.
     pub private_key_path: Option<String>,
 }
+
+const DEFAULT_TAB_WIDTH: u32 = 4;
+
+/// What the file list does with files matching the generated-file globs.
+#[derive(Debug, Serialize, Deserialize, Type, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum GeneratedFilesMode {
+    /// List them with a "generated" badge.
+    #[default]
+    Show,
+    /// Leave them out of the file list.
+    Hide,
+    /// List them with a button that marks all of them reviewed at once.
+    MarkReviewed,
+}
+
+/// Diff display settings stored in Tauri plugin-store and managed as app state.
+#[derive(Debug, Serialize, Deserialize, Type, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DiffSettings {
+    /// Unchanged lines shown around each hunk.
+    pub context_lines: u32,
+    /// Line-matching algorithm used to compute hunks.
+    pub algorithm: DiffAlgorithm,
+    /// Render diffs without syntax colors or word-level highlights.
+    pub plain: bool,
+    /// Hide changes that only touch whitespace.
+    pub ignore_whitespace: bool,
+    /// Syntax highlighting theme, one of the names from `list_highlight_themes`.
+    pub highlight_theme: String,
+    /// Columns between tab stops when displaying tabs.
+    pub tab_width: u32,
+    /// Show tabs and trailing spaces as visible glyphs.
+    pub show_whitespace: bool,
+    /// Minimum similarity (0-100) for a deleted and an added file to show as a rename.
+    pub rename_threshold: u16,
+    /// Also pair new files with a similar modified file as copies.
+    pub detect_copies: bool,
+    /// Globs (matched against repo-relative paths) for lockfiles and other generated files.
+    pub generated_globs: Vec<String>,
+    pub generated_files: GeneratedFilesMode,
+    /// Order of the file list.
+    pub file_sort: FileSort,
+    /// Keep files of the same directory together, sorted by `file_sort` within it.
+    pub group_files_by_dir: bool,
+    /// Command for "open in editor", with optional `{file}` and `{line}` placeholders.
+    /// Empty opens files with the OS default application.
+    pub editor_command: String,
+}
+
+impl Default for DiffSettings {
+    fn default() -> Self {
+        Self {
+            context_lines: DEFAULT_CONTEXT_LINES,
+            algorithm: DiffAlgorithm::default(),
+            plain: false,
+            ignore_whitespace: false,
+            highlight_theme: HighlightService::default_theme_name().to_string(),
+            tab_width: DEFAULT_TAB_WIDTH,
+            show_whitespace: false,
+            rename_threshold: DEFAULT_RENAME_THRESHOLD,
+            detect_copies: false,
+            generated_globs: DEFAULT_GENERATED_GLOBS
+                .iter()
+                .map(|g| g.to_string())
+                .collect(),
+            generated_files: GeneratedFilesMode::default(),
+            file_sort: FileSort::default(),
+            group_files_by_dir: false,
+            editor_command: String::new(),
+        }
+    }
+}
+
+impl DiffSettings {
+    /// Unknown names keep the current theme, so a theme dropped from two-face falls back
+    /// to the default on startup.
+    pub fn apply_theme(&self) {
+        HighlightService::global().set_theme(&self.highlight_theme);
+    }
+
+    pub fn diff_config(&self) -> DiffConfig {
+        DiffConfig {
+            context_lines: self.context_lines,
+            algorithm: self.algorithm,
+            plain: self.plain,
+            ignore_whitespace: self.ignore_whitespace,
+            rename_threshold: self.rename_threshold,
+            find_copies: self.detect_copies,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use std::path::Path;
+
+/// Create the app data directory, logging instead of failing when it can't be created.
+///
+/// A missing or read-only data directory only breaks persisting PR links and settings,
+/// which fail per command, so startup carries on either way. Returns whether the
+/// directory is usable.
+pub fn ensure_app_data_dir(dir: &Path) -> bool {
+    match std::fs::create_dir_all(dir) {
+        Ok(()) => true,
+        Err(err) => {
+            log::error!("Failed to create data directory {}: {err}", dir.display());
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creates_missing_directories() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("a").join("b");
+
+        assert!(ensure_app_data_dir(&dir));
+        assert!(dir.is_dir());
+    }
+
+    #[test]
+    fn unwritable_location_is_reported_not_fatal() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("not-a-dir");
+        std::fs::write(&file, "").unwrap();
+
+        assert!(!ensure_app_data_dir(&file.join("data")));
+    }
+}
@@ -16,6 +16,7 @@ pub enum Error {
 const DEVICE_CODE_URL: &str = "https://github.com/login/device/code";
 const TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
 const CLIENT_ID: &str = "Iv23licutsPQwDRYefce";
+const TOKEN_ENV_VAR: &str = "GITHUB_TOKEN";
 
 #[derive(Debug, Deserialize)]
 struct DeviceCodeResponse {
@@ -32,6 +33,15 @@ struct TokenResponse {
     error: Option<String>,
 }
 
+/// Token from the `GITHUB_TOKEN` environment variable, which takes precedence over the token
+/// stored by the device flow. Blank values count as unset.
+pub fn env_token() -> Option<String> {
+    std::env::var(TOKEN_ENV_VAR)
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
 pub async fn init_auth_flow(app_handle: &AppHandle) -> Result<DeviceFlowInfo> {
     log::info!("Initializing GitHub Device Flow");
 
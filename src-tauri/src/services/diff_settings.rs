@@ -0,0 +1,93 @@
+use std::path::Path;
+use std::sync::Mutex;
+
+use kenjutu_core::services::diff::{DiffCache, DiffConfig, FileSort, GeneratedFiles};
+use kenjutu_core::services::repo_settings::{self, RepoSettings};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::models::{DiffSettings, GeneratedFilesMode};
+use crate::services::ssh::SETTINGS_STORE;
+
+const DIFF_SETTINGS_KEY: &str = "diff";
+
+pub struct DiffSettingsState(pub Mutex<DiffSettings>);
+
+impl DiffSettingsState {
+    /// Config for the file list and per-file diffs. Both must use the same value so the region
+    /// ids the UI sends back match the hunks they were computed from.
+    pub fn config(&self) -> DiffConfig {
+        self.0.lock().map(|s| s.diff_config()).unwrap_or_default()
+    }
+
+    /// Like [`Self::config`], with the overrides from the repository's settings file applied.
+    pub fn config_for(&self, local_dir: &Path) -> DiffConfig {
+        repo_settings_or_default(local_dir).diff_config(self.config())
+    }
+
+    /// Order of the file list and whether it is grouped by directory.
+    pub fn file_sort(&self) -> (FileSort, bool) {
+        self.0
+            .lock()
+            .map(|s| (s.file_sort, s.group_files_by_dir))
+            .unwrap_or_default()
+    }
+
+    /// Globs for generated files and what the file list should do with them. The repository's
+    /// own globs, if it sets any, replace the global list.
+    pub fn generated_files_for(&self, local_dir: &Path) -> (GeneratedFiles, GeneratedFilesMode) {
+        let repo = repo_settings_or_default(local_dir);
+        self.0
+            .lock()
+            .map(|s| (repo.generated_files(&s.generated_globs), s.generated_files))
+            .unwrap_or_default()
+    }
+}
+
+/// A broken settings file should not stop diffs from loading, so fall back to the globals.
+fn repo_settings_or_default(local_dir: &Path) -> RepoSettings {
+    repo_settings::load(local_dir).unwrap_or_else(|e| {
+        log::warn!(
+            "Ignoring repository settings in {}: {e}",
+            local_dir.display()
+        );
+        RepoSettings::default()
+    })
+}
+
+pub fn load_diff_settings(app: &AppHandle) -> DiffSettings {
+    let store = app.store(SETTINGS_STORE);
+    match store {
+        Ok(store) => store
+            .get(DIFF_SETTINGS_KEY)
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default(),
+        Err(e) => {
+            log::warn!("Failed to open settings store: {e}");
+            DiffSettings::default()
+        }
+    }
+}
+
+pub fn save_diff_settings(
+    app: &AppHandle,
+    settings: &DiffSettings,
+) -> Result<(), DiffSettingsError> {
+    let store = app.store(SETTINGS_STORE).map_err(|_| DiffSettingsError)?;
+    let value = serde_json::to_value(settings).map_err(|_| DiffSettingsError)?;
+    store.set(DIFF_SETTINGS_KEY, value);
+    store.save().map_err(|_| DiffSettingsError)?;
+
+    let state = app.state::<DiffSettingsState>();
+    let mut current = state.0.lock().map_err(|_| DiffSettingsError)?;
+    *current = settings.clone();
+    settings.apply_theme();
+
+    // Cached entries are keyed on the config, so they would never be hit again.
+    app.state::<DiffCache>().clear();
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct DiffSettingsError;
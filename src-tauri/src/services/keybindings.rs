@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use tauri::{AppHandle, Manager};
+
+const KEYBINDINGS_FILE: &str = "keybindings.toml";
+
+/// Keybinding overrides from `keybindings.toml` in the app config dir, as action name → hotkey.
+///
+/// A missing file means no overrides. An unreadable or invalid file is logged and ignored, so
+/// the UI keeps its default bindings rather than failing to start.
+pub fn load_keybindings(app: &AppHandle) -> HashMap<String, String> {
+    match app.path().app_config_dir() {
+        Ok(dir) => read_keybindings(&dir.join(KEYBINDINGS_FILE)),
+        Err(e) => {
+            log::warn!("Failed to resolve config dir for keybindings: {e}");
+            HashMap::new()
+        }
+    }
+}
+
+fn read_keybindings(path: &Path) -> HashMap<String, String> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return HashMap::new(),
+        Err(e) => {
+            log::warn!("Failed to read {}: {e}", path.display());
+            return HashMap::new();
+        }
+    };
+    let table: toml::Table = match toml::from_str(&content) {
+        Ok(table) => table,
+        Err(e) => {
+            log::warn!("Ignoring invalid {}: {e}", path.display());
+            return HashMap::new();
+        }
+    };
+    // A binding that isn't a string is skipped on its own, so one typo doesn't drop the rest.
+    table
+        .into_iter()
+        .filter_map(|(action, value)| match value {
+            toml::Value::String(hotkey) => Some((action, hotkey)),
+            other => {
+                log::warn!(
+                    "Ignoring keybinding {action} in {}: expected a string, got {}",
+                    path.display(),
+                    other.type_str()
+                );
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(content: &str) -> HashMap<String, String> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(KEYBINDINGS_FILE);
+        std::fs::write(&path, content).unwrap();
+        read_keybindings(&path)
+    }
+
+    #[test]
+    fn reads_overrides() {
+        let bindings = read("comment = \"Shift+C\"\nopen_in_editor = \"Mod+E\"\n");
+        assert_eq!(bindings.len(), 2);
+        assert_eq!(bindings["comment"], "Shift+C");
+        assert_eq!(bindings["open_in_editor"], "Mod+E");
+    }
+
+    #[test]
+    fn empty_file_has_no_overrides() {
+        assert!(read("").is_empty());
+    }
+
+    #[test]
+    fn missing_file_has_no_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_keybindings(&dir.path().join(KEYBINDINGS_FILE)).is_empty());
+    }
+
+    #[test]
+    fn invalid_toml_is_ignored() {
+        assert!(read("comment = \"Shift+C\"\nopen_in_editor = ").is_empty());
+    }
+
+    #[test]
+    fn non_string_bindings_are_skipped() {
+        let bindings = read("comment = \"Shift+C\"\ncursor_down = 5\n[nested]\nkey = \"x\"\n");
+        assert_eq!(
+            bindings,
+            HashMap::from([("comment".to_string(), "Shift+C".to_string())])
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use std::path::PathBuf;
+
+use tauri_plugin_log::log::LevelFilter;
+use tauri_plugin_log::{Target, TargetKind};
+
+const LOG_LEVEL_ENV: &str = "KENJUTU_LOG";
+const LOG_DIR_ENV: &str = "KENJUTU_LOG_DIR";
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::Info;
+
+/// Parse a level name such as `debug` or `WARN`, ignoring case and surrounding whitespace.
+pub fn parse_log_level(level: &str) -> Option<LevelFilter> {
+    level.trim().parse().ok()
+}
+
+/// Logger configured from the environment.
+///
+/// `KENJUTU_LOG` sets the level (`off`, `error`, `warn`, `info`, `debug`, `trace`; default
+/// `info`) and `KENJUTU_LOG_DIR` writes the log file to that directory instead of the
+/// platform log dir. Runs before the logger exists, so a bad value is reported on stderr.
+pub fn log_builder() -> tauri_plugin_log::Builder {
+    let level = match std::env::var(LOG_LEVEL_ENV) {
+        Ok(value) => parse_log_level(&value).unwrap_or_else(|| {
+            eprintln!("Ignoring invalid {LOG_LEVEL_ENV}={value:?}, using {DEFAULT_LEVEL}");
+            DEFAULT_LEVEL
+        }),
+        Err(_) => DEFAULT_LEVEL,
+    };
+
+    let file_target = match std::env::var_os(LOG_DIR_ENV) {
+        Some(dir) if !dir.is_empty() => TargetKind::Folder {
+            path: PathBuf::from(dir),
+            file_name: None,
+        },
+        _ => TargetKind::LogDir { file_name: None },
+    };
+
+    tauri_plugin_log::Builder::new()
+        .level(level)
+        .targets([Target::new(TargetKind::Stdout), Target::new(file_target)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_level() {
+        assert_eq!(parse_log_level("off"), Some(LevelFilter::Off));
+        assert_eq!(parse_log_level("error"), Some(LevelFilter::Error));
+        assert_eq!(parse_log_level("warn"), Some(LevelFilter::Warn));
+        assert_eq!(parse_log_level("info"), Some(LevelFilter::Info));
+        assert_eq!(parse_log_level("debug"), Some(LevelFilter::Debug));
+        assert_eq!(parse_log_level("trace"), Some(LevelFilter::Trace));
+    }
+
+    #[test]
+    fn ignores_case_and_surrounding_whitespace() {
+        assert_eq!(parse_log_level("WARN"), Some(LevelFilter::Warn));
+        assert_eq!(parse_log_level("  Debug\n"), Some(LevelFilter::Debug));
+    }
+
+    #[test]
+    fn rejects_unknown_levels() {
+        assert_eq!(parse_log_level(""), None);
+        assert_eq!(parse_log_level("verbose"), None);
+        assert_eq!(parse_log_level("3"), None);
+    }
+}
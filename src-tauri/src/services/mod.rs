@@ -1,2 +1,7 @@
+pub mod app_data;
 pub mod auth;
+pub mod diff_settings;
+pub mod keybindings;
+pub mod logging;
+pub mod pr_links;
 pub mod ssh;
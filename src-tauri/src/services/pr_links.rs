@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use kenjutu_types::ChangeId;
+use tauri::{AppHandle, Wry};
+use tauri_plugin_store::{Store, StoreExt};
+
+use crate::models::PrLink;
+
+const PR_LINKS_STORE: &str = "pr_links.json";
+
+fn link_key(owner: &str, repo: &str, pr_number: u32) -> String {
+    format!("{owner}/{repo}#{pr_number}")
+}
+
+/// Record that `owner/repo#pr_number` is reviewed as `change_id`. Re-linking a PR replaces
+/// the previous change, since a force-push can give the head a different change id.
+pub fn save_pr_link(app: &AppHandle, link: &PrLink) -> Result<(), PrLinkError> {
+    let store = open_store(app)?;
+    let value = serde_json::to_value(link).map_err(|_| PrLinkError)?;
+    store.set(link_key(&link.owner, &link.repo, link.pr_number), value);
+    store.save().map_err(|err| {
+        log::error!("Failed to save {PR_LINKS_STORE}: {err}");
+        PrLinkError
+    })?;
+    Ok(())
+}
+
+/// The PR linked to `change_id`, if any. When several PRs point at the same change the
+/// highest-numbered one wins, as it is the most recently opened.
+pub fn find_pr_for_change(
+    app: &AppHandle,
+    change_id: ChangeId,
+) -> Result<Option<PrLink>, PrLinkError> {
+    let store = open_store(app)?;
+    Ok(latest_link_for(store.values(), change_id))
+}
+
+/// Pick the highest-numbered link to `change_id` from stored values, skipping entries that
+/// no longer parse.
+fn latest_link_for(
+    values: impl IntoIterator<Item = serde_json::Value>,
+    change_id: ChangeId,
+) -> Option<PrLink> {
+    values
+        .into_iter()
+        .filter_map(|v| serde_json::from_value::<PrLink>(v).ok())
+        .filter(|link| link.change_id == change_id)
+        .max_by_key(|link| link.pr_number)
+}
+
+/// Open the PR link store, logging why it failed. A locked or corrupt file surfaces as an
+/// internal error from the calling command instead of taking down the app.
+fn open_store(app: &AppHandle) -> Result<Arc<Store<Wry>>, PrLinkError> {
+    app.store(PR_LINKS_STORE).map_err(|err| {
+        log::error!("Failed to open {PR_LINKS_STORE}: {err}");
+        PrLinkError
+    })
+}
+
+#[derive(Debug)]
+pub struct PrLinkError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(pr_number: u32, change_id: &str) -> PrLink {
+        PrLink {
+            owner: "octo".to_string(),
+            repo: "kenjutu".to_string(),
+            pr_number,
+            change_id: ChangeId::parse(change_id).unwrap(),
+        }
+    }
+
+    const CHANGE_A: &str = "kkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkk";
+    const CHANGE_B: &str = "zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz";
+
+    #[test]
+    fn saved_link_round_trips() {
+        let saved = link(7, CHANGE_A);
+        let value = serde_json::to_value(&saved).unwrap();
+
+        let found = latest_link_for([value], saved.change_id);
+
+        assert_eq!(found, Some(saved));
+    }
+
+    #[test]
+    fn highest_pr_wins_and_other_changes_are_ignored() {
+        let values = [link(3, CHANGE_A), link(9, CHANGE_A), link(12, CHANGE_B)]
+            .into_iter()
+            .map(|l| serde_json::to_value(l).unwrap())
+            .chain([serde_json::json!({ "corrupt": true })]);
+
+        let found = latest_link_for(values, ChangeId::parse(CHANGE_A).unwrap());
+
+        assert_eq!(found, Some(link(9, CHANGE_A)));
+    }
+
+    #[test]
+    fn key_names_the_pr() {
+        assert_eq!(link_key("octo", "kenjutu", 7), "octo/kenjutu#7");
+    }
+}
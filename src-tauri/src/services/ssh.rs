@@ -7,7 +7,7 @@ use tauri_plugin_store::StoreExt;
 use crate::models::SshSettings;
 use kenjutu_core::services::git::{SshCredential, SshCredentialProvider};
 
-const SETTINGS_STORE: &str = "settings.json";
+pub(crate) const SETTINGS_STORE: &str = "settings.json";
 const SSH_SETTINGS_KEY: &str = "ssh";
 const DEFAULT_KEY_NAMES: &[&str] = &["id_ed25519", "id_ecdsa", "id_rsa"];
 
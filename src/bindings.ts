@@ -56,6 +56,105 @@ export const commands = {
       else return { status: "error", error: e as any }
     }
   },
+  /**
+   * Make a jj revision the working-copy commit (`jj edit`).
+   */
+  async editCommit(
+    localDir: string,
+    changeId: string,
+  ): Promise<Result<null, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("edit_commit", { localDir, changeId }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  async exportUnifiedDiff(
+    localDir: string,
+    commitSha: string,
+  ): Promise<Result<string, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("export_unified_diff", { localDir, commitSha }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  async getBinaryDiff(
+    localDir: string,
+    commitSha: string,
+    filePath: string,
+    oldPath: string | null,
+  ): Promise<Result<BinaryDiff, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_binary_diff", {
+          localDir,
+          commitSha,
+          filePath,
+          oldPath,
+        }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  /**
+   * Who last changed each line of the base version of a file, for annotating context lines.
+   */
+  async getBlame(
+    localDir: string,
+    commitSha: string,
+    filePath: string,
+    oldPath: string | null,
+  ): Promise<Result<BlameLine[], Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_blame", {
+          localDir,
+          commitSha,
+          filePath,
+          oldPath,
+        }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  async getBlobBase64(
+    localDir: string,
+    commitSha: string,
+    filePath: string,
+    side: BlobSide,
+    maxSize: number | null,
+  ): Promise<Result<EncodedBlob, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_blob_base64", {
+          localDir,
+          commitSha,
+          filePath,
+          side,
+          maxSize,
+        }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
   async getChangeIdFromSha(
     localDir: string,
     sha: string,
@@ -133,6 +232,7 @@ export const commands = {
     startLine: number,
     endLine: number,
     oldStartLine: number,
+    forcePlain: boolean,
   ): Promise<Result<DiffLine[], Error>> {
     try {
       return {
@@ -144,6 +244,7 @@ export const commands = {
           startLine,
           endLine,
           oldStartLine,
+          forcePlain,
         }),
       }
     } catch (e) {
@@ -152,13 +253,63 @@ export const commands = {
     }
   },
   /**
-   * Get mutable commits from jj log with graph layout
+   * Get commits from jj log with graph layout. Defaults to the mutable stack when `revset` is
+   * empty or omitted.
    */
-  async getJjLog(localDir: string): Promise<Result<CommitGraph, Error>> {
+  async getDiffSettings(): Promise<Result<DiffSettings, Error>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("get_diff_settings") }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  /**
+   * GitHub token from the `GITHUB_TOKEN` environment variable, if set.
+   */
+  async getEnvGithubToken(): Promise<Result<string | null, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_env_github_token"),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  async getJjLog(
+    localDir: string,
+    revset: string | null,
+  ): Promise<Result<CommitGraph, Error>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("get_jj_log", { localDir }),
+        data: await TAURI_INVOKE("get_jj_log", { localDir, revset }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  /**
+   * Like `get_jj_log`, but returns at most the first `limit` commits.
+   *
+   * The log view pages by asking again with a larger limit.
+   */
+  async getJjLogPaged(
+    localDir: string,
+    revset: string | null,
+    limit: number,
+  ): Promise<Result<CommitGraph, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_jj_log_paged", {
+          localDir,
+          revset,
+          limit,
+        }),
       }
     } catch (e) {
       if (e instanceof Error) throw e
@@ -179,11 +330,25 @@ export const commands = {
       else return { status: "error", error: e as any }
     }
   },
+  /**
+   * Keybinding overrides from `keybindings.toml`, re-read on every call so edits apply on reload.
+   */
+  async getKeybindings(): Promise<
+    Result<Partial<{ [key in string]: string }>, Error>
+  > {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("get_keybindings") }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
   async getPartialReviewDiffs(
     localDir: string,
     commitSha: string,
     filePath: string,
     oldPath: string | null,
+    forcePlain: boolean,
   ): Promise<Result<PartialReviewDiffs, Error>> {
     try {
       return {
@@ -193,6 +358,7 @@ export const commands = {
           commitSha,
           filePath,
           oldPath,
+          forcePlain,
         }),
       }
     } catch (e) {
@@ -200,6 +366,52 @@ export const commands = {
       else return { status: "error", error: e as any }
     }
   },
+  async getPrForChange(
+    changeId: string,
+  ): Promise<Result<PrLink | null, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_pr_for_change", { changeId }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  /**
+   * Overrides from the repository's `.jj/kenjutu/settings.toml`.
+   */
+  async getRepoSettings(
+    localDir: string,
+  ): Promise<Result<RepoSettings, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_repo_settings", { localDir }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  /**
+   * Files reviewed and open comment threads for each revision in `shas`, in order.
+   */
+  async getReviewSummary(
+    localDir: string,
+    shas: string[],
+  ): Promise<Result<ChangeProgress[], Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_review_summary", { localDir, shas }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
   async getSshSettings(): Promise<Result<SshSettings, Error>> {
     try {
       return { status: "ok", data: await TAURI_INVOKE("get_ssh_settings") }
@@ -208,13 +420,105 @@ export const commands = {
       else return { status: "error", error: e as any }
     }
   },
+  /**
+   * Comment threads of every change in a stack, in the order of `change_ids`. Comments are not
+   * ported; each stays anchored to the revision it was written on.
+   */
+  async getStackComments(
+    input: GetStackCommentsInput,
+  ): Promise<Result<ChangeComments[], Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_stack_comments", { input }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  /**
+   * Whether a review has been started locally for `commit_sha`. Commits that haven't been
+   * fetched yet have no review, so this never touches the network.
+   */
+  async hasLocalReview(
+    localDir: string,
+    commitSha: string,
+  ): Promise<Result<boolean, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("has_local_review", { localDir, commitSha }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  /**
+   * Remember which jj change a GitHub PR is reviewed as, so the PR can be found again from
+   * the change.
+   */
+  async linkPrToChange(
+    owner: string,
+    repo: string,
+    prNumber: number,
+    changeId: string,
+  ): Promise<Result<null, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("link_pr_to_change", {
+          owner,
+          repo,
+          prNumber,
+          changeId,
+        }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  async listHighlightThemes(): Promise<Result<string[], Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("list_highlight_themes"),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  /**
+   * Mark every generated file that still has unreviewed changes as reviewed, returning the
+   * marker tree from before so the marks can be undone in one step.
+   */
+  async markGeneratedFilesReviewed(
+    localDir: string,
+    commitSha: string,
+  ): Promise<Result<string, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("mark_generated_files_reviewed", {
+          localDir,
+          commitSha,
+        }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
   async markRegionReviewed(
     localDir: string,
     sha: string,
     filePath: string,
     oldPath: string | null,
     region: RegionId,
-  ): Promise<Result<null, Error>> {
+  ): Promise<Result<string, Error>> {
     try {
       return {
         status: "ok",
@@ -231,6 +535,48 @@ export const commands = {
       else return { status: "error", error: e as any }
     }
   },
+  /**
+   * Start a new working-copy commit on top of a jj revision (`jj new`).
+   */
+  async newCommit(
+    localDir: string,
+    changeId: string,
+  ): Promise<Result<null, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("new_commit", { localDir, changeId }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  /**
+   * Open a working-copy file in the configured editor command, at `line` when given.
+   *
+   * Without a configured command the file goes to the OS default application, since a GUI
+   * app has no terminal to run `$EDITOR` in.
+   */
+  async openInEditor(
+    localDir: string,
+    filePath: string,
+    line: number | null,
+  ): Promise<Result<null, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("open_in_editor", {
+          localDir,
+          filePath,
+          line,
+        }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
   async replyToComment(
     input: ReplyToCommentInput,
   ): Promise<Result<null, Error>> {
@@ -257,6 +603,54 @@ export const commands = {
       else return { status: "error", error: e as any }
     }
   },
+  /**
+   * Put back a marker tree returned by one of the marking commands, undoing that mark. The
+   * restored tree is written as a new marker commit.
+   */
+  async restoreMarkerTree(
+    localDir: string,
+    sha: string,
+    treeId: string,
+  ): Promise<Result<null, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("restore_marker_tree", {
+          localDir,
+          sha,
+          treeId,
+        }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  async setDiffSettings(settings: DiffSettings): Promise<Result<null, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("set_diff_settings", { settings }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
+  async setRepoSettings(
+    localDir: string,
+    settings: RepoSettings,
+  ): Promise<Result<null, Error>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("set_repo_settings", { localDir, settings }),
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: "error", error: e as any }
+    }
+  },
   async setSshSettings(settings: SshSettings): Promise<Result<null, Error>> {
     try {
       return {
@@ -274,7 +668,7 @@ export const commands = {
     filePath: string,
     oldPath: string | null,
     isReviewed: boolean,
-  ): Promise<Result<null, Error>> {
+  ): Promise<Result<string, Error>> {
     try {
       return {
         status: "ok",
@@ -297,7 +691,7 @@ export const commands = {
     filePath: string,
     oldPath: string | null,
     region: RegionId,
-  ): Promise<Result<null, Error>> {
+  ): Promise<Result<string, Error>> {
     try {
       return {
         status: "ok",
@@ -376,6 +770,90 @@ export type AnchorContext = {
    */
   after: string[]
 }
+/**
+ * How a comment's anchor lines up with the file it is displayed against.
+ */
+export type AnchorStatus =
+  /**
+   * The anchored lines are still at the stored line.
+   */
+  | "Exact"
+  /**
+   * The anchored lines moved; holds the new line.
+   */
+  | { Shifted: number }
+  /**
+   * The anchored lines could not be found.
+   */
+  | "Orphaned"
+/**
+ * Size and blob summary shown in place of a text diff for binary files.
+ *
+ * Either side is `None` when the file does not exist there (added or deleted files).
+ */
+export type BinaryDiff = {
+  /**
+   * Size in bytes of the base blob.
+   */
+  oldSize: number | null
+  /**
+   * Size in bytes of the target blob.
+   */
+  newSize: number | null
+  oldOid: string | null
+  newOid: string | null
+  /**
+   * Whether the base and target blobs differ.
+   */
+  changed: boolean
+}
+/**
+ * Who last changed a line of the file before the reviewed commit.
+ */
+export type BlameLine = {
+  /**
+   * 1-based line number in the base version of the file
+   */
+  line: number
+  /**
+   * Abbreviated id of the commit that last changed the line
+   */
+  shortId: string
+  author: string
+}
+/**
+ * Which side of the change to read a blob from.
+ */
+export type BlobSide =
+  /**
+   * The base tree (the parent of the commit).
+   */
+  | "Old"
+  /**
+   * The commit's own tree.
+   */
+  | "New"
+export type ChangeComments = {
+  change_id: string
+  files: StackFileComments[]
+}
+/**
+ * Review progress of one change, for dashboards that summarize a whole stack.
+ */
+export type ChangeProgress = {
+  changeId: string
+  commitId: string
+  /**
+   * First line of the commit message.
+   */
+  summary: string
+  reviewedFiles: number
+  totalFiles: number
+  /**
+   * Unresolved comment threads across all files.
+   */
+  openComments: number
+}
 /**
  * Response for get_commit_file_list command
  */
@@ -421,8 +899,30 @@ export type CommitRow = {
    * connecting to this commit.
    */
   passingColumns: number[]
+  /**
+   * Intermediate fork columns (the `┬`s in `├─┬─╮`) below an octopus merge.
+   * Empty for commits with fewer than three parents.
+   */
+  mergeSpread: number[]
 }
 export type DeviceFlowInfo = { userCode: string; verificationUri: string }
+/**
+ * Line-matching algorithm used to compute hunks.
+ */
+export type DiffAlgorithm =
+  /**
+   * git's default.
+   */
+  | "myers"
+  /**
+   * Myers, spending extra time to find the smallest possible diff.
+   */
+  | "minimal"
+  /**
+   * Anchors on lines that are unique to both sides, which keeps reformatted or moved code
+   * from being matched against unrelated braces and blank lines.
+   */
+  | "patience"
 export type DiffHunk = {
   oldStart: number
   oldLines: number
@@ -430,6 +930,14 @@ export type DiffHunk = {
   newLines: number
   header: string
   lines: DiffLine[]
+  /**
+   * True if the removed and added lines only differ in whitespace
+   */
+  whitespaceOnly: boolean
+  /**
+   * True if the new side of the hunk contains jj conflict markers
+   */
+  hasConflict: boolean
 }
 export type DiffLine = {
   lineType: DiffLineType
@@ -443,6 +951,65 @@ export type DiffLineType =
   | "deletion"
   | "addeofnl"
   | "deleofnl"
+/**
+ * Diff display settings stored in Tauri plugin-store and managed as app state.
+ */
+export type DiffSettings = {
+  /**
+   * Unchanged lines shown around each hunk.
+   */
+  contextLines: number
+  /**
+   * Line-matching algorithm used to compute hunks.
+   */
+  algorithm: DiffAlgorithm
+  /**
+   * Render diffs without syntax colors or word-level highlights.
+   */
+  plain: boolean
+  /**
+   * Hide changes that only touch whitespace.
+   */
+  ignoreWhitespace: boolean
+  /**
+   * Syntax highlighting theme, one of the names from `list_highlight_themes`.
+   */
+  highlightTheme: string
+  /**
+   * Columns between tab stops when displaying tabs.
+   */
+  tabWidth: number
+  /**
+   * Show tabs and trailing spaces as visible glyphs.
+   */
+  showWhitespace: boolean
+  /**
+   * Minimum similarity (0-100) for a deleted and an added file to show as a rename.
+   */
+  renameThreshold: number
+  /**
+   * Also pair new files with a similar modified file as copies.
+   */
+  detectCopies: boolean
+  /**
+   * Globs (matched against repo-relative paths) for lockfiles and other generated files.
+   */
+  generatedGlobs: string[]
+  generatedFiles: GeneratedFilesMode
+  /**
+   * Order of the file list.
+   */
+  fileSort: FileSort
+  /**
+   * Keep files of the same directory together, sorted by `file_sort` within it.
+   */
+  groupFilesByDir: boolean
+  /**
+   * Command for "open in editor", with optional `{file}` and `{line}` placeholders.
+   * Empty opens files with the OS default application.
+   */
+  editorCommand: string
+}
 /**
  * Which side of the diff the comment is attached to.
  */
@@ -492,12 +1059,24 @@ export type ElisionRow = {
    */
   passingColumns: number[]
 }
+/**
+ * A blob encoded for embedding in the UI as a `data:` URL.
+ */
+export type EncodedBlob = {
+  /**
+   * MIME type guessed from the file extension.
+   */
+  mime: string
+  base64: string
+}
 export type Error =
   | { type: "BadInput"; message: string }
   | { type: "Repository"; message: string }
   | { type: "Git"; message: string }
   | { type: "Jj"; message: string }
   | { type: "FileNotFound"; path: string }
+  | { type: "BlobTooLarge"; size: number; max: number }
+  | { type: "DiffTooLarge"; size: number; max: number }
   | { type: "Internal" }
   | { type: "MarkerCommit"; message: string }
   | { type: "CommentCommit"; message: string }
@@ -528,8 +1107,46 @@ export type FileEntry = {
   deletions: number
   isBinary: boolean
   reviewStatus: ReviewStatus
+  /**
+   * Matches a generated-file glob (lockfiles, build output). Set by
+   * [`GeneratedFiles::tag`](crate::services::diff::GeneratedFiles::tag).
+   */
+  generated: boolean
 }
+/**
+ * Order of the file list. libgit2 emits deltas in its own order, which reviewers rarely want.
+ */
+export type FileSort =
+  /**
+   * Alphabetical by path.
+   */
+  | "path"
+  /**
+   * Added, modified, renamed and copied, type changes, then deletions.
+   */
+  | "status"
+  /**
+   * Most changed lines first.
+   */
+  | "changeSize"
+/**
+ * What the file list does with files matching the generated-file globs.
+ */
+export type GeneratedFilesMode =
+  /**
+   * List them with a "generated" badge.
+   */
+  | "show"
+  /**
+   * Leave them out of the file list.
+   */
+  | "hide"
+  /**
+   * List them with a button that marks all of them reviewed at once.
+   */
+  | "markReviewed"
 export type GetCommentsInput = { local_dir: string; commit_id: string }
+export type GetStackCommentsInput = { local_dir: string; change_ids: string[] }
 /**
  * An edge from a commit to a parent (or to an elision marker)
  */
@@ -570,6 +1187,10 @@ export type HighlightToken = {
    * True if this token is part of a character-level change (for inline diff highlighting)
    */
   changed: boolean
+  /**
+   * True if the whole line was moved within its hunk rather than changed
+   */
+  moved: boolean
 }
 /**
  * A commit from jj log output (for frontend consumption)
@@ -587,13 +1208,25 @@ export type JjCommit = {
   description: string
   author: string
   email: string
+  /**
+   * CSS color derived from `email`, stable across runs
+   */
+  authorColor: string
   timestamp: string
   isImmutable: boolean
   isWorkingCopy: boolean
+  /**
+   * The commit has unresolved conflicts
+   */
+  hasConflict: boolean
   /**
    * Parent change_ids (for graph edges) - supports multiple parents for merges
    */
   parents: string[]
+  /**
+   * Local bookmark names pointing at this commit
+   */
+  bookmarks: string[]
 }
 /**
  * Status of jj availability
@@ -605,7 +1238,7 @@ export type JjStatus = { isInstalled: boolean; isJjRepo: boolean }
 export type MaterializedComment = {
   id: string
   /**
-   * The commit SHA this comment was originally anchored to.
+   * The commit SHA this comment is anchored to, moved forward by reanchoring.
    */
   target_sha: string
   side: DiffSide
@@ -613,7 +1246,16 @@ export type MaterializedComment = {
   start_line: number | null
   body: string
   anchor: AnchorContext
+  author: string
   resolved: boolean
+  /**
+   * The anchor could not be found after the commit was rewritten.
+   */
+  orphaned: boolean
+  /**
+   * The thread was deleted. Hidden from the default views.
+   */
+  deleted: boolean
   created_at: string
   updated_at: string
   edit_count: number
@@ -625,9 +1267,14 @@ export type MaterializedComment = {
 export type MaterializedReply = {
   id: string
   body: string
+  author: string
   created_at: string
   updated_at: string
   edit_count: number
+  /**
+   * Replies to this reply, in creation order.
+   */
+  replies: MaterializedReply[]
 }
 export type PRCommit = {
   changeId: string
@@ -644,6 +1291,11 @@ export type PartialReviewDiffs = {
    * diff(B→M) — already reviewed changes
    */
   reviewed: FileDiff
+  /**
+   * Where the file lives in the marker tree. For a rename this stays the old path until the
+   * first region is reviewed, so `reviewed` is old→marker path and `remaining` marker→new.
+   */
+  markerPath: string
 }
 /**
  * A materialized comment with ported line numbers for display on a different commit.
@@ -656,6 +1308,16 @@ export type PortedComment = {
    * Whether this comment was ported from a different commit SHA.
    */
   is_ported: boolean
+  anchor_status: AnchorStatus
+}
+/**
+ * A GitHub pull request linked to the jj change it was reviewed as.
+ */
+export type PrLink = {
+  owner: string
+  repo: string
+  prNumber: number
+  changeId: string
 }
 /**
  * Identifies a region in a diff by its header coordinates.
@@ -673,6 +1335,36 @@ export type ReplyToCommentInput = {
   parent_comment_id: string
   body: string
 }
+/**
+ * Per-repository overrides for the global settings. Every field is optional; anything left
+ * out falls back to the global value.
+ */
+export type RepoSettings = {
+  /**
+   * Unchanged lines shown around each hunk.
+   */
+  context_lines?: number | null
+  /**
+   * Line-matching algorithm used to compute hunks.
+   */
+  algorithm?: DiffAlgorithm | null
+  /**
+   * Hide changes that only touch whitespace.
+   */
+  ignore_whitespace?: boolean | null
+  /**
+   * Syntax highlighting theme name.
+   */
+  theme?: string | null
+  /**
+   * Globs for generated files, replacing the global list.
+   */
+  generated_globs?: string[] | null
+  /**
+   * Largest blob, in bytes, diffed with highlighting.
+   */
+  max_diff_bytes?: number | null
+}
 export type ResolveCommentInput = {
   local_dir: string
   commit_id: string
@@ -700,6 +1392,10 @@ export type SshSettings = {
    */
   privateKeyPath: string | null
 }
+export type StackFileComments = {
+  file_path: string
+  comments: MaterializedComment[]
+}
 export type UnresolveCommentInput = {
   local_dir: string
   commit_id: string
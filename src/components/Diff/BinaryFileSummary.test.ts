@@ -0,0 +1,27 @@
+import { describe, expect, it } from "vitest"
+
+import { formatBytes, isImagePath } from "./BinaryFileSummary"
+
+describe("formatBytes", () => {
+  it("keeps small sizes in bytes", () => {
+    expect(formatBytes(0)).toBe("0 B")
+    expect(formatBytes(1023)).toBe("1023 B")
+  })
+
+  it("scales to kilobytes and megabytes", () => {
+    expect(formatBytes(1229)).toBe("1.2 KB")
+    expect(formatBytes(3 * 1024 * 1024)).toBe("3.0 MB")
+  })
+})
+
+describe("isImagePath", () => {
+  it("matches image extensions case-insensitively", () => {
+    expect(isImagePath("assets/logo.PNG")).toBe(true)
+    expect(isImagePath("photo.jpeg")).toBe(true)
+  })
+
+  it("rejects other files", () => {
+    expect(isImagePath("archive.zip")).toBe(false)
+    expect(isImagePath("png")).toBe(false)
+  })
+})
@@ -0,0 +1,114 @@
+import { BlobSide, commands } from "@/bindings"
+import { ErrorDisplay } from "@/components/error"
+import { useRpcQuery } from "@/hooks/useRpcQuery"
+import { queryKeys } from "@/lib/queryKeys"
+
+import { useDiffContext } from "./CommitDiffSection"
+
+export function formatBytes(bytes: number): string {
+  if (bytes < 1024) return `${bytes} B`
+  const units = ["KB", "MB", "GB"]
+  let value = bytes / 1024
+  let unit = 0
+  while (value >= 1024 && unit < units.length - 1) {
+    value /= 1024
+    unit++
+  }
+  return `${value.toFixed(1)} ${units[unit]}`
+}
+
+const IMAGE_EXTENSIONS = ["png", "jpg", "jpeg", "gif", "svg", "webp"]
+
+export function isImagePath(path: string): boolean {
+  const extension = path.split(".").pop()?.toLowerCase() ?? ""
+  return path.includes(".") && IMAGE_EXTENSIONS.includes(extension)
+}
+
+export function BinaryFileSummary({
+  filePath,
+  oldPath,
+}: {
+  filePath: string
+  oldPath?: string
+}) {
+  const { localDir, commitSha } = useDiffContext()
+
+  const { data, error, isLoading } = useRpcQuery({
+    queryKey: queryKeys.binaryDiff(localDir, commitSha, filePath, oldPath),
+    queryFn: () =>
+      commands.getBinaryDiff(localDir, commitSha, filePath, oldPath ?? null),
+  })
+
+  if (error) {
+    return <ErrorDisplay error={error} />
+  }
+
+  let summary = "Binary file changed"
+  if (!isLoading && data) {
+    const { oldSize, newSize, changed } = data
+    if (oldSize == null && newSize != null) {
+      summary = `Binary file added (${formatBytes(newSize)})`
+    } else if (oldSize != null && newSize == null) {
+      summary = `Binary file deleted (${formatBytes(oldSize)})`
+    } else if (oldSize != null && newSize != null) {
+      summary = changed
+        ? `Binary file changed (${formatBytes(oldSize)} → ${formatBytes(newSize)})`
+        : `Binary file unchanged (${formatBytes(newSize)})`
+    }
+  }
+
+  return (
+    <div className="p-4 text-center text-muted-foreground text-sm">
+      {summary}
+      {data && isImagePath(filePath) && (
+        <div className="mt-3 flex items-start justify-center gap-4">
+          {data.oldSize != null && (
+            <ImagePreview
+              filePath={oldPath ?? filePath}
+              side="Old"
+              label="Before"
+            />
+          )}
+          {data.newSize != null && (
+            <ImagePreview filePath={filePath} side="New" label="After" />
+          )}
+        </div>
+      )}
+    </div>
+  )
+}
+
+function ImagePreview({
+  filePath,
+  side,
+  label,
+}: {
+  filePath: string
+  side: BlobSide
+  label: string
+}) {
+  const { localDir, commitSha } = useDiffContext()
+
+  const { data, error } = useRpcQuery({
+    queryKey: queryKeys.blobBase64(localDir, commitSha, filePath, side),
+    queryFn: () =>
+      commands.getBlobBase64(localDir, commitSha, filePath, side, null),
+  })
+
+  return (
+    <figure className="flex flex-col items-center gap-1 max-w-[45%]">
+      {error ? (
+        <ErrorDisplay error={error} />
+      ) : data ? (
+        <img
+          src={`data:${data.mime};base64,${data.base64}`}
+          alt={`${label}: ${filePath}`}
+          className="max-h-80 object-contain border rounded bg-[repeating-conic-gradient(#8882_0_25%,transparent_0_50%)] bg-[length:16px_16px]"
+        />
+      ) : (
+        <div className="text-xs">Loading…</div>
+      )}
+      <figcaption className="text-xs">{label}</figcaption>
+    </figure>
+  )
+}
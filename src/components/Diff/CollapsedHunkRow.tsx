@@ -0,0 +1,25 @@
+import { ChevronRight } from "lucide-react"
+
+import { DiffHunk } from "@/bindings"
+
+export function CollapsedHunkRow({
+  hunk,
+  onExpand,
+}: {
+  hunk: DiffHunk
+  onExpand?: () => void
+}) {
+  return (
+    <button
+      type="button"
+      className="flex w-full items-center gap-1 px-2 py-0.5 font-mono text-xs text-muted-foreground bg-muted/30 border-y border-border/50 hover:bg-muted/60 select-none"
+      onClick={onExpand}
+      title="Unfold hunk"
+    >
+      <ChevronRight className="w-3 h-3" />
+      <span className="truncate">
+        {hunk.header.trim()} ({hunk.lines.length} lines hidden)
+      </span>
+    </button>
+  )
+}
@@ -1,11 +1,15 @@
 import { createContext, useContext, useMemo } from "react"
 
-import { FileEntry } from "@/bindings"
+import { commands, FileEntry } from "@/bindings"
 import { ErrorDisplay } from "@/components/error"
 import { useCommitFileList } from "@/hooks/useCommitFileList"
+import { useRpcQuery } from "@/hooks/useRpcQuery"
 import { compareFilePaths } from "@/lib/fileTree"
+import { queryKeys } from "@/lib/queryKeys"
 
 import { DiffViewMode, useDiffViewMode } from "./useDiffViewMode"
+import { useLineWrap } from "./useLineWrap"
+import { DEFAULT_WHITESPACE, WhitespaceContext } from "./whitespace"
 
 type DiffContextValue = {
   files: FileEntry[]
@@ -15,6 +19,9 @@ type DiffContextValue = {
   diffViewMode: DiffViewMode
   setDiffViewMode: (mode: DiffViewMode) => void
   toggleDiffViewMode: () => void
+  /** Wrap long lines; when off, unified diffs scroll horizontally. */
+  lineWrap: boolean
+  toggleLineWrap: () => void
 }
 
 const DiffContext = createContext<DiffContextValue | null>(null)
@@ -40,14 +47,31 @@ export function CommitDiffSection({
 }: CommitDiffSectionProps) {
   const { diffViewMode, setDiffViewMode, toggleDiffViewMode } =
     useDiffViewMode()
+  const { lineWrap, toggleLineWrap } = useLineWrap()
   const { data, error, isLoading } = useCommitFileList(localDir, commitSha)
+  const { data: diffSettings } = useRpcQuery({
+    queryKey: queryKeys.diffSettings(),
+    queryFn: () => commands.getDiffSettings(),
+  })
 
+  const tabWidth = diffSettings?.tabWidth ?? DEFAULT_WHITESPACE.tabWidth
+  const showWhitespace =
+    diffSettings?.showWhitespace ?? DEFAULT_WHITESPACE.showWhitespace
+  const whitespace = useMemo(
+    () => ({ tabWidth, showWhitespace }),
+    [tabWidth, showWhitespace],
+  )
+
+  // Other orders come sorted from the file list; path order follows the tree.
+  const fileSort = diffSettings?.fileSort ?? "path"
   const files = useMemo(
     () =>
-      data?.files.sort(
-        compareFilePaths((file) => (file.newPath || file.oldPath) ?? ""),
-      ) ?? [],
-    [data?.files],
+      fileSort === "path"
+        ? (data?.files.sort(
+            compareFilePaths((file) => (file.newPath || file.oldPath) ?? ""),
+          ) ?? [])
+        : (data?.files ?? []),
+    [data?.files, fileSort],
   )
 
   if (isLoading) {
@@ -82,9 +106,13 @@ export function CommitDiffSection({
         diffViewMode,
         setDiffViewMode,
         toggleDiffViewMode,
+        lineWrap,
+        toggleLineWrap,
       }}
     >
-      {children}
+      <WhitespaceContext.Provider value={whitespace}>
+        {children}
+      </WhitespaceContext.Provider>
     </DiffContext.Provider>
   )
 }
@@ -0,0 +1,133 @@
+import { useEffect, useMemo, useState } from "react"
+
+import { cn } from "@/lib/utils"
+
+import { DiffElement } from "./hunkGaps"
+import {
+  coverageRows,
+  CoverageState,
+  Viewport,
+  viewportFromLines,
+} from "./minimap"
+
+const MINIMAP_ROWS = 100
+
+const coverageColor: Record<CoverageState, string> = {
+  unchanged: "bg-transparent",
+  reviewed: "bg-green-500/70",
+  unreviewed: "bg-amber-500/80",
+}
+
+const viewportListeners = new Set<() => void>()
+let pendingFrame: number | null = null
+
+function notifyViewportListeners() {
+  if (pendingFrame !== null) return
+  pendingFrame = requestAnimationFrame(() => {
+    pendingFrame = null
+    viewportListeners.forEach((listener) => listener())
+  })
+}
+
+/**
+ * Run `listener` at most once per frame on any scroll or resize. Every
+ * minimap on the page shares the same pair of document listeners.
+ */
+function subscribeViewport(listener: () => void): () => void {
+  if (viewportListeners.size === 0) {
+    document.addEventListener("scroll", notifyViewportListeners, true)
+    window.addEventListener("resize", notifyViewportListeners)
+  }
+  viewportListeners.add(listener)
+  return () => {
+    viewportListeners.delete(listener)
+    if (viewportListeners.size === 0) {
+      document.removeEventListener("scroll", notifyViewportListeners, true)
+      window.removeEventListener("resize", notifyViewportListeners)
+      if (pendingFrame !== null) cancelAnimationFrame(pendingFrame)
+      pendingFrame = null
+    }
+  }
+}
+
+/** Line numbers of the rendered rows in `container` that are on screen. */
+function visibleLines(container: HTMLElement): number[] {
+  const lines: number[] = []
+  const rows = container.querySelectorAll<HTMLElement>("[data-lineno]")
+  for (const row of rows) {
+    const rect = row.getBoundingClientRect()
+    if (rect.bottom <= 0 || rect.top >= window.innerHeight) continue
+    lines.push(Number(row.dataset.lineno))
+  }
+  return lines
+}
+
+/** Part of the file shown by the rows of `container` currently on screen. */
+function useViewport(
+  containerRef: React.RefObject<HTMLDivElement | null>,
+  totalLines: number,
+): Viewport | null {
+  const [viewport, setViewport] = useState<Viewport | null>(null)
+
+  useEffect(() => {
+    const update = () => {
+      const container = containerRef.current
+      if (!container) return
+      setViewport(viewportFromLines(visibleLines(container), totalLines))
+    }
+    update()
+    return subscribeViewport(update)
+  }, [containerRef, totalLines])
+
+  return viewport
+}
+
+/**
+ * One-column overview of where a file's reviewed and unreviewed changes are,
+ * with the part of the diff currently on screen outlined.
+ */
+export function CoverageMinimap({
+  remainingElements,
+  reviewedElements,
+  totalLines,
+  containerRef,
+}: {
+  remainingElements: DiffElement[]
+  reviewedElements: DiffElement[]
+  totalLines: number
+  containerRef: React.RefObject<HTMLDivElement | null>
+}) {
+  const rows = useMemo(
+    () =>
+      coverageRows(
+        remainingElements,
+        reviewedElements,
+        totalLines,
+        MINIMAP_ROWS,
+      ),
+    [remainingElements, reviewedElements, totalLines],
+  )
+  const viewport = useViewport(containerRef, totalLines)
+
+  return (
+    <div className="w-2 bg-muted/50 border-l">
+      <div
+        className="sticky top-0 flex max-h-full h-screen flex-col"
+        aria-hidden
+      >
+        {rows.map((state, i) => (
+          <div key={i} className={cn("flex-1", coverageColor[state])} />
+        ))}
+        {viewport && (
+          <div
+            className="absolute inset-x-0 border border-foreground/40"
+            style={{
+              top: `${viewport.top * 100}%`,
+              height: `${(viewport.bottom - viewport.top) * 100}%`,
+            }}
+          />
+        )}
+      </div>
+    </div>
+  )
+}
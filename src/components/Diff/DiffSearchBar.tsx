@@ -0,0 +1,72 @@
+import { useState } from "react"
+
+import { UseDiffSearchReturn } from "./useDiffSearch"
+import { UseLineSelectionReturn } from "./useLineSelection"
+
+export function DiffSearchBar({
+  initialQuery,
+  onSubmit,
+  onCancel,
+}: {
+  initialQuery: string
+  onSubmit: (query: string) => void
+  onCancel: () => void
+}) {
+  const [value, setValue] = useState(initialQuery)
+
+  return (
+    <div className="flex items-center gap-2 px-3 py-1 border-b bg-muted/50 font-mono text-xs">
+      <span className="text-muted-foreground">/</span>
+      <input
+        autoFocus
+        value={value}
+        onChange={(e) => setValue(e.target.value)}
+        onKeyDown={(e) => {
+          if (e.key === "Enter") {
+            e.preventDefault()
+            onSubmit(value)
+          } else if (e.key === "Escape") {
+            e.preventDefault()
+            e.stopPropagation()
+            onCancel()
+          }
+        }}
+        placeholder="Search diff (empty to clear)"
+        className="flex-1 bg-transparent outline-none"
+      />
+    </div>
+  )
+}
+
+/**
+ * Search prompt for line mode. Submitting jumps to the first match after the
+ * cursor; either way focus returns to the diff so line mode keys keep working.
+ */
+export function LineModeSearchBar({
+  search,
+  selection,
+  containerRef,
+}: {
+  search: UseDiffSearchReturn
+  selection: UseLineSelectionReturn
+  containerRef: React.RefObject<HTMLElement | null>
+}) {
+  if (!search.isOpen) return null
+
+  const close = () => {
+    search.close()
+    containerRef.current?.focus()
+  }
+
+  return (
+    <DiffSearchBar
+      initialQuery={search.query}
+      onSubmit={(query) => {
+        search.setQuery(query)
+        close()
+        if (query) selection.moveToSearchMatch(query, "next")
+      }}
+      onCancel={close}
+    />
+  )
+}
@@ -1,11 +1,15 @@
 import { useHotkey } from "@tanstack/react-hotkeys"
-import { useState } from "react"
+import { useRef, useState } from "react"
 
 import { DiffLine, RegionId } from "@/bindings"
 import { cn } from "@/lib/utils"
 
+import { CoverageMinimap } from "./CoverageMinimap"
+import { LineModeSearchBar } from "./DiffSearchBar"
 import { DiffElement } from "./hunkGaps"
+import { lineNumberGutterStyle } from "./LineNumberGutter"
 import { UnifiedHunkLines } from "./UnifiedDiff"
+import { UseDiffSearchReturn } from "./useDiffSearch"
 import { useLineDrag } from "./useLineDrag"
 import { useLineMode } from "./useLineMode"
 import {
@@ -21,19 +25,25 @@ export type DualDiffPanel = "remaining" | "reviewed"
 type DualDiffProps = {
   remainingElements: DiffElement[]
   reviewedElements: DiffElement[]
+  /** Line count of the target file, used to scale the coverage minimap. */
+  totalLines: number
   lineSelection?: LineSelectionControl
   onMarkRegion?: (region: RegionId, panel: DualDiffPanel) => void
   fileItemRef: React.RefObject<HTMLDivElement | null>
+  search: UseDiffSearchReturn
 }
 
 export function DualDiff({
   remainingElements,
   reviewedElements,
+  totalLines,
   lineSelection,
   onMarkRegion,
   fileItemRef,
+  search,
 }: DualDiffProps) {
   const [activePanel, setActivePanel] = useState<DualDiffPanel>("remaining")
+  const panelsRef = useRef<HTMLDivElement>(null)
 
   const isLineModeActive =
     lineSelection?.state !== null && lineSelection?.state !== undefined
@@ -98,39 +108,56 @@ export function DualDiff({
     active: isLineModeActive,
     onExit: lineSelection?.onExit ?? (() => {}),
     onMarkRegion: handleMarkRegionForPanel,
+    searchQuery: search.query,
+    onSearch: search.open,
   })
 
   return (
-    <div className="grid grid-cols-2 divide-x">
-      <DualPanel
-        label="Remaining"
-        elements={remainingElements}
-        isActive={isLineModeActive && activePanel === "remaining"}
-        onRowMouseDown={onRowMouseDown("remaining")}
-        onRowMouseEnter={
-          activePanel === "remaining" ? drag.onRowMouseEnter : undefined
-        }
-        onRowMouseUp={
-          activePanel === "remaining" ? drag.onRowMouseUp : undefined
-        }
-        selectedRange={selection.selectionRange}
-        cursor={selection.state?.cursor ?? null}
+    <>
+      <LineModeSearchBar
+        search={search}
+        selection={selection}
+        containerRef={fileItemRef}
       />
-      <DualPanel
-        label="Reviewed"
-        elements={reviewedElements}
-        isActive={isLineModeActive && activePanel === "reviewed"}
-        onRowMouseDown={onRowMouseDown("reviewed")}
-        onRowMouseEnter={
-          activePanel === "reviewed" ? drag.onRowMouseEnter : undefined
-        }
-        onRowMouseUp={
-          activePanel === "reviewed" ? drag.onRowMouseUp : undefined
-        }
-        selectedRange={selection.selectionRange}
-        cursor={selection.state?.cursor ?? null}
-      />
-    </div>
+      <div ref={panelsRef} className="grid grid-cols-[1fr_1fr_auto] divide-x">
+        <DualPanel
+          label="Remaining"
+          elements={remainingElements}
+          isActive={isLineModeActive && activePanel === "remaining"}
+          onRowMouseDown={onRowMouseDown("remaining")}
+          onRowMouseEnter={
+            activePanel === "remaining" ? drag.onRowMouseEnter : undefined
+          }
+          onRowMouseUp={
+            activePanel === "remaining" ? drag.onRowMouseUp : undefined
+          }
+          selectedRange={selection.selectionRange}
+          cursor={selection.state?.cursor ?? null}
+          searchQuery={activePanel === "remaining" ? search.query : undefined}
+        />
+        <DualPanel
+          label="Reviewed"
+          elements={reviewedElements}
+          isActive={isLineModeActive && activePanel === "reviewed"}
+          onRowMouseDown={onRowMouseDown("reviewed")}
+          onRowMouseEnter={
+            activePanel === "reviewed" ? drag.onRowMouseEnter : undefined
+          }
+          onRowMouseUp={
+            activePanel === "reviewed" ? drag.onRowMouseUp : undefined
+          }
+          selectedRange={selection.selectionRange}
+          cursor={selection.state?.cursor ?? null}
+          searchQuery={activePanel === "reviewed" ? search.query : undefined}
+        />
+        <CoverageMinimap
+          remainingElements={remainingElements}
+          reviewedElements={reviewedElements}
+          totalLines={totalLines}
+          containerRef={panelsRef}
+        />
+      </div>
+    </>
   )
 }
 
@@ -143,6 +170,7 @@ function DualPanel({
   onRowMouseUp,
   cursor,
   selectedRange,
+  searchQuery,
 }: {
   label: string
   elements: DiffElement[]
@@ -152,6 +180,7 @@ function DualPanel({
   onRowMouseUp?: () => void
   cursor: CursorPosition | null
   selectedRange: SelectionRange
+  searchQuery?: string
 }) {
   const hunkElements = elements.flatMap((el, idx) =>
     el.type === "hunk" ? [{ element: el, originalIndex: idx }] : [],
@@ -160,9 +189,10 @@ function DualPanel({
   return (
     <div
       className={cn(
-        "bg-background",
+        "bg-background min-w-0 overflow-x-auto",
         isActive && "ring-2 ring-inset ring-blue-400 dark:ring-blue-600",
       )}
+      style={lineNumberGutterStyle(elements)}
     >
       <div className="px-3 py-1 text-xs font-medium text-muted-foreground bg-muted/50 border-b">
         {label}
@@ -186,6 +216,7 @@ function DualPanel({
               selectedRange={
                 isActive ? selectedRange : { left: null, right: null }
               }
+              searchQuery={searchQuery}
             />
           </div>
         ))
@@ -1,36 +1,58 @@
 import { useHotkey } from "@tanstack/react-hotkeys"
 import { keepPreviousData, useQueryClient } from "@tanstack/react-query"
-import { Check, ChevronDown, ChevronRight, Copy } from "lucide-react"
+import {
+  Check,
+  ChevronDown,
+  ChevronRight,
+  Copy,
+  SquarePen,
+  TriangleAlert,
+} from "lucide-react"
 import { useCallback, useMemo, useState } from "react"
+import { toast } from "sonner"
 
 import { commands, DiffLine, FileEntry } from "@/bindings"
-import { ErrorDisplay } from "@/components/error"
+import { ErrorDisplay, getErrorMessage } from "@/components/error"
 import { PANEL_KEYS, usePaneItem, usePaneManager } from "@/components/Pane"
+import { Button } from "@/components/ui/button"
 import {
   Collapsible,
   CollapsibleContent,
   CollapsibleTrigger,
 } from "@/components/ui/collapsible"
+import { useKeybindings } from "@/hooks/useKeybindings"
 import { useRpcMutation, useRpcQuery } from "@/hooks/useRpcQuery"
+import { recordMarkerUndo } from "@/lib/markerUndo"
 import { queryKeys } from "@/lib/queryKeys"
 import { cn } from "@/lib/utils"
 
+import { BinaryFileSummary, formatBytes } from "./BinaryFileSummary"
 import { useDiffContext } from "./CommitDiffSection"
+import { LineModeSearchBar } from "./DiffSearchBar"
 import { getStatusStyle } from "./diffStyles"
 import { DualDiff } from "./DualDiff"
-import { augmentHunks, buildDiffElements } from "./hunkGaps"
+import {
+  augmentHunks,
+  buildDiffElements,
+  collapseHunks,
+  HunkKey,
+} from "./hunkGaps"
+import { BlameContext, useBlame } from "./useBlame"
 import { SplitDiff } from "./SplitDiff"
 import type {
   CommentContext,
+  CommentKey,
   InlineCommentFormProps,
   InlineCommentsMap,
 } from "./types"
 import { UnifiedDiff } from "./UnifiedDiff"
 import { useCommentForm } from "./useCommentForm"
 import { useContextExpansion } from "./useContextExpansion"
+import { useDiffSearch } from "./useDiffSearch"
 import { useLineDrag } from "./useLineDrag"
 import { useLineMode } from "./useLineMode"
 import {
+  hunkKeyAtCursor,
   LineSelectionControl,
   LineSelectionState,
   useLineSelection,
@@ -46,19 +68,10 @@ export type {
 } from "./types"
 
 const LARGE_FILE_THRESHOLD = 500
-const GENERATED_FILE_PATTERNS = [
-  "pnpm-lock.yaml",
-  "package-lock.json",
-  "yarn.lock",
-]
 
 function shouldAutoCollapse(file: FileEntry): boolean {
   const totalChanges = file.additions + file.deletions
-  if (totalChanges > LARGE_FILE_THRESHOLD) return true
-
-  const filePath = file.newPath || file.oldPath || ""
-  const fileName = filePath.split("/").pop() ?? ""
-  return GENERATED_FILE_PATTERNS.includes(fileName)
+  return file.generated || totalChanges > LARGE_FILE_THRESHOLD
 }
 
 export function FileDiffItem({
@@ -72,8 +85,9 @@ export function FileDiffItem({
   InlineCommentForm?: React.FC<InlineCommentFormProps>
   inlineComments?: InlineCommentsMap
 }) {
-  const { localDir, commitSha, changeId } = useDiffContext()
+  const { localDir, commitSha, changeId, lineWrap } = useDiffContext()
   const { softFocusPaneItem } = usePaneManager()
+  const keys = useKeybindings()
   const [isOpen, setIsOpen] = useState(
     file.reviewStatus !== "reviewed" &&
       file.reviewStatus !== "reviewedReverted" &&
@@ -116,7 +130,8 @@ export function FileDiffItem({
         isReviewed,
       )
     },
-    onSuccess: () => {
+    onSuccess: (previousTreeId) => {
+      recordMarkerUndo({ localDir, commitSha, treeId: previousTreeId })
       queryClient.invalidateQueries({
         queryKey: queryKeys.commitFileList(localDir, commitSha),
       })
@@ -133,6 +148,30 @@ export function FileDiffItem({
         ),
       })
     },
+    onError: (error) => {
+      // Conflicted files are refused; keep the conflict in view.
+      setIsOpen(true)
+      toast.error(getErrorMessage(error))
+    },
+  })
+
+  const canOpenInEditor = file.status !== "deleted" && !!file.newPath
+
+  const openInEditorMutation = useRpcMutation({
+    mutationFn: (line: number | null) =>
+      commands.openInEditor(localDir, file.newPath ?? "", line),
+  })
+
+  const openInEditor = () => {
+    // Only the new side has a line number that exists in the working copy.
+    const cursor = selectionState?.cursor
+    const line = cursor?.side === "RIGHT" ? cursor.line : null
+    openInEditorMutation.mutate(line)
+  }
+
+  useHotkey(keys.open_in_editor, openInEditor, {
+    enabled: canOpenInEditor,
+    target: ref,
   })
 
   const handleCheckboxChange = (e: React.ChangeEvent<HTMLInputElement>) => {
@@ -158,7 +197,7 @@ export function FileDiffItem({
   }
 
   useHotkey(
-    "Space",
+    keys.toggle_reviewed,
     () => {
       const newIsReviewed = file.reviewStatus !== "reviewed"
       toggleMutation.mutate(newIsReviewed)
@@ -171,12 +210,12 @@ export function FileDiffItem({
       target: ref,
     },
   )
-  useHotkey("Enter", () => enterLineMode(), {
+  useHotkey(keys.enter_line_mode, () => enterLineMode(), {
     enabled: !isLineModeActive,
     target: ref,
   })
   useHotkey(
-    "O",
+    keys.toggle_file_open,
     () => {
       if (isOpen) {
         onClose()
@@ -210,7 +249,7 @@ export function FileDiffItem({
     copyFilePath()
   }
 
-  useHotkey("C", () => copyFilePath(), {
+  useHotkey(keys.copy_file_path, () => copyFilePath(), {
     enabled: !isLineModeActive,
     target: ref,
   })
@@ -267,6 +306,11 @@ export function FileDiffItem({
                   Reverted
                 </span>
               )}
+              {file.generated && (
+                <span className="text-xs text-muted-foreground shrink-0">
+                  Generated
+                </span>
+              )}
               <span className="font-mono text-sm truncate" title={displayPath}>
                 {displayPath}
               </span>
@@ -281,6 +325,15 @@ export function FileDiffItem({
                   onClick={handleCopyFilePath}
                 />
               )}
+              {canOpenInEditor && (
+                <SquarePen
+                  className="w-4 h-4 shrink-0 text-muted-foreground hover:text-foreground cursor-pointer"
+                  onClick={(e) => {
+                    e.stopPropagation()
+                    openInEditor()
+                  }}
+                />
+              )}
             </div>
           </CollapsibleTrigger>
         </div>
@@ -296,11 +349,19 @@ export function FileDiffItem({
 
       {/* File Content - Lazy loaded */}
       <CollapsibleContent>
-        <div className="overflow-x-auto rounded-b-lg">
+        <div
+          className="overflow-x-auto rounded-b-lg group/diff"
+          data-nowrap={!lineWrap || undefined}
+        >
           {file.isBinary ? (
-            <div className="p-4 text-center text-muted-foreground text-sm">
-              Binary file changed
-            </div>
+            <BinaryFileSummary
+              filePath={file.newPath || file.oldPath || ""}
+              oldPath={
+                file.status === "renamed"
+                  ? (file.oldPath ?? undefined)
+                  : undefined
+              }
+            />
           ) : (
             <LazyFileDiff
               filePath={file.newPath || file.oldPath || ""}
@@ -345,20 +406,28 @@ function LazyFileDiff({
 }) {
   const { localDir, commitSha, changeId, diffViewMode } = useDiffContext()
 
+  // Files over the size limit are only diffed once the user asks, and then
+  // without highlighting.
+  const [forcePlain, setForcePlain] = useState(false)
   const { data, error, isLoading } = useRpcQuery({
-    queryKey: queryKeys.partialReviewDiffs(
-      localDir,
-      changeId,
-      commitSha,
-      filePath,
-      oldPath,
-    ),
+    // Appended after the shared key so invalidations still match by prefix.
+    queryKey: [
+      ...queryKeys.partialReviewDiffs(
+        localDir,
+        changeId,
+        commitSha,
+        filePath,
+        oldPath,
+      ),
+      forcePlain,
+    ],
     queryFn: () =>
       commands.getPartialReviewDiffs(
         localDir,
         commitSha,
         filePath,
         oldPath ?? null,
+        forcePlain,
       ),
     placeholderData: keepPreviousData,
   })
@@ -366,6 +435,11 @@ function LazyFileDiff({
   const hasRemaining = (data?.remaining.hunks.length ?? 0) > 0
   const hasReviewed = (data?.reviewed.hunks.length ?? 0) > 0
   const isSplit = hasRemaining && hasReviewed
+  // Conflicted content isn't reviewable; block marking until it's resolved.
+  const hasConflict =
+    data?.remaining.hunks.some((h) => h.hasConflict) ||
+    data?.reviewed.hunks.some((h) => h.hasConflict) ||
+    false
 
   const singleSide: "remaining" | "reviewed" = hasRemaining
     ? "remaining"
@@ -380,8 +454,11 @@ function LazyFileDiff({
     localDir,
     commitSha,
     filePath,
+    forcePlain,
   })
 
+  const blame = useBlame({ localDir, commitSha, filePath, oldPath })
+
   const { handleDualMarkRegion } = useRegionReview({
     localDir,
     commitSha,
@@ -426,8 +503,36 @@ function LazyFileDiff({
     [augmentedHunks, singleDiff],
   )
 
+  const [collapsedHunks, setCollapsedHunks] = useState<ReadonlySet<HunkKey>>(
+    () => new Set(),
+  )
+  const visibleElements = useMemo(
+    () => collapseHunks(elements, collapsedHunks),
+    [elements, collapsedHunks],
+  )
+
+  const toggleFoldAtCursor = () => {
+    const cursor = lineSelection.state?.cursor
+    if (!cursor) return
+    const key = hunkKeyAtCursor(visibleElements, cursor)
+    if (key == null) return
+    setCollapsedHunks((prev) => {
+      const next = new Set(prev)
+      if (!next.delete(key)) next.add(key)
+      return next
+    })
+  }
+
+  const expandHunk = (key: HunkKey) => {
+    setCollapsedHunks((prev) => {
+      const next = new Set(prev)
+      next.delete(key)
+      return next
+    })
+  }
+
   const selection = useLineSelection({
-    elements,
+    elements: visibleElements,
     diffViewMode,
     state: isSplit ? null : lineSelection.state,
     setState: lineSelection.setState,
@@ -442,6 +547,25 @@ function LazyFileDiff({
     },
   })
 
+  const search = useDiffSearch()
+
+  const [includeResolved, setIncludeResolved] = useState(false)
+  const commentKeys = useMemo(() => {
+    const keys = new Set<CommentKey>()
+    for (const [key, threads] of inlineComments ?? []) {
+      if (threads.some((t) => includeResolved || !t.resolved)) keys.add(key)
+    }
+    return keys
+  }, [inlineComments, includeResolved])
+  const toggleResolvedComments = () => {
+    setIncludeResolved((prev) => !prev)
+    toast(
+      includeResolved
+        ? "Comment jumps skip resolved threads"
+        : "Comment jumps include resolved threads",
+    )
+  }
+
   const commentForm = useCommentForm({
     selection,
     commentContext,
@@ -462,7 +586,16 @@ function LazyFileDiff({
       commentContext && InlineCommentForm
         ? commentForm.initiateComment
         : undefined,
-    onMarkRegion: !isSplit ? handleMarkRegionForSinglePanel : undefined,
+    onMarkRegion:
+      !isSplit && !hasConflict ? handleMarkRegionForSinglePanel : undefined,
+    searchQuery: search.query,
+    onSearch: search.open,
+    onToggleFold: toggleFoldAtCursor,
+    onUnfoldAll: () => setCollapsedHunks(new Set()),
+    commentKeys: inlineComments ? commentKeys : undefined,
+    onToggleResolvedComments: inlineComments
+      ? toggleResolvedComments
+      : undefined,
   })
 
   if (isLoading) {
@@ -473,6 +606,21 @@ function LazyFileDiff({
     )
   }
 
+  if (error?.type === "DiffTooLarge") {
+    return (
+      <div className="p-4 flex flex-col items-center gap-2 text-muted-foreground text-sm">
+        <span>File is too large to highlight ({formatBytes(error.size)})</span>
+        <Button
+          variant="outline"
+          size="sm"
+          onClick={() => setForcePlain(true)}
+        >
+          Show anyway (no highlight)
+        </Button>
+      </div>
+    )
+  }
+
   if (error) {
     return (
       <div className="p-4">
@@ -491,21 +639,29 @@ function LazyFileDiff({
     )
   }
 
+  const conflictWarning = hasConflict ? <ConflictWarning /> : null
+
   if (isSplit) {
     return (
-      <DualDiff
-        remainingElements={remainingElements}
-        reviewedElements={reviewedElements}
-        lineSelection={lineSelection}
-        onMarkRegion={handleDualMarkRegion}
-        fileItemRef={fileItemRef}
-      />
+      <>
+        {conflictWarning}
+        <DualDiff
+          remainingElements={remainingElements}
+          reviewedElements={reviewedElements}
+          totalLines={data.remaining.newFileLines}
+          lineSelection={lineSelection}
+          onMarkRegion={hasConflict ? undefined : handleDualMarkRegion}
+          fileItemRef={fileItemRef}
+          search={search}
+        />
+      </>
     )
   }
 
   const sharedProps = {
-    elements,
+    elements: visibleElements,
     onExpandGap: handleExpandGap,
+    onExpandHunk: expandHunk,
     onRowMouseDown: (line: DiffLine) => {
       fileItemRef.current?.focus()
       drag.onRowMouseDown?.(line)
@@ -523,11 +679,33 @@ function LazyFileDiff({
     commentContext,
     cursor: selection.state?.cursor ?? null,
     selectedRange: selection.selectionRange,
+    searchQuery: search.query,
   }
 
-  return diffViewMode === "split" ? (
-    <SplitDiff {...sharedProps} />
-  ) : (
-    <UnifiedDiff {...sharedProps} />
+  // With only one panel, its old side matches the parent, so blame lines up.
+  return (
+    <BlameContext.Provider value={blame}>
+      {conflictWarning}
+      <LineModeSearchBar
+        search={search}
+        selection={selection}
+        containerRef={fileItemRef}
+      />
+      {diffViewMode === "split" ? (
+        <SplitDiff {...sharedProps} />
+      ) : (
+        <UnifiedDiff {...sharedProps} />
+      )}
+    </BlameContext.Provider>
+  )
+}
+
+function ConflictWarning() {
+  return (
+    <div className="flex items-center gap-2 px-3 py-2 text-xs border-b bg-amber-50 text-amber-800 dark:bg-amber-950/40 dark:text-amber-300">
+      <TriangleAlert className="w-3.5 h-3.5 shrink-0" />
+      This file contains unresolved conflict markers. Resolve the conflict
+      before marking it reviewed.
+    </div>
   )
 }
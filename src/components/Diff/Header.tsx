@@ -1,16 +1,35 @@
 import { useHotkey } from "@tanstack/react-hotkeys"
-import { Columns2, Rows3 } from "lucide-react"
+import { useQueryClient } from "@tanstack/react-query"
+import { Columns2, Rows3, WrapText } from "lucide-react"
+import { toast } from "sonner"
 
+import { commands } from "@/bindings"
+import { getErrorMessage } from "@/components/error"
 import { Alert, AlertDescription, AlertTitle } from "@/components/ui/alert"
+import { Button } from "@/components/ui/button"
+import { useKeybindings } from "@/hooks/useKeybindings"
+import { useRpcMutation, useRpcQuery } from "@/hooks/useRpcQuery"
+import { recordMarkerUndo } from "@/lib/markerUndo"
+import { queryKeys } from "@/lib/queryKeys"
 import { cn } from "@/lib/utils"
 
 import { useDiffContext } from "./CommitDiffSection"
 
 export function Header() {
-  const { files, diffViewMode, setDiffViewMode, toggleDiffViewMode } =
-    useDiffContext()
+  const {
+    files,
+    localDir,
+    commitSha,
+    diffViewMode,
+    setDiffViewMode,
+    toggleDiffViewMode,
+    lineWrap,
+    toggleLineWrap,
+  } = useDiffContext()
 
-  useHotkey("T", () => toggleDiffViewMode())
+  const keys = useKeybindings()
+  useHotkey(keys.toggle_diff_view, () => toggleDiffViewMode())
+  useHotkey(keys.toggle_line_wrap, () => toggleLineWrap())
 
   const reviewedCount = files.filter(
     (f) => f.reviewStatus === "reviewed",
@@ -20,6 +39,31 @@ export function Header() {
     (f) => f.reviewStatus === "reviewedReverted",
   ).length
 
+  const queryClient = useQueryClient()
+  const { data: diffSettings } = useRpcQuery({
+    queryKey: queryKeys.diffSettings(),
+    queryFn: () => commands.getDiffSettings(),
+  })
+  const unreviewedGeneratedCount = files.filter(
+    (f) =>
+      f.generated &&
+      (f.reviewStatus === "unreviewed" ||
+        f.reviewStatus === "partiallyReviewed"),
+  ).length
+  const markGeneratedMutation = useRpcMutation({
+    mutationFn: () => commands.markGeneratedFilesReviewed(localDir, commitSha),
+    onSuccess: (previousTreeId) => {
+      recordMarkerUndo({ localDir, commitSha, treeId: previousTreeId })
+      queryClient.invalidateQueries({
+        queryKey: queryKeys.commitFileList(localDir, commitSha),
+      })
+      queryClient.invalidateQueries({
+        queryKey: ["partial-review-diffs", localDir],
+      })
+    },
+    onError: (error) => toast.error(getErrorMessage(error)),
+  })
+
   if (files.length === 0) {
     return (
       <Alert>
@@ -56,32 +100,58 @@ export function Header() {
           </span>
         </div>
       </div>
-      <div
-        className="inline-flex items-center rounded-md border bg-muted p-0.5"
-        tabIndex={-1}
-      >
+      <div className="flex items-center gap-2">
+        {diffSettings?.generatedFiles === "markReviewed" &&
+          unreviewedGeneratedCount > 0 && (
+            <Button
+              variant="outline"
+              size="sm"
+              tabIndex={-1}
+              disabled={markGeneratedMutation.isPending}
+              onClick={() => markGeneratedMutation.mutate()}
+            >
+              Mark {unreviewedGeneratedCount} generated reviewed
+            </Button>
+          )}
         <button
-          onClick={() => setDiffViewMode("unified")}
+          onClick={toggleLineWrap}
           tabIndex={-1}
           className={cn(
             baseClass,
-            diffViewMode === "unified" ? activeClass : inactiveClass,
+            "border",
+            lineWrap ? "bg-background text-foreground" : inactiveClass,
           )}
-          title="Unified view"
+          title={lineWrap ? "Scroll long lines" : "Wrap long lines"}
         >
-          <Rows3 className="w-4 h-4" />
+          <WrapText className="w-4 h-4" />
         </button>
-        <button
-          onClick={() => setDiffViewMode("split")}
+        <div
+          className="inline-flex items-center rounded-md border bg-muted p-0.5"
           tabIndex={-1}
-          className={cn(
-            baseClass,
-            diffViewMode === "split" ? activeClass : inactiveClass,
-          )}
-          title="Split view"
         >
-          <Columns2 className="w-4 h-4" />
-        </button>
+          <button
+            onClick={() => setDiffViewMode("unified")}
+            tabIndex={-1}
+            className={cn(
+              baseClass,
+              diffViewMode === "unified" ? activeClass : inactiveClass,
+            )}
+            title="Unified view"
+          >
+            <Rows3 className="w-4 h-4" />
+          </button>
+          <button
+            onClick={() => setDiffViewMode("split")}
+            tabIndex={-1}
+            className={cn(
+              baseClass,
+              diffViewMode === "split" ? activeClass : inactiveClass,
+            )}
+            title="Split view"
+          >
+            <Columns2 className="w-4 h-4" />
+          </button>
+        </div>
       </div>
     </div>
   )
@@ -49,7 +49,10 @@ function CommentHeader({
 
 function ReplyItem({ reply }: { reply: InlineReply }) {
   return (
-    <div className="border-t px-3 py-2">
+    <div
+      className="border-t px-3 py-2"
+      style={{ paddingLeft: `${0.75 + (reply.depth ?? 0)}rem` }}
+    >
       <CommentHeader user={reply.user} createdAt={reply.createdAt} />
       <MarkdownContent className="text-xs [&_p]:text-xs">
         {reply.body}
@@ -2,21 +2,47 @@ import { MessageSquare } from "lucide-react"
 
 import { cn } from "@/lib/utils"
 
+import { DiffElement, lineNumberDigits } from "./hunkGaps"
+import { blameTitle, useBlameContext } from "./useBlame"
+
+/**
+ * Style for the root of a diff view: widens every gutter inside it to fit the
+ * largest line number in `elements`.
+ */
+export function lineNumberGutterStyle(
+  elements: DiffElement[],
+): React.CSSProperties {
+  return {
+    "--line-number-digits": lineNumberDigits(elements),
+  } as React.CSSProperties
+}
+
 export function LineNumberGutter({
   className,
   hasComments,
+  blameLine,
   children,
 }: {
   className?: string
   hasComments?: boolean
+  /** Old line number whose blame is shown on hover. */
+  blameLine?: number | null
   children: React.ReactNode
 }) {
+  const blame = useBlameContext()
+  const showsBlame = blame != null && blameLine != null
+
   return (
     <span
       className={cn(
         "text-right pr-2 text-muted-foreground select-none shrink-0 relative",
         className,
       )}
+      style={{
+        minWidth: "calc(var(--line-number-digits, 3) * 1ch + 1rem)",
+      }}
+      title={showsBlame ? blameTitle(blame.get(blameLine)) : undefined}
+      onMouseEnter={showsBlame ? blame.request : undefined}
     >
       {hasComments && (
         <span className="absolute left-0 top-1/2 -translate-y-1/2 inline-flex text-blue-500 rounded-sm p-0.5 z-10">
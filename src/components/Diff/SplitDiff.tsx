@@ -1,26 +1,37 @@
-import { Fragment } from "react"
+import { Fragment, useContext } from "react"
 
 import { DiffHunk, DiffLine } from "@/bindings"
 import { cn } from "@/lib/utils"
 
-import { changedTokenBg, getLineStyle } from "./diffStyles"
+import { CollapsedHunkRow } from "./CollapsedHunkRow"
+import {
+  changedTokenBg,
+  getLineStyle,
+  movedTokenBg,
+  whitespaceOnlyHunk,
+  whitespaceOnlyTitle,
+} from "./diffStyles"
 import { GapRow } from "./GapRow"
-import { DiffElement, HunkGap } from "./hunkGaps"
+import { DiffElement, HunkGap, hunkKey, HunkKey } from "./hunkGaps"
 import { InlineThreadDisplay } from "./InlineThreadDisplay"
-import { LineNumberGutter } from "./LineNumberGutter"
+import { LineNumberGutter, lineNumberGutterStyle } from "./LineNumberGutter"
 import { PairedLine, pairLinesForSplitView } from "./splitViewPairing"
 import { CommentContext, inlineCommentsKey, InlineCommentsMap } from "./types"
 import {
   CursorPosition,
   getLineHighlightBg,
+  lineMatchesQuery,
   SelectionRange,
 } from "./useLineSelection"
+import { renderWhitespace, WhitespaceContext } from "./whitespace"
 
 export type ExpandDirection = "up" | "down" | "all"
 
 export type DiffViewProps = {
   elements: DiffElement[]
   onExpandGap: (gap: HunkGap, direction: ExpandDirection) => void
+  /** Unfold a hunk previously folded with `collapseHunks`. */
+  onExpandHunk?: (key: HunkKey) => void
   onRowMouseDown?: (line: DiffLine) => void
   onRowMouseEnter?: (line: DiffLine) => void
   onRowMouseUp?: () => void
@@ -29,25 +40,39 @@ export type DiffViewProps = {
   commentContext?: CommentContext
   cursor: CursorPosition | null
   selectedRange: SelectionRange
+  searchQuery?: string
 }
 
 export function SplitDiff(props: DiffViewProps) {
-  const { elements, onExpandGap, ...rest } = props
+  const { elements, onExpandGap, onExpandHunk, ...rest } = props
 
   return (
-    <div className="bg-background">
-      {elements.map((el, idx) =>
-        el.type === "gap" ? (
-          <GapRow
-            key={`gap-${idx}`}
-            gap={el.gap}
-            isLast={idx === elements.length - 1}
-            onExpandGap={onExpandGap}
-          />
-        ) : (
-          <SplitHunkLines key={`hunk-${idx}`} hunk={el.hunk} {...rest} />
-        ),
-      )}
+    <div className="bg-background" style={lineNumberGutterStyle(elements)}>
+      {elements.map((el, idx) => {
+        switch (el.type) {
+          case "gap":
+            return (
+              <GapRow
+                key={`gap-${idx}`}
+                gap={el.gap}
+                isLast={idx === elements.length - 1}
+                onExpandGap={onExpandGap}
+              />
+            )
+          case "collapsed":
+            return (
+              <CollapsedHunkRow
+                key={`hunk-${idx}`}
+                hunk={el.hunk}
+                onExpand={
+                  onExpandHunk ? () => onExpandHunk(hunkKey(el.hunk)) : undefined
+                }
+              />
+            )
+          case "hunk":
+            return <SplitHunkLines key={`hunk-${idx}`} hunk={el.hunk} {...rest} />
+        }
+      })}
     </div>
   )
 }
@@ -62,6 +87,7 @@ type HunkLinesProps = {
   commentContext?: CommentContext
   cursor: CursorPosition | null
   selectedRange: SelectionRange
+  searchQuery?: string
 }
 
 function SplitHunkLines({
@@ -74,6 +100,7 @@ function SplitHunkLines({
   commentContext,
   cursor,
   selectedRange,
+  searchQuery,
 }: HunkLinesProps) {
   const pairedLines = pairLinesForSplitView(hunk.lines)
   const isPairCursorLine = (pair: PairedLine): boolean => {
@@ -112,7 +139,11 @@ function SplitHunkLines({
   const lineHeight = 20
   return (
     <div
-      className="font-mono text-xs"
+      className={cn(
+        "font-mono text-xs",
+        hunk.whitespaceOnly && whitespaceOnlyHunk,
+      )}
+      title={hunk.whitespaceOnly ? whitespaceOnlyTitle : undefined}
       style={{
         contentVisibility: "auto",
         containIntrinsicSize: `auto ${pairedLines.length * lineHeight}px`,
@@ -153,6 +184,14 @@ function SplitHunkLines({
               leftHasComments={leftThreads.length > 0}
               rightHasComments={rightThreads.length > 0}
               isCursor={isCursor}
+              leftIsSearchMatch={
+                pair.left != null &&
+                lineMatchesQuery(pair.left, searchQuery ?? "")
+              }
+              rightIsSearchMatch={
+                pair.right != null &&
+                lineMatchesQuery(pair.right, searchQuery ?? "")
+              }
             />
             {(hasThreads || (isCursor && commentForm)) && (
               <div className="flex border-y border-border">
@@ -211,6 +250,8 @@ function SplitLineRow({
   leftHasComments,
   rightHasComments,
   isCursor,
+  leftIsSearchMatch,
+  rightIsSearchMatch,
 }: {
   pair: PairedLine
   onRowMouseDown?: () => void
@@ -221,7 +262,14 @@ function SplitLineRow({
   leftHasComments?: boolean
   rightHasComments?: boolean
   isCursor: boolean
+  leftIsSearchMatch?: boolean
+  rightIsSearchMatch?: boolean
 }) {
+  const whitespace = useContext(WhitespaceContext)
+  const leftTokens = pair.left && renderWhitespace(pair.left.tokens, whitespace)
+  const rightTokens =
+    pair.right && renderWhitespace(pair.right.tokens, whitespace)
+
   const defaultLeftBg = pair.left
     ? getLineStyle(pair.left.lineType).bgColor
     : "bg-muted/30"
@@ -234,12 +282,14 @@ function SplitLineRow({
   const leftBg = getLineHighlightBg({
     isCursor,
     isSelected: leftInRange,
+    isSearchMatch: leftIsSearchMatch,
     defaultBg: defaultLeftBg,
   })
 
   const rightBg = getLineHighlightBg({
     isCursor,
     isSelected: rightInRange,
+    isSearchMatch: rightIsSearchMatch,
     defaultBg: defaultRightBg,
   })
 
@@ -266,12 +316,18 @@ function SplitLineRow({
           leftBg,
         )}
       >
-        <LineNumberGutter className="w-10" hasComments={leftHasComments}>
+        <LineNumberGutter
+          className="w-10"
+          hasComments={leftHasComments}
+          blameLine={
+            pair.left?.lineType === "context" ? pair.left.oldLineno : null
+          }
+        >
           {pair.left?.oldLineno ?? ""}
         </LineNumberGutter>
         <span className="flex-1 pl-2 whitespace-pre-wrap wrap-break-word overflow-hidden">
-          {pair.left
-            ? pair.left.tokens.map((token, idx) => (
+          {leftTokens
+            ? leftTokens.map((token, idx) => (
                 <span
                   key={idx}
                   style={{ color: token.color ?? undefined }}
@@ -279,6 +335,7 @@ function SplitLineRow({
                     token.changed &&
                       pair.left?.lineType === "deletion" &&
                       changedTokenBg.deletion,
+                    token.moved && movedTokenBg,
                   )}
                 >
                   {token.content}
@@ -294,8 +351,8 @@ function SplitLineRow({
           {pair.right?.newLineno ?? ""}
         </LineNumberGutter>
         <span className="flex-1 pl-2 whitespace-pre-wrap wrap-break-word overflow-hidden">
-          {pair.right
-            ? pair.right.tokens.map((token, idx) => (
+          {rightTokens
+            ? rightTokens.map((token, idx) => (
                 <span
                   key={idx}
                   style={{ color: token.color ?? undefined }}
@@ -303,6 +360,7 @@ function SplitLineRow({
                     token.changed &&
                       pair.right?.lineType === "addition" &&
                       changedTokenBg.addition,
+                    token.moved && movedTokenBg,
                   )}
                 >
                   {token.content}
@@ -1,38 +1,63 @@
-import { Fragment } from "react"
+import { Fragment, useContext } from "react"
 
 import { DiffHunk, DiffLine } from "@/bindings"
 import { cn } from "@/lib/utils"
 
-import { changedTokenBg, getLineStyle } from "./diffStyles"
+import { CollapsedHunkRow } from "./CollapsedHunkRow"
+import {
+  changedTokenBg,
+  getLineStyle,
+  movedTokenBg,
+  unifiedLineContent,
+  unifiedLineRow,
+  whitespaceOnlyHunk,
+  whitespaceOnlyTitle,
+} from "./diffStyles"
 import { GapRow } from "./GapRow"
+import { hunkKey } from "./hunkGaps"
 import { InlineThreadDisplay } from "./InlineThreadDisplay"
-import { LineNumberGutter } from "./LineNumberGutter"
+import { LineNumberGutter, lineNumberGutterStyle } from "./LineNumberGutter"
 import { DiffViewProps } from "./SplitDiff"
 import { CommentContext, inlineCommentsKey, InlineCommentsMap } from "./types"
 import {
   CursorPosition,
   diffLineToCursorPosition,
   getLineHighlightBg,
+  lineMatchesQuery,
   SelectionRange,
 } from "./useLineSelection"
+import { renderWhitespace, WhitespaceContext } from "./whitespace"
 
 export function UnifiedDiff(props: DiffViewProps) {
-  const { elements, onExpandGap, ...rest } = props
+  const { elements, onExpandGap, onExpandHunk, ...rest } = props
 
   return (
-    <div className="bg-background">
-      {elements.map((el, idx) =>
-        el.type === "gap" ? (
-          <GapRow
-            key={`gap-${idx}`}
-            gap={el.gap}
-            isLast={idx === elements.length - 1}
-            onExpandGap={onExpandGap}
-          />
-        ) : (
-          <UnifiedHunkLines key={`hunk-${idx}`} hunk={el.hunk} {...rest} />
-        ),
-      )}
+    <div className="bg-background" style={lineNumberGutterStyle(elements)}>
+      {elements.map((el, idx) => {
+        switch (el.type) {
+          case "gap":
+            return (
+              <GapRow
+                key={`gap-${idx}`}
+                gap={el.gap}
+                isLast={idx === elements.length - 1}
+                onExpandGap={onExpandGap}
+              />
+            )
+          case "collapsed":
+            return (
+              <CollapsedHunkRow
+                key={`hunk-${idx}`}
+                hunk={el.hunk}
+                onExpand={
+                  onExpandHunk ? () => onExpandHunk(hunkKey(el.hunk)) : undefined
+                }
+              />
+            )
+          case "hunk":
+            return <UnifiedHunkLines key={`hunk-${idx}`} hunk={el.hunk} {...rest} />
+        }
+      })}
     </div>
   )
 }
@@ -47,6 +72,7 @@ type HunkLinesProps = {
   commentContext?: CommentContext
   cursor: CursorPosition | null
   selectedRange: SelectionRange
+  searchQuery?: string
 }
 
 export function UnifiedHunkLines({
@@ -59,6 +85,7 @@ export function UnifiedHunkLines({
   commentContext,
   cursor,
   selectedRange,
+  searchQuery,
 }: HunkLinesProps) {
   const key = (line: DiffLine) =>
     line.lineType === "deletion"
@@ -86,7 +113,11 @@ export function UnifiedHunkLines({
 
   return (
     <div
-      className="font-mono text-xs"
+      className={cn(
+        "font-mono text-xs",
+        hunk.whitespaceOnly && whitespaceOnlyHunk,
+      )}
+      title={hunk.whitespaceOnly ? whitespaceOnlyTitle : undefined}
       style={{
         contentVisibility: "auto",
         containIntrinsicSize: `auto ${hunk.lines.length * lineHeight}px`,
@@ -119,6 +150,7 @@ export function UnifiedHunkLines({
               onRowMouseUp={onRowMouseUp}
               isInRange={isInRange(line)}
               isCursor={isCursor(line)}
+              isSearchMatch={lineMatchesQuery(line, searchQuery ?? "")}
               hasComments={threads.length > 0}
             />
             {unResolvedThreads.length > 0 && (
@@ -152,6 +184,7 @@ function DiffLineComponent({
   onRowMouseUp,
   isInRange,
   isCursor,
+  isSearchMatch,
   hasComments,
 }: {
   line: DiffLine
@@ -160,8 +193,10 @@ function DiffLineComponent({
   onRowMouseUp?: () => void
   isInRange: boolean
   isCursor: boolean
+  isSearchMatch?: boolean
   hasComments?: boolean
 }) {
+  const whitespace = useContext(WhitespaceContext)
   const { bgColor } = getLineStyle(line.lineType)
 
   const lineNumber =
@@ -172,6 +207,7 @@ function DiffLineComponent({
   const lineBg = getLineHighlightBg({
     isCursor: isCursor,
     isSelected: isInRange,
+    isSearchMatch,
     defaultBg: bgColor,
   })
 
@@ -179,11 +215,13 @@ function DiffLineComponent({
     <div
       className={cn(
         "flex hover:bg-muted/30 group/line relative",
+        unifiedLineRow,
         lineBg,
         onRowMouseDown && "cursor-pointer",
       )}
       style={{ contain: "content" }}
       data-cursor={isCursor || undefined}
+      data-lineno={lineNumber ?? undefined}
       onMouseDown={
         onRowMouseDown
           ? (e) => {
@@ -195,14 +233,18 @@ function DiffLineComponent({
       onMouseEnter={onRowMouseEnter}
       onMouseUp={onRowMouseUp}
     >
-      <LineNumberGutter className="w-12" hasComments={hasComments}>
+      <LineNumberGutter
+        className="w-12"
+        hasComments={hasComments}
+        blameLine={line.lineType === "context" ? line.oldLineno : null}
+      >
         {line.lineType !== "addition" && line.oldLineno}
       </LineNumberGutter>
       <LineNumberGutter className="w-12">
         {line.lineType !== "deletion" && lineNumber}
       </LineNumberGutter>
-      <span className="flex-1 pl-2 whitespace-pre-wrap wrap-break-word">
-        {line.tokens.map((token, idx) => (
+      <span className={cn("flex-1 pl-2", unifiedLineContent)}>
+        {renderWhitespace(line.tokens, whitespace).map((token, idx) => (
           <span
             key={idx}
             style={{ color: token.color ?? undefined }}
@@ -213,6 +255,7 @@ function DiffLineComponent({
               token.changed &&
                 line.lineType === "addition" &&
                 changedTokenBg.addition,
+              token.moved && movedTokenBg,
             )}
           >
             {token.content}
@@ -60,6 +60,20 @@ export const changedTokenBg = {
   deletion: "bg-red-300/60 dark:bg-red-700/70",
 } as const
 
+export const movedTokenBg = "bg-blue-200/60 dark:bg-blue-800/60"
+
+/** Whitespace-only hunks recede so real changes stand out. */
+export const whitespaceOnlyHunk = "opacity-60"
+export const whitespaceOnlyTitle = "Only whitespace changed"
+
+/**
+ * Unified line content wraps unless an ancestor `group/diff` carries
+ * `data-nowrap`, in which case the row grows and the diff scrolls sideways.
+ */
+export const unifiedLineRow = "group-data-nowrap/diff:min-w-max"
+export const unifiedLineContent =
+  "whitespace-pre-wrap wrap-break-word group-data-nowrap/diff:whitespace-pre group-data-nowrap/diff:wrap-normal"
+
 export function getLineStyle(lineType: DiffLineType): {
   bgColor: string
 } {
@@ -2,7 +2,15 @@ import { describe, expect, it } from "vitest"
 
 import { DiffHunk, DiffLine } from "@/bindings"
 
-import { augmentHunks, buildDiffElements, DiffElement } from "./hunkGaps"
+import {
+  augmentHunks,
+  buildDiffElements,
+  collapseHunks,
+  DiffElement,
+  hunkKey,
+  lineNumberDigits,
+  visibleHunkRows,
+} from "./hunkGaps"
 
 function makeLine(newLineno: number, oldLineno: number): DiffLine {
   return {
@@ -27,6 +35,8 @@ function makeHunk(
     newLines,
     header: "",
     lines: lines ?? [],
+    whitespaceOnly: false,
+    hasConflict: false,
   }
 }
 
@@ -224,3 +234,67 @@ describe("augmentHunks", () => {
     expect(result[0].lines).toHaveLength(8)
   })
 })
+
+describe("collapseHunks", () => {
+  const lines = (start: number, count: number) =>
+    Array.from({ length: count }, (_, i) => makeLine(start + i, start + i))
+
+  const elements = buildDiffElements(
+    [
+      makeHunk(2, 3, 2, 3, lines(2, 3)),
+      makeHunk(10, 4, 10, 4, lines(10, 4)),
+    ],
+    20,
+  )
+
+  it("returns elements unchanged when nothing is collapsed", () => {
+    expect(collapseHunks(elements, new Set())).toBe(elements)
+    expect(visibleHunkRows(elements)).toBe(7)
+  })
+
+  it("replaces a folded hunk with a one-row placeholder", () => {
+    const collapsed = collapseHunks(elements, new Set(["10,10"]))
+
+    expect(visibleHunkRows(collapsed)).toBe(3 + 1)
+    expect(hunks(collapsed)).toHaveLength(1)
+    expect(gaps(collapsed)).toEqual(gaps(elements))
+
+    const placeholder = collapsed.find((e) => e.type === "collapsed")
+    expect(placeholder).toMatchObject({ type: "collapsed" })
+    expect(placeholder && hunkKey(placeholder.hunk)).toBe("10,10")
+  })
+
+  it("keeps the fold on the same hunk when a hunk is added before it", () => {
+    const folded = new Set([hunkKey(hunks(elements)[1])])
+    const withEarlierHunk = buildDiffElements(
+      [
+        makeHunk(1, 1, 1, 1, lines(1, 1)),
+        makeHunk(5, 3, 5, 3, lines(5, 3)),
+        makeHunk(10, 4, 10, 4, lines(10, 4)),
+      ],
+      20,
+    )
+
+    const collapsed = collapseHunks(withEarlierHunk, folded)
+    const placeholder = collapsed.find((e) => e.type === "collapsed")
+    expect(placeholder && placeholder.hunk.newStart).toBe(10)
+    expect(hunks(collapsed)).toHaveLength(2)
+  })
+})
+
+describe("lineNumberDigits", () => {
+  it("reserves three digits for short files", () => {
+    const elements = buildDiffElements([makeHunk(1, 5, 1, 5)], 5)
+    expect(lineNumberDigits(elements)).toBe(3)
+  })
+
+  it("fits the largest line number, including trailing gap lines", () => {
+    const elements = buildDiffElements([makeHunk(10, 2, 10, 3)], 1234)
+    expect(lineNumberDigits(elements)).toBe(4)
+  })
+
+  it("counts the old side when the file shrank", () => {
+    const elements = buildDiffElements([makeHunk(9990, 20, 9990, 2)], 9991)
+    expect(lineNumberDigits(elements)).toBe(5)
+  })
+})
@@ -14,6 +14,67 @@ export type HunkGap = {
 export type DiffElement =
   | { type: "gap"; gap: HunkGap }
   | { type: "hunk"; hunk: DiffHunk }
+  | { type: "collapsed"; hunk: DiffHunk }
+
+/**
+ * Identifies a hunk by where it starts rather than its position, so folds stay
+ * on the same hunk when expanding context merges or adds hunks before it.
+ */
+export type HunkKey = string
+
+export function hunkKey(hunk: DiffHunk): HunkKey {
+  return `${hunk.oldStart},${hunk.newStart}`
+}
+
+/**
+ * Replace the hunks whose key is in `collapsed` with placeholders. Line
+ * navigation only visits `hunk` elements, so folded lines are skipped.
+ */
+export function collapseHunks(
+  elements: DiffElement[],
+  collapsed: ReadonlySet<HunkKey>,
+): DiffElement[] {
+  if (collapsed.size === 0) return elements
+
+  return elements.map((el) =>
+    el.type === "hunk" && collapsed.has(hunkKey(el.hunk))
+      ? { type: "collapsed", hunk: el.hunk }
+      : el,
+  )
+}
+
+/** Rows rendered for hunks: every line of an open hunk, one per folded hunk. */
+export function visibleHunkRows(elements: DiffElement[]): number {
+  return elements.reduce((total, el) => {
+    if (el.type === "hunk") return total + el.hunk.lines.length
+    if (el.type === "collapsed") return total + 1
+    return total
+  }, 0)
+}
+
+/** Digits reserved for line numbers even in short files, so gutters line up. */
+const MIN_LINE_NUMBER_DIGITS = 3
+
+/**
+ * Digits in the largest line number `elements` can show, counting gap lines
+ * that may be expanded later. Sizes the line number gutter.
+ */
+export function lineNumberDigits(elements: DiffElement[]): number {
+  let max = 0
+  for (const el of elements) {
+    if (el.type === "gap") {
+      max = Math.max(max, el.gap.newEnd, el.gap.oldStart + el.gap.count - 1)
+    } else {
+      const { hunk } = el
+      max = Math.max(
+        max,
+        hunk.oldStart + hunk.oldLines - 1,
+        hunk.newStart + hunk.newLines - 1,
+      )
+    }
+  }
+  return Math.max(MIN_LINE_NUMBER_DIGITS, String(max).length)
+}
 
 /**
  * Build an interleaved sequence of gaps and hunks.
@@ -206,6 +267,8 @@ export function augmentHunks(
       prev.lines = [...prev.lines, ...curr.lines]
       prev.newLines = curr.newStart + curr.newLines - prev.newStart
       prev.oldLines = curr.oldStart + curr.oldLines - prev.oldStart
+      prev.whitespaceOnly = prev.whitespaceOnly && curr.whitespaceOnly
+      prev.hasConflict = prev.hasConflict || curr.hasConflict
     } else {
       merged.push(curr)
     }
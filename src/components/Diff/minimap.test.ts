@@ -0,0 +1,89 @@
+import { describe, expect, it } from "vitest"
+
+import { DiffHunk, DiffLine, DiffLineType } from "@/bindings"
+
+import { DiffElement } from "./hunkGaps"
+import { coverageRows, viewportFromLines } from "./minimap"
+
+function makeLine(
+  lineType: DiffLineType,
+  oldLineno: number | null,
+  newLineno: number | null,
+): DiffLine {
+  return { lineType, oldLineno, newLineno, tokens: [] }
+}
+
+function makeHunk(newStart: number, lines: DiffLine[]): DiffElement {
+  const hunk: DiffHunk = {
+    oldStart: newStart,
+    oldLines: lines.filter((l) => l.oldLineno !== null).length,
+    newStart,
+    newLines: lines.filter((l) => l.newLineno !== null).length,
+    header: "",
+    lines,
+    whitespaceOnly: false,
+    hasConflict: false,
+  }
+  return { type: "hunk", hunk }
+}
+
+describe("coverageRows", () => {
+  it("colors reviewed and unreviewed halves of a file", () => {
+    const reviewed = [makeHunk(2, [makeLine("addition", null, 2)])]
+    const remaining = [makeHunk(90, [makeLine("addition", null, 90)])]
+
+    expect(coverageRows(remaining, reviewed, 100, 4)).toEqual([
+      "reviewed",
+      "unchanged",
+      "unchanged",
+      "unreviewed",
+    ])
+  })
+
+  it("places deletions at the following new-file line", () => {
+    const remaining = [
+      makeHunk(50, [
+        makeLine("context", 50, 50),
+        makeLine("deletion", 51, null),
+        makeLine("context", 52, 51),
+      ]),
+    ]
+
+    expect(coverageRows(remaining, [], 100, 2)).toEqual([
+      "unchanged",
+      "unreviewed",
+    ])
+  })
+
+  it("lets unreviewed changes win over reviewed ones in the same row", () => {
+    const reviewed = [makeHunk(1, [makeLine("addition", null, 1)])]
+    const remaining = [makeHunk(3, [makeLine("addition", null, 3)])]
+
+    expect(coverageRows(remaining, reviewed, 10, 1)).toEqual(["unreviewed"])
+  })
+
+  it("returns no rows for an empty minimap", () => {
+    expect(coverageRows([], [], 10, 0)).toEqual([])
+  })
+})
+
+describe("viewportFromLines", () => {
+  it("covers the span of visible line numbers", () => {
+    expect(viewportFromLines([60, 51, 55], 100)).toEqual({
+      top: 0.5,
+      bottom: 0.6,
+    })
+  })
+
+  it("stays on the file scale when rows far apart are both visible", () => {
+    // Two hunks with a collapsed gap between them fill the screen.
+    expect(viewportFromLines([1, 2, 3, 98, 99, 100], 100)).toEqual({
+      top: 0,
+      bottom: 1,
+    })
+  })
+
+  it("returns null when no rows are on screen", () => {
+    expect(viewportFromLines([], 100)).toBeNull()
+  })
+})
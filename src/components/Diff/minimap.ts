@@ -0,0 +1,81 @@
+import { DiffLine } from "@/bindings"
+
+import { DiffElement } from "./hunkGaps"
+
+export type CoverageState = "unchanged" | "reviewed" | "unreviewed"
+
+export type Viewport = { top: number; bottom: number }
+
+/**
+ * New-file line a changed line sits at. Deletions have no new line number,
+ * so they are placed at the line that follows them in the new file.
+ */
+function changedLineno(line: DiffLine, fallback: number): number | null {
+  if (line.lineType === "addition") return line.newLineno
+  if (line.lineType === "deletion") return fallback
+  return null
+}
+
+function changedLines(elements: DiffElement[]): number[] {
+  const lines: number[] = []
+  for (const el of elements) {
+    if (el.type === "gap") continue
+    let next = el.hunk.newStart
+    for (const line of el.hunk.lines) {
+      const lineno = changedLineno(line, next)
+      if (lineno !== null) lines.push(lineno)
+      if (line.newLineno !== null) next = line.newLineno + 1
+    }
+  }
+  return lines
+}
+
+/**
+ * Split a file of `totalLines` lines into `rows` minimap rows and report
+ * whether each row holds unreviewed changes, only reviewed changes, or
+ * neither. Reviewed lines are numbered against the marker, which is close
+ * enough to the target for a proportional overview.
+ */
+export function coverageRows(
+  remaining: DiffElement[],
+  reviewed: DiffElement[],
+  totalLines: number,
+  rows: number,
+): CoverageState[] {
+  const states: CoverageState[] = Array.from(
+    { length: rows },
+    () => "unchanged",
+  )
+  if (rows === 0) return states
+  const lines = Math.max(totalLines, 1)
+  const rowOf = (lineno: number) => {
+    const row = Math.floor(((Math.max(lineno, 1) - 1) * rows) / lines)
+    return Math.min(rows - 1, row)
+  }
+
+  for (const lineno of changedLines(reviewed)) {
+    states[rowOf(lineno)] = "reviewed"
+  }
+  for (const lineno of changedLines(remaining)) {
+    states[rowOf(lineno)] = "unreviewed"
+  }
+  return states
+}
+
+/**
+ * Fraction of a `totalLines` file covered by the rendered rows currently on
+ * screen, on the same line scale as `coverageRows` so the outline lines up
+ * with the colored rows even when unchanged stretches are collapsed.
+ */
+export function viewportFromLines(
+  visible: number[],
+  totalLines: number,
+): Viewport | null {
+  if (visible.length === 0) return null
+  const lines = Math.max(totalLines, 1)
+  const clamp = (v: number) => Math.min(1, Math.max(0, v))
+  return {
+    top: clamp((Math.max(Math.min(...visible), 1) - 1) / lines),
+    bottom: clamp(Math.max(...visible) / lines),
+  }
+}
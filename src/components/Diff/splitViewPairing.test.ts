@@ -0,0 +1,76 @@
+import { describe, expect, it } from "vitest"
+
+import { DiffLine, DiffLineType } from "@/bindings"
+
+import { PairedLine, pairLinesForSplitView } from "./splitViewPairing"
+
+function makeLine(
+  lineType: DiffLineType,
+  oldLineno: number | null,
+  newLineno: number | null,
+): DiffLine {
+  return { lineType, oldLineno, newLineno, tokens: [] }
+}
+
+/** `[left.oldLineno, right.newLineno]` per row, `null` for an empty cell. */
+function linenos(pairs: PairedLine[]) {
+  return pairs.map((p) => [
+    p.left?.oldLineno ?? null,
+    p.right?.newLineno ?? null,
+  ])
+}
+
+describe("pairLinesForSplitView", () => {
+  it("keeps deletions on the left and additions on the right", () => {
+    const pairs = pairLinesForSplitView([
+      makeLine("context", 1, 1),
+      makeLine("deletion", 2, null),
+      makeLine("deletion", 3, null),
+      makeLine("addition", null, 2),
+      makeLine("context", 4, 3),
+    ])
+
+    for (const { left, right } of pairs) {
+      expect(left?.lineType).not.toBe("addition")
+      expect(right?.lineType).not.toBe("deletion")
+    }
+    expect(linenos(pairs)).toEqual([
+      [1, 1],
+      [2, 2],
+      [3, null],
+      [4, 3],
+    ])
+  })
+
+  it("spans context lines across both columns", () => {
+    const context = makeLine("context", 5, 7)
+    const pairs = pairLinesForSplitView([context])
+
+    expect(pairs).toEqual([{ left: context, right: context }])
+  })
+
+  it("aligns a deletion with the addition it was matched to", () => {
+    const pairs = pairLinesForSplitView([
+      makeLine("deletion", 10, null),
+      makeLine("deletion", 11, 21),
+      makeLine("addition", null, 20),
+      makeLine("addition", null, 21),
+    ])
+
+    expect(linenos(pairs)).toEqual([
+      [10, null],
+      [null, 20],
+      [11, 21],
+    ])
+  })
+
+  it("puts additions without preceding deletions on the right only", () => {
+    const pairs = pairLinesForSplitView([
+      makeLine("addition", null, 1),
+      makeLine("addition", null, 2),
+    ])
+
+    expect(pairs.every((p) => p.left === null)).toBe(true)
+    expect(pairs.map((p) => p.right?.newLineno)).toEqual([1, 2])
+  })
+})
@@ -9,6 +9,11 @@ export type CommentContext = {
     startSide?: "LEFT" | "RIGHT"
   }) => Promise<void>
   onReplyToThread?: (threadId: string, body: string) => Promise<void>
+  /**
+   * Comments anchor to a single side (kenjutu comments). A selection that
+   * touches both sides is attached to the new side.
+   */
+  singleSide?: boolean
 }
 
 export type InlineCommentFormProps = {
@@ -36,6 +41,8 @@ export type InlineReply = {
   body: string
   createdAt: string
   user?: InlineCommentUser
+  /** Nesting below the thread root, for replies to replies. */
+  depth?: number
 }
 
 export type InlineThread = {
@@ -0,0 +1,56 @@
+import { createContext, useContext, useMemo, useState } from "react"
+
+import { BlameLine, commands } from "@/bindings"
+import { useRpcQuery } from "@/hooks/useRpcQuery"
+import { queryKeys } from "@/lib/queryKeys"
+
+export type BlameLookup = {
+  /** Blame of a line in the base version of the file, once loaded. */
+  get: (oldLineno: number) => BlameLine | undefined
+  /** Start loading blame. Blame is only fetched once someone hovers a line. */
+  request: () => void
+}
+
+/**
+ * Provided only where the old side of the diff is the commit's parent, so old
+ * line numbers can be looked up in the parent's blame.
+ */
+export const BlameContext = createContext<BlameLookup | null>(null)
+
+export function useBlameContext(): BlameLookup | null {
+  return useContext(BlameContext)
+}
+
+export function blameTitle(blame: BlameLine | undefined): string | undefined {
+  return blame ? `${blame.shortId} ${blame.author}` : undefined
+}
+
+export function useBlame({
+  localDir,
+  commitSha,
+  filePath,
+  oldPath,
+}: {
+  localDir: string
+  commitSha: string
+  filePath: string
+  oldPath?: string
+}): BlameLookup {
+  const [requested, setRequested] = useState(false)
+
+  const { data } = useRpcQuery({
+    queryKey: queryKeys.blame(localDir, commitSha, filePath, oldPath),
+    queryFn: () =>
+      commands.getBlame(localDir, commitSha, filePath, oldPath ?? null),
+    enabled: requested,
+    staleTime: Infinity,
+  })
+
+  return useMemo(() => {
+    const byLine = new Map(data?.map((blame) => [blame.line, blame]))
+    return {
+      get: (oldLineno: number) => byLine.get(oldLineno),
+      request: () => setRequested(true),
+    }
+  }, [data])
+}
@@ -18,7 +18,9 @@ export function useCommentForm({
 
   const initiateComment = () => {
     if (!commentContext) return
-    const resolved = selection.toCommentLineState()
+    const resolved = commentContext.singleSide
+      ? selection.toSingleSideCommentLineState()
+      : selection.toCommentLineState()
     if (!resolved) return
     setCommentLine(resolved)
   }
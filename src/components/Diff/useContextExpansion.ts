@@ -12,10 +12,13 @@ export function useContextExpansion({
   localDir,
   commitSha,
   filePath,
+  forcePlain,
 }: {
   localDir: string
   commitSha: string
   filePath: string
+  /** Fetch uncolored lines, matching a diff shown plain because it was too large. */
+  forcePlain: boolean
 }) {
   const [fetchedContextLines, setFetchedContextLines] = useState<
     Map<number, DiffLine>
@@ -48,6 +51,7 @@ export function useContextExpansion({
         fetchStart,
         fetchEnd,
         oldStartLine,
+        forcePlain,
       )
 
       if (result.status === "error") {
@@ -65,7 +69,7 @@ export function useContextExpansion({
         return next
       })
     },
-    [localDir, commitSha, filePath],
+    [localDir, commitSha, filePath, forcePlain],
   )
 
   return { fetchedContextLines, handleExpandGap }
@@ -0,0 +1,29 @@
+import { useState } from "react"
+
+const DIFF_SEARCH_QUERY_KEY = "kenjutu-diff-search-query"
+
+/** Search state for line mode. The query is persisted so it survives switching files. */
+export function useDiffSearch() {
+  const [query, _setQuery] = useState<string>(() => {
+    if (typeof window !== "undefined") {
+      return localStorage.getItem(DIFF_SEARCH_QUERY_KEY) ?? ""
+    }
+    return ""
+  })
+  const [isOpen, setIsOpen] = useState(false)
+
+  const setQuery = (query: string) => {
+    _setQuery(query)
+    localStorage.setItem(DIFF_SEARCH_QUERY_KEY, query)
+  }
+
+  return {
+    query,
+    setQuery,
+    isOpen,
+    open: () => setIsOpen(true),
+    close: () => setIsOpen(false),
+  }
+}
+
+export type UseDiffSearchReturn = ReturnType<typeof useDiffSearch>
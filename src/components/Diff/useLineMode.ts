@@ -2,7 +2,10 @@ import { useHotkey, useHotkeySequence } from "@tanstack/react-hotkeys"
 import { useRef } from "react"
 
 import { RegionId } from "@/bindings"
+import { useKeybindings } from "@/hooks/useKeybindings"
+import { COMMENT_JUMP_KEYS } from "@/lib/keybindings"
 
+import { CommentKey } from "./types"
 import { UseLineSelectionReturn } from "./useLineSelection"
 
 export function useLineMode({
@@ -12,6 +15,12 @@ export function useLineMode({
   onExit,
   onComment,
   onMarkRegion,
+  searchQuery,
+  onSearch,
+  onToggleFold,
+  onUnfoldAll,
+  commentKeys,
+  onToggleResolvedComments,
 }: {
   selection: UseLineSelectionReturn
   containerRef: React.RefObject<HTMLElement | null>
@@ -19,7 +28,17 @@ export function useLineMode({
   onExit: () => void
   onComment?: () => void
   onMarkRegion?: (region: RegionId) => void
+  /** While non-empty, N / Shift+N jump between matches instead of hunks. */
+  searchQuery?: string
+  onSearch?: () => void
+  onToggleFold?: () => void
+  onUnfoldAll?: () => void
+  /** Lines `]M` / `[M` jump between. */
+  commentKeys?: ReadonlySet<CommentKey>
+  onToggleResolvedComments?: () => void
 }) {
+  const keys = useKeybindings()
+
   // Keep a ref to selection for use in hotkey closures
   const selectionRef = useRef(selection)
   selectionRef.current = selection
@@ -30,14 +49,14 @@ export function useLineMode({
   }
 
   useHotkey(
-    "J",
+    keys.cursor_down,
     () => {
       selectionRef.current.moveCursorBy(1)
     },
     hotkeyGuard,
   )
   useHotkey(
-    "K",
+    keys.cursor_up,
     () => {
       selectionRef.current.moveCursorBy(-1)
     },
@@ -49,27 +68,100 @@ export function useLineMode({
     () => selectionRef.current.moveToTop(),
     hotkeyGuard,
   )
-  useHotkey("Shift+G", () => selectionRef.current.moveToBottom(), hotkeyGuard)
+  useHotkey(
+    keys.cursor_bottom,
+    () => selectionRef.current.moveToBottom(),
+    hotkeyGuard,
+  )
 
   const HALF_PAGE = 20
   useHotkey(
-    "Control+D",
+    keys.half_page_down,
     () => selectionRef.current.moveCursorBy(HALF_PAGE),
     hotkeyGuard,
   )
   useHotkey(
-    "Control+U",
+    keys.half_page_up,
     () => selectionRef.current.moveCursorBy(-HALF_PAGE),
     hotkeyGuard,
   )
 
-  useHotkey("N", () => selectionRef.current.moveToNextHunk(), hotkeyGuard)
-  useHotkey("Shift+N", () => selectionRef.current.moveToPrevHunk(), hotkeyGuard)
+  useHotkey(
+    keys.next_hunk,
+    () => {
+      if (searchQuery) {
+        selectionRef.current.moveToSearchMatch(searchQuery, "next")
+      } else {
+        selectionRef.current.moveToNextHunk()
+      }
+    },
+    hotkeyGuard,
+  )
+  useHotkey(
+    keys.prev_hunk,
+    () => {
+      if (searchQuery) {
+        selectionRef.current.moveToSearchMatch(searchQuery, "prev")
+      } else {
+        selectionRef.current.moveToPrevHunk()
+      }
+    },
+    hotkeyGuard,
+  )
+
+  useHotkeySequence(
+    COMMENT_JUMP_KEYS.next,
+    () => {
+      if (commentKeys) selectionRef.current.moveToComment(commentKeys, "next")
+    },
+    { ...hotkeyGuard, enabled: active && commentKeys != null },
+  )
+  useHotkeySequence(
+    COMMENT_JUMP_KEYS.prev,
+    () => {
+      if (commentKeys) selectionRef.current.moveToComment(commentKeys, "prev")
+    },
+    { ...hotkeyGuard, enabled: active && commentKeys != null },
+  )
+  useHotkey(
+    keys.toggle_resolved_comments,
+    () => {
+      if (onToggleResolvedComments) onToggleResolvedComments()
+    },
+    { ...hotkeyGuard, enabled: active && onToggleResolvedComments != null },
+  )
+
+  useHotkey(
+    keys.search,
+    () => {
+      if (onSearch) onSearch()
+    },
+    { ...hotkeyGuard, enabled: active && onSearch != null },
+  )
 
-  useHotkey("V", () => selectionRef.current.toggleSelect(), hotkeyGuard)
+  useHotkey(
+    keys.toggle_selection,
+    () => selectionRef.current.toggleSelect(),
+    hotkeyGuard,
+  )
 
   useHotkey(
-    "Space",
+    keys.toggle_fold,
+    () => {
+      if (onToggleFold) onToggleFold()
+    },
+    { ...hotkeyGuard, enabled: active && onToggleFold != null },
+  )
+  useHotkey(
+    keys.unfold_all,
+    () => {
+      if (onUnfoldAll) onUnfoldAll()
+    },
+    { ...hotkeyGuard, enabled: active && onUnfoldAll != null },
+  )
+
+  useHotkey(
+    keys.mark_selection,
     () => {
       if (onMarkRegion) {
         const region = selectionRef.current.regionId()
@@ -83,12 +175,26 @@ export function useLineMode({
   )
 
   useHotkey(
-    "C",
+    keys.mark_hunk,
+    () => {
+      if (onMarkRegion) {
+        const region = selectionRef.current.hunkRegionId()
+        if (region) {
+          onMarkRegion(region)
+          selectionRef.current.clearSelection()
+        }
+      }
+    },
+    hotkeyGuard,
+  )
+
+  useHotkey(
+    keys.comment,
     () => {
       if (onComment) onComment()
     },
     { ...hotkeyGuard, enabled: active && onComment != null },
   )
 
-  useHotkey("Escape", () => onExit(), hotkeyGuard)
+  useHotkey(keys.exit_line_mode, () => onExit(), hotkeyGuard)
 }
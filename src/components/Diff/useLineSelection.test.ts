@@ -2,12 +2,19 @@ import { describe, expect, it } from "vitest"
 
 import { DiffHunk, DiffLine, DiffLineType } from "@/bindings"
 
-import { DiffElement } from "./hunkGaps"
+import { collapseHunks, DiffElement } from "./hunkGaps"
+import { inlineCommentsKey } from "./types"
 import {
   computeRegionId,
   CursorPosition,
+  findCommentLine,
+  findSearchMatch,
   getSelectedRegion,
+  hunkKeyAtCursor,
+  hunkRegionAtCursor,
+  lineMatchesQuery,
   LineSelectionState,
+  singleSideCommentLineState,
 } from "./useLineSelection"
 
 // ---------------------------------------------------------------------------
@@ -22,6 +29,20 @@ function makeLine(
   return { lineType, oldLineno, newLineno, tokens: [] }
 }
 
+function makeTextLine(
+  lineType: DiffLineType,
+  oldLineno: number | null,
+  newLineno: number | null,
+  text: string,
+): DiffLine {
+  return {
+    lineType,
+    oldLineno,
+    newLineno,
+    tokens: [{ content: text, color: null, changed: false, moved: false }],
+  }
+}
+
 function makeHunk(lines: DiffLine[]): DiffHunk {
   return {
     oldStart: 1,
@@ -34,6 +55,8 @@ function makeHunk(lines: DiffLine[]): DiffHunk {
     ).length,
     header: "",
     lines,
+    whitespaceOnly: false,
+    hasConflict: false,
   }
 }
 
@@ -352,4 +375,344 @@ describe("computeRegionId", () => {
       newLines: 2,
     })
   })
+
+  it("left-only scans backward for the newStart insertion point", () => {
+    // Remaining panel (M→T): deleting lines 11-12 of M after T's line 20.
+    const lines = [
+      makeLine("context", 10, 20),
+      makeLine("deletion", 11, null),
+      makeLine("deletion", 12, null),
+      makeLine("context", 13, 21),
+    ]
+    const result = computeRegionId(
+      { left: { start: 11, end: 12 }, right: null },
+      wrapHunk(lines),
+    )
+    expect(result).toEqual({
+      oldStart: 11,
+      oldLines: 2,
+      newStart: 20,
+      newLines: 0,
+    })
+  })
+
+  it("left-only in the reviewed panel points newStart into the marker", () => {
+    // Reviewed panel (B→M): base lines 4-5 were dropped from M after line 2.
+    // Unmarking splices them back in after M's line 2.
+    const lines = [
+      makeLine("context", 3, 2),
+      makeLine("deletion", 4, null),
+      makeLine("deletion", 5, null),
+    ]
+    const result = computeRegionId(
+      { left: { start: 5, end: 5 }, right: null },
+      wrapHunk(lines),
+    )
+    expect(result).toEqual({
+      oldStart: 5,
+      oldLines: 1,
+      newStart: 2,
+      newLines: 0,
+    })
+  })
+
+  it("left-only at the top of the file gives newStart 0", () => {
+    const lines = [
+      makeLine("deletion", 1, null),
+      makeLine("deletion", 2, null),
+      makeLine("context", 3, 1),
+    ]
+    const result = computeRegionId(
+      { left: { start: 1, end: 2 }, right: null },
+      wrapHunk(lines),
+    )
+    expect(result).toEqual({
+      oldStart: 1,
+      oldLines: 2,
+      newStart: 0,
+      newLines: 0,
+    })
+  })
+})
+
+// ---------------------------------------------------------------------------
+// lineMatchesQuery / findSearchMatch
+// ---------------------------------------------------------------------------
+
+describe("lineMatchesQuery", () => {
+  it("matches case-insensitively across tokens", () => {
+    const line: DiffLine = {
+      lineType: "context",
+      oldLineno: 1,
+      newLineno: 1,
+      tokens: [
+        { content: "let Foo", color: null, changed: false, moved: false },
+        { content: "Bar = 1", color: null, changed: false, moved: false },
+      ],
+    }
+    expect(lineMatchesQuery(line, "foobar")).toBe(true)
+    expect(lineMatchesQuery(line, "baz")).toBe(false)
+  })
+
+  it("never matches an empty query", () => {
+    expect(lineMatchesQuery(makeTextLine("context", 1, 1, "x"), "")).toBe(
+      false,
+    )
+  })
+})
+
+describe("findSearchMatch", () => {
+  const elements = wrapHunk([
+    makeTextLine("context", 1, 1, "fn needle() {"),
+    makeTextLine("deletion", 2, null, "  old()"),
+    makeTextLine("addition", null, 2, "  NEEDLE()"),
+    makeTextLine("context", 3, 3, "}"),
+  ])
+
+  it("moves to the next matching line", () => {
+    const match = findSearchMatch(
+      elements,
+      { line: 1, side: "RIGHT" },
+      "needle",
+      "next",
+    )
+    expect(match?.newLineno).toBe(2)
+    expect(match?.lineType).toBe("addition")
+  })
+
+  it("wraps around past the last line", () => {
+    const match = findSearchMatch(
+      elements,
+      { line: 2, side: "RIGHT" },
+      "needle",
+      "next",
+    )
+    expect(match?.lineType).toBe("context")
+    expect(match?.newLineno).toBe(1)
+  })
+
+  it("wraps around before the first line when searching backwards", () => {
+    const match = findSearchMatch(
+      elements,
+      { line: 1, side: "RIGHT" },
+      "needle",
+      "prev",
+    )
+    expect(match?.lineType).toBe("addition")
+  })
+
+  it("matches deletions on the left side", () => {
+    const match = findSearchMatch(
+      elements,
+      { line: 3, side: "RIGHT" },
+      "OLD",
+      "next",
+    )
+    expect(match?.lineType).toBe("deletion")
+    expect(match?.oldLineno).toBe(2)
+  })
+
+  it("returns null when nothing matches", () => {
+    expect(
+      findSearchMatch(elements, { line: 1, side: "RIGHT" }, "absent", "next"),
+    ).toBeNull()
+  })
+})
+
+// ---------------------------------------------------------------------------
+// findCommentLine
+// ---------------------------------------------------------------------------
+
+describe("findCommentLine", () => {
+  const elements = wrapHunk([
+    makeLine("context", 1, 1),
+    makeLine("deletion", 2, null),
+    makeLine("addition", null, 2),
+    makeLine("context", 3, 3),
+    makeLine("addition", null, 4),
+  ])
+  const commentKeys = new Set([
+    inlineCommentsKey("LEFT", 2),
+    inlineCommentsKey("RIGHT", 4),
+  ])
+
+  it("moves to the next commented line after the cursor", () => {
+    const line = findCommentLine(
+      elements,
+      { line: 1, side: "RIGHT" },
+      commentKeys,
+      "next",
+    )
+    expect(line?.lineType).toBe("deletion")
+    expect(line?.oldLineno).toBe(2)
+  })
+
+  it("skips past the current thread", () => {
+    const line = findCommentLine(
+      elements,
+      { line: 2, side: "LEFT" },
+      commentKeys,
+      "next",
+    )
+    expect(line?.newLineno).toBe(4)
+  })
+
+  it("moves backwards and wraps around", () => {
+    const prev = findCommentLine(
+      elements,
+      { line: 3, side: "RIGHT" },
+      commentKeys,
+      "prev",
+    )
+    expect(prev?.oldLineno).toBe(2)
+    const wrapped = findCommentLine(
+      elements,
+      { line: 4, side: "RIGHT" },
+      commentKeys,
+      "next",
+    )
+    expect(wrapped?.oldLineno).toBe(2)
+  })
+
+  it("matches context lines by either side", () => {
+    const line = findCommentLine(
+      elements,
+      { line: 4, side: "RIGHT" },
+      new Set([inlineCommentsKey("LEFT", 3)]),
+      "prev",
+    )
+    expect(line?.lineType).toBe("context")
+    expect(line?.newLineno).toBe(3)
+  })
+
+  it("returns null without comments", () => {
+    expect(
+      findCommentLine(elements, { line: 1, side: "RIGHT" }, new Set(), "next"),
+    ).toBeNull()
+  })
+})
+
+// ---------------------------------------------------------------------------
+// hunkKeyAtCursor
+// ---------------------------------------------------------------------------
+
+describe("hunkKeyAtCursor", () => {
+  const startingAt = (start: number, lines: DiffLine[]): DiffHunk => ({
+    ...makeHunk(lines),
+    oldStart: start,
+    newStart: start,
+  })
+  const elements: DiffElement[] = [
+    { type: "hunk", hunk: startingAt(1, [makeLine("context", 1, 1)]) },
+    { type: "gap", gap: { newStart: 2, newEnd: 4, oldStart: 2, count: 3 } },
+    { type: "hunk", hunk: startingAt(5, [makeLine("addition", null, 5)]) },
+    { type: "hunk", hunk: startingAt(9, [makeLine("deletion", 9, null)]) },
+  ]
+
+  it("keys the hunk by its start lines", () => {
+    expect(hunkKeyAtCursor(elements, { line: 5, side: "RIGHT" })).toBe("5,5")
+  })
+
+  it("finds open hunks after a folded one", () => {
+    const folded = collapseHunks(elements, new Set(["5,5"]))
+    expect(hunkKeyAtCursor(folded, { line: 9, side: "LEFT" })).toBe("9,9")
+  })
+
+  it("returns null when the cursor is not on any line", () => {
+    expect(hunkKeyAtCursor(elements, { line: 42, side: "LEFT" })).toBeNull()
+  })
+})
+
+// ---------------------------------------------------------------------------
+// hunkRegionAtCursor
+// ---------------------------------------------------------------------------
+
+describe("hunkRegionAtCursor", () => {
+  const second: DiffHunk = {
+    ...makeHunk([
+      makeLine("context", 7, 8),
+      makeLine("deletion", 8, null),
+      makeLine("addition", null, 9),
+    ]),
+    oldStart: 7,
+    newStart: 8,
+  }
+  const elements: DiffElement[] = [
+    { type: "hunk", hunk: makeHunk([makeLine("addition", null, 1)]) },
+    { type: "gap", gap: { newStart: 2, newEnd: 7, oldStart: 1, count: 6 } },
+    { type: "hunk", hunk: second },
+  ]
+
+  it("returns the header of the hunk under the cursor", () => {
+    expect(hunkRegionAtCursor(elements, { line: 8, side: "LEFT" })).toEqual({
+      oldStart: 7,
+      oldLines: 2,
+      newStart: 8,
+      newLines: 2,
+    })
+  })
+
+  it("returns null when the cursor is not on any line", () => {
+    expect(hunkRegionAtCursor(elements, { line: 4, side: "RIGHT" })).toBeNull()
+  })
+})
+
+// ---------------------------------------------------------------------------
+// singleSideCommentLineState
+// ---------------------------------------------------------------------------
+
+describe("singleSideCommentLineState", () => {
+  const lines = [
+    makeLine("context", 4, 4),
+    makeLine("deletion", 5, null),
+    makeLine("deletion", 6, null),
+    makeLine("addition", null, 5),
+    makeLine("addition", null, 6),
+    makeLine("addition", null, 7),
+  ]
+  const elements = wrapHunk(lines)
+  const region = (cursor: CursorPosition, anchor?: CursorPosition) =>
+    getSelectedRegion(sel(cursor, anchor), elements)
+
+  it("puts a selection of added lines on the new side", () => {
+    const range = region({ line: 7, side: "RIGHT" }, { line: 5, side: "RIGHT" })
+    expect(singleSideCommentLineState(range)).toEqual({
+      line: 7,
+      side: "RIGHT",
+      startLine: 5,
+      startSide: "RIGHT",
+    })
+  })
+
+  it("puts a selection of deleted lines on the old side", () => {
+    const range = region({ line: 6, side: "LEFT" }, { line: 5, side: "LEFT" })
+    expect(singleSideCommentLineState(range)).toEqual({
+      line: 6,
+      side: "LEFT",
+      startLine: 5,
+      startSide: "LEFT",
+    })
+  })
+
+  it("attaches a mixed selection to the new side only", () => {
+    const range = region({ line: 6, side: "RIGHT" }, { line: 5, side: "LEFT" })
+    expect(singleSideCommentLineState(range)).toEqual({
+      line: 6,
+      side: "RIGHT",
+      startLine: 5,
+      startSide: "RIGHT",
+    })
+  })
+
+  it("omits startLine for a single line", () => {
+    const range = region({ line: 5, side: "RIGHT" })
+    expect(singleSideCommentLineState(range)).toEqual({
+      line: 5,
+      side: "RIGHT",
+    })
+  })
+
+  it("returns null for an empty selection", () => {
+    expect(singleSideCommentLineState({ left: null, right: null })).toBeNull()
+  })
 })
@@ -2,8 +2,8 @@ import { useEffect, useMemo } from "react"
 
 import { DiffLine, DiffLineType, RegionId } from "@/bindings"
 
-import { DiffElement } from "./hunkGaps"
-import { CommentLineState } from "./types"
+import { DiffElement, HunkKey, hunkKey } from "./hunkGaps"
+import { CommentKey, CommentLineState, inlineCommentsKey } from "./types"
 import { DiffViewMode } from "./useDiffViewMode"
 
 export type LineSelectionState = {
@@ -108,20 +108,161 @@ function selectionToCommentLineState(
   }
 }
 
+/**
+ * Collapse a selection onto one side for comments that can only anchor to a
+ * single file version: any added or context line puts it on the new side,
+ * otherwise (deletions only) it goes on the old side.
+ */
+export function singleSideCommentLineState(
+  range: SelectionRange,
+): CommentLineState {
+  const [side, sideRange]: ["LEFT" | "RIGHT", SelectionRange["left"]] =
+    range.right ? ["RIGHT", range.right] : ["LEFT", range.left]
+  if (!sideRange) return null
+  if (sideRange.start === sideRange.end) {
+    return { line: sideRange.end, side }
+  }
+  return {
+    line: sideRange.end,
+    side,
+    startLine: sideRange.start,
+    startSide: side,
+  }
+}
+
 export function getLineHighlightBg({
   isCursor,
   isSelected,
+  isSearchMatch,
   defaultBg,
 }: {
   isCursor?: boolean
   isSelected?: boolean
+  isSearchMatch?: boolean
   defaultBg: string
 }): string {
   if (isCursor) return "bg-yellow-200/80 dark:bg-yellow-700/40"
   if (isSelected) return "bg-yellow-100/60 dark:bg-yellow-800/30"
+  if (isSearchMatch) return "bg-orange-200/60 dark:bg-orange-800/40"
   return defaultBg
 }
 
+/** Key of the open hunk containing the cursor. */
+export function hunkKeyAtCursor(
+  elements: DiffElement[],
+  cursor: CursorPosition,
+): HunkKey | null {
+  for (const el of elements) {
+    if (
+      el.type === "hunk" &&
+      el.hunk.lines.some((line) => isCursorLine(cursor, line))
+    ) {
+      return hunkKey(el.hunk)
+    }
+  }
+  return null
+}
+
+/** Region covering the whole hunk under the cursor, header coordinates included. */
+export function hunkRegionAtCursor(
+  elements: DiffElement[],
+  cursor: CursorPosition,
+): RegionId | null {
+  for (const el of elements) {
+    if (
+      el.type === "hunk" &&
+      el.hunk.lines.some((line) => isCursorLine(cursor, line))
+    ) {
+      const { oldStart, oldLines, newStart, newLines } = el.hunk
+      return { oldStart, oldLines, newStart, newLines }
+    }
+  }
+  return null
+}
+
+export type SearchDirection = "next" | "prev"
+
+/** Case-insensitive match against the text of a line's tokens. */
+export function lineMatchesQuery(line: DiffLine, query: string): boolean {
+  if (query === "") return false
+  const content = line.tokens.map((token) => token.content).join("")
+  return content.toLowerCase().includes(query.toLowerCase())
+}
+
+/**
+ * Find the closest line after (or before) the cursor whose content contains
+ * `query`, wrapping around at either end of the diff.
+ */
+export function findSearchMatch(
+  elements: DiffElement[],
+  cursor: CursorPosition,
+  query: string,
+  direction: SearchDirection,
+): DiffLine | null {
+  return findLineFrom(elements, cursor, direction, (line) =>
+    lineMatchesQuery(line, query),
+  )
+}
+
+/** Whether a comment thread in `commentKeys` is anchored to `line`. */
+export function lineHasComment(
+  line: DiffLine,
+  commentKeys: ReadonlySet<CommentKey>,
+): boolean {
+  if (
+    isLeftLineType(line.lineType) &&
+    line.oldLineno != null &&
+    commentKeys.has(inlineCommentsKey("LEFT", line.oldLineno))
+  ) {
+    return true
+  }
+  return (
+    isRightLineType(line.lineType) &&
+    line.newLineno != null &&
+    commentKeys.has(inlineCommentsKey("RIGHT", line.newLineno))
+  )
+}
+
+/**
+ * Find the closest commented line after (or before) the cursor, wrapping
+ * around at either end of the diff.
+ */
+export function findCommentLine(
+  elements: DiffElement[],
+  cursor: CursorPosition,
+  commentKeys: ReadonlySet<CommentKey>,
+  direction: SearchDirection,
+): DiffLine | null {
+  if (commentKeys.size === 0) return null
+  return findLineFrom(elements, cursor, direction, (line) =>
+    lineHasComment(line, commentKeys),
+  )
+}
+
+function findLineFrom(
+  elements: DiffElement[],
+  cursor: CursorPosition,
+  direction: SearchDirection,
+  predicate: (line: DiffLine) => boolean,
+): DiffLine | null {
+  const flatElements = elements.flatMap((el) =>
+    el.type === "hunk" ? el.hunk.lines : [],
+  )
+  const total = flatElements.length
+  const cursorIdx = flatElements.findIndex((line) =>
+    isCursorLine(cursor, line),
+  )
+  const start =
+    cursorIdx !== -1 ? cursorIdx : direction === "next" ? -1 : total
+  const step = direction === "next" ? 1 : -1
+
+  for (let i = 1; i <= total; i++) {
+    const idx = (((start + i * step) % total) + total) % total
+    if (predicate(flatElements[idx])) return flatElements[idx]
+  }
+  return null
+}
+
 export type LineSelectionControl = {
   state: LineSelectionState | null
   setState: React.Dispatch<React.SetStateAction<LineSelectionState | null>>
@@ -175,6 +316,37 @@ function findNearestLine(
   return best
 }
 
+/**
+ * The line on the other side that a one-sided selection sits after: the
+ * nearest preceding line of that side, or 0 at the top of the file.
+ *
+ * A zero-length side of a region is an insertion point, so a deletion-only
+ * selection needs its `newStart` just as an addition-only one needs its
+ * `oldStart`. Unmarking a deletion in the reviewed (B→M) panel splices the
+ * base lines back into the marker at `newStart`.
+ */
+function precedingLineno(
+  lines: DiffLine[],
+  fromIdx: number,
+  side: "old" | "new",
+): number {
+  for (let i = fromIdx; i >= 0; i--) {
+    const line = lines[i]
+    if (side === "old") {
+      if (isLeftLineType(line.lineType) && line.oldLineno != null) {
+        return line.oldLineno
+      }
+    } else if (isRightLineType(line.lineType) && line.newLineno != null) {
+      return line.newLineno
+    }
+  }
+  return 0
+}
+
+/**
+ * Region of a selection, in the coordinate space of the diff it was made in:
+ * M/T for the remaining panel, B/M for the reviewed one.
+ */
 export function computeRegionId(
   selectionRange: SelectionRange,
   elements: DiffElement[],
@@ -189,33 +361,30 @@ export function computeRegionId(
       newLines: right.end - right.start + 1,
     }
   }
+
+  const flatElements = elements.flatMap((el) =>
+    el.type === "hunk" ? el.hunk.lines : [],
+  )
+
   if (left) {
+    const lineIdx = flatElements.findIndex(
+      (line) => line.oldLineno === left.start && isLeftLineType(line.lineType),
+    )
     return {
       oldStart: left.start,
       oldLines: left.end - left.start + 1,
-      newStart: 0,
+      newStart: lineIdx < 0 ? 0 : precedingLineno(flatElements, lineIdx, "new"),
       newLines: 0,
     }
   }
 
   if (right) {
-    const flatElements = elements.flatMap((el) =>
-      el.type === "hunk" ? el.hunk.lines : [],
-    )
     const lineIdx = flatElements.findIndex(
       (line) =>
         line.newLineno === right.start && isRightLineType(line.lineType),
     )
-    let oldStart = 0
-    for (let i = lineIdx; i >= 0; i--) {
-      const line = flatElements[i]
-      if (isLeftLineType(line.lineType) && line.oldLineno != null) {
-        oldStart = line.oldLineno
-        break
-      }
-    }
     return {
-      oldStart,
+      oldStart: lineIdx < 0 ? 0 : precedingLineno(flatElements, lineIdx, "old"),
       oldLines: 0,
       newStart: right.start,
       newLines: right.end - right.start + 1,
@@ -340,6 +509,32 @@ export function useLineSelection({
     })
   }
 
+  const moveToSearchMatch = (query: string, direction: SearchDirection) => {
+    setState((prev) => {
+      if (!prev) return prev
+      const match = findSearchMatch(elements, prev.cursor, query, direction)
+      if (!match) return prev
+      return { ...prev, cursor: diffLineToCursorPosition(match) }
+    })
+  }
+
+  const moveToComment = (
+    commentKeys: ReadonlySet<CommentKey>,
+    direction: SearchDirection,
+  ) => {
+    setState((prev) => {
+      if (!prev) return prev
+      const line = findCommentLine(
+        elements,
+        prev.cursor,
+        commentKeys,
+        direction,
+      )
+      if (!line) return prev
+      return { anchor: null, cursor: diffLineToCursorPosition(line) }
+    })
+  }
+
   const startSelect = (line: DiffLine) => {
     setState((prev) => {
       if (!prev) return prev
@@ -403,7 +598,12 @@ export function useLineSelection({
     return selectionToCommentLineState(state, elements)
   }
 
+  const toSingleSideCommentLineState = (): CommentLineState =>
+    singleSideCommentLineState(selectionRange)
+
   const regionId = () => computeRegionId(selectionRange, elements)
+  const hunkRegionId = () =>
+    state ? hunkRegionAtCursor(elements, state.cursor) : null
 
   return {
     state,
@@ -415,11 +615,15 @@ export function useLineSelection({
     moveToTop,
     moveToNextHunk,
     moveToPrevHunk,
+    moveToSearchMatch,
+    moveToComment,
     startSelect,
     toggleSelect,
     clearSelection,
     toCommentLineState,
+    toSingleSideCommentLineState,
     regionId,
+    hunkRegionId,
   }
 }
 
@@ -0,0 +1,33 @@
+import { beforeEach, describe, expect, it, vi } from "vitest"
+
+import { loadLineWrap, saveLineWrap } from "./useLineWrap"
+
+describe("line wrap persistence", () => {
+  let stored: Map<string, string>
+
+  beforeEach(() => {
+    stored = new Map()
+    vi.stubGlobal("window", {})
+    vi.stubGlobal("localStorage", {
+      getItem: (key: string) => stored.get(key) ?? null,
+      setItem: (key: string, value: string) => stored.set(key, value),
+    })
+  })
+
+  it("wraps by default", () => {
+    expect(loadLineWrap()).toBe(true)
+  })
+
+  it("remembers turning wrapping off and back on", () => {
+    saveLineWrap(!loadLineWrap())
+    expect(loadLineWrap()).toBe(false)
+
+    saveLineWrap(!loadLineWrap())
+    expect(loadLineWrap()).toBe(true)
+  })
+
+  it("wraps when rendered outside a browser", () => {
+    vi.stubGlobal("window", undefined)
+    expect(loadLineWrap()).toBe(true)
+  })
+})
@@ -0,0 +1,30 @@
+import { useState } from "react"
+
+const LINE_WRAP_KEY = "kenjutu-diff-line-wrap"
+
+/** The remembered wrap choice, defaulting to wrapping. */
+export function loadLineWrap(): boolean {
+  if (typeof window !== "undefined") {
+    return localStorage.getItem(LINE_WRAP_KEY) !== "false"
+  }
+  return true
+}
+
+export function saveLineWrap(lineWrap: boolean) {
+  localStorage.setItem(LINE_WRAP_KEY, String(lineWrap))
+}
+
+/**
+ * Whether long diff lines wrap or scroll horizontally. Wrapping is the
+ * default; the choice is remembered across sessions.
+ */
+export function useLineWrap() {
+  const [lineWrap, _setLineWrap] = useState<boolean>(loadLineWrap)
+
+  const toggleLineWrap = () => {
+    _setLineWrap(!lineWrap)
+    saveLineWrap(!lineWrap)
+  }
+
+  return { lineWrap, toggleLineWrap }
+}
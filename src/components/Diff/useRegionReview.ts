@@ -3,6 +3,7 @@ import { useCallback } from "react"
 
 import { commands, RegionId } from "@/bindings"
 import { useRpcMutation } from "@/hooks/useRpcQuery"
+import { recordMarkerUndo } from "@/lib/markerUndo"
 import { queryKeys } from "@/lib/queryKeys"
 
 import { DualDiffPanel } from "./DualDiff"
@@ -22,20 +23,24 @@ export function useRegionReview({
 }) {
   const queryClient = useQueryClient()
 
-  const invalidateAfterRegionMark = useCallback(() => {
-    queryClient.invalidateQueries({
-      queryKey: queryKeys.commitFileList(localDir, commitSha),
-    })
-    queryClient.invalidateQueries({
-      queryKey: queryKeys.partialReviewDiffs(
-        localDir,
-        changeId,
-        commitSha,
-        filePath,
-        oldPath,
-      ),
-    })
-  }, [queryClient, localDir, commitSha, filePath, oldPath, changeId])
+  const invalidateAfterRegionMark = useCallback(
+    (previousTreeId: string) => {
+      recordMarkerUndo({ localDir, commitSha, treeId: previousTreeId })
+      queryClient.invalidateQueries({
+        queryKey: queryKeys.commitFileList(localDir, commitSha),
+      })
+      queryClient.invalidateQueries({
+        queryKey: queryKeys.partialReviewDiffs(
+          localDir,
+          changeId,
+          commitSha,
+          filePath,
+          oldPath,
+        ),
+      })
+    },
+    [queryClient, localDir, commitSha, filePath, oldPath, changeId],
+  )
 
   const markRegionMutation = useRpcMutation({
     mutationFn: async (region: RegionId) => {
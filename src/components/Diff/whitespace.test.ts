@@ -0,0 +1,54 @@
+import { describe, expect, it } from "vitest"
+
+import { HighlightToken } from "@/bindings"
+
+import { renderWhitespace } from "./whitespace"
+
+function token(content: string, changed = false): HighlightToken {
+  return { content, color: null, changed, moved: false }
+}
+
+const contents = (tokens: HighlightToken[]) => tokens.map((t) => t.content)
+
+const tabs4 = { tabWidth: 4, showWhitespace: false }
+const visible = { tabWidth: 4, showWhitespace: true }
+
+describe("renderWhitespace", () => {
+  it("expands a tab to the configured width", () => {
+    const tokens = [token("\tx")]
+
+    expect(contents(renderWhitespace(tokens, tabs4))).toEqual(["    x"])
+    expect(
+      contents(renderWhitespace(tokens, { ...tabs4, tabWidth: 2 })),
+    ).toEqual(["  x"])
+  })
+
+  it("expands tabs to the next tab stop across tokens", () => {
+    const tokens = [token("ab"), token("\tc")]
+
+    expect(contents(renderWhitespace(tokens, tabs4))).toEqual(["ab", "  c"])
+  })
+
+  it("marks tabs and trailing spaces when enabled", () => {
+    const tokens = [token("\tif x"), token("  \n")]
+
+    expect(contents(renderWhitespace(tokens, visible))).toEqual([
+      "→   if x",
+      "··\n",
+    ])
+  })
+
+  it("leaves inner spaces alone and keeps changed flags", () => {
+    const tokens = [token("a b"), token(" ", true)]
+
+    const rendered = renderWhitespace(tokens, visible)
+    expect(contents(rendered)).toEqual(["a b", "·"])
+    expect(rendered.map((t) => t.changed)).toEqual([false, true])
+  })
+
+  it("returns the tokens untouched when there is nothing to render", () => {
+    const tokens = [token("plain")]
+
+    expect(renderWhitespace(tokens, visible)).toBe(tokens)
+  })
+})
@@ -0,0 +1,62 @@
+import { createContext } from "react"
+
+import { HighlightToken } from "@/bindings"
+
+export type WhitespaceOptions = {
+  /** Columns between tab stops when expanding tabs. */
+  tabWidth: number
+  /** Mark tabs with `→` and trailing spaces with `·`. */
+  showWhitespace: boolean
+}
+
+export const DEFAULT_WHITESPACE: WhitespaceOptions = {
+  tabWidth: 4,
+  showWhitespace: false,
+}
+
+export const WhitespaceContext =
+  createContext<WhitespaceOptions>(DEFAULT_WHITESPACE)
+
+const isBlank = (ch: string) =>
+  ch === " " || ch === "\t" || ch === "\r" || ch === "\n"
+
+/**
+ * Expand tabs to the next tab stop and, when enabled, make tabs and trailing
+ * spaces visible. Rewriting happens inside each token, so the `changed` and
+ * `moved` flags of word-level highlights still cover the same text.
+ */
+export function renderWhitespace(
+  tokens: HighlightToken[],
+  { tabWidth, showWhitespace }: WhitespaceOptions,
+): HighlightToken[] {
+  const text = tokens.map((t) => t.content).join("")
+  const hasTab = text.includes("\t")
+  let trailingStart = text.length
+  while (trailingStart > 0 && isBlank(text[trailingStart - 1])) trailingStart--
+  const hasTrailingSpace = text.slice(trailingStart).includes(" ")
+  if (!hasTab && !(showWhitespace && hasTrailingSpace)) return tokens
+
+  const width = Math.max(1, tabWidth)
+  let offset = 0
+  let column = 0
+  return tokens.map((token) => {
+    let content = ""
+    for (const ch of token.content) {
+      if (ch === "\t") {
+        const span = width - (column % width)
+        content += showWhitespace
+          ? "→" + " ".repeat(span - 1)
+          : " ".repeat(span)
+        column += span
+      } else if (ch === " " && showWhitespace && offset >= trailingStart) {
+        content += "·"
+        column++
+      } else {
+        content += ch
+        if (ch !== "\r" && ch !== "\n") column++
+      }
+      offset += ch.length
+    }
+    return { ...token, content }
+  })
+}
@@ -6,9 +6,10 @@ import {
   Circle,
   Folder,
   FolderOpen,
+  MessageSquare,
   Minus,
 } from "lucide-react"
-import { useRef, useState } from "react"
+import { createContext, useContext, useRef, useState } from "react"
 
 import { FileChangeStatus, FileEntry } from "@/bindings"
 import { ErrorDisplay } from "@/components/error"
@@ -20,6 +21,9 @@ import {
   buildFileTree,
   DirectoryNode as TDirectoryNode,
   FileNode as TFileNode,
+  filterFilesByPath,
+  sortFilesInTreeOrder,
+  summarizeDirectory,
   TreeNode as TTreeNode,
 } from "@/lib/fileTree"
 import { cn } from "@/lib/utils"
@@ -32,9 +36,19 @@ type FileNode = TFileNode<FileEntry>
 type FileTreeProps = {
   localDir: string
   commitSha: string | undefined
+  /** Unresolved comment thread count keyed by file path. */
+  unresolvedCommentCounts?: Map<string, number>
 }
 
-export function FileTree({ localDir, commitSha }: FileTreeProps) {
+const UnresolvedCommentCountsContext = createContext<
+  Map<string, number> | undefined
+>(undefined)
+
+export function FileTree({
+  localDir,
+  commitSha,
+  unresolvedCommentCounts,
+}: FileTreeProps) {
   const { data, error, isLoading } = useCommitFileList(localDir, commitSha)
 
   if (!commitSha) {
@@ -80,13 +94,24 @@ export function FileTree({ localDir, commitSha }: FileTreeProps) {
     )
   }
 
-  return <FileTreeContent files={data.files} />
+  return (
+    <UnresolvedCommentCountsContext.Provider value={unresolvedCommentCounts}>
+      <FileTreeContent files={data.files} />
+    </UnresolvedCommentCountsContext.Provider>
+  )
+}
+
+function filePath(file: FileEntry): string {
+  return file.newPath || file.oldPath || ""
 }
 
 function FileTreeContent({ files }: { files: FileEntry[] }) {
   const [filterQuery, setFilterQuery] = useState("")
   const searchRef = useRef<HTMLInputElement>(null)
-  const { focusPane } = usePaneManager()
+  const { focusPane, focusPaneItem } = usePaneManager()
+
+  const displayFiles = filterFilesByPath(files, filePath, filterQuery)
+  const tree = buildFileTree(displayFiles, filePath)
 
   useHotkey("S", () => searchRef.current?.focus())
   useHotkey(
@@ -99,22 +124,21 @@ function FileTreeContent({ files }: { files: FileEntry[] }) {
   )
   useHotkey(
     "Enter",
-    () => setTimeout(() => focusPane(PANEL_KEYS.fileTree), 0),
+    () => {
+      // Jump to the first match rather than the last focused file, which the
+      // filter may have hidden.
+      const [firstMatch] = sortFilesInTreeOrder(displayFiles, filePath)
+      setTimeout(() => {
+        if (filterQuery && firstMatch) {
+          focusPaneItem(PANEL_KEYS.fileTree, filePath(firstMatch))
+        } else {
+          focusPane(PANEL_KEYS.fileTree)
+        }
+      }, 0)
+    },
     { target: searchRef, ignoreInputs: false },
   )
 
-  const displayFiles = filterQuery
-    ? files.filter((file) => {
-        const path = (file.newPath || file.oldPath || "").toLowerCase()
-        return path.includes(filterQuery.toLowerCase())
-      })
-    : files
-
-  const tree = buildFileTree(
-    displayFiles,
-    (file) => file.newPath || file.oldPath || "",
-  )
-
   return (
     <div className="px-2 py-3">
       <h3 className="text-xs font-medium text-muted-foreground mb-2">
@@ -159,7 +183,7 @@ function TreeNodeComponent({ node, depth }: { node: TreeNode; depth: number }) {
           node={node}
           depth={depth}
           isOpen={isOpen}
-          onToggle={() => setIsOpen(!isOpen)}
+          onOpenChange={setIsOpen}
         />
         <CollapsibleContent>
           <div>
@@ -183,20 +207,24 @@ function DirectoryRow({
   node,
   depth,
   isOpen,
-  onToggle,
+  onOpenChange,
 }: {
   node: DirectoryNode
   depth: number
   isOpen: boolean
-  onToggle: () => void
+  onOpenChange: (isOpen: boolean) => void
 }) {
   const { ref } = usePaneItem<HTMLButtonElement>(node.path)
+  const stats = summarizeDirectory(node, fileStats)
+
+  useHotkey("H", () => onOpenChange(false), { target: ref })
+  useHotkey("L", () => onOpenChange(true), { target: ref })
 
   return (
     <Collapsible asChild>
       <button
         ref={ref}
-        onClick={onToggle}
+        onClick={() => onOpenChange(!isOpen)}
         className="flex items-center gap-1.5 w-full text-left py-0.5 px-1 rounded hover:bg-muted/50 cursor-pointer focusKey"
         style={{ paddingLeft: `${depth * 12 + 4}px` }}
       >
@@ -211,17 +239,38 @@ function DirectoryRow({
           <Folder className="w-3 h-3 text-muted-foreground shrink-0" />
         )}
         <ReviewIndicator status={reviewStatus(node)} />
-        <span className="text-xs font-medium truncate">{node.name}</span>
+        <span className="text-xs font-medium truncate flex-1">{node.name}</span>
+        <span
+          className="text-[10px] text-muted-foreground whitespace-nowrap shrink-0"
+          title={`${stats.reviewedFiles} of ${stats.files} files reviewed`}
+        >
+          {stats.reviewedFiles}/{stats.files}{" "}
+          <span className="text-green-600">+{stats.additions}</span>{" "}
+          <span className="text-red-600">-{stats.deletions}</span>
+        </span>
       </button>
     </Collapsible>
   )
 }
 
+function fileStats(file: FileEntry) {
+  // Binary and reverted files show no line counts of their own, so they add
+  // none to their directory either.
+  const counted = !file.isBinary && file.reviewStatus !== "reviewedReverted"
+  return {
+    additions: counted ? file.additions : 0,
+    deletions: counted ? file.deletions : 0,
+    reviewed: file.reviewStatus === "reviewed",
+  }
+}
+
 function FileRow({ node, depth }: { node: FileNode; depth: number }) {
   const { file } = node
   const statusIndicator = getStatusIndicator(file.status)
   const { ref } = usePaneItem<HTMLButtonElement>(node.path)
   const { focusPaneItem } = usePaneManager()
+  const unresolvedComments =
+    useContext(UnresolvedCommentCountsContext)?.get(node.path) ?? 0
 
   return (
     <button
@@ -243,6 +292,15 @@ function FileRow({ node, depth }: { node: FileNode; depth: number }) {
       </span>
       <span className="text-xs truncate flex-1">{node.name}</span>
       <div className="flex items-center gap-1 shrink-0">
+        {unresolvedComments > 0 && (
+          <span
+            className="flex items-center gap-0.5 text-[10px] text-blue-600 dark:text-blue-400"
+            title={`${unresolvedComments} unresolved comment${unresolvedComments === 1 ? "" : "s"}`}
+          >
+            <MessageSquare className="w-3 h-3" />
+            {unresolvedComments}
+          </span>
+        )}
         {!file.isBinary && file.reviewStatus !== "reviewedReverted" && (
           <span className="text-[10px] text-muted-foreground whitespace-nowrap">
             <span className="text-green-600">+{file.additions}</span>{" "}
@@ -27,6 +27,10 @@ export function getErrorMessage(error: CommandError): string {
       return `Jj error: ${error.message}`
     case "FileNotFound":
       return `File not found: ${error.path}`
+    case "BlobTooLarge":
+      return `File is too large to preview: ${error.size} bytes (max ${error.max})`
+    case "DiffTooLarge":
+      return `File is too large to highlight: ${error.size} bytes (max ${error.max})`
     case "Internal":
       return "An unexpected error occurred"
     case "MarkerCommit":
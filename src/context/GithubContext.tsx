@@ -8,7 +8,7 @@ import {
   useState,
 } from "react"
 
-import { clearStoredToken, getStoredToken, setupAuthListener } from "@/lib/auth"
+import { clearStoredToken, resolveToken, setupAuthListener } from "@/lib/auth"
 
 interface GithubContextValue {
   octokit: Octokit | null
@@ -41,11 +41,13 @@ export function GithubProvider({ children }: { children: ReactNode }) {
   }, [])
 
   useEffect(() => {
-    getStoredToken().then((token) => {
-      if (token) {
-        initializeOctokit(token)
-      }
-    })
+    resolveToken()
+      .then((token) => {
+        if (token) {
+          initializeOctokit(token)
+        }
+      })
+      .catch((error) => console.error("Failed to load GitHub token:", error))
 
     const unlistenPromise = setupAuthListener((token) => {
       initializeOctokit(token)
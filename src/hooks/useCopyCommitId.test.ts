@@ -0,0 +1,47 @@
+import { beforeEach, describe, expect, it, vi } from "vitest"
+
+import { copyCommitId } from "./useCopyCommitId"
+
+const { success, error } = vi.hoisted(() => ({
+  success: vi.fn(),
+  error: vi.fn(),
+}))
+
+vi.mock("sonner", () => ({ toast: { success, error } }))
+
+const commit = {
+  commitId: "0123456789abcdef0123456789abcdef01234567",
+  changeId: "kmzvxtqxqlvsoyrzrnnuptkpqvoozwqx",
+}
+
+describe("copyCommitId", () => {
+  const writeText = vi.fn()
+
+  beforeEach(() => {
+    writeText.mockReset()
+    success.mockReset()
+    error.mockReset()
+    vi.stubGlobal("navigator", { clipboard: { writeText } })
+  })
+
+  it("copies the id and confirms it", async () => {
+    writeText.mockResolvedValue(undefined)
+
+    await copyCommitId(commit, "change")
+
+    expect(writeText).toHaveBeenCalledWith(commit.changeId)
+    expect(success).toHaveBeenCalledWith(`Copied change ID ${commit.changeId}`)
+    expect(error).not.toHaveBeenCalled()
+  })
+
+  it("reports a rejected clipboard write instead of throwing", async () => {
+    writeText.mockRejectedValue(new Error("Document is not focused."))
+
+    await expect(copyCommitId(commit, "commit")).resolves.toBeUndefined()
+
+    expect(success).not.toHaveBeenCalled()
+    expect(error).toHaveBeenCalledWith(
+      "Failed to copy commit ID: Document is not focused.",
+    )
+  })
+})
@@ -0,0 +1,37 @@
+import { useHotkey } from "@tanstack/react-hotkeys"
+import { toast } from "sonner"
+
+import { JjCommit } from "@/bindings"
+import { CommitIdKind, commitIdToCopy, loadCopyIdLength } from "@/lib/commitIds"
+
+type CopyableCommit = Pick<JjCommit, "commitId" | "changeId">
+
+/**
+ * Copy a commit or change id in the length chosen in settings. The clipboard
+ * can refuse the write (e.g. when the window isn't focused), so both outcomes
+ * are reported in a toast.
+ */
+export async function copyCommitId(commit: CopyableCommit, kind: CommitIdKind) {
+  const text = commitIdToCopy(commit, kind, loadCopyIdLength())
+  try {
+    await navigator.clipboard.writeText(text)
+    toast.success(`Copied ${kind} ID ${text}`)
+  } catch (err) {
+    const reason = err instanceof Error ? err.message : String(err)
+    toast.error(`Failed to copy ${kind} ID: ${reason}`)
+  }
+}
+
+/** `Y` copies the commit id of `commit` and `Shift+Y` its change id. */
+export function useCopyCommitIdHotkeys(
+  commit: CopyableCommit | undefined,
+  { enabled = true }: { enabled?: boolean } = {},
+) {
+  const isEnabled = enabled && !!commit
+  useHotkey("Y", () => commit && copyCommitId(commit, "commit"), {
+    enabled: isEnabled,
+  })
+  useHotkey("Shift+Y", () => commit && copyCommitId(commit, "change"), {
+    enabled: isEnabled,
+  })
+}
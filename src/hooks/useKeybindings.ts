@@ -0,0 +1,16 @@
+import { useMemo } from "react"
+
+import { commands } from "@/bindings"
+import { useRpcQuery } from "@/hooks/useRpcQuery"
+import { type Keybindings, resolveKeybindings } from "@/lib/keybindings"
+import { queryKeys } from "@/lib/queryKeys"
+
+/** Review keybindings with user overrides applied; defaults until loaded. */
+export function useKeybindings(): Keybindings {
+  const { data } = useRpcQuery({
+    queryKey: queryKeys.keybindings(),
+    queryFn: () => commands.getKeybindings(),
+    staleTime: Infinity,
+  })
+  return useMemo(() => resolveKeybindings(data ?? {}), [data])
+}
@@ -0,0 +1,41 @@
+import { useQueryClient } from "@tanstack/react-query"
+import { toast } from "sonner"
+
+import { commands } from "@/bindings"
+import { getErrorMessage } from "@/components/error"
+import { MarkerUndoEntry, popMarkerUndo } from "@/lib/markerUndo"
+import { queryKeys } from "@/lib/queryKeys"
+
+import { useRpcMutation } from "./useRpcQuery"
+
+/** Undo the most recent mark made in `localDir` this session. */
+export function useMarkerUndo(localDir: string | null) {
+  const queryClient = useQueryClient()
+
+  const restoreMutation = useRpcMutation({
+    mutationFn: (entry: MarkerUndoEntry) =>
+      commands.restoreMarkerTree(entry.localDir, entry.commitSha, entry.treeId),
+    onSuccess: (_, entry) => {
+      queryClient.invalidateQueries({
+        queryKey: queryKeys.commitFileList(entry.localDir, entry.commitSha),
+      })
+      queryClient.invalidateQueries({
+        queryKey: ["partial-review-diffs", entry.localDir],
+      })
+    },
+    onError: (err) => {
+      toast.error(`Failed to undo: ${getErrorMessage(err)}`)
+    },
+  })
+
+  const undo = () => {
+    const entry = localDir ? popMarkerUndo(localDir) : undefined
+    if (!entry) {
+      toast("Nothing to undo")
+      return
+    }
+    restoreMutation.mutate(entry)
+  }
+
+  return { undo }
+}
@@ -2,6 +2,7 @@ import { RestEndpointMethodTypes } from "@octokit/rest"
 import { useQuery } from "@tanstack/react-query"
 
 import { useGithub } from "@/context/GithubContext"
+import { withRateLimitRetry } from "@/lib/githubRetry"
 import { queryKeys } from "@/lib/queryKeys"
 
 export interface Repo {
@@ -20,14 +21,17 @@ export function useRepositories() {
   return useQuery({
     queryKey: queryKeys.repositories(),
     queryFn: async (): Promise<ListRepo[]> => {
-      const { data } = await octokit!.repos.listForAuthenticatedUser({
-        visibility: "all",
-        sort: "updated",
-        per_page: 100,
-      })
+      const { data } = await withRateLimitRetry(() =>
+        octokit!.repos.listForAuthenticatedUser({
+          visibility: "all",
+          sort: "updated",
+          per_page: 100,
+        }),
+      )
 
       return data
     },
     enabled: isAuthenticated,
+    retry: false,
   })
 }
@@ -0,0 +1,52 @@
+import { beforeEach, describe, expect, it, vi } from "vitest"
+
+import { resolveToken, TokenUnreadableError } from "./auth"
+
+const { getEnvGithubToken, storeGet } = vi.hoisted(() => ({
+  getEnvGithubToken: vi.fn(),
+  storeGet: vi.fn(),
+}))
+
+vi.mock("@/bindings", () => ({ commands: { getEnvGithubToken } }))
+vi.mock("@tauri-apps/plugin-store", () => ({
+  LazyStore: class {
+    get = storeGet
+  },
+}))
+vi.mock("@tauri-apps/api/event", () => ({ listen: vi.fn() }))
+
+describe("resolveToken", () => {
+  beforeEach(() => {
+    getEnvGithubToken.mockReset()
+    storeGet.mockReset()
+  })
+
+  it("prefers GITHUB_TOKEN over the stored token", async () => {
+    getEnvGithubToken.mockResolvedValue({ status: "ok", data: "env-token" })
+    storeGet.mockResolvedValue("stored-token")
+
+    await expect(resolveToken()).resolves.toBe("env-token")
+    expect(storeGet).not.toHaveBeenCalled()
+  })
+
+  it("falls back to the stored token", async () => {
+    getEnvGithubToken.mockResolvedValue({ status: "ok", data: null })
+    storeGet.mockResolvedValue("stored-token")
+
+    await expect(resolveToken()).resolves.toBe("stored-token")
+  })
+
+  it("returns null when no token is configured", async () => {
+    getEnvGithubToken.mockResolvedValue({ status: "ok", data: null })
+    storeGet.mockResolvedValue(undefined)
+
+    await expect(resolveToken()).resolves.toBeNull()
+  })
+
+  it("distinguishes an unreadable store from a missing token", async () => {
+    getEnvGithubToken.mockResolvedValue({ status: "ok", data: null })
+    storeGet.mockRejectedValue(new Error("corrupt auth.json"))
+
+    await expect(resolveToken()).rejects.toBeInstanceOf(TokenUnreadableError)
+  })
+})
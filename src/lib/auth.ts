@@ -1,6 +1,8 @@
 import { listen, type UnlistenFn } from "@tauri-apps/api/event"
 import { LazyStore } from "@tauri-apps/plugin-store"
 
+import { commands } from "@/bindings"
+
 const store = new LazyStore("auth.json")
 const TOKEN_KEY = "github_token"
 
@@ -11,6 +13,35 @@ export async function getStoredToken(): Promise<string | null> {
   return (await store.get<string>(TOKEN_KEY)) ?? null
 }
 
+/** A token is configured but could not be read. */
+export class TokenUnreadableError extends Error {
+  constructor(source: string, cause: unknown) {
+    const reason =
+      cause instanceof Error ? cause.message : JSON.stringify(cause)
+    super(`Failed to read GitHub token from ${source}: ${reason}`)
+    this.name = "TokenUnreadableError"
+  }
+}
+
+/**
+ * Resolve the GitHub token: the `GITHUB_TOKEN` environment variable first,
+ * then the token stored by the device flow. Returns null when neither is
+ * configured and throws {@link TokenUnreadableError} when a source fails.
+ */
+export async function resolveToken(): Promise<string | null> {
+  const env = await commands.getEnvGithubToken().catch((e: unknown) => {
+    throw new TokenUnreadableError("GITHUB_TOKEN", e)
+  })
+  if (env.status === "error") {
+    throw new TokenUnreadableError("GITHUB_TOKEN", env.error)
+  }
+  if (env.data) return env.data
+
+  return getStoredToken().catch((e: unknown) => {
+    throw new TokenUnreadableError("the token store", e)
+  })
+}
+
 /**
  * Clear stored token from plugin-store.
  * Used when token is expired or revoked.
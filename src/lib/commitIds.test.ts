@@ -0,0 +1,41 @@
+import { describe, expect, it } from "vitest"
+
+import { commitIdToCopy, shortId } from "./commitIds"
+
+const commit = {
+  commitId: "0123456789abcdef0123456789abcdef01234567",
+  changeId: "kmzvxtqxqlvsoyrzrnnuptkpqvoozwqx",
+}
+
+describe("commitIdToCopy", () => {
+  it("copies the full commit id", () => {
+    expect(commitIdToCopy(commit, "commit", "full")).toBe(commit.commitId)
+  })
+
+  it("copies the full change id", () => {
+    expect(commitIdToCopy(commit, "change", "full")).toBe(commit.changeId)
+  })
+
+  it("shortens the commit id to 12 characters", () => {
+    expect(commitIdToCopy(commit, "commit", "short")).toBe("0123456789ab")
+  })
+
+  it("shortens the change id to 8 characters", () => {
+    expect(commitIdToCopy(commit, "change", "short")).toBe("kmzvxtqx")
+  })
+})
+
+describe("shortId", () => {
+  it("returns a prefix of the full id", () => {
+    for (const kind of ["commit", "change"] as const) {
+      const full = kind === "commit" ? commit.commitId : commit.changeId
+      const short = shortId(full, kind)
+      expect(full.startsWith(short)).toBe(true)
+      expect(short).toHaveLength(kind === "commit" ? 12 : 8)
+    }
+  })
+
+  it("keeps ids that are already short", () => {
+    expect(shortId("abc", "commit")).toBe("abc")
+  })
+})
@@ -0,0 +1,41 @@
+import { JjCommit } from "@/bindings"
+
+export type CommitIdKind = "commit" | "change"
+export type CommitIdLength = "short" | "full"
+
+/** Abbreviated lengths, matching how the commit graph and details show ids. */
+const SHORT_ID_LENGTH: Record<CommitIdKind, number> = {
+  commit: 12,
+  change: 8,
+}
+
+const COPY_ID_LENGTH_KEY = "kenjutu-copy-id-length"
+
+/** The abbreviated form of a commit or change id shown in lists and headers. */
+export function shortId(id: string, kind: CommitIdKind): string {
+  return id.slice(0, SHORT_ID_LENGTH[kind])
+}
+
+/** The text copied to the clipboard for a commit's commit or change id. */
+export function commitIdToCopy(
+  commit: Pick<JjCommit, "commitId" | "changeId">,
+  kind: CommitIdKind,
+  length: CommitIdLength,
+): string {
+  const id = kind === "commit" ? commit.commitId : commit.changeId
+  return length === "short" ? shortId(id, kind) : id
+}
+
+export function loadCopyIdLength(): CommitIdLength {
+  if (typeof window !== "undefined") {
+    const stored = localStorage.getItem(COPY_ID_LENGTH_KEY)
+    if (stored === "short" || stored === "full") {
+      return stored
+    }
+  }
+  return "full"
+}
+
+export function saveCopyIdLength(length: CommitIdLength) {
+  localStorage.setItem(COPY_ID_LENGTH_KEY, length)
+}
@@ -0,0 +1,103 @@
+import { describe, expect, it } from "vitest"
+
+import {
+  buildFileTree,
+  DirectoryNode,
+  filterFilesByPath,
+  sortFilesInTreeOrder,
+  summarizeDirectory,
+} from "./fileTree"
+
+const paths = [
+  "README.md",
+  "src/lib/fileTree.ts",
+  "src/components/FileTree.tsx",
+  "src-tauri/src/lib.rs",
+]
+
+const identity = (path: string) => path
+
+describe("filterFilesByPath", () => {
+  it("keeps every file for an empty query", () => {
+    expect(filterFilesByPath(paths, identity, "")).toEqual(paths)
+  })
+
+  it("matches substrings anywhere in the path, ignoring case", () => {
+    expect(filterFilesByPath(paths, identity, "FILETREE")).toEqual([
+      "src/lib/fileTree.ts",
+      "src/components/FileTree.tsx",
+    ])
+    expect(filterFilesByPath(paths, identity, "tauri/src")).toEqual([
+      "src-tauri/src/lib.rs",
+    ])
+  })
+
+  it("returns nothing when no path matches", () => {
+    expect(filterFilesByPath(paths, identity, "missing")).toEqual([])
+  })
+
+  it("puts the first match in tree order first after sorting", () => {
+    const matches = filterFilesByPath(paths, identity, "lib")
+    expect(sortFilesInTreeOrder(matches, identity)[0]).toBe(
+      "src-tauri/src/lib.rs",
+    )
+  })
+})
+
+describe("buildFileTree", () => {
+  it("nests files under their directories, directories first", () => {
+    const tree = buildFileTree(["a/d.rs", "a/b/c.rs"], identity)
+    expect(tree).toEqual([
+      {
+        type: "directory",
+        name: "a",
+        path: "a",
+        children: [
+          {
+            type: "directory",
+            name: "b",
+            path: "a/b",
+            children: [
+              {
+                type: "file",
+                name: "c.rs",
+                path: "a/b/c.rs",
+                file: "a/b/c.rs",
+              },
+            ],
+          },
+          { type: "file", name: "d.rs", path: "a/d.rs", file: "a/d.rs" },
+        ],
+      },
+    ])
+  })
+
+  it("merges directories with a single subdirectory", () => {
+    const tree = buildFileTree(["a/b/c.rs", "a/b/d.rs"], identity)
+    expect(tree.map((node) => [node.type, node.name])).toEqual([
+      ["directory", "a/b"],
+    ])
+  })
+})
+
+describe("summarizeDirectory", () => {
+  it("adds up every file below the directory", () => {
+    type File = {
+      path: string
+      additions: number
+      deletions: number
+      reviewed: boolean
+    }
+    const files: File[] = [
+      { path: "a/b/c.rs", additions: 3, deletions: 1, reviewed: true },
+      { path: "a/d.rs", additions: 2, deletions: 4, reviewed: false },
+    ]
+    const [root] = buildFileTree(files, (file) => file.path)
+    expect(summarizeDirectory(root as DirectoryNode<File>, (f) => f)).toEqual({
+      files: 2,
+      reviewedFiles: 1,
+      additions: 5,
+      deletions: 5,
+    })
+  })
+})
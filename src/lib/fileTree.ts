@@ -102,6 +102,65 @@ export function sortFilesInTreeOrder<T>(
   return sortedFiles
 }
 
+export type DirectoryStats = {
+  files: number
+  reviewedFiles: number
+  additions: number
+  deletions: number
+}
+
+/**
+ * Totals over every file below `node`, for showing on a collapsed directory
+ * what it hides.
+ */
+export function summarizeDirectory<T>(
+  node: DirectoryNode<T>,
+  getStats: (file: T) => {
+    additions: number
+    deletions: number
+    reviewed: boolean
+  },
+): DirectoryStats {
+  const stats: DirectoryStats = {
+    files: 0,
+    reviewedFiles: 0,
+    additions: 0,
+    deletions: 0,
+  }
+  for (const child of node.children) {
+    if (child.type === "directory") {
+      const childStats = summarizeDirectory(child, getStats)
+      stats.files += childStats.files
+      stats.reviewedFiles += childStats.reviewedFiles
+      stats.additions += childStats.additions
+      stats.deletions += childStats.deletions
+    } else {
+      const fileStats = getStats(child.file)
+      stats.files += 1
+      stats.reviewedFiles += fileStats.reviewed ? 1 : 0
+      stats.additions += fileStats.additions
+      stats.deletions += fileStats.deletions
+    }
+  }
+  return stats
+}
+
+/**
+ * Keeps the files whose path contains `query`, ignoring case. An empty query
+ * keeps every file.
+ */
+export function filterFilesByPath<T>(
+  files: T[],
+  getFilePath: (file: T) => string,
+  query: string,
+): T[] {
+  if (!query) return files
+  const needle = query.toLowerCase()
+  return files.filter((file) =>
+    getFilePath(file).toLowerCase().includes(needle),
+  )
+}
+
 function insertIntoTree<T>(
   parent: DirectoryNode<T>,
   pathParts: string[],
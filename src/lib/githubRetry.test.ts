@@ -0,0 +1,78 @@
+import { describe, expect, it, vi } from "vitest"
+
+import {
+  RateLimitedError,
+  rateLimitReset,
+  withRateLimitRetry,
+} from "./githubRetry"
+
+const NOW = 1_700_000_000_000
+
+function rateLimitError(resetInSeconds: number) {
+  return {
+    status: 403,
+    response: {
+      headers: {
+        "x-ratelimit-remaining": "0",
+        "x-ratelimit-reset": String(NOW / 1000 + resetInSeconds),
+      },
+    },
+  }
+}
+
+const options = { now: () => NOW, sleep: () => Promise.resolve() }
+
+describe("rateLimitReset", () => {
+  it("reads the reset time of an exhausted quota", () => {
+    expect(rateLimitReset(rateLimitError(30))).toEqual(new Date(NOW + 30_000))
+  })
+
+  it("ignores 403s that are not rate limits", () => {
+    expect(rateLimitReset({ status: 403, response: { headers: {} } })).toBe(
+      null,
+    )
+  })
+})
+
+describe("withRateLimitRetry", () => {
+  it("retries once after a rate limit that resets soon", async () => {
+    const request = vi
+      .fn()
+      .mockRejectedValueOnce(rateLimitError(5))
+      .mockResolvedValueOnce("ok")
+    const sleep = vi.fn(() => Promise.resolve())
+
+    await expect(
+      withRateLimitRetry(request, { ...options, sleep }),
+    ).resolves.toBe("ok")
+    expect(request).toHaveBeenCalledTimes(2)
+    expect(sleep).toHaveBeenCalledWith(5_000)
+  })
+
+  it("surfaces a distant reset as RateLimitedError", async () => {
+    const request = vi.fn().mockRejectedValue(rateLimitError(3600))
+
+    const error = await withRateLimitRetry(request, options).catch((e) => e)
+    expect(error).toBeInstanceOf(RateLimitedError)
+    expect(error.resetAt).toEqual(new Date(NOW + 3_600_000))
+    expect(request).toHaveBeenCalledTimes(1)
+  })
+
+  it("gives up on transient failures after maxRetries", async () => {
+    const request = vi.fn().mockRejectedValue({ status: 502 })
+
+    await expect(
+      withRateLimitRetry(request, { ...options, maxRetries: 2 }),
+    ).rejects.toEqual({ status: 502 })
+    expect(request).toHaveBeenCalledTimes(3)
+  })
+
+  it("does not retry client errors", async () => {
+    const request = vi.fn().mockRejectedValue({ status: 404 })
+
+    await expect(withRateLimitRetry(request, options)).rejects.toEqual({
+      status: 404,
+    })
+    expect(request).toHaveBeenCalledTimes(1)
+  })
+})
@@ -0,0 +1,84 @@
+/** GitHub refused the request until its rate-limit window resets. */
+export class RateLimitedError extends Error {
+  constructor(readonly resetAt: Date) {
+    super(`GitHub rate limit exceeded; resets at ${resetAt.toLocaleString()}`)
+    this.name = "RateLimitedError"
+  }
+}
+
+export type RetryOptions = {
+  /** Attempts after the first failure before giving up. */
+  maxRetries?: number
+  /** Delay before the first retry, doubled for each later one. */
+  baseDelayMs?: number
+  /** Longest wait for a rate-limit reset before surfacing the error. */
+  maxResetWaitMs?: number
+  now?: () => number
+  sleep?: (ms: number) => Promise<void>
+}
+
+type ErrorWithResponse = {
+  status?: number
+  response?: { headers?: Record<string, string | number | undefined> }
+}
+
+/**
+ * Reset time from the `X-RateLimit-Reset` header of a 403/429 response whose
+ * quota is used up, or null if the error is not a rate limit.
+ */
+export function rateLimitReset(error: unknown): Date | null {
+  if (!error || typeof error !== "object") return null
+  const { status, response } = error as ErrorWithResponse
+  if (status !== 403 && status !== 429) return null
+  const headers = response?.headers ?? {}
+  const reset = Number(headers["x-ratelimit-reset"])
+  if (headers["x-ratelimit-remaining"] !== "0" || !Number.isFinite(reset)) {
+    return null
+  }
+  return new Date(reset * 1000)
+}
+
+/** Network failures and 5xx responses are worth another attempt. */
+function isTransient(error: unknown): boolean {
+  if (!error || typeof error !== "object") return false
+  const { status } = error as ErrorWithResponse
+  return status === undefined || status === 0 || status >= 500
+}
+
+const defaultSleep = (ms: number) =>
+  new Promise<void>((resolve) => setTimeout(resolve, ms))
+
+/**
+ * Run a GitHub request, retrying transient failures with exponential backoff.
+ * A rate limit that resets soon is waited out; one that resets later, or
+ * persists past the last retry, is thrown as {@link RateLimitedError}.
+ */
+export async function withRateLimitRetry<T>(
+  request: () => Promise<T>,
+  {
+    maxRetries = 3,
+    baseDelayMs = 500,
+    maxResetWaitMs = 60_000,
+    now = Date.now,
+    sleep = defaultSleep,
+  }: RetryOptions = {},
+): Promise<T> {
+  for (let attempt = 0; ; attempt++) {
+    try {
+      return await request()
+    } catch (error) {
+      const resetAt = rateLimitReset(error)
+      const exhausted = attempt >= maxRetries
+      if (resetAt) {
+        const wait = Math.max(0, resetAt.getTime() - now())
+        if (exhausted || wait > maxResetWaitMs) {
+          throw new RateLimitedError(resetAt)
+        }
+        await sleep(Math.max(wait, baseDelayMs * 2 ** attempt))
+        continue
+      }
+      if (exhausted || !isTransient(error)) throw error
+      await sleep(baseDelayMs * 2 ** attempt)
+    }
+  }
+}
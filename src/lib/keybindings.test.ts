@@ -0,0 +1,37 @@
+import { describe, expect, it, vi } from "vitest"
+
+import {
+  COMMENT_JUMP_KEYS,
+  DEFAULT_KEYBINDINGS,
+  resolveKeybindings,
+} from "./keybindings"
+
+describe("resolveKeybindings", () => {
+  it("uses the defaults without overrides", () => {
+    expect(resolveKeybindings({})).toEqual(DEFAULT_KEYBINDINGS)
+  })
+
+  it("maps an overridden action to its new key", () => {
+    const bindings = resolveKeybindings({ toggle_selection: "Shift+V" })
+    expect(bindings.toggle_selection).toBe("Shift+V")
+    expect(bindings.mark_hunk).toBe(DEFAULT_KEYBINDINGS.mark_hunk)
+  })
+
+  it("skips unknown actions and empty keys", () => {
+    const warn = vi.spyOn(console, "warn").mockImplementation(() => {})
+    const bindings = resolveKeybindings({ next_flie: "L", mark_hunk: " " })
+    expect(bindings).toEqual(DEFAULT_KEYBINDINGS)
+    expect(warn).toHaveBeenCalledTimes(2)
+    warn.mockRestore()
+  })
+})
+
+describe("COMMENT_JUMP_KEYS", () => {
+  it("ends on a key no default action is bound to", () => {
+    const bound = new Set<string>(Object.values(DEFAULT_KEYBINDINGS))
+
+    for (const sequence of Object.values(COMMENT_JUMP_KEYS)) {
+      expect(bound).not.toContain(sequence[sequence.length - 1])
+    }
+  })
+})
@@ -0,0 +1,74 @@
+import type { Hotkey } from "@tanstack/react-hotkeys"
+
+/**
+ * Default keys for the diff review actions. Users can override any of them by
+ * action name in `keybindings.toml` in the app config dir.
+ */
+export const DEFAULT_KEYBINDINGS = {
+  // Global
+  undo: "U",
+  // Diff view
+  toggle_diff_view: "T",
+  toggle_line_wrap: "W",
+  // File
+  toggle_reviewed: "Space",
+  enter_line_mode: "Enter",
+  toggle_file_open: "O",
+  open_in_editor: "E",
+  copy_file_path: "C",
+  // Line mode
+  cursor_down: "J",
+  cursor_up: "K",
+  cursor_bottom: "Shift+G",
+  half_page_down: "Control+D",
+  half_page_up: "Control+U",
+  next_hunk: "N",
+  prev_hunk: "Shift+N",
+  search: "/",
+  toggle_selection: "V",
+  toggle_fold: "Z",
+  unfold_all: "Shift+Z",
+  mark_selection: "Space",
+  mark_hunk: "X",
+  comment: "C",
+  toggle_resolved_comments: "Shift+R",
+  exit_line_mode: "Escape",
+} as const satisfies Record<string, Hotkey>
+
+/**
+ * Line mode sequences that jump between comment threads. Their last key must
+ * not be bound on its own, or pressing it would also run that action.
+ */
+export const COMMENT_JUMP_KEYS: Record<"next" | "prev", Hotkey[]> = {
+  next: ["]", "M"],
+  prev: ["[", "M"],
+}
+
+export type KeybindingAction = keyof typeof DEFAULT_KEYBINDINGS
+export type Keybindings = Record<KeybindingAction, Hotkey>
+
+function isAction(action: string): action is KeybindingAction {
+  return Object.hasOwn(DEFAULT_KEYBINDINGS, action)
+}
+
+/**
+ * Apply user overrides on top of the defaults. Unknown actions and empty keys
+ * are reported and skipped so a typo never leaves an action unbound.
+ */
+export function resolveKeybindings(
+  overrides: Partial<{ [key in string]: string }>,
+): Keybindings {
+  const resolved: Keybindings = { ...DEFAULT_KEYBINDINGS }
+  for (const [action, key] of Object.entries(overrides)) {
+    if (!isAction(action)) {
+      console.warn(`Ignoring keybinding for unknown action "${action}"`)
+      continue
+    }
+    if (!key?.trim()) {
+      console.warn(`Ignoring empty keybinding for "${action}"`)
+      continue
+    }
+    resolved[action] = key.trim() as Hotkey
+  }
+  return resolved
+}
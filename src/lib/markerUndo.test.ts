@@ -0,0 +1,33 @@
+import { describe, expect, it } from "vitest"
+
+import { MAX_UNDO_ENTRIES, popMarkerUndo, recordMarkerUndo } from "./markerUndo"
+
+describe("marker undo stack", () => {
+  it("pops the latest mark first", () => {
+    recordMarkerUndo({ localDir: "/lifo", commitSha: "a", treeId: "t1" })
+    recordMarkerUndo({ localDir: "/lifo", commitSha: "b", treeId: "t2" })
+    expect(popMarkerUndo("/lifo")?.treeId).toBe("t2")
+    expect(popMarkerUndo("/lifo")?.treeId).toBe("t1")
+    expect(popMarkerUndo("/lifo")).toBeUndefined()
+  })
+
+  it("keeps repositories apart", () => {
+    recordMarkerUndo({ localDir: "/one", commitSha: "a", treeId: "t1" })
+    expect(popMarkerUndo("/two")).toBeUndefined()
+    expect(popMarkerUndo("/one")?.treeId).toBe("t1")
+  })
+
+  it("drops the oldest marks past the limit", () => {
+    for (let i = 0; i <= MAX_UNDO_ENTRIES; i++) {
+      recordMarkerUndo({ localDir: "/full", commitSha: "a", treeId: `t${i}` })
+    }
+    const popped: string[] = []
+    let entry = popMarkerUndo("/full")
+    while (entry) {
+      popped.push(entry.treeId)
+      entry = popMarkerUndo("/full")
+    }
+    expect(popped).toHaveLength(MAX_UNDO_ENTRIES)
+    expect(popped.at(-1)).toBe("t1")
+  })
+})
@@ -0,0 +1,24 @@
+/** A mark that can be undone: the marker tree of `commitSha` before it. */
+export type MarkerUndoEntry = {
+  localDir: string
+  commitSha: string
+  treeId: string
+}
+
+/** Oldest marks are dropped past this many, per repository. */
+export const MAX_UNDO_ENTRIES = 50
+
+const stacks = new Map<string, MarkerUndoEntry[]>()
+
+/** Remember the marker tree a mark replaced. Only kept for this session. */
+export function recordMarkerUndo(entry: MarkerUndoEntry): void {
+  const stack = stacks.get(entry.localDir) ?? []
+  stack.push(entry)
+  if (stack.length > MAX_UNDO_ENTRIES) stack.shift()
+  stacks.set(entry.localDir, stack)
+}
+
+/** The most recent mark in `localDir`, removed from the stack. */
+export function popMarkerUndo(localDir: string): MarkerUndoEntry | undefined {
+  return stacks.get(localDir)?.pop()
+}
@@ -8,6 +8,8 @@ export const queryKeys = {
   localRepoPath: (id: string) => ["localRepoPath", id] as const,
   pullRequests: (owner: string | null, repo: string | null) =>
     ["pullRequests", owner, repo] as const,
+  openPullRequestCount: (owner: string, repo: string) =>
+    ["pullRequests", owner, repo, "openCount"] as const,
   pr: (owner: string, repo: string, pullNumber: number) =>
     ["pr", owner, repo, pullNumber] as const,
   pullRequest: (owner: string, repo: string, pullNumber: number) =>
@@ -42,11 +44,38 @@ export const queryKeys = {
       filePath,
       oldPath,
     ] as const,
+  binaryDiff: (
+    localDir: string,
+    commitSha: string,
+    filePath: string,
+    oldPath?: string,
+  ) => ["binary-diff", localDir, commitSha, filePath, oldPath] as const,
+  blame: (
+    localDir: string,
+    commitSha: string,
+    filePath: string,
+    oldPath?: string,
+  ) => ["blame", localDir, commitSha, filePath, oldPath] as const,
+  blobBase64: (
+    localDir: string,
+    commitSha: string,
+    filePath: string,
+    side: string,
+  ) => ["blob-base64", localDir, commitSha, filePath, side] as const,
   changeIdFromSha: (localDir: string, sha: string) =>
     ["change-id-from-sha", localDir, sha] as const,
   jjLog: (localDir: string | undefined) => ["jj-log", localDir] as const,
+  jjLogPage: (localDir: string | undefined, revset: string, limit: number) =>
+    ["jj-log", localDir, revset, limit] as const,
   jjStatus: (localDir: string | undefined) => ["jj-status", localDir] as const,
   localComments: (localDir: string, commitId: string) =>
     ["local-comments", localDir, commitId] as const,
+  localReview: (localDir: string, commitSha: string) =>
+    ["local-review", localDir, commitSha] as const,
+  reviewSummary: (localDir: string, shas: string[]) =>
+    ["review-summary", localDir, shas] as const,
   sshSettings: () => ["ssh-settings"] as const,
+  diffSettings: () => ["diff-settings"] as const,
+  highlightThemes: () => ["highlight-themes"] as const,
+  keybindings: () => ["keybindings"] as const,
 }
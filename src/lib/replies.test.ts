@@ -0,0 +1,42 @@
+import { describe, expect, it } from "vitest"
+
+import type { MaterializedReply } from "@/bindings"
+
+import { countReplies, flattenReplies } from "./replies"
+
+function reply(
+  id: string,
+  replies: MaterializedReply[] = [],
+): MaterializedReply {
+  return {
+    id,
+    body: id,
+    author: "alice",
+    created_at: "2025-01-01T00:00:00Z",
+    updated_at: "2025-01-01T00:00:00Z",
+    edit_count: 0,
+    replies,
+  }
+}
+
+const thread = [reply("r1", [reply("r2", [reply("r3")])]), reply("r4")]
+
+describe("flattenReplies", () => {
+  it("lists each reply before its own replies", () => {
+    expect(
+      flattenReplies(thread).map(({ reply, depth }) => [reply.id, depth]),
+    ).toEqual([
+      ["r1", 0],
+      ["r2", 1],
+      ["r3", 2],
+      ["r4", 0],
+    ])
+  })
+})
+
+describe("countReplies", () => {
+  it("counts nested replies", () => {
+    expect(countReplies(thread)).toBe(4)
+    expect(countReplies([])).toBe(0)
+  })
+})
@@ -0,0 +1,26 @@
+import type { MaterializedReply } from "@/bindings"
+
+export type FlatReply = {
+  reply: MaterializedReply
+  /** 0 for a direct reply to the thread root. */
+  depth: number
+}
+
+/** Replies of a thread in reading order: each reply followed by its own. */
+export function flattenReplies(
+  replies: MaterializedReply[],
+  depth = 0,
+): FlatReply[] {
+  return replies.flatMap((reply) => [
+    { reply, depth },
+    ...flattenReplies(reply.replies, depth + 1),
+  ])
+}
+
+/** Number of replies in a thread, counting nested ones. */
+export function countReplies(replies: MaterializedReply[]): number {
+  return replies.reduce(
+    (sum, reply) => sum + 1 + countReplies(reply.replies),
+    0,
+  )
+}
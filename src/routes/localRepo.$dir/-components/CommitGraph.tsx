@@ -1,6 +1,7 @@
 import { useHotkey } from "@tanstack/react-hotkeys"
 import { useQueryClient } from "@tanstack/react-query"
-import { useMemo, useState } from "react"
+import { useEffect, useMemo, useRef, useState } from "react"
+import { toast } from "sonner"
 
 import type {
   CommitGraph as CommitGraphData,
@@ -10,6 +11,7 @@ import type {
   JjCommit,
 } from "@/bindings"
 import { commands } from "@/bindings"
+import { getErrorMessage } from "@/components/error"
 import { Pane, PANEL_KEYS, usePaneItem } from "@/components/Pane"
 import { Button } from "@/components/ui/button"
 import {
@@ -21,15 +23,22 @@ import {
 } from "@/components/ui/dialog"
 import { Textarea } from "@/components/ui/textarea"
 import { useCommitFileList } from "@/hooks/useCommitFileList"
+import { copyCommitId, useCopyCommitIdHotkeys } from "@/hooks/useCopyCommitId"
 import { useRpcMutation } from "@/hooks/useRpcQuery"
+import { shortId } from "@/lib/commitIds"
 import { queryKeys } from "@/lib/queryKeys"
 import { cn } from "@/lib/utils"
 
+import { ReviewSummaryDialog } from "./ReviewSummaryDialog"
+
 type CommitGraphProps = {
   localDir: string
   graph: CommitGraphData
   selectedChangeId: string | null
   onSelectCommit: (commit: JjCommit) => void
+  /** True when the log was truncated and more commits can be loaded. */
+  hasMore?: boolean
+  onLoadMore?: () => void
 }
 
 const COL_WIDTH = 16
@@ -98,7 +107,7 @@ function DescribeDialog({
           <DialogTitle>
             Describe{" "}
             <code className="bg-muted px-1 rounded text-sm">
-              {commit.changeId.slice(0, 8)}
+              {shortId(commit.changeId, "change")}
             </code>
           </DialogTitle>
         </DialogHeader>
@@ -130,6 +139,7 @@ function CommitGraphCommitRow({
   isSelected,
   onClick,
   onDescribe,
+  onShowSummary,
 }: {
   localDir: string
   commitRow: CommitRow
@@ -137,20 +147,57 @@ function CommitGraphCommitRow({
   isSelected: boolean
   onClick: () => void
   onDescribe: () => void
+  onShowSummary: () => void
 }) {
   const { commit } = commitRow
   const { ref, isFocused } = usePaneItem<HTMLButtonElement>(commit.changeId)
 
   const { data } = useCommitFileList(localDir, commit.commitId)
 
-  useHotkey("C", () => navigator.clipboard.writeText(commit.changeId), {
+  useHotkey("C", () => copyCommitId(commit, "change"), {
     enabled: isFocused,
   })
 
-  useHotkey("D", () => onDescribe(), {
-    enabled: isFocused && !commit.isImmutable,
+  useCopyCommitIdHotkeys(commit, { enabled: isFocused })
+
+  const describe = () => {
+    if (commit.isImmutable) {
+      toast.warning("Immutable commits can't be described")
+      return
+    }
+    onDescribe()
+  }
+
+  useHotkey("D", describe, { enabled: isFocused })
+
+  const queryClient = useQueryClient()
+  const moveWorkingCopy = useRpcMutation({
+    mutationFn: (action: "edit" | "new") =>
+      action === "edit"
+        ? commands.editCommit(localDir, commit.changeId)
+        : commands.newCommit(localDir, commit.changeId),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.jjLog(localDir) })
+    },
+    onError: (err) => {
+      toast.error(`Failed to move working copy: ${getErrorMessage(err)}`)
+    },
   })
 
+  useHotkey(
+    "E",
+    () => {
+      if (commit.isImmutable) {
+        toast.warning("Immutable commits can't be edited")
+        return
+      }
+      moveWorkingCopy.mutate("edit")
+    },
+    { enabled: isFocused && !commit.isWorkingCopy },
+  )
+  useHotkey("N", () => moveWorkingCopy.mutate("new"), { enabled: isFocused })
+  useHotkey("Shift+R", onShowSummary, { enabled: isFocused })
+
   const progress = data
     ? {
         reviewed: data.files.filter((f) => f.reviewStatus === "reviewed")
@@ -164,9 +211,7 @@ function CommitGraphCommitRow({
     <button
       ref={ref}
       onClick={onClick}
-      onDoubleClick={() => {
-        if (!commit.isImmutable) onDescribe()
-      }}
+      onDoubleClick={describe}
       style={{ height: ROW_HEIGHT }}
       className={cn(
         "w-full flex items-center gap-2 px-2 text-left hover:bg-accent rounded transition-colors focusKey",
@@ -185,8 +230,16 @@ function CommitGraphCommitRow({
               : "bg-muted text-muted-foreground",
           )}
         >
-          {commit.changeId.slice(0, 8)}
+          {shortId(commit.changeId, "change")}
         </span>
+        {commit.hasConflict && (
+          <span
+            className="shrink-0 font-mono text-xs px-1 rounded bg-red-500/20 text-red-700 dark:text-red-300"
+            title="Unresolved conflicts"
+          >
+            conflict
+          </span>
+        )}
         {progress && progress.total > 0 && (
           <span
             className="shrink-0"
@@ -206,6 +259,21 @@ function CommitGraphCommitRow({
             )}
           </span>
         )}
+        <span
+          className="shrink-0 inline-flex items-center justify-center w-5 h-4 rounded text-[10px] font-semibold text-white"
+          style={{ backgroundColor: commit.authorColor }}
+          title={`${commit.author} <${commit.email}>`}
+        >
+          {authorInitials(commit.author)}
+        </span>
+        {commit.bookmarks.map((bookmark) => (
+          <span
+            key={bookmark}
+            className="shrink-0 font-mono text-xs px-1 rounded bg-blue-500/20 text-blue-700 dark:text-blue-300"
+          >
+            [{bookmark}]
+          </span>
+        ))}
         <span className="ml-1 truncate text-xs">
           {commit.summary || (
             <span className="italic text-muted-foreground">
@@ -232,6 +300,31 @@ function ElisionGraphRow({ svgWidth }: { svgWidth: number }) {
   )
 }
 
+/** Calls `onVisible` whenever the sentinel scrolls into view. */
+function LoadMoreSentinel({ onVisible }: { onVisible: () => void }) {
+  const ref = useRef<HTMLDivElement>(null)
+
+  useEffect(() => {
+    const el = ref.current
+    if (!el) return
+    const observer = new IntersectionObserver((entries) => {
+      if (entries.some((entry) => entry.isIntersecting)) onVisible()
+    })
+    observer.observe(el)
+    return () => observer.disconnect()
+  }, [onVisible])
+
+  return (
+    <div
+      ref={ref}
+      style={{ height: ROW_HEIGHT }}
+      className="flex items-center px-2 text-xs text-muted-foreground italic"
+    >
+      Loading more commits...
+    </div>
+  )
+}
+
 function isCommitRow(row: GraphRow): row is GraphRow & { type: "commit" } {
   return row.type === "commit"
 }
@@ -241,10 +334,13 @@ export function CommitGraph({
   graph,
   selectedChangeId,
   onSelectCommit,
+  hasMore,
+  onLoadMore,
 }: CommitGraphProps) {
   const svgWidth = graph.maxColumns * COL_WIDTH
   const svgHeight = graph.rows.length * ROW_HEIGHT
   const [describeCommit, setDescribeCommit] = useState<JjCommit | null>(null)
+  const [summaryOpen, setSummaryOpen] = useState(false)
 
   // Collect all edges and node positions for SVG rendering
   const { edges, nodes, elisionNodes } = useMemo(() => {
@@ -270,6 +366,12 @@ export function CommitGraph({
     return { edges, nodes, elisionNodes }
   }, [graph])
 
+  const reviewableCommits = useMemo(
+    () =>
+      nodes.map((n) => n.row.commit).filter((commit) => !commit.isImmutable),
+    [nodes],
+  )
+
   return (
     <Pane
       className="font-mono text-sm relative"
@@ -302,9 +404,11 @@ export function CommitGraph({
             cy={rowY(n.idx)}
             r={n.row.commit.isWorkingCopy ? 5 : 4}
             fill={
-              n.row.commit.isWorkingCopy
-                ? "var(--color-green-500)"
-                : "var(--color-blue-500)"
+              n.row.commit.hasConflict
+                ? "var(--color-red-500)"
+                : n.row.commit.isWorkingCopy
+                  ? "var(--color-green-500)"
+                  : "var(--color-blue-500)"
             }
           />
         ))}
@@ -336,12 +440,15 @@ export function CommitGraph({
             isSelected={row.commit.changeId === selectedChangeId}
             onClick={() => onSelectCommit(row.commit)}
             onDescribe={() => setDescribeCommit(row.commit)}
+            onShowSummary={() => setSummaryOpen(true)}
           />
         ) : (
           <ElisionGraphRow key={`elision-${row.row}`} svgWidth={svgWidth} />
         ),
       )}
 
+      {hasMore && onLoadMore && <LoadMoreSentinel onVisible={onLoadMore} />}
+
       {describeCommit && (
         <DescribeDialog
           localDir={localDir}
@@ -352,6 +459,24 @@ export function CommitGraph({
           }}
         />
       )}
+
+      <ReviewSummaryDialog
+        localDir={localDir}
+        commits={reviewableCommits}
+        open={summaryOpen}
+        onOpenChange={setSummaryOpen}
+        onSelectCommit={onSelectCommit}
+      />
     </Pane>
   )
 }
+
+function authorInitials(author: string): string {
+  const initials = author
+    .split(/\s+/)
+    .filter((word) => word.length > 0)
+    .slice(0, 2)
+    .map((word) => word[0].toUpperCase())
+    .join("")
+  return initials || "?"
+}
@@ -16,16 +16,22 @@ import { InlineCommentForm } from "@/components/InlineCommentForm"
 import { MarkdownContent } from "@/components/MarkdownContent"
 import { Pane, PANEL_KEYS, usePaneManager } from "@/components/Pane"
 import { Alert, AlertDescription, AlertTitle } from "@/components/ui/alert"
+import { Input } from "@/components/ui/input"
 import {
   ResizableHandle,
   ResizablePanel,
   ResizablePanelGroup,
 } from "@/components/ui/resizable"
+import { useKeybindings } from "@/hooks/useKeybindings"
+import { useMarkerUndo } from "@/hooks/useMarkerUndo"
+import { shortId } from "@/lib/commitIds"
 
 import { useJjLogGraph } from "../-hooks/useJjLogGraph"
 import { useLocalCommentMutations } from "../-hooks/useLocalCommentMutations"
 import { useLocalComments } from "../-hooks/useLocalComments"
+import { useLogRevset } from "../-hooks/useLogRevset"
 import { useNormalizedLocalComments } from "../-hooks/useNormalizedLocalComments"
+import { useUnresolvedCommentCounts } from "../-hooks/useUnresolvedCommentCounts"
 import { CommitGraph } from "./CommitGraph"
 import { LocalCommentsSidebar } from "./LocalCommentsSidebar"
 
@@ -34,12 +40,18 @@ type LocalChangesTabProps = {
 }
 
 export function LocalChangesTab({ localDir }: LocalChangesTabProps) {
-  const { data, error, isLoading } = useJjLogGraph(localDir)
+  const { revset, setRevset } = useLogRevset(localDir)
+  const { data, error, isLoading, hasMore, loadMore } = useJjLogGraph(
+    localDir,
+    revset,
+  )
   const [selectedChangeId, setSelectedChangeId] = useState<string | null>(null)
   const leftSidebarRef = usePanelRef()
   const rightSidebarRef = usePanelRef()
   const isLeftCollapsed = () => leftSidebarRef.current?.isCollapsed() ?? false
   const { focusPane } = usePaneManager()
+  const { undo } = useMarkerUndo(localDir)
+  const keys = useKeybindings()
   const expandLeftAndFocus = (panelKey: string) => {
     leftSidebarRef.current?.expand()
     focusPane(panelKey)
@@ -56,6 +68,8 @@ export function LocalChangesTab({ localDir }: LocalChangesTabProps) {
     }
   })
 
+  useHotkey(keys.undo, undo)
+
   useHotkey("Mod+B", () => {
     if (isLeftCollapsed()) {
       leftSidebarRef.current?.expand()
@@ -75,22 +89,46 @@ export function LocalChangesTab({ localDir }: LocalChangesTabProps) {
       .map((row) => row.commit)
   }, [data])
 
+  const revsetInput = (
+    <RevsetInput key={revset} revset={revset} onChange={setRevset} />
+  )
+
   if (isLoading) {
     return <p className="text-muted-foreground p-4">Loading commits...</p>
   }
 
+  // Keep the revset editable so a bad or empty one can be fixed in place.
   if (error) {
-    return <ErrorDisplay error={error} />
+    return (
+      <div className="space-y-2 p-2">
+        {revsetInput}
+        <ErrorDisplay error={error} />
+      </div>
+    )
   }
 
   if (!data || commits.length === 0) {
     return (
-      <Alert className="mt-4">
-        <AlertTitle>No Local Changes</AlertTitle>
-        <AlertDescription>
-          No mutable commits found. All changes have been pushed.
-        </AlertDescription>
-      </Alert>
+      <div className="space-y-2 p-2">
+        {revsetInput}
+        <Alert>
+          {revset ? (
+            <>
+              <AlertTitle>No Matching Commits</AlertTitle>
+              <AlertDescription>
+                No commits match <code>{revset}</code>.
+              </AlertDescription>
+            </>
+          ) : (
+            <>
+              <AlertTitle>No Local Changes</AlertTitle>
+              <AlertDescription>
+                No mutable commits found. All changes have been pushed.
+              </AlertDescription>
+            </>
+          )}
+        </Alert>
+      </div>
     )
   }
 
@@ -101,15 +139,25 @@ export function LocalChangesTab({ localDir }: LocalChangesTabProps) {
       {/* Left: Commit Graph + File Tree - Collapsible */}
       <ResizablePanel defaultSize="20%" collapsible panelRef={leftSidebarRef}>
         <div className="pb-4 border-b">
+          <div className="p-2">{revsetInput}</div>
           <CommitGraph
             localDir={localDir}
             graph={data}
             selectedChangeId={selectedChangeId ?? null}
             onSelectCommit={(commit) => setSelectedChangeId(commit.changeId)}
+            hasMore={hasMore}
+            onLoadMore={loadMore}
           />
         </div>
         <div className="pt-4">
-          <FileTree localDir={localDir} commitSha={selectedCommit?.commitId} />
+          {selectedCommit ? (
+            <LocalFileTree
+              localDir={localDir}
+              commitSha={selectedCommit.commitId}
+            />
+          ) : (
+            <FileTree localDir={localDir} commitSha={undefined} />
+          )}
         </div>
       </ResizablePanel>
       <ResizableHandle withHandle />
@@ -155,6 +203,55 @@ export function LocalChangesTab({ localDir }: LocalChangesTabProps) {
   )
 }
 
+/** Edits the log revset, applying it on Enter or when focus leaves. */
+function RevsetInput({
+  revset,
+  onChange,
+}: {
+  revset: string
+  onChange: (revset: string) => void
+}) {
+  const [draft, setDraft] = useState(revset)
+  const apply = () => {
+    if (draft.trim() !== revset) onChange(draft)
+  }
+
+  return (
+    <Input
+      value={draft}
+      onChange={(e) => setDraft(e.target.value)}
+      onBlur={apply}
+      onKeyDown={(e) => {
+        if (e.key === "Enter") apply()
+        if (e.key === "Escape") setDraft(revset)
+      }}
+      placeholder="Revset (default: mutable changes)"
+      aria-label="Revset"
+      className="h-7 font-mono text-xs"
+    />
+  )
+}
+
+function LocalFileTree({
+  localDir,
+  commitSha,
+}: {
+  localDir: string
+  commitSha: string
+}) {
+  const unresolvedCommentCounts = useUnresolvedCommentCounts(
+    localDir,
+    commitSha,
+  )
+  return (
+    <FileTree
+      localDir={localDir}
+      commitSha={commitSha}
+      unresolvedCommentCounts={unresolvedCommentCounts}
+    />
+  )
+}
+
 function LocalDiffContent({ localDir }: { localDir: string }) {
   const { files, changeId, commitSha } = useDiffContext()
 
@@ -192,6 +289,7 @@ function LocalDiffContent({ localDir }: { localDir: string }) {
       })
     },
     onReplyToThread,
+    singleSide: true,
   }
 
   return (
@@ -232,7 +330,7 @@ function CommitDetail({ commit }: { commit: JjCommit }) {
         <p>
           <span className="font-medium">Commit:</span>{" "}
           <code className="bg-muted px-1 rounded">
-            {commit.commitId.slice(0, 12)}
+            {shortId(commit.commitId, "commit")}
           </code>
         </p>
         <p>
@@ -243,15 +341,27 @@ function CommitDetail({ commit }: { commit: JjCommit }) {
           <span className="font-medium">Date:</span> {commit.timestamp}
         </p>
         {commit.isWorkingCopy && (
-          <p className="text-green-600 dark:text-green-400 font-medium">
-            Working copy
-          </p>
+          <>
+            <p className="text-green-600 dark:text-green-400 font-medium">
+              Working copy
+            </p>
+            <p className="text-amber-600 dark:text-amber-400">
+              The working copy changes as files are edited. The diff follows
+              the latest snapshot, and review progress carries over like a
+              rebase.
+            </p>
+          </>
         )}
         {commit.isImmutable && (
           <p className="text-amber-600 dark:text-amber-400 font-medium">
             Immutable
           </p>
         )}
+        {commit.hasConflict && (
+          <p className="text-red-600 dark:text-red-400 font-medium">
+            Has unresolved conflicts
+          </p>
+        )}
       </div>
     </div>
   )
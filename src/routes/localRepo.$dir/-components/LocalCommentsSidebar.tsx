@@ -20,6 +20,7 @@ import {
   CollapsibleTrigger,
 } from "@/components/ui/collapsible"
 import { compareFilePaths } from "@/lib/fileTree"
+import { countReplies, flattenReplies } from "@/lib/replies"
 import { formatRelativeTime } from "@/lib/timeUtils"
 
 import { useLocalCommentMutations } from "../-hooks/useLocalCommentMutations"
@@ -43,7 +44,11 @@ export function LocalCommentsSidebar({
 
   const totalComments = sortedFileComments.reduce(
     (sum, fc) =>
-      sum + fc.comments.reduce((s, pc) => s + 1 + pc.comment.replies.length, 0),
+      sum +
+      fc.comments.reduce(
+        (s, pc) => s + 1 + countReplies(pc.comment.replies),
+        0,
+      ),
     0,
   )
 
@@ -100,7 +105,7 @@ function FileSection({
   const [isOpen, setIsOpen] = useState(true)
 
   const totalCount = portedComments.reduce(
-    (sum, pc) => sum + 1 + pc.comment.replies.length,
+    (sum, pc) => sum + 1 + countReplies(pc.comment.replies),
     0,
   )
 
@@ -149,7 +154,8 @@ function CommentThread({
   filePath: string
   mutations: Mutations
 }) {
-  const { comment, ported_line, ported_start_line, is_ported } = portedComment
+  const { comment, ported_line, ported_start_line, is_ported, anchor_status } =
+    portedComment
   const [isReplying, setIsReplying] = useState(false)
   const [isEditing, setIsEditing] = useState(false)
 
@@ -187,6 +193,7 @@ function CommentThread({
       {/* Root Comment */}
       <div className="p-3">
         <div className="flex items-center gap-2 mb-2">
+          <span className="text-xs font-semibold">{comment.author}</span>
           <span className="text-xs text-muted-foreground">
             {formatRelativeTime(comment.created_at)}
           </span>
@@ -196,10 +203,20 @@ function CommentThread({
             </span>
           )}
           <div className="ml-auto flex items-center gap-1">
-            {is_ported && (
-              <Badge variant="outline" className="text-[10px] px-1 py-0">
-                ported
+            {anchor_status === "Orphaned" ? (
+              <Badge
+                variant="outline"
+                className="text-[10px] px-1 py-0 text-amber-600"
+                title="The commented code no longer exists in this revision"
+              >
+                outdated
               </Badge>
+            ) : (
+              is_ported && (
+                <Badge variant="outline" className="text-[10px] px-1 py-0">
+                  ported
+                </Badge>
+              )
             )}
             <span className="text-xs text-muted-foreground">
               {displayStartLine != null && displayStartLine !== displayLine
@@ -221,10 +238,11 @@ function CommentThread({
       </div>
 
       {/* Replies */}
-      {comment.replies.map((reply) => (
+      {flattenReplies(comment.replies).map(({ reply, depth }) => (
         <ReplyItem
           key={reply.id}
           reply={reply}
+          depth={depth}
           filePath={filePath}
           mutations={mutations}
         />
@@ -298,10 +316,13 @@ function CommentThread({
 
 function ReplyItem({
   reply,
+  depth,
   filePath,
   mutations,
 }: {
   reply: MaterializedReply
+  /** Nesting below the thread root, shown as indentation. */
+  depth: number
   filePath: string
   mutations: Mutations
 }) {
@@ -317,8 +338,9 @@ function ReplyItem({
   }
 
   return (
-    <div className="border-t p-3">
+    <div className="border-t p-3" style={{ paddingLeft: `${0.75 + depth}rem` }}>
       <div className="flex items-center gap-2 mb-2">
+        <span className="text-xs font-semibold">{reply.author}</span>
         <span className="text-xs text-muted-foreground">
           {formatRelativeTime(reply.created_at)}
         </span>
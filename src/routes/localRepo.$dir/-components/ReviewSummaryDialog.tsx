@@ -0,0 +1,117 @@
+import type { JjCommit } from "@/bindings"
+import { commands } from "@/bindings"
+import { ErrorDisplay } from "@/components/error"
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog"
+import { useRpcQuery } from "@/hooks/useRpcQuery"
+import { shortId } from "@/lib/commitIds"
+import { queryKeys } from "@/lib/queryKeys"
+import { cn } from "@/lib/utils"
+
+type ReviewSummaryDialogProps = {
+  localDir: string
+  /** Changes to summarize, in graph order. */
+  commits: JjCommit[]
+  open: boolean
+  onOpenChange: (open: boolean) => void
+  onSelectCommit: (commit: JjCommit) => void
+}
+
+/** Lists review progress and open comments for every change in the graph. */
+export function ReviewSummaryDialog({
+  localDir,
+  commits,
+  open,
+  onOpenChange,
+  onSelectCommit,
+}: ReviewSummaryDialogProps) {
+  const shas = commits.map((c) => c.commitId)
+  const { data, error, isLoading } = useRpcQuery({
+    queryKey: queryKeys.reviewSummary(localDir, shas),
+    queryFn: () => commands.getReviewSummary(localDir, shas),
+    enabled: open,
+  })
+
+  const select = (changeId: string) => {
+    const commit = commits.find((c) => c.changeId === changeId)
+    if (!commit) return
+    onSelectCommit(commit)
+    onOpenChange(false)
+  }
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="sm:max-w-2xl">
+        <DialogHeader>
+          <DialogTitle>Review summary</DialogTitle>
+          <DialogDescription>
+            Select a change to jump to it in the graph.
+          </DialogDescription>
+        </DialogHeader>
+        {error && <ErrorDisplay error={error} />}
+        {isLoading && (
+          <p className="text-sm text-muted-foreground">Loading...</p>
+        )}
+        {data && (
+          <div className="max-h-[60vh] overflow-y-auto">
+            <table className="w-full text-sm">
+              <thead className="text-xs text-muted-foreground">
+                <tr className="text-left">
+                  <th className="px-2 py-1 font-medium">Change</th>
+                  <th className="px-2 py-1 font-medium">Summary</th>
+                  <th className="px-2 py-1 font-medium text-right">Files</th>
+                  <th className="px-2 py-1 font-medium text-right">
+                    Comments
+                  </th>
+                </tr>
+              </thead>
+              <tbody>
+                {data.map((change) => (
+                  <tr
+                    key={change.changeId}
+                    onClick={() => select(change.changeId)}
+                    className="cursor-pointer hover:bg-accent"
+                  >
+                    <td className="px-2 py-1 font-mono text-xs">
+                      {shortId(change.changeId, "change")}
+                    </td>
+                    <td className="px-2 py-1 max-w-xs truncate">
+                      {change.summary || (
+                        <span className="italic text-muted-foreground">
+                          (no description)
+                        </span>
+                      )}
+                    </td>
+                    <td
+                      className={cn(
+                        "px-2 py-1 text-right tabular-nums",
+                        change.totalFiles > 0 &&
+                          change.reviewedFiles === change.totalFiles &&
+                          "text-green-600 dark:text-green-400",
+                      )}
+                    >
+                      {change.reviewedFiles}/{change.totalFiles}
+                    </td>
+                    <td
+                      className={cn(
+                        "px-2 py-1 text-right tabular-nums",
+                        change.openComments === 0 && "text-muted-foreground",
+                      )}
+                    >
+                      {change.openComments}
+                    </td>
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          </div>
+        )}
+      </DialogContent>
+    </Dialog>
+  )
+}
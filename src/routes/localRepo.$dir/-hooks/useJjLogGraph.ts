@@ -1,12 +1,42 @@
+import { keepPreviousData } from "@tanstack/react-query"
+import { useCallback, useState } from "react"
+
 import { commands } from "@/bindings"
 import { useRpcQuery } from "@/hooks/useRpcQuery"
 import { queryKeys } from "@/lib/queryKeys"
 
-export function useJjLogGraph(localDir: string | undefined) {
-  return useRpcQuery({
-    queryKey: queryKeys.jjLog(localDir),
-    queryFn: () => commands.getJjLog(localDir!),
+export const LOG_PAGE_SIZE = 100
+
+/**
+ * The log graph of `revset`, loaded a page at a time. A blank revset shows the
+ * backend's default; changing it starts again from the first page.
+ */
+export function useJjLogGraph(localDir: string | undefined, revset: string) {
+  const [page, setPage] = useState({ revset, limit: LOG_PAGE_SIZE })
+  if (page.revset !== revset) {
+    setPage({ revset, limit: LOG_PAGE_SIZE })
+  }
+  const limit = page.revset === revset ? page.limit : LOG_PAGE_SIZE
+
+  // Each "page" refetches from the top with a larger limit, so the 5s refresh
+  // keeps every loaded row current and edges never span two separate fetches.
+  const query = useRpcQuery({
+    queryKey: queryKeys.jjLogPage(localDir, revset, limit),
+    queryFn: () => commands.getJjLogPaged(localDir!, revset || null, limit),
     enabled: !!localDir,
     refetchInterval: 5_000,
+    placeholderData: keepPreviousData,
   })
+
+  const commitCount =
+    query.data?.rows.filter((row) => row.type === "commit").length ?? 0
+  const hasMore = !query.isPlaceholderData && commitCount >= limit
+
+  const loadMore = useCallback(() => {
+    if (hasMore) {
+      setPage((prev) => ({ ...prev, limit: prev.limit + LOG_PAGE_SIZE }))
+    }
+  }, [hasMore])
+
+  return { ...query, hasMore, loadMore }
 }
@@ -0,0 +1,34 @@
+import { beforeEach, describe, expect, it, vi } from "vitest"
+
+import { loadLogRevset, saveLogRevset } from "./useLogRevset"
+
+describe("log revset persistence", () => {
+  let stored: Map<string, string>
+
+  beforeEach(() => {
+    stored = new Map()
+    vi.stubGlobal("window", {})
+    vi.stubGlobal("localStorage", {
+      getItem: (key: string) => stored.get(key) ?? null,
+      setItem: (key: string, value: string) => stored.set(key, value),
+      removeItem: (key: string) => stored.delete(key),
+    })
+  })
+
+  it("uses the default revset until one is saved", () => {
+    expect(loadLogRevset("/repo")).toBe("")
+  })
+
+  it("remembers a revset per repository", () => {
+    saveLogRevset("/repo", " main..@ ")
+    expect(loadLogRevset("/repo")).toBe("main..@")
+    expect(loadLogRevset("/other")).toBe("")
+  })
+
+  it("forgets a cleared revset", () => {
+    saveLogRevset("/repo", "main..@")
+    saveLogRevset("/repo", "  ")
+    expect(loadLogRevset("/repo")).toBe("")
+    expect(stored.size).toBe(0)
+  })
+})
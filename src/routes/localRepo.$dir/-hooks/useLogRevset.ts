@@ -0,0 +1,39 @@
+import { useState } from "react"
+
+const LOG_REVSET_KEY = "kenjutu-log-revset"
+
+function storageKey(localDir: string) {
+  return `${LOG_REVSET_KEY}:${localDir}`
+}
+
+/** The remembered revset for a repository; empty means the backend default. */
+export function loadLogRevset(localDir: string): string {
+  if (typeof window !== "undefined") {
+    return localStorage.getItem(storageKey(localDir)) ?? ""
+  }
+  return ""
+}
+
+export function saveLogRevset(localDir: string, revset: string) {
+  const trimmed = revset.trim()
+  if (trimmed === "") {
+    localStorage.removeItem(storageKey(localDir))
+  } else {
+    localStorage.setItem(storageKey(localDir), trimmed)
+  }
+}
+
+/**
+ * Revset selecting the commits shown in a repository's log graph, remembered
+ * per repository. A blank revset shows the mutable stack.
+ */
+export function useLogRevset(localDir: string) {
+  const [revset, _setRevset] = useState(() => loadLogRevset(localDir))
+
+  const setRevset = (next: string) => {
+    _setRevset(next.trim())
+    saveLogRevset(localDir, next)
+  }
+
+  return { revset, setRevset }
+}
@@ -6,6 +6,7 @@ import {
   InlineCommentsMap,
   InlineThread,
 } from "@/components/Diff/"
+import { flattenReplies } from "@/lib/replies"
 
 function mapSide(side: DiffSide): "LEFT" | "RIGHT" {
   return side === "Old" ? "LEFT" : "RIGHT"
@@ -34,10 +35,13 @@ export function useNormalizedLocalComments(
           id: comment.id,
           body: comment.body,
           createdAt: comment.created_at,
-          replies: comment.replies.map((r) => ({
-            id: r.id,
-            body: r.body,
-            createdAt: r.created_at,
+          user: { login: comment.author, avatarUrl: "" },
+          replies: flattenReplies(comment.replies).map(({ reply, depth }) => ({
+            id: reply.id,
+            body: reply.body,
+            createdAt: reply.created_at,
+            user: { login: reply.author, avatarUrl: "" },
+            depth,
           })),
           line,
           startLine:
@@ -0,0 +1,34 @@
+import { describe, expect, it } from "vitest"
+
+import { FileComments, PortedComment } from "@/bindings"
+
+import { countUnresolvedComments } from "./useUnresolvedCommentCounts"
+
+function makeComment(id: string, resolved: boolean): PortedComment {
+  return {
+    comment: { id, resolved } as PortedComment["comment"],
+    ported_line: 1,
+    ported_start_line: null,
+    is_ported: false,
+    anchor_status: "Exact",
+  }
+}
+
+describe("countUnresolvedComments", () => {
+  it("skips resolved threads", () => {
+    const fileComments: FileComments[] = [
+      {
+        file_path: "src/main.rs",
+        comments: [makeComment("a", false), makeComment("b", true)],
+      },
+    ]
+    expect(countUnresolvedComments(fileComments).get("src/main.rs")).toBe(1)
+  })
+
+  it("omits files whose threads are all resolved", () => {
+    const fileComments: FileComments[] = [
+      { file_path: "lib.rs", comments: [makeComment("a", true)] },
+    ]
+    expect(countUnresolvedComments(fileComments).has("lib.rs")).toBe(false)
+  })
+})
@@ -0,0 +1,25 @@
+import { useMemo } from "react"
+
+import { FileComments } from "@/bindings"
+
+import { useLocalComments } from "./useLocalComments"
+
+/** Count unresolved top-level threads per file path. */
+export function countUnresolvedComments(
+  fileComments: FileComments[],
+): Map<string, number> {
+  const counts = new Map<string, number>()
+  for (const { file_path, comments } of fileComments) {
+    const unresolved = comments.filter((c) => !c.comment.resolved).length
+    if (unresolved > 0) counts.set(file_path, unresolved)
+  }
+  return counts
+}
+
+export function useUnresolvedCommentCounts(
+  localDir: string,
+  commitId: string,
+): Map<string, number> | undefined {
+  const { data } = useLocalComments(localDir, commitId)
+  return useMemo(() => data && countUnresolvedComments(data), [data])
+}
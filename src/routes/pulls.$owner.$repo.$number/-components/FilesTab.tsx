@@ -13,8 +13,13 @@ import {
   ResizablePanel,
   ResizablePanelGroup,
 } from "@/components/ui/resizable"
+import { useCopyCommitIdHotkeys } from "@/hooks/useCopyCommitId"
+import { useKeybindings } from "@/hooks/useKeybindings"
+import { useMarkerUndo } from "@/hooks/useMarkerUndo"
+import { shortId } from "@/lib/commitIds"
 
 import { useCommitsInRange } from "../-hooks/useCommitsInRange"
+import { useLinkPrToChange } from "../-hooks/useLinkPrToChange"
 import { usePullRequestDetails } from "../-hooks/usePullRequestDetails"
 import { PRCommitList } from "./PRCommitList"
 import { PRDiffContent } from "./PRDiffContent"
@@ -50,11 +55,18 @@ export function FilesTab({ localDir, owner, repo, prNumber }: FilesTabProps) {
     prQuery.data?.head.sha,
     remoteUrls,
   )
+  const headChangeId = commits?.find(
+    (commit) => commit.sha === prQuery.data?.head.sha,
+  )?.changeId
+  useLinkPrToChange(owner, repo, prNumber, headChangeId)
+
   const [commitSelection, setCommitSelection] =
     useState<CommitSelection | null>(null)
   const leftSidebarRef = usePanelRef()
   const rightSidebarRef = usePanelRef()
   const { focusPane } = usePaneManager()
+  const { undo } = useMarkerUndo(localDir)
+  const keys = useKeybindings()
 
   const selectedCommit = commits?.find((commit: PRCommit) => {
     switch (commitSelection?.type) {
@@ -91,6 +103,13 @@ export function FilesTab({ localDir, owner, repo, prNumber }: FilesTabProps) {
       rightSidebarRef.current?.collapse()
     }
   })
+  useHotkey(keys.undo, undo)
+  useCopyCommitIdHotkeys(
+    selectedCommit && {
+      commitId: selectedCommit.sha,
+      changeId: selectedCommit.changeId,
+    },
+  )
   useHotkey("Mod+B", () => {
     if (leftSidebarRef.current?.isCollapsed()) {
       leftSidebarRef.current.expand()
@@ -220,7 +239,7 @@ function CommitDetail({ commit }: { commit: PRCommit }) {
         <p>
           <span className="font-medium">Commit:</span>{" "}
           <code className="bg-muted px-1 rounded">
-            {commit.sha.slice(0, 12)}
+            {shortId(commit.sha, "commit")}
           </code>
         </p>
         <p>
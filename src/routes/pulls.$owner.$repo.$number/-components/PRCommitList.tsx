@@ -1,6 +1,7 @@
 import { PRCommit } from "@/bindings"
 import { Pane, PANEL_KEYS, usePaneItem } from "@/components/Pane"
 import { useCommitFileList } from "@/hooks/useCommitFileList"
+import { shortId } from "@/lib/commitIds"
 import { cn } from "@/lib/utils"
 
 const ROW_HEIGHT = 32
@@ -75,7 +76,7 @@ function PRCommitRow({
     >
       <span className="flex-1 min-w-0 flex items-center gap-1">
         <span className="font-mono text-xs px-1 rounded bg-muted text-muted-foreground shrink-0">
-          {commit.sha.slice(0, 8)}
+          {shortId(commit.sha, "commit")}
         </span>
         {progress && progress.total > 0 && (
           <span
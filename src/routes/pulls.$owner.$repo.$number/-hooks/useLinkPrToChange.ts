@@ -0,0 +1,25 @@
+import { useEffect } from "react"
+
+import { commands } from "@/bindings"
+
+/**
+ * Records which jj change the PR's head commit belongs to, so the PR can be
+ * found again from a local review of that change.
+ */
+export function useLinkPrToChange(
+  owner: string,
+  repo: string,
+  prNumber: number,
+  headChangeId: string | undefined,
+) {
+  useEffect(() => {
+    if (!headChangeId) return
+    commands
+      .linkPrToChange(owner, repo, prNumber, headChangeId)
+      .then((result) => {
+        if (result.status === "error") {
+          console.warn("Failed to link PR to change", result.error)
+        }
+      })
+  }, [owner, repo, prNumber, headChangeId])
+}
@@ -0,0 +1,75 @@
+import { describe, expect, it } from "vitest"
+
+import { toReviewComment } from "./useReviewComments"
+
+type OctokitReviewComment = Parameters<typeof toReviewComment>[0]
+
+function makeOctokitComment(
+  overrides: Partial<OctokitReviewComment> = {},
+): OctokitReviewComment {
+  return {
+    id: 1,
+    body: "nit: rename this",
+    created_at: "2025-01-15T10:00:00Z",
+    updated_at: "2025-01-15T11:00:00Z",
+    original_commit_id: "aaa",
+    commit_id: "bbb",
+    path: "src/main.rs",
+    line: 12,
+    original_line: 10,
+    start_line: null,
+    original_start_line: null,
+    side: "RIGHT",
+    subject_type: "line",
+    user: { login: "octocat", avatar_url: "https://example.com/a.png" },
+    ...overrides,
+  } as OctokitReviewComment
+}
+
+describe("toReviewComment", () => {
+  it("maps path, lines, body and author", () => {
+    const comment = toReviewComment(makeOctokitComment())
+
+    expect(comment).toMatchObject({
+      id: 1,
+      path: "src/main.rs",
+      line: 12,
+      original_line: 10,
+      body: "nit: rename this",
+      side: "RIGHT",
+      user: { login: "octocat", avatar_url: "https://example.com/a.png" },
+    })
+    expect(comment.start_line).toBeUndefined()
+    expect(comment.in_reply_to_id).toBeUndefined()
+  })
+
+  it("keeps outdated comments, which have no current line", () => {
+    const comment = toReviewComment(
+      makeOctokitComment({ line: null, original_line: 10 }),
+    )
+
+    expect(comment.line).toBeUndefined()
+    expect(comment.original_line).toBe(10)
+  })
+
+  it("handles file-level comments without a side or line", () => {
+    const comment = toReviewComment(
+      makeOctokitComment({
+        line: null,
+        original_line: null,
+        side: undefined,
+        subject_type: "file",
+      }),
+    )
+
+    expect(comment.side).toBe("RIGHT")
+    expect(comment.subject_type).toBe("file")
+    expect(comment.line).toBeUndefined()
+  })
+
+  it("handles comments from deleted accounts", () => {
+    const comment = toReviewComment(makeOctokitComment({ user: null as never }))
+
+    expect(comment.user).toBeNull()
+  })
+})
@@ -28,6 +28,12 @@ export interface ReviewComment {
   user: ReviewCommentUser | null
 }
 
+/**
+ * Review comments only change through GitHub or our own mutations, which
+ * invalidate the query, so they can stay fresh longer than the default.
+ */
+const REVIEW_COMMENTS_STALE_TIME = 2 * 60 * 1000
+
 type OctokitReviewComment =
   RestEndpointMethodTypes["pulls"]["listReviewComments"]["response"]["data"][number]
 
@@ -47,12 +53,16 @@ export function toReviewComment(octokit: OctokitReviewComment): ReviewComment {
     original_start_line: octokit.original_start_line ?? undefined,
     start_side:
       (octokit.start_side as "LEFT" | "RIGHT" | undefined) ?? undefined,
-    side: octokit.side as "LEFT" | "RIGHT",
-    subject_type: octokit.subject_type as "line" | "file",
-    user: {
-      login: octokit.user.login,
-      avatar_url: octokit.user.avatar_url,
-    },
+    // File-level comments carry no side.
+    side: (octokit.side ?? "RIGHT") as "LEFT" | "RIGHT",
+    subject_type: (octokit.subject_type ?? "line") as "line" | "file",
+    // Comments by deleted accounts have no user.
+    user: octokit.user
+      ? {
+          login: octokit.user.login,
+          avatar_url: octokit.user.avatar_url,
+        }
+      : null,
   }
 }
 
@@ -66,7 +76,7 @@ export function useReviewComments(
   return useQuery({
     queryKey: queryKeys.reviewComments(owner, repo, pullNumber),
     queryFn: async (): Promise<ReviewComment[]> => {
-      const { data } = await octokit!.pulls.listReviewComments({
+      const data = await octokit!.paginate(octokit!.pulls.listReviewComments, {
         owner,
         repo,
         pull_number: pullNumber,
@@ -75,6 +85,7 @@ export function useReviewComments(
 
       return data.map(toReviewComment)
     },
+    staleTime: REVIEW_COMMENTS_STALE_TIME,
     enabled: !!octokit && isAuthenticated,
   })
 }
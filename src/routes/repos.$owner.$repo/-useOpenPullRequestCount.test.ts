@@ -0,0 +1,24 @@
+import { describe, expect, it, vi } from "vitest"
+
+import { fetchOpenPullRequestCount } from "./-useOpenPullRequestCount"
+
+describe("fetchOpenPullRequestCount", () => {
+  it("searches open pull requests of the repository", async () => {
+    const search = vi.fn().mockResolvedValue({ data: { total_count: 42 } })
+
+    await expect(fetchOpenPullRequestCount(search, "o", "r")).resolves.toBe(42)
+    expect(search).toHaveBeenCalledWith({
+      q: "repo:o/r is:pr is:open",
+      per_page: 1,
+    })
+  })
+
+  it("returns null instead of failing when the search fails", async () => {
+    const search = vi.fn().mockRejectedValue({ status: 422 })
+    vi.spyOn(console, "error").mockImplementation(() => {})
+
+    await expect(fetchOpenPullRequestCount(search, "o", "r")).resolves.toBe(
+      null,
+    )
+  })
+})
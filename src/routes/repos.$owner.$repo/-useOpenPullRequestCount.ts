@@ -0,0 +1,45 @@
+import { RestEndpointMethodTypes } from "@octokit/rest"
+import { useQuery } from "@tanstack/react-query"
+
+import { useGithub } from "@/context/GithubContext"
+import { queryKeys } from "@/lib/queryKeys"
+
+type SearchIssues = (
+  params: RestEndpointMethodTypes["search"]["issuesAndPullRequests"]["parameters"],
+) => Promise<{ data: { total_count: number } }>
+
+/**
+ * Number of open pull requests, via a search that returns a single item.
+ * Null when the search fails, so the repository page still renders.
+ */
+export async function fetchOpenPullRequestCount(
+  searchIssues: SearchIssues,
+  owner: string,
+  repo: string,
+): Promise<number | null> {
+  try {
+    const { data } = await searchIssues({
+      q: `repo:${owner}/${repo} is:pr is:open`,
+      per_page: 1,
+    })
+    return data.total_count
+  } catch (error) {
+    console.error("Failed to count open pull requests:", error)
+    return null
+  }
+}
+
+export function useOpenPullRequestCount(owner: string, repo: string) {
+  const { octokit } = useGithub()
+
+  return useQuery({
+    queryKey: queryKeys.openPullRequestCount(owner, repo),
+    queryFn: () =>
+      fetchOpenPullRequestCount(
+        octokit!.search.issuesAndPullRequests,
+        owner,
+        repo,
+      ),
+    enabled: !!octokit,
+  })
+}
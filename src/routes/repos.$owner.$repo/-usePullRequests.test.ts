@@ -0,0 +1,35 @@
+import { describe, expect, it, vi } from "vitest"
+
+import { fetchPullRequestPage, PullRequests } from "./-usePullRequests"
+
+const pulls = (...numbers: number[]) =>
+  numbers.map((number) => ({ number })) as unknown as PullRequests
+
+const nextLink =
+  '<https://api.github.com/repositories/1/pulls?page=2>; rel="next"'
+
+describe("fetchPullRequestPage", () => {
+  it("requests the given page sorted by update time", async () => {
+    const listPulls = vi
+      .fn()
+      .mockResolvedValueOnce({ data: pulls(1, 2), headers: { link: nextLink } })
+      .mockResolvedValueOnce({ data: pulls(3), headers: {} })
+
+    const first = await fetchPullRequestPage(listPulls, "o", "r", 1, 2)
+    expect(first.items.map((pr) => pr.number)).toEqual([1, 2])
+    expect(first.hasNext).toBe(true)
+
+    const second = await fetchPullRequestPage(listPulls, "o", "r", 2, 2)
+    expect(second.items.map((pr) => pr.number)).toEqual([3])
+    expect(second.hasNext).toBe(false)
+
+    expect(listPulls).toHaveBeenLastCalledWith({
+      owner: "o",
+      repo: "r",
+      state: "open",
+      sort: "updated",
+      page: 2,
+      per_page: 2,
+    })
+  })
+})
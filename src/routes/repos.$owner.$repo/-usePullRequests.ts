@@ -1,7 +1,8 @@
 import { RestEndpointMethodTypes } from "@octokit/rest"
-import { useQuery } from "@tanstack/react-query"
+import { useInfiniteQuery } from "@tanstack/react-query"
 
 import { useGithub } from "@/context/GithubContext"
+import { withRateLimitRetry } from "@/lib/githubRetry"
 import { queryKeys } from "@/lib/queryKeys"
 
 export interface User {
@@ -15,20 +16,52 @@ export interface User {
 export type PullRequests =
   RestEndpointMethodTypes["pulls"]["list"]["response"]["data"]
 
+export type PullRequestPage = {
+  items: PullRequests
+  hasNext: boolean
+}
+
+type ListPulls = (
+  params: RestEndpointMethodTypes["pulls"]["list"]["parameters"],
+) => Promise<{ data: PullRequests; headers: { link?: string } }>
+
+export const PULL_REQUESTS_PER_PAGE = 50
+
+/**
+ * Fetch one page of open pull requests, most recently updated first. GitHub
+ * advertises a following page with a `rel="next"` entry in the Link header.
+ */
+export async function fetchPullRequestPage(
+  listPulls: ListPulls,
+  owner: string,
+  repo: string,
+  page: number,
+  perPage = PULL_REQUESTS_PER_PAGE,
+): Promise<PullRequestPage> {
+  const { data, headers } = await withRateLimitRetry(() =>
+    listPulls({
+      owner,
+      repo,
+      state: "open",
+      sort: "updated",
+      page,
+      per_page: perPage,
+    }),
+  )
+  return { items: data, hasNext: /rel="next"/.test(headers.link ?? "") }
+}
+
 export function usePullRequests(owner: string, repo: string) {
   const { octokit, isAuthenticated } = useGithub()
 
-  return useQuery({
+  return useInfiniteQuery({
     queryKey: queryKeys.pullRequests(owner, repo),
-    queryFn: async (): Promise<PullRequests> => {
-      const { data } = await octokit!.pulls.list({
-        owner,
-        repo,
-        state: "open",
-        sort: "updated",
-      })
-      return data
-    },
+    queryFn: ({ pageParam }) =>
+      fetchPullRequestPage(octokit!.pulls.list, owner, repo, pageParam),
+    initialPageParam: 1,
+    getNextPageParam: (lastPage, _pages, lastPageParam) =>
+      lastPage.hasNext ? lastPageParam + 1 : undefined,
     enabled: isAuthenticated && !!octokit,
+    retry: false,
   })
 }
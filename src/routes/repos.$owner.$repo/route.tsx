@@ -8,6 +8,7 @@ import { z } from "zod"
 import { commands } from "@/bindings"
 import { getErrorMessage } from "@/components/error"
 import { Alert, AlertDescription, AlertTitle } from "@/components/ui/alert"
+import { Badge } from "@/components/ui/badge"
 import { Button } from "@/components/ui/button"
 import {
   Table,
@@ -18,12 +19,13 @@ import {
   TableRow,
 } from "@/components/ui/table"
 import { useGithub } from "@/context/GithubContext"
-import { useRpcMutation } from "@/hooks/useRpcQuery"
+import { useRpcMutation, useRpcQuery } from "@/hooks/useRpcQuery"
 import { useTab } from "@/hooks/useTab"
 import { queryKeys } from "@/lib/queryKeys"
 import { getLocalPath, setLocalPath } from "@/lib/repos"
 
 import { useJjStatus } from "./-useJjStatus"
+import { useOpenPullRequestCount } from "./-useOpenPullRequestCount"
 import { PullRequests, usePullRequests } from "./-usePullRequests"
 import { useRepository } from "./-useRepository"
 
@@ -45,6 +47,7 @@ function RouteComponent() {
   useTab(`Repo: ${owner}/${repo}`)
 
   const { data: repoData, error: repoError } = useRepository(owner, repo)
+  const { data: openPrCount } = useOpenPullRequestCount(owner, repo)
 
   const { data: localRepoPath, refetch: refetchLocalPath } = useQuery({
     queryKey: queryKeys.localRepoPath(id),
@@ -79,6 +82,9 @@ function RouteComponent() {
     error: prError,
     refetch,
     isLoading: prLoading,
+    hasNextPage,
+    fetchNextPage,
+    isFetchingNextPage,
   } = usePullRequests(owner, repo)
 
   // Check if this is a jj repository
@@ -107,6 +113,13 @@ function RouteComponent() {
         {repoData?.description && (
           <p className="text-muted-foreground">{repoData.description}</p>
         )}
+        {repoData && (
+          <p className="text-sm text-muted-foreground">
+            Default branch:{" "}
+            <span className="font-mono">{repoData.default_branch}</span>
+            {openPrCount != null && ` · ${openPrCount} open pull requests`}
+          </p>
+        )}
       </div>
 
       {repoError && (
@@ -159,12 +172,15 @@ function RouteComponent() {
       <PullRequestsContent
         isAuthenticated={isAuthenticated}
         prLoading={prLoading}
-        prData={prData ?? []}
+        prData={prData?.pages.flatMap((page) => page.items) ?? []}
         prError={prError}
         refetch={refetch}
+        onLoadMore={hasNextPage ? () => fetchNextPage() : undefined}
+        isLoadingMore={isFetchingNextPage}
         owner={owner}
         repo={repo}
         repoId={id}
+        localRepoPath={localRepoPath ?? undefined}
       />
     </main>
   )
@@ -177,9 +193,13 @@ type PullRequestsContentProps = {
   prData: PullRequests
   prError: ReturnType<typeof usePullRequests>["error"]
   refetch: () => void
+  /** Fetch the next page of PRs; absent once every page is loaded. */
+  onLoadMore?: () => void
+  isLoadingMore: boolean
   owner: string
   repo: string
   repoId: string
+  localRepoPath: string | undefined
 }
 
 function PullRequestsContent({
@@ -188,9 +208,12 @@ function PullRequestsContent({
   prData,
   prError,
   refetch,
+  onLoadMore,
+  isLoadingMore,
   owner,
   repo,
   repoId,
+  localRepoPath,
 }: PullRequestsContentProps) {
   return (
     <>
@@ -212,6 +235,7 @@ function PullRequestsContent({
               <TableHead>Title</TableHead>
               <TableHead>Author</TableHead>
               <TableHead>GitHub URL</TableHead>
+              <TableHead>Local Review</TableHead>
             </TableRow>
           </TableHeader>
           <TableBody>
@@ -258,12 +282,32 @@ function PullRequestsContent({
                     {pr.html_url}
                   </a>
                 </TableCell>
+                <TableCell>
+                  {localRepoPath && (
+                    <LocalReviewBadge
+                      localDir={localRepoPath}
+                      commitSha={pr.head.sha}
+                    />
+                  )}
+                </TableCell>
               </TableRow>
             ))}
           </TableBody>
         </Table>
       )}
 
+      {onLoadMore && (
+        <div className="flex justify-center mt-4">
+          <Button
+            onClick={onLoadMore}
+            variant="outline"
+            disabled={isLoadingMore}
+          >
+            {isLoadingMore ? "Loading..." : "Load more"}
+          </Button>
+        </div>
+      )}
+
       {prData.length === 0 && (
         <Alert className="mt-4">
           <AlertTitle>No Pull Requests</AlertTitle>
@@ -286,3 +330,19 @@ function PullRequestsContent({
     </>
   )
 }
+
+function LocalReviewBadge({
+  localDir,
+  commitSha,
+}: {
+  localDir: string
+  commitSha: string
+}) {
+  const { data: hasReview } = useRpcQuery({
+    queryKey: queryKeys.localReview(localDir, commitSha),
+    queryFn: () => commands.hasLocalReview(localDir, commitSha),
+  })
+
+  if (!hasReview) return null
+  return <Badge variant="secondary">In progress</Badge>
+}
@@ -1,17 +1,28 @@
 import { createFileRoute } from "@tanstack/react-router"
+import { useQueryClient } from "@tanstack/react-query"
 import { open } from "@tauri-apps/plugin-dialog"
 import { useCallback, useState } from "react"
 
 import {
   commands,
   type Error as CommandError,
+  type DiffAlgorithm,
+  type DiffSettings,
+  type FileSort,
+  type GeneratedFilesMode,
   type SshSettings,
 } from "@/bindings"
 import { Alert, AlertDescription, AlertTitle } from "@/components/ui/alert"
 import { Button } from "@/components/ui/button"
 import { Card, CardContent, CardHeader } from "@/components/ui/card"
 import { Input } from "@/components/ui/input"
+import { Textarea } from "@/components/ui/textarea"
 import { useRpcMutation, useRpcQuery } from "@/hooks/useRpcQuery"
+import {
+  type CommitIdLength,
+  loadCopyIdLength,
+  saveCopyIdLength,
+} from "@/lib/commitIds"
 import { queryKeys } from "@/lib/queryKeys"
 
 export const Route = createFileRoute("/settings")({
@@ -23,6 +34,8 @@ function SettingsPage() {
     <div className="flex flex-col gap-4 p-4 max-w-2xl mx-auto">
       <h1 className="text-2xl font-semibold">Settings</h1>
       <SshSettingsSection />
+      <DiffSettingsSection />
+      <CommitIdSettingsSection />
     </div>
   )
 }
@@ -144,3 +157,412 @@ function SshSettingsForm({ settings }: { settings: SshSettings }) {
     </Card>
   )
 }
+
+function DiffSettingsSection() {
+  const {
+    data: settings,
+    error: loadError,
+    isLoading,
+  } = useRpcQuery({
+    queryKey: queryKeys.diffSettings(),
+    queryFn: () => commands.getDiffSettings(),
+  })
+
+  if (isLoading) {
+    return <p className="text-muted-foreground">Loading settings...</p>
+  }
+
+  if (loadError) {
+    return (
+      <Alert variant="destructive">
+        <AlertTitle>Error</AlertTitle>
+        <AlertDescription>Failed to load diff settings</AlertDescription>
+      </Alert>
+    )
+  }
+
+  return <DiffSettingsForm settings={settings!} />
+}
+
+function DiffSettingsForm({ settings }: { settings: DiffSettings }) {
+  const queryClient = useQueryClient()
+  const [contextLines, setContextLines] = useState(
+    String(settings.contextLines),
+  )
+  const [algorithm, setAlgorithm] = useState<DiffAlgorithm>(
+    settings.algorithm,
+  )
+  const [plain, setPlain] = useState(settings.plain)
+  const [ignoreWhitespace, setIgnoreWhitespace] = useState(
+    settings.ignoreWhitespace,
+  )
+  const [highlightTheme, setHighlightTheme] = useState(settings.highlightTheme)
+  const { data: themes } = useRpcQuery({
+    queryKey: queryKeys.highlightThemes(),
+    queryFn: () => commands.listHighlightThemes(),
+  })
+  const [tabWidth, setTabWidth] = useState(String(settings.tabWidth))
+  const [showWhitespace, setShowWhitespace] = useState(
+    settings.showWhitespace,
+  )
+  const [renameThreshold, setRenameThreshold] = useState(
+    String(settings.renameThreshold),
+  )
+  const [detectCopies, setDetectCopies] = useState(settings.detectCopies)
+  const [generatedGlobs, setGeneratedGlobs] = useState(
+    settings.generatedGlobs.join("\n"),
+  )
+  const [generatedFiles, setGeneratedFiles] = useState<GeneratedFilesMode>(
+    settings.generatedFiles,
+  )
+  const [fileSort, setFileSort] = useState<FileSort>(settings.fileSort)
+  const [groupFilesByDir, setGroupFilesByDir] = useState(
+    settings.groupFilesByDir,
+  )
+  const [editorCommand, setEditorCommand] = useState(settings.editorCommand)
+
+  const saveMutation = useRpcMutation<
+    null,
+    CommandError,
+    DiffSettings,
+    unknown
+  >({
+    mutationFn: (newSettings) => commands.setDiffSettings(newSettings),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.diffSettings() })
+      // Hunks and region ids depend on the context size and algorithm.
+      queryClient.invalidateQueries({ queryKey: ["commit-file-list"] })
+      queryClient.invalidateQueries({ queryKey: ["partial-review-diffs"] })
+    },
+  })
+
+  const parsed = Number(contextLines)
+  const parsedTabWidth = Number(tabWidth)
+  const parsedThreshold = Number(renameThreshold)
+  const parsedGlobs = generatedGlobs
+    .split("\n")
+    .map((glob) => glob.trim())
+    .filter((glob) => glob !== "")
+  const isValid =
+    contextLines.trim() !== "" &&
+    Number.isInteger(parsed) &&
+    parsed >= 0 &&
+    Number.isInteger(parsedTabWidth) &&
+    parsedTabWidth >= 1 &&
+    renameThreshold.trim() !== "" &&
+    Number.isInteger(parsedThreshold) &&
+    parsedThreshold >= 0 &&
+    parsedThreshold <= 100
+  const isDirty =
+    isValid &&
+    (parsed !== settings.contextLines ||
+      algorithm !== settings.algorithm ||
+      plain !== settings.plain ||
+      ignoreWhitespace !== settings.ignoreWhitespace ||
+      highlightTheme !== settings.highlightTheme ||
+      parsedTabWidth !== settings.tabWidth ||
+      showWhitespace !== settings.showWhitespace ||
+      parsedThreshold !== settings.renameThreshold ||
+      detectCopies !== settings.detectCopies ||
+      parsedGlobs.join("\n") !== settings.generatedGlobs.join("\n") ||
+      generatedFiles !== settings.generatedFiles ||
+      fileSort !== settings.fileSort ||
+      groupFilesByDir !== settings.groupFilesByDir ||
+      editorCommand.trim() !== settings.editorCommand)
+
+  const handleSave = useCallback(() => {
+    saveMutation.mutate({
+      contextLines: parsed,
+      algorithm,
+      plain,
+      ignoreWhitespace,
+      highlightTheme,
+      tabWidth: parsedTabWidth,
+      showWhitespace,
+      renameThreshold: parsedThreshold,
+      detectCopies,
+      generatedGlobs: parsedGlobs,
+      generatedFiles,
+      fileSort,
+      groupFilesByDir,
+      editorCommand: editorCommand.trim(),
+    })
+  }, [
+    parsed,
+    algorithm,
+    plain,
+    ignoreWhitespace,
+    highlightTheme,
+    parsedTabWidth,
+    showWhitespace,
+    parsedThreshold,
+    detectCopies,
+    parsedGlobs,
+    generatedFiles,
+    fileSort,
+    groupFilesByDir,
+    editorCommand,
+    saveMutation,
+  ])
+
+  return (
+    <Card>
+      <CardHeader>
+        <h2 className="text-lg font-medium">Diff</h2>
+      </CardHeader>
+      <CardContent className="flex flex-col gap-4">
+        <div className="flex flex-col gap-2">
+          <label className="text-sm font-medium">Context lines</label>
+          <Input
+            type="number"
+            min={0}
+            value={contextLines}
+            onChange={(e) => setContextLines(e.target.value)}
+            className="w-32"
+          />
+          <p className="text-xs text-muted-foreground">
+            Unchanged lines shown around each change. Changes closer together
+            than this are grouped into a single hunk for review.
+          </p>
+        </div>
+
+        <div className="flex flex-col gap-2">
+          <label className="text-sm font-medium">Algorithm</label>
+          <select
+            value={algorithm}
+            onChange={(e) => setAlgorithm(e.target.value as DiffAlgorithm)}
+            className="h-9 w-40 rounded-md border bg-transparent px-3 text-sm"
+          >
+            <option value="myers">Myers</option>
+            <option value="minimal">Minimal</option>
+            <option value="patience">Patience</option>
+          </select>
+          <p className="text-xs text-muted-foreground">
+            Patience often gives cleaner diffs for reformatted or reordered
+            code.
+          </p>
+        </div>
+
+        <div className="flex flex-col gap-2">
+          <label className="flex items-center gap-2 text-sm font-medium">
+            <input
+              type="checkbox"
+              checked={plain}
+              onChange={(e) => setPlain(e.target.checked)}
+            />
+            Plain diffs
+          </label>
+          <p className="text-xs text-muted-foreground">
+            Hide syntax colors and word-level highlights, keeping only added
+            and removed lines. Useful for screenshots.
+          </p>
+        </div>
+
+        <div className="flex flex-col gap-2">
+          <label className="text-sm font-medium">Syntax theme</label>
+          <select
+            value={highlightTheme}
+            onChange={(e) => setHighlightTheme(e.target.value)}
+            disabled={plain}
+            className="h-9 w-64 rounded-md border bg-transparent px-3 text-sm"
+          >
+            {(themes ?? [highlightTheme]).map((theme) => (
+              <option key={theme} value={theme}>
+                {theme}
+              </option>
+            ))}
+          </select>
+        </div>
+
+        <div className="flex flex-col gap-2">
+          <label className="text-sm font-medium">Tab width</label>
+          <Input
+            type="number"
+            min={1}
+            value={tabWidth}
+            onChange={(e) => setTabWidth(e.target.value)}
+            className="w-32"
+          />
+        </div>
+
+        <div className="flex flex-col gap-2">
+          <label className="flex items-center gap-2 text-sm font-medium">
+            <input
+              type="checkbox"
+              checked={showWhitespace}
+              onChange={(e) => setShowWhitespace(e.target.checked)}
+            />
+            Show whitespace
+          </label>
+          <p className="text-xs text-muted-foreground">
+            Mark tabs with → and trailing spaces with · to catch whitespace
+            mistakes.
+          </p>
+        </div>
+
+        <div className="flex flex-col gap-2">
+          <label className="flex items-center gap-2 text-sm font-medium">
+            <input
+              type="checkbox"
+              checked={ignoreWhitespace}
+              onChange={(e) => setIgnoreWhitespace(e.target.checked)}
+            />
+            Ignore whitespace changes
+          </label>
+          <p className="text-xs text-muted-foreground">
+            Hide lines that only changed indentation or spacing. When off,
+            hunks that only differ in whitespace are dimmed instead. Review
+            status is not affected.
+          </p>
+        </div>
+
+        <div className="flex flex-col gap-2">
+          <label className="text-sm font-medium">Rename similarity (%)</label>
+          <Input
+            type="number"
+            min={0}
+            max={100}
+            value={renameThreshold}
+            onChange={(e) => setRenameThreshold(e.target.value)}
+            className="w-32"
+          />
+          <p className="text-xs text-muted-foreground">
+            How similar a deleted and an added file must be to show as a
+            rename. Lower it if heavily edited renames appear as separate
+            files.
+          </p>
+        </div>
+
+        <div className="flex flex-col gap-2">
+          <label className="flex items-center gap-2 text-sm font-medium">
+            <input
+              type="checkbox"
+              checked={detectCopies}
+              onChange={(e) => setDetectCopies(e.target.checked)}
+            />
+            Detect copies
+          </label>
+          <p className="text-xs text-muted-foreground">
+            Show new files that closely match a modified file as copies.
+          </p>
+        </div>
+
+        <div className="flex flex-col gap-2">
+          <label className="text-sm font-medium">Generated files</label>
+          <Textarea
+            value={generatedGlobs}
+            onChange={(e) => setGeneratedGlobs(e.target.value)}
+            rows={4}
+            className="font-mono text-xs"
+          />
+          <select
+            value={generatedFiles}
+            onChange={(e) =>
+              setGeneratedFiles(e.target.value as GeneratedFilesMode)
+            }
+            className="h-9 w-48 rounded-md border bg-transparent px-3 text-sm"
+          >
+            <option value="show">Show collapsed</option>
+            <option value="hide">Hide</option>
+            <option value="markReviewed">Offer to mark reviewed</option>
+          </select>
+          <p className="text-xs text-muted-foreground">
+            One glob per line, such as <code>**/Cargo.lock</code>. Matching
+            files are collapsed, left out of the file list, or collapsed with
+            a button in the diff header that marks them all reviewed.
+          </p>
+        </div>
+
+        <div className="flex flex-col gap-2">
+          <label className="text-sm font-medium">File order</label>
+          <select
+            value={fileSort}
+            onChange={(e) => setFileSort(e.target.value as FileSort)}
+            className="h-9 w-48 rounded-md border bg-transparent px-3 text-sm"
+          >
+            <option value="path">Path</option>
+            <option value="status">Change type</option>
+            <option value="changeSize">Most changed first</option>
+          </select>
+          <label className="flex items-center gap-2 text-sm">
+            <input
+              type="checkbox"
+              checked={groupFilesByDir}
+              onChange={(e) => setGroupFilesByDir(e.target.checked)}
+            />
+            Group by directory
+          </label>
+          <p className="text-xs text-muted-foreground">
+            Order of the files in a diff. Path order always keeps directories
+            together, matching the file tree.
+          </p>
+        </div>
+
+        <div className="flex flex-col gap-2">
+          <label className="text-sm font-medium">Editor command</label>
+          <Input
+            value={editorCommand}
+            onChange={(e) => setEditorCommand(e.target.value)}
+            placeholder="code --goto {file}:{line}"
+            className="font-mono text-xs"
+          />
+          <p className="text-xs text-muted-foreground">
+            Used to open files from a diff. <code>{"{file}"}</code> and{" "}
+            <code>{"{line}"}</code> are filled in. Leave empty to open files
+            with the system default application.
+          </p>
+        </div>
+
+        <div className="flex gap-2">
+          <Button
+            onClick={handleSave}
+            disabled={!isDirty || saveMutation.isPending}
+          >
+            {saveMutation.isPending ? "Saving..." : "Save"}
+          </Button>
+        </div>
+
+        {saveMutation.isSuccess && (
+          <p className="text-sm text-green-600">Settings saved.</p>
+        )}
+        {saveMutation.isError && (
+          <Alert variant="destructive">
+            <AlertDescription>Failed to save settings</AlertDescription>
+          </Alert>
+        )}
+      </CardContent>
+    </Card>
+  )
+}
+
+function CommitIdSettingsSection() {
+  const [length, setLength] = useState<CommitIdLength>(loadCopyIdLength)
+
+  const handleChange = (next: CommitIdLength) => {
+    setLength(next)
+    saveCopyIdLength(next)
+  }
+
+  return (
+    <Card>
+      <CardHeader>
+        <h2 className="text-lg font-medium">Commit IDs</h2>
+      </CardHeader>
+      <CardContent className="flex flex-col gap-2">
+        <label className="text-sm font-medium">Copied IDs</label>
+        <select
+          value={length}
+          onChange={(e) => handleChange(e.target.value as CommitIdLength)}
+          className="h-9 w-40 rounded-md border bg-transparent px-3 text-sm"
+        >
+          <option value="full">Full</option>
+          <option value="short">Short</option>
+        </select>
+        <p className="text-xs text-muted-foreground">
+          Length of the commit or change ID copied with Y or Shift+Y in the
+          commit graph.
+        </p>
+      </CardContent>
+    </Card>
+  )
+}
@@ -272,6 +272,7 @@ comments_case("go opens thread float and q closes it", function()
           body = "this is wrong",
           anchor = { before = {}, target = {}, after = {} },
           resolved = false,
+          author = "Test User <test@test.com>",
           created_at = "2025-01-15T10:00:00Z",
           updated_at = "2025-01-15T10:00:00Z",
           edit_count = 0,
@@ -279,6 +280,7 @@ comments_case("go opens thread float and q closes it", function()
             {
               id = "r1",
               body = "fixed it",
+              author = "Test User <test@test.com>",
               created_at = "2025-01-16T10:00:00Z",
               updated_at = "2025-01-16T10:00:00Z",
               edit_count = 0,
@@ -306,6 +308,7 @@ comments_case("go opens thread float and q closes it", function()
   local content = table.concat(lines, "\n")
   assert(content:find("this is wrong"), "expected root comment body")
   assert(content:find("2025%-01%-15"), "expected root comment date")
+  assert(content:find("Test User <test@test.com>", 1, true), "expected comment author")
   assert(content:find("  fixed it"), "expected indented reply body")
   assert(content:find("2025%-01%-16"), "expected reply date")
 
@@ -331,6 +334,7 @@ comments_case("go on line without comment does nothing", function()
           body = "some comment",
           anchor = { before = {}, target = {}, after = {} },
           resolved = false,
+          author = "Test User <test@test.com>",
           created_at = "2025-01-15T10:00:00Z",
           updated_at = "2025-01-15T10:00:00Z",
           edit_count = 0,
@@ -367,6 +371,7 @@ comments_case("gC opens comment list float", function()
           body = "first comment",
           anchor = { before = {}, target = {}, after = {} },
           resolved = false,
+          author = "Test User <test@test.com>",
           created_at = "2025-01-15T10:00:00Z",
           updated_at = "2025-01-15T10:00:00Z",
           edit_count = 0,
@@ -386,6 +391,7 @@ comments_case("gC opens comment list float", function()
           body = "second comment",
           anchor = { before = {}, target = {}, after = {} },
           resolved = true,
+          author = "Test User <test@test.com>",
           created_at = "2025-01-16T10:00:00Z",
           updated_at = "2025-01-16T10:00:00Z",
           edit_count = 0,
@@ -393,6 +399,7 @@ comments_case("gC opens comment list float", function()
             {
               id = "r1",
               body = "reply",
+              author = "Test User <test@test.com>",
               created_at = "2025-01-17T10:00:00Z",
               updated_at = "2025-01-17T10:00:00Z",
               edit_count = 0,
@@ -468,6 +475,7 @@ comments_case("gC enter jumps to comment line in diff", function()
           body = "jump here",
           anchor = { before = {}, target = {}, after = {} },
           resolved = false,
+          author = "Test User <test@test.com>",
           created_at = "2025-01-15T10:00:00Z",
           updated_at = "2025-01-15T10:00:00Z",
           edit_count = 0,
@@ -509,6 +517,7 @@ comments_case("gC enter does not jump for Old comment in remaining mode", functi
           body = "old side comment",
           anchor = { before = {}, target = {}, after = {} },
           resolved = false,
+          author = "Test User <test@test.com>",
           created_at = "2025-01-15T10:00:00Z",
           updated_at = "2025-01-15T10:00:00Z",
           edit_count = 0,
@@ -550,6 +559,7 @@ comments_case("gC enter jumps to Old comment in reviewed mode", function()
           body = "old side comment",
           anchor = { before = {}, target = {}, after = {} },
           resolved = false,
+          author = "Test User <test@test.com>",
           created_at = "2025-01-15T10:00:00Z",
           updated_at = "2025-01-15T10:00:00Z",
           edit_count = 0,
@@ -588,6 +598,7 @@ t.run_case("build_comment_list: single comment with code snippet and reply", fun
       body = "fix this\nsecond line",
       anchor = { before = {}, target = { "local x = 1", "local y = 2" }, after = {} },
       resolved = false,
+      author = "Test User <test@test.com>",
       created_at = "2025-01-15T10:00:00Z",
       updated_at = "2025-01-15T10:00:00Z",
       edit_count = 0,
@@ -595,6 +606,7 @@ t.run_case("build_comment_list: single comment with code snippet and reply", fun
         {
           id = "r1",
           body = "done",
+          author = "Test User <test@test.com>",
           created_at = "2025-01-16T10:00:00Z",
           updated_at = "2025-01-16T10:00:00Z",
           edit_count = 0,
@@ -648,6 +660,7 @@ t.run_case("build_comment_list: resolved comment without replies or code", funct
       body = "looks wrong",
       anchor = { before = {}, target = {}, after = {} },
       resolved = true,
+      author = "Test User <test@test.com>",
       created_at = "2025-02-01T10:00:00Z",
       updated_at = "2025-02-01T10:00:00Z",
       edit_count = 0,
@@ -681,6 +694,7 @@ t.run_case("build_comment_list: two comments separated by blank line", function(
       body = "first",
       anchor = { before = {}, target = {}, after = {} },
       resolved = false,
+      author = "Test User <test@test.com>",
       created_at = "2025-01-15T10:00:00Z",
       updated_at = "2025-01-15T10:00:00Z",
       edit_count = 0,
@@ -700,6 +714,7 @@ t.run_case("build_comment_list: two comments separated by blank line", function(
       body = "second",
       anchor = { before = {}, target = {}, after = {} },
       resolved = false,
+      author = "Test User <test@test.com>",
       created_at = "2025-01-16T10:00:00Z",
       updated_at = "2025-01-16T10:00:00Z",
       edit_count = 0,
@@ -744,6 +759,7 @@ comments_case("gC x resolves comment and updates buffer in place", function()
           body = "needs fix",
           anchor = { before = {}, target = {}, after = {} },
           resolved = false,
+          author = "Test User <test@test.com>",
           created_at = "2025-01-15T10:00:00Z",
           updated_at = "2025-01-15T10:00:00Z",
           edit_count = 0,
@@ -799,6 +815,7 @@ comments_case("gC x unresolves a resolved comment", function()
           body = "was resolved",
           anchor = { before = {}, target = {}, after = {} },
           resolved = true,
+          author = "Test User <test@test.com>",
           created_at = "2025-01-15T10:00:00Z",
           updated_at = "2025-01-15T10:00:00Z",
           edit_count = 0,
@@ -32,23 +32,29 @@ end
 
 ---@param file_opts? { reviewStatus?: string }
 ---@param blob_map? table<string, string>
-local function open_review(file_opts, blob_map)
+---@param paths? string[]
+---@return kenjutu.ReviewState
+local function open_review(file_opts, blob_map, paths)
   file_opts = file_opts or {}
   blob_map = blob_map or mock_content
-
-  local file = {
-    newPath = "src/foo.lua",
-    oldPath = "src/foo.lua",
-    status = "modified",
-    reviewStatus = file_opts.reviewStatus or "unreviewed",
-    additions = 3,
-    deletions = 1,
-    isBinary = false,
-  }
+  paths = paths or { "src/foo.lua" }
+
+  local files = {}
+  for _, path in ipairs(paths) do
+    table.insert(files, {
+      newPath = path,
+      oldPath = path,
+      status = "modified",
+      reviewStatus = file_opts.reviewStatus or "unreviewed",
+      additions = 3,
+      deletions = 1,
+      isBinary = false,
+    })
+  end
 
   kjn.files = function(_, _, cb)
     cb(nil, {
-      files = { file },
+      files = files,
       commitId = "abc123",
       changeId = mock_change_id,
     })
@@ -59,10 +65,11 @@ local function open_review(file_opts, blob_map)
 
   local log_bufnr = vim.api.nvim_get_current_buf()
   local commit = { change_id = mock_change_id, commit_id = "abc123" }
-  review.open(vim.fn.getcwd(), commit, log_bufnr, function() end)
+  local s = review.open(vim.fn.getcwd(), commit, log_bufnr, function() end)
 
   vim.api.nvim_feedkeys("jjj", "x", false)
   vim.cmd("doautocmd CursorMoved")
+  return s
 end
 
 local function diff_case(name, fn)
@@ -179,6 +186,20 @@ diff_case("mark_action from non-marker buffer applies hunk via diffput", functio
   t.eq(got_content, "same line1\nright only\nsame line3\n")
 end)
 
+diff_case("mark_action forgets the file's saved view", function()
+  local left = "same line1\nleft only\nsame line3\n"
+  local right = "same line1\nright only\nsame line3\n"
+  local s = open_review({ reviewStatus = "unreviewed" }, { marker = left, target = right, base = "" })
+
+  local _, _, diff_right = t_util.review_wins()
+  vim.api.nvim_set_current_win(diff_right)
+  vim.api.nvim_win_set_cursor(diff_right, { 2, 0 })
+  s.diff_state:save_view()
+  vim.api.nvim_feedkeys("s", "x", false)
+
+  t.eq(s.diff_state.saved_views["src/foo.lua"], nil)
+end)
+
 diff_case("mark_action from marker buffer absorbs hunk via diffget", function()
   local left = "same line1\nleft only\nsame line3\n"
   local right = "same line1\nright only\nsame line3\n"
@@ -221,3 +242,60 @@ diff_case("mark_action with visual selection applies only selected range", funct
 
   t.eq(got_content, "same1\nright A\nsame2\nleft B\nsame3\n")
 end)
+
+diff_case("returning to a file restores its cursor line", function()
+  local long_content = {}
+  for i = 1, 50 do
+    table.insert(long_content, "line " .. i)
+  end
+  local blob_map = {
+    base = "",
+    marker = "",
+    target = table.concat(long_content, "\n") .. "\n",
+  }
+
+  open_review({ reviewStatus = "unreviewed" }, blob_map, { "src/a.lua", "src/b.lua" })
+
+  local _, _, diff_right = t_util.review_wins()
+  vim.api.nvim_set_current_win(diff_right)
+  t.ok(win_buf_name(diff_right):find("src/a.lua") ~= nil, "first file should be shown")
+  vim.api.nvim_win_set_cursor(diff_right, { 30, 0 })
+
+  vim.api.nvim_feedkeys("gj", "x", false)
+  t.ok(win_buf_name(diff_right):find("src/b.lua") ~= nil, "second file should be shown")
+  vim.api.nvim_win_set_cursor(diff_right, { 5, 0 })
+
+  vim.api.nvim_feedkeys("gk", "x", false)
+  t.ok(win_buf_name(diff_right):find("src/a.lua") ~= nil, "first file should be shown again")
+  t.eq(vim.api.nvim_win_get_cursor(diff_right)[1], 30)
+end)
+
+diff_case("a superseded load does not replace the file opened after it", function()
+  open_review({ reviewStatus = "unreviewed" }, nil, { "src/a.lua", "src/b.lua" })
+
+  local held = {}
+  local fetch_blob = kjn.fetch_blob
+  kjn.fetch_blob = function(opts, cb)
+    if opts.file_path == "src/b.lua" then
+      table.insert(held, function()
+        cb(nil, "stale\n")
+      end)
+      return
+    end
+    fetch_blob(opts, cb)
+  end
+
+  local _, _, diff_right = t_util.review_wins()
+  vim.api.nvim_set_current_win(diff_right)
+  vim.api.nvim_feedkeys("gj", "x", false)
+  t.eq(vim.wo[diff_right].winbar, "Loading…")
+
+  vim.api.nvim_feedkeys("gk", "x", false)
+  for _, release in ipairs(held) do
+    release()
+  end
+
+  t.ok(win_buf_name(diff_right):find("src/a.lua") ~= nil, "first file should still be shown")
+  t.eq(win_buf_lines(diff_right), target_lines)
+  t.eq(vim.wo[diff_right].winbar, "New")
+end)
@@ -92,3 +92,15 @@ t.run_case("build_tree compaction stops when dir has mixed children", function()
   t.eq(tree[1].name, "a/b")
   t.eq(#tree[1].children, 2)
 end)
+
+-- format_file_line ------------------------------------------------------------
+
+t.run_case("format_file_line shows unresolved comment count", function()
+  local line = file_render.format_file_line(make_file({ unresolvedComments = 2 }), "")
+  t.eq(line.text:sub(-#" 💬2"), " 💬2")
+end)
+
+t.run_case("format_file_line omits badge without unresolved comments", function()
+  local line = file_render.format_file_line(make_file({ unresolvedComments = 0 }), "")
+  t.eq(line.text:find("💬", 1, true), nil)
+end)
@@ -93,7 +93,7 @@ review_case("file list keymaps are registered", function()
   assert(file_list_bufnr, "file list buffer not found")
 
   local keymaps = vim.api.nvim_buf_get_keymap(file_list_bufnr, "n")
-  local expected_keys = { "<CR>", " ", "r", "t", "q" }
+  local expected_keys = { "<CR>", "<2-LeftMouse>", " ", "r", "t", "]u", "[u", "q" }
   for _, key in ipairs(expected_keys) do
     local found = false
     for _, km in ipairs(keymaps) do
@@ -137,6 +137,124 @@ review_case("file selection follows cursor", function()
   t.eq(get_left_lines(), { mock_files[2].newPath })
 end)
 
+review_case("resize shrinks the file list and keeps the cursor in view", function()
+  local columns = vim.o.columns
+  local ok, err = pcall(function()
+    vim.o.columns = 200
+    open_review()
+    local file_list_winnr, left_winnr, right_winnr = t_util.review_wins()
+    t.eq(vim.api.nvim_win_get_width(file_list_winnr), 40)
+
+    vim.api.nvim_set_current_win(file_list_winnr)
+    vim.api.nvim_win_set_cursor(file_list_winnr, { 5, 0 })
+    vim.o.columns = 90
+    vim.cmd("doautocmd VimResized")
+
+    t.eq(vim.api.nvim_win_get_width(file_list_winnr), review.file_list_width(90))
+    local left_width = vim.api.nvim_win_get_width(left_winnr)
+    local right_width = vim.api.nvim_win_get_width(right_winnr)
+    t.ok(math.abs(left_width - right_width) <= 1, "diff panes should be split evenly")
+
+    local cursor = vim.api.nvim_win_get_cursor(file_list_winnr)[1]
+    local top = vim.fn.line("w0", file_list_winnr)
+    local bottom = vim.fn.line("w$", file_list_winnr)
+    t.ok(cursor >= top and cursor <= bottom, "cursor should stay in view")
+  end)
+  vim.o.columns = columns
+  if not ok then
+    error(err, 0)
+  end
+end)
+
+t.run_case("file_list_width gives up columns on narrow screens", function()
+  t.eq(review.file_list_width(200), 40)
+  t.eq(review.file_list_width(90), 30)
+  t.eq(review.file_list_width(30), 20)
+end)
+
+review_case("double-click on a file row focuses its diff", function()
+  open_review()
+  local file_list_winnr, _, right_winnr = t_util.review_wins()
+  local file_list_bufnr = vim.api.nvim_win_get_buf(file_list_winnr)
+
+  local function double_click()
+    for _, km in ipairs(vim.api.nvim_buf_get_keymap(file_list_bufnr, "n")) do
+      if km.lhs == "<2-LeftMouse>" then
+        km.callback()
+        return
+      end
+    end
+    error("double-click keymap not registered")
+  end
+
+  vim.api.nvim_set_current_win(file_list_winnr)
+  vim.api.nvim_win_set_cursor(file_list_winnr, { 1, 0 })
+  double_click()
+  t.eq(vim.api.nvim_get_current_win(), file_list_winnr)
+
+  vim.api.nvim_win_set_cursor(file_list_winnr, { 5, 0 })
+  double_click()
+  t.eq(vim.api.nvim_get_current_win(), right_winnr)
+end)
+
+review_case("refresh after marking keeps the selected file and diff cursor", function()
+  local content = {}
+  for i = 1, 40 do
+    table.insert(content, "line" .. i)
+  end
+  kjn.fetch_blob = function(_, cb)
+    cb(nil, table.concat(content, "\n") .. "\n")
+  end
+  local commit = { change_id = "test_change", commit_id = "test_commit" }
+  local s = review.open(vim.fn.getcwd(), commit, vim.api.nvim_get_current_buf(), function() end)
+  local file_list_winnr, _, right_winnr = t_util.review_wins()
+
+  vim.api.nvim_win_set_cursor(file_list_winnr, { 5, 0 })
+  vim.cmd("doautocmd CursorMoved")
+  vim.api.nvim_set_current_win(right_winnr)
+  vim.api.nvim_win_set_cursor(right_winnr, { 20, 0 })
+
+  kjn.files = function(_, change_id, cb)
+    cb(nil, { files = mock_files, commitId = "def456", changeId = change_id })
+  end
+  s:refresh_file_list()
+
+  t.eq(vim.api.nvim_win_get_cursor(file_list_winnr)[1], 5)
+  t.eq(s:selected_file().newPath, mock_files[2].newPath)
+  t.eq(vim.api.nvim_win_get_cursor(right_winnr)[1], 20)
+end)
+
+t.run_case("find_file_line locates a file's row", function()
+  local line_map = {
+    [4] = { newPath = "a.lua", oldPath = "a.lua" },
+    [5] = { newPath = "b.lua", oldPath = "b.lua" },
+  }
+  t.eq(review.find_file_line(line_map, "b.lua"), 5)
+  t.eq(review.find_file_line(line_map, "c.lua"), nil)
+end)
+
+t.run_case("find_unreviewed_line skips reviewed files and wraps", function()
+  local line_map = {
+    [4] = { reviewStatus = "unreviewed" },
+    [5] = { reviewStatus = "reviewed" },
+    [6] = { reviewStatus = "partiallyReviewed" },
+    [7] = { reviewStatus = "reviewed" },
+  }
+  t.eq(review.find_unreviewed_line(line_map, 4, "down"), 6)
+  t.eq(review.find_unreviewed_line(line_map, 6, "down"), 4)
+  t.eq(review.find_unreviewed_line(line_map, 6, "up"), 4)
+  t.eq(review.find_unreviewed_line(line_map, 4, "up"), 6)
+  t.eq(review.find_unreviewed_line(line_map, 1, "down"), 4)
+end)
+
+t.run_case("find_unreviewed_line returns nil when everything is reviewed", function()
+  local line_map = {
+    [4] = { reviewStatus = "reviewed" },
+    [5] = { reviewStatus = "reviewed" },
+  }
+  t.eq(review.find_unreviewed_line(line_map, 4, "down"), nil)
+end)
+
 review_case("close restores log buffer", function()
   local log_bufnr = open_review()
 
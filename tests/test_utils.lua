@@ -29,6 +29,23 @@ t.run_case("file_path errors when both paths are nil", function()
   end)
 end)
 
+-- flatten_replies -------------------------------------------------------------
+
+t.run_case("flatten_replies lists nested replies after their parent", function()
+  local replies = {
+    { id = "r1", replies = { { id = "r2", replies = { { id = "r3" } } } } },
+    { id = "r4", replies = {} },
+  }
+  local flat = vim.tbl_map(function(item)
+    return { item.reply.id, item.depth }
+  end, utils.flatten_replies(replies))
+  t.eq(flat, { { "r1", 0 }, { "r2", 1 }, { "r3", 2 }, { "r4", 0 } })
+end)
+
+t.run_case("flatten_replies handles missing replies", function()
+  t.eq(utils.flatten_replies(nil), {})
+end)
+
 -- await_all -------------------------------------------------------------------
 
 t.run_case("await_all collects results from multiple tasks", function()